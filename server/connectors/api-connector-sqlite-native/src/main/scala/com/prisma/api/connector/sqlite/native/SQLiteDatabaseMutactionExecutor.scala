@@ -1,13 +1,13 @@
 package com.prisma.api.connector.sqlite.native
 import com.google.protobuf.ByteString
-import com.prisma.api.connector.jdbc.{NestedDatabaseMutactionInterpreter, TopLevelDatabaseMutactionInterpreter}
+import com.prisma.api.connector.jdbc.TopLevelDatabaseMutactionInterpreter
 import com.prisma.api.connector.jdbc.impl._
 import com.prisma.api.connector._
 import com.prisma.api.connector.jdbc.database.JdbcActionsBuilder
 import com.prisma.api.schema.APIErrors
 import com.prisma.api.schema.APIErrors.{FieldCannotBeNull, NodeNotFoundForWhereError}
 import com.prisma.connector.shared.jdbc.SlickDatabase
-import com.prisma.gc_values.{IdGCValue, ListGCValue}
+import com.prisma.gc_values.ListGCValue
 import com.prisma.rs.{NativeBinding, NodeNotFoundForWhere, NodeSelectorInfo, NodesNotConnected, RelationViolation, UniqueConstraintViolation}
 import com.prisma.shared.models.Project
 import play.api.libs.json.{JsValue, Json}
@@ -17,6 +17,7 @@ import slick.dbio.DBIO
 import slick.jdbc.TransactionIsolation
 
 import scala.concurrent.{ExecutionContext, Future}
+import scala.util.{Failure, Success}
 
 case class SQLiteDatabaseMutactionExecutor(
     slickDatabaseArg: SlickDatabase
@@ -47,75 +48,11 @@ case class SQLiteDatabaseMutactionExecutor(
     runAttached(mutaction.project, finalAction)
   }
 
+  // The connector executes the nested mutactions in the transaction of the top level mutaction and returns all their results.
   def executeTopLevelMutaction(
       mutaction: TopLevelDatabaseMutaction,
       mutationBuilder: JdbcActionsBuilder
   ): DBIO[MutactionResults] = {
-    mutaction match {
-      case m: TopLevelUpsertNode =>
-        for {
-          result <- interpreterFor(m).dbioActionWithErrorMapped(mutationBuilder)
-          childResults <- result match {
-                           case result: CreateNodeResult =>
-                             DBIO.sequence(m.create.allNestedMutactions.map(executeNestedMutaction(_, result.id, mutationBuilder)))
-                           case result: UpdateNodeResult =>
-                             DBIO.sequence(m.update.allNestedMutactions.map(executeNestedMutaction(_, result.id, mutationBuilder)))
-                           case _ => DBIO.successful(Vector.empty)
-                         }
-        } yield MutactionResults(result +: childResults.flatMap(_.results))
-      case m: FurtherNestedMutaction =>
-        for {
-          result <- interpreterFor(m).dbioActionWithErrorMapped(mutationBuilder)
-          childResults <- result match {
-                           case result: FurtherNestedMutactionResult =>
-                             DBIO.sequence(m.allNestedMutactions.map(executeNestedMutaction(_, result.id, mutationBuilder)))
-                           case _ => DBIO.successful(Vector.empty)
-                         }
-        } yield MutactionResults(result +: childResults.flatMap(_.results))
-
-      case m: FinalMutaction =>
-        for {
-          result <- interpreterFor(m).dbioActionWithErrorMapped(mutationBuilder)
-        } yield MutactionResults(Vector(result))
-    }
-  }
-
-  def executeNestedMutaction(
-      mutaction: NestedDatabaseMutaction,
-      parentId: IdGCValue,
-      mutationBuilder: JdbcActionsBuilder
-  ): DBIO[MutactionResults] = {
-    mutaction match {
-      case m: UpsertNode =>
-        for {
-          result <- interpreterFor(m, parentId).dbioActionWithErrorMapped(mutationBuilder, parentId)
-          childResults <- result match {
-                           case result: CreateNodeResult =>
-                             DBIO.sequence(m.create.allNestedMutactions.map(executeNestedMutaction(_, result.id, mutationBuilder)))
-                           case result: UpdateNodeResult =>
-                             DBIO.sequence(m.update.allNestedMutactions.map(executeNestedMutaction(_, result.id, mutationBuilder)))
-                           case _ => DBIO.successful(Vector.empty)
-                         }
-        } yield MutactionResults(result +: childResults.flatMap(_.results))
-
-      case m: FurtherNestedMutaction =>
-        for {
-          result <- interpreterFor(m, parentId).dbioActionWithErrorMapped(mutationBuilder, parentId)
-          childResults <- result match {
-                           case result: FurtherNestedMutactionResult =>
-                             DBIO.sequence(m.allNestedMutactions.map(executeNestedMutaction(_, result.id, mutationBuilder)))
-                           case _ => DBIO.successful(Vector.empty)
-                         }
-        } yield MutactionResults(result +: childResults.flatMap(_.results))
-
-      case m: FinalMutaction =>
-        for {
-          result <- interpreterFor(m, parentId).dbioActionWithErrorMapped(mutationBuilder, parentId)
-        } yield MutactionResults(Vector(result))
-    }
-  }
-
-  def interpreterFor(mutaction: TopLevelDatabaseMutaction): TopLevelDatabaseMutactionInterpreter = {
     import com.prisma.shared.models.ProjectJsonFormatter._
     val projectJson = ByteString.copyFromUtf8(Json.toJson(mutaction.project).toString())
     val headerName  = mutaction.getClass.getSimpleName
@@ -128,7 +65,7 @@ case class SQLiteDatabaseMutactionExecutor(
           createNodeToProtocol(m)
         )
         val envelope = prisma.protocol.DatabaseMutaction(projectJson, None, protoMutaction)
-        top_level_mutaction_interpreter(envelope, m)
+        forwarding_dbio(envelope, m)
 
       case m: TopLevelUpdateNode =>
         val protoMutaction = prisma.protocol.DatabaseMutaction.Type.Update(
@@ -139,7 +76,7 @@ case class SQLiteDatabaseMutactionExecutor(
           case Error.Value.NodeNotFoundForWhere(_)  => throw NodeNotFoundForWhereError(m.where)
           case Error.Value.FieldCannotBeNull(field) => throw FieldCannotBeNull(field)
         }
-        top_level_mutaction_interpreter(envelope, m, errorHandler)
+        forwarding_dbio(envelope, m, errorHandler)
 
       case m: TopLevelUpsertNode =>
         val protoMutaction = prisma.protocol.DatabaseMutaction.Type.Upsert(
@@ -150,7 +87,7 @@ case class SQLiteDatabaseMutactionExecutor(
             update = updateNodeToProtocol(m.update),
           ))
         val envelope = prisma.protocol.DatabaseMutaction(projectJson, None, protoMutaction)
-        top_level_mutaction_interpreter(envelope, m)
+        forwarding_dbio(envelope, m)
 
       case m: TopLevelUpdateNodes =>
         val protoMutaction = prisma.protocol.DatabaseMutaction.Type.UpdateNodes(
@@ -162,7 +99,7 @@ case class SQLiteDatabaseMutactionExecutor(
             listArgs = listArgsToProtocolArgs(m.listArgs),
           ))
         val envelope = prisma.protocol.DatabaseMutaction(projectJson, None, protoMutaction)
-        top_level_mutaction_interpreter(envelope, m)
+        forwarding_dbio(envelope, m)
 
       case m: TopLevelDeleteNode =>
         val protoMutaction = prisma.protocol.DatabaseMutaction.Type.Delete(
@@ -171,7 +108,7 @@ case class SQLiteDatabaseMutactionExecutor(
             where = toNodeSelector(m.where),
           ))
         val envelope = prisma.protocol.DatabaseMutaction(projectJson, None, protoMutaction)
-        top_level_mutaction_interpreter(envelope, m)
+        forwarding_dbio(envelope, m)
 
       case m: TopLevelDeleteNodes =>
         val protoMutaction = prisma.protocol.DatabaseMutaction.Type.DeleteNodes(
@@ -181,141 +118,16 @@ case class SQLiteDatabaseMutactionExecutor(
             filter = toProtocolFilter(m.whereFilter.getOrElse(AndFilter(Vector.empty))),
           ))
         val envelope = prisma.protocol.DatabaseMutaction(projectJson, None, protoMutaction)
-        top_level_mutaction_interpreter(envelope, m)
+        forwarding_dbio(envelope, m)
 
       case m: ResetData =>
         val protoMutaction = prisma.protocol.DatabaseMutaction.Type.Reset(prisma.protocol.ResetData())
         val envelope       = prisma.protocol.DatabaseMutaction(projectJson, None, protoMutaction)
-        top_level_mutaction_interpreter(envelope, m)
-
-      case m: ImportNodes       => ImportNodesInterpreter(m)
-      case m: ImportRelations   => ImportRelationsInterpreter(m)
-      case m: ImportScalarLists => ImportScalarListsInterpreter(m)
-    }
-  }
-
-  def interpreterFor(mutaction: NestedDatabaseMutaction, parentId: IdGCValue): NestedDatabaseMutactionInterpreter = {
-    import com.prisma.shared.models.ProjectJsonFormatter._
-    val projectJson = ByteString.copyFromUtf8(Json.toJson(mutaction.project).toString())
-    val headerName  = mutaction.getClass.getSimpleName
-    val prismaId    = toPrismaId(parentId)
-
-    mutaction match {
-      case m: NestedCreateNode =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedCreate(
-          nestedCreateNodeToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-
-        val errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = {
-          case Error.Value.RelationViolation(rv) => throw RelationViolation(rv.relationName, rv.modelAName, rv.modelBName)
-        }
-
-        nested_mutaction_interpreter(envelope, m, errorHandler)
-
-      case m: NestedUpdateNode =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedUpdate(
-          nestedUpdateNodeToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-
-        val mapNodeSelector = (ns: protocol.NodeSelector) => {
-          NodeSelectorInfo(ns.modelName, ns.fieldName, toGcValue(ns.value.prismaValue))
-        }
-
-        val errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = {
-          case Error.Value.NodesNotConnected(rv) =>
-            throw NodesNotConnected(rv.relationName, rv.parentName, rv.parentWhere.map(mapNodeSelector), rv.childName, rv.childWhere.map(mapNodeSelector))
-        }
-
-        nested_mutaction_interpreter(envelope, m, errorHandler)
-
-      case m: NestedUpsertNode =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedUpsert(
-          nestedUpsertToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-        nested_mutaction_interpreter(envelope, m)
-
-      case m: NestedConnect =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedConnect(
-          nestedConnectToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-
-        val errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = {
-          case Error.Value.NodeNotFoundForWhere(_) => throw NodeNotFoundForWhereError(m.where)
-          case Error.Value.RelationViolation(rv)   => throw RelationViolation(rv.relationName, rv.modelAName, rv.modelBName)
-        }
-
-        nested_mutaction_interpreter(envelope, m, errorHandler)
-
-      case m: NestedDisconnect =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedDisconnect(
-          nestedDisconnectToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-
-        val mapNodeSelector = (ns: protocol.NodeSelector) => {
-          NodeSelectorInfo(ns.modelName, ns.fieldName, toGcValue(ns.value.prismaValue))
-        }
-
-        val errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = {
-          case Error.Value.NodesNotConnected(rv) =>
-            throw NodesNotConnected(rv.relationName, rv.parentName, rv.parentWhere.map(mapNodeSelector), rv.childName, rv.childWhere.map(mapNodeSelector))
-          case Error.Value.RelationViolation(rv) => throw RelationViolation(rv.relationName, rv.modelAName, rv.modelBName)
-        }
-
-        nested_mutaction_interpreter(envelope, m, errorHandler)
+        forwarding_dbio(envelope, m)
 
-      case m: NestedSet =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedSet(
-          nestedSetToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-        nested_mutaction_interpreter(envelope, m)
-
-      case m: NestedUpdateNodes =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedUpdateNodes(
-          nestedUpdateManyToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-        nested_mutaction_interpreter(envelope, m)
-
-      case m: NestedDeleteNodes =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedDeleteNodes(
-          nestedDeleteManyToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-        val mapNodeSelector = (ns: protocol.NodeSelector) => {
-          NodeSelectorInfo(ns.modelName, ns.fieldName, toGcValue(ns.value.prismaValue))
-        }
-
-        val errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = {
-          case Error.Value.NodesNotConnected(rv) =>
-            throw NodesNotConnected(rv.relationName, rv.parentName, rv.parentWhere.map(mapNodeSelector), rv.childName, rv.childWhere.map(mapNodeSelector))
-          case Error.Value.RelationViolation(rv) => throw RelationViolation(rv.relationName, rv.modelAName, rv.modelBName)
-        }
-
-        nested_mutaction_interpreter(envelope, m, errorHandler)
-
-      case m: NestedDeleteNode =>
-        val protoMutaction = prisma.protocol.DatabaseMutaction.Type.NestedDelete(
-          nestedDeleteToProtocol(m)
-        )
-        val envelope = prisma.protocol.DatabaseMutaction(projectJson, Some(prismaId), protoMutaction)
-
-        val mapNodeSelector = (ns: protocol.NodeSelector) => {
-          NodeSelectorInfo(ns.modelName, ns.fieldName, toGcValue(ns.value.prismaValue))
-        }
-
-        val errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = {
-          case Error.Value.NodesNotConnected(rv) =>
-            throw NodesNotConnected(rv.relationName, rv.parentName, rv.parentWhere.map(mapNodeSelector), rv.childName, rv.childWhere.map(mapNodeSelector))
-          case Error.Value.RelationViolation(rv) => throw RelationViolation(rv.relationName, rv.modelAName, rv.modelBName)
-        }
-
-        nested_mutaction_interpreter(envelope, m, errorHandler)
+      case m: ImportNodes       => singleResult(ImportNodesInterpreter(m), mutationBuilder)
+      case m: ImportRelations   => singleResult(ImportRelationsInterpreter(m), mutationBuilder)
+      case m: ImportScalarLists => singleResult(ImportScalarListsInterpreter(m), mutationBuilder)
     }
   }
 
@@ -456,74 +268,103 @@ case class SQLiteDatabaseMutactionExecutor(
     )
   }
 
-  private def top_level_mutaction_interpreter(
+  private def singleResult(
+      interpreter: TopLevelDatabaseMutactionInterpreter,
+      mutationBuilder: JdbcActionsBuilder
+  ): DBIO[MutactionResults] = {
+    interpreter.dbioActionWithErrorMapped(mutationBuilder).map(result => MutactionResults(Vector(result)))
+  }
+
+  private def forwarding_dbio(
       protoMutaction: prisma.protocol.DatabaseMutaction,
-      mutaction: DatabaseMutaction,
+      mutaction: TopLevelDatabaseMutaction,
       errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = PartialFunction.empty
-  ): TopLevelDatabaseMutactionInterpreter = {
-
-    new TopLevelDatabaseMutactionInterpreter {
-      override protected def dbioAction(mutationBuilder: JdbcActionsBuilder): DBIO[DatabaseMutactionResult] = {
-        forwarding_dbio(protoMutaction, mutaction, errorHandler)
-      }
+  ): DBIO[MutactionResults] = {
+    val action = SimpleDBIO { _ =>
+      val executionResults = NativeBinding.execute_mutaction(protoMutaction, errorHandler)
+      MutactionResults(executionResults.results.map(toMutactionResult(mutaction, _)).toVector)
+    }
 
-      override val errorMapper: PartialFunction[Throwable, APIErrors.ClientApiError] = sharedErrorMapper
+    action.asTry.flatMap {
+      case Success(results) => DBIO.successful(results)
+      case Failure(e)       => DBIO.failed(sharedErrorMapper.lift(e).getOrElse(e))
     }
   }
 
-  private def nested_mutaction_interpreter(
-      protoMutaction: prisma.protocol.DatabaseMutaction,
-      mutaction: DatabaseMutaction,
-      errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = PartialFunction.empty
-  ): NestedDatabaseMutactionInterpreter = {
+  // Records deleted through a cascade have no mutaction of their own, the other results belong to the mutaction at their path.
+  private def toMutactionResult(
+      executed: TopLevelDatabaseMutaction,
+      result: prisma.protocol.DatabaseMutactionResult
+  ): DatabaseMutactionResult = {
+    result.cascadedModel match {
+      case Some(modelName) => cascadedDeleteResult(executed.project, modelName, result)
+      case None            => resultOf(mutactionAt(executed, result.path), result)
+    }
+  }
 
-    new NestedDatabaseMutactionInterpreter {
-      override protected def dbioAction(mutationBuilder: JdbcActionsBuilder, parentId: IdGCValue): DBIO[DatabaseMutactionResult] = {
-        forwarding_dbio(protoMutaction, mutaction, errorHandler)
+  private def mutactionAt(executed: DatabaseMutaction, path: Seq[prisma.protocol.NestedPosition]): DatabaseMutaction = {
+    path.foldLeft[DatabaseMutaction](executed) { (mutaction, position) =>
+      (mutaction, position.field) match {
+        case (m: UpsertNode, "create")                   => m.create
+        case (m: UpsertNode, "update")                   => m.update
+        case (m: FurtherNestedMutaction, "creates")      => m.nestedCreates(position.index)
+        case (m: FurtherNestedMutaction, "updates")      => m.nestedUpdates(position.index)
+        case (m: FurtherNestedMutaction, "upserts")      => m.nestedUpserts(position.index)
+        case (m: FurtherNestedMutaction, "deletes")      => m.nestedDeletes(position.index)
+        case (m: FurtherNestedMutaction, "connects")     => m.nestedConnects(position.index)
+        case (m: FurtherNestedMutaction, "disconnects")  => m.nestedDisconnects(position.index)
+        case (m: FurtherNestedMutaction, "sets")         => m.nestedSets(position.index)
+        case (m: FurtherNestedMutaction, "update_manys") => m.nestedUpdateManys(position.index)
+        case (m: FurtherNestedMutaction, "delete_manys") => m.nestedDeleteManys(position.index)
+        case (m, field)                                  => sys.error(s"mutaction [$m] has no nested mutaction at [$field]")
       }
+    }
+  }
 
-      override val errorMapper: PartialFunction[Throwable, APIErrors.ClientApiError] = sharedErrorMapper
+  private def cascadedDeleteResult(project: Project, modelName: String, result: prisma.protocol.DatabaseMutactionResult): DatabaseMutactionResult = {
+    val model = project.schema.getModelByName_!(modelName)
+
+    result.`type` match {
+      case prisma.protocol.DatabaseMutactionResult.Type.Delete(nodeResult) =>
+        val node = transformNode((nodeResult.node, nodeResult.fields.toVector), model)
+        DeleteNodeResult(node, TopLevelDeleteNode(project, NodeSelector.forId(model, node.id), node))
+
+      case x =>
+        sys.error(s"cascaded result of type [$x] is disallowed here")
     }
   }
 
-  private def forwarding_dbio(
-      protoMutaction: prisma.protocol.DatabaseMutaction,
-      mutaction: DatabaseMutaction,
-      errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable] = PartialFunction.empty
-  ): DBIO[DatabaseMutactionResult] = {
-    SimpleDBIO { _ =>
-      val executionResult = NativeBinding.execute_mutaction(protoMutaction, errorHandler)
-      executionResult.`type` match {
-        case prisma.protocol.DatabaseMutactionResult.Type.Create(result) =>
-          val m = mutaction match {
-            case m: CreateNode => m
-            case m: UpsertNode => m.create
-            case m             => sys.error(s"mutaction of type [$m] is disallowed here")
-          }
-
-          CreateNodeResult(toIdGcValue(result.id), m)
-
-        case prisma.protocol.DatabaseMutactionResult.Type.Update(result) =>
-          val m = mutaction match {
-            case m: UpdateNode => m
-            case m: UpsertNode => m.update
-            case m             => sys.error(s"mutaction of type [$m] is disallowed here")
-          }
-
-          UpdateNodeResult(toIdGcValue(result.id), PrismaNode.dummy, m)
-
-        case prisma.protocol.DatabaseMutactionResult.Type.Delete(_) =>
-          DeleteNodeResult(PrismaNode.dummy, mutaction.asInstanceOf[DeleteNode])
-
-        case prisma.protocol.DatabaseMutactionResult.Type.Many(result) =>
-          ManyNodesResult(mutaction.asInstanceOf[FinalMutaction], result.count)
-
-        case prisma.protocol.DatabaseMutactionResult.Type.Unit(_) =>
-          UnitDatabaseMutactionResult
-
-        case prisma.protocol.DatabaseMutactionResult.Type.Empty =>
-          UnitDatabaseMutactionResult
-      }
+  private def resultOf(mutaction: DatabaseMutaction, result: prisma.protocol.DatabaseMutactionResult): DatabaseMutactionResult = {
+    result.`type` match {
+      case prisma.protocol.DatabaseMutactionResult.Type.Create(result) =>
+        val m = mutaction match {
+          case m: CreateNode => m
+          case m: UpsertNode => m.create
+          case m             => sys.error(s"mutaction of type [$m] is disallowed here")
+        }
+
+        CreateNodeResult(toIdGcValue(result.id), m)
+
+      case prisma.protocol.DatabaseMutactionResult.Type.Update(result) =>
+        val m = mutaction match {
+          case m: UpdateNode => m
+          case m: UpsertNode => m.update
+          case m             => sys.error(s"mutaction of type [$m] is disallowed here")
+        }
+
+        UpdateNodeResult(toIdGcValue(result.id), PrismaNode.dummy, m)
+
+      case prisma.protocol.DatabaseMutactionResult.Type.Delete(_) =>
+        DeleteNodeResult(PrismaNode.dummy, mutaction.asInstanceOf[DeleteNode])
+
+      case prisma.protocol.DatabaseMutactionResult.Type.Many(result) =>
+        ManyNodesResult(mutaction.asInstanceOf[FinalMutaction], result.count)
+
+      case prisma.protocol.DatabaseMutactionResult.Type.Unit(_) =>
+        UnitDatabaseMutactionResult
+
+      case prisma.protocol.DatabaseMutactionResult.Type.Empty =>
+        UnitDatabaseMutactionResult
     }
   }
 
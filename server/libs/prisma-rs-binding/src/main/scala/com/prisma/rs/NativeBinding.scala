@@ -76,10 +76,9 @@ object NativeBinding {
   def execute_mutaction(
       input: DatabaseMutaction,
       errorHandler: PartialFunction[prisma.protocol.Error.Value, Throwable]
-  ): DatabaseMutactionResult = {
+  ): DatabaseMutactionResults = {
     val (pointer, length) = writeBuffer(input)
-    // FIXME: this must return proper result intead of this int
-    handleProtoResult(library.execute_mutaction(pointer, length), errorHandler) { x: DatabaseMutactionResult =>
+    handleProtoResult(library.execute_mutaction(pointer, length), errorHandler) { x: DatabaseMutactionResults =>
       x
     }
   }
@@ -109,7 +108,7 @@ object NativeBinding {
           case Result.Value.Integer(value) =>
             processMessage(value.asInstanceOf[T])
 
-          case Result.Value.MutactionResult(value) =>
+          case Result.Value.MutactionResults(value) =>
             processMessage(value.asInstanceOf[T])
 
           case Result.Value.Empty =>
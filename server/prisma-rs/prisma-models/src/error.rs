@@ -23,4 +23,12 @@ pub enum DomainError {
     #[fail(display = "Model id `{}` for relation `{}` not found", model_id, relation)]
     ModelForRelationNotFound { model_id: String, relation: String },
 
+    #[fail(display = "Unique constraint `{}` on model `{}` not found", name, model)]
+    UniqueConstraintNotFound { name: String, model: String },
+
+    #[fail(display = "Invalid selector on model `{}`, {}", model, reason)]
+    InvalidNodeSelector { model: String, reason: String },
+
+    #[fail(display = "Invalid JSON path `{}`, {}", path, reason)]
+    InvalidJsonPath { path: String, reason: String },
 }
@@ -88,6 +88,7 @@ impl FieldTemplate {
                     is_auto_generated: st.is_auto_generated,
                     manifestation: st.manifestation,
                     behaviour: st.behaviour,
+                    internal_enum: st.internal_enum,
                     model,
                 };
 
@@ -24,6 +24,8 @@ pub struct ScalarFieldTemplate {
     pub is_auto_generated: bool,
     pub manifestation: Option<FieldManifestation>,
     pub behaviour: Option<FieldBehaviour>,
+    #[serde(rename = "enum")]
+    pub internal_enum: Option<PrismaEnum>,
 }
 
 #[derive(DebugStub)]
@@ -37,6 +39,7 @@ pub struct ScalarField {
     pub is_auto_generated: bool,
     pub manifestation: Option<FieldManifestation>,
     pub behaviour: Option<FieldBehaviour>,
+    pub internal_enum: Option<PrismaEnum>,
     #[debug_stub = "#ModelWeakRef#"]
     pub model: ModelWeakRef,
 }
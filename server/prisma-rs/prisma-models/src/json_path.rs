@@ -0,0 +1,156 @@
+use crate::DomainError;
+use serde_json::Value;
+use std::{fmt, str::FromStr};
+
+/// A path into a JSON document in the syntax of SQLite and MySQL, e.g.
+/// `$.address."street name"[0]`. The path starts with `$` for the root,
+/// followed by object keys and array indexes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    pub segments: Vec<JsonPathSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl JsonPath {
+    /// The value at the path, if it exists.
+    pub fn get<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.segments.iter().try_fold(value, |value, segment| match segment {
+            JsonPathSegment::Key(key) => value.get(key.as_str()),
+            JsonPathSegment::Index(index) => value.get(*index),
+        })
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = DomainError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| DomainError::InvalidJsonPath {
+            path: path.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let mut chars = path.chars().peekable();
+        let mut segments = Vec::new();
+
+        if chars.next() != Some('$') {
+            return Err(invalid("it must start with `$`"));
+        }
+
+        while let Some(c) = chars.next() {
+            match c {
+                '.' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    let mut key = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => key.push(c),
+                            None => return Err(invalid("a quoted key is not terminated")),
+                        }
+                    }
+
+                    segments.push(JsonPathSegment::Key(key));
+                }
+                '.' => {
+                    let mut key = String::new();
+
+                    while let Some(c) = chars.peek().cloned() {
+                        if c == '.' || c == '[' {
+                            break;
+                        }
+
+                        key.push(c);
+                        chars.next();
+                    }
+
+                    if key.is_empty() {
+                        return Err(invalid("keys can't be empty unless quoted"));
+                    }
+
+                    segments.push(JsonPathSegment::Key(key));
+                }
+                '[' => {
+                    let mut index = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => index.push(c),
+                            None => return Err(invalid("an array index is not terminated")),
+                        }
+                    }
+
+                    let index = index
+                        .parse()
+                        .map_err(|_| invalid("array indexes must be non-negative integers"))?;
+
+                    segments.push(JsonPathSegment::Index(index));
+                }
+                _ => return Err(invalid("keys must start with `.` and array indexes with `[`")),
+            }
+        }
+
+        Ok(JsonPath { segments })
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "$")?;
+
+        for segment in self.segments.iter() {
+            match segment {
+                JsonPathSegment::Key(key) if key.is_empty() || key.contains(|c| c == '.' || c == '[') => {
+                    write!(f, ".\"{}\"", key)?
+                }
+                JsonPathSegment::Key(key) => write!(f, ".{}", key)?,
+                JsonPathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_display() {
+        let path: JsonPath = "$.address.\"street.name\"[1]".parse().unwrap();
+
+        assert_eq!(
+            vec![
+                JsonPathSegment::Key(String::from("address")),
+                JsonPathSegment::Key(String::from("street.name")),
+                JsonPathSegment::Index(1),
+            ],
+            path.segments
+        );
+
+        assert_eq!("$.address.\"street.name\"[1]", path.to_string());
+        assert!("address".parse::<JsonPath>().is_err());
+        assert!("$.tags[first]".parse::<JsonPath>().is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let value = json!({ "tags": ["a", { "b": null }] });
+
+        let get = |path: &str| path.parse::<JsonPath>().unwrap().get(&value).cloned();
+
+        assert_eq!(Some(json!("a")), get("$.tags[0]"));
+        assert_eq!(Some(json!(null)), get("$.tags[1].b"));
+        assert_eq!(None, get("$.tags[2]"));
+        assert_eq!(Some(value.clone()), get("$"));
+    }
+}
@@ -8,6 +8,7 @@ extern crate debug_stub_derive;
 mod error;
 mod field;
 mod fields;
+mod json_path;
 mod model;
 mod node;
 mod order_by;
@@ -24,6 +25,7 @@ pub mod prelude;
 pub use error::*;
 pub use field::*;
 pub use fields::*;
+pub use json_path::*;
 pub use model::*;
 pub use node::*;
 pub use order_by::*;
@@ -15,6 +15,12 @@ pub struct ModelTemplate {
     pub is_embedded: bool,
     pub fields: Vec<FieldTemplate>,
     pub manifestation: Option<ModelManifestation>,
+
+    #[serde(default)]
+    pub unique_constraints: Vec<UniqueConstraint>,
+
+    #[serde(default)]
+    pub is_searchable: bool,
 }
 
 #[derive(DebugStub)]
@@ -23,6 +29,10 @@ pub struct Model {
     pub stable_identifier: String,
     pub is_embedded: bool,
     pub manifestation: Option<ModelManifestation>,
+    pub unique_constraints: Vec<UniqueConstraint>,
+
+    /// Whether the string fields of the model are indexed for full-text search.
+    pub is_searchable: bool,
 
     fields: OnceCell<Fields>,
 
@@ -36,6 +46,16 @@ pub struct ModelManifestation {
     pub db_name: String,
 }
 
+/// A unique constraint over several scalar fields of a model, e.g. the
+/// natural key `(tenantId, slug)`. Nodes can be selected by the values of all
+/// its fields, given under the name of the constraint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
 impl ModelTemplate {
     pub fn build(self, schema: SchemaWeakRef) -> ModelRef {
         let model = Arc::new(Model {
@@ -44,6 +64,8 @@ impl ModelTemplate {
             is_embedded: self.is_embedded,
             fields: OnceCell::new(),
             manifestation: self.manifestation,
+            unique_constraints: self.unique_constraints,
+            is_searchable: self.is_searchable,
             schema: schema,
         });
 
@@ -117,4 +139,23 @@ impl Model {
     pub fn id_column(&self) -> Column {
         self.fields().id().as_column()
     }
+
+    pub fn find_unique_constraint(&self, name: &str) -> DomainResult<&UniqueConstraint> {
+        self.unique_constraints
+            .iter()
+            .find(|constraint| constraint.name == name)
+            .ok_or_else(|| DomainError::UniqueConstraintNotFound {
+                name: name.to_string(),
+                model: self.name.clone(),
+            })
+    }
+
+    /// The fields of the unique constraint, in the order of the constraint.
+    pub fn unique_constraint_fields(&self, constraint: &UniqueConstraint) -> DomainResult<Vec<Arc<ScalarField>>> {
+        constraint
+            .fields
+            .iter()
+            .map(|name| self.fields().find_from_scalar(name))
+            .collect()
+    }
 }
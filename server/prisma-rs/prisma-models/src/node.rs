@@ -25,9 +25,24 @@ impl SingleNode {
 pub struct ManyNodes {
     pub nodes: Vec<Node>,
     pub field_names: Vec<String>,
+
+    /// Whether more nodes exist after the nodes of a paginated query
+    pub has_next_page: bool,
+
+    /// Whether more nodes exist before the nodes of a paginated query
+    pub has_previous_page: bool,
 }
 
 impl ManyNodes {
+    pub fn new(nodes: Vec<Node>, field_names: Vec<String>) -> Self {
+        Self {
+            nodes,
+            field_names,
+            has_next_page: false,
+            has_previous_page: false,
+        }
+    }
+
     pub fn into_single_node(mut self) -> Option<SingleNode> {
         self.nodes.reverse();
         let node = self.nodes.pop();
@@ -1,4 +1,4 @@
-use crate::{ModelRef, ScalarField};
+use crate::{JsonPath, ModelRef, RelationField, ScalarField};
 use std::sync::Arc;
 
 pub trait IntoOrderBy {
@@ -11,8 +11,31 @@ pub enum SortOrder {
     Descending,
 }
 
+/// Where nodes without a value are placed, regardless of the sort order.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
 #[derive(Clone, Debug)]
 pub struct OrderBy {
     pub field: Arc<ScalarField>,
+    /// The to-one relation fields leading from the ordered model to the model
+    /// of the field, e.g. `author` when ordering posts by `author.name`. Empty
+    /// for fields of the ordered model.
+    pub relations: Vec<Arc<RelationField>>,
     pub sort_order: SortOrder,
+    /// Defaults to the placement of the database, which for SQLite is first
+    /// when ascending and last when descending.
+    pub nulls: Option<NullsOrder>,
+    /// Orders by the relevance of the field for a full-text search query
+    /// instead of the field value. Descending puts the best matches first.
+    pub search: Option<String>,
+    /// Orders by the value at the path of a JSON field instead of the whole
+    /// document.
+    pub path: Option<JsonPath>,
+    /// Orders by the number of nodes related through the list relation field
+    /// instead of a value, `field` being the id of the ordered model.
+    pub related_count: Option<Arc<RelationField>>,
 }
@@ -1,21 +1,67 @@
-use crate::prelude::{ModelRef, PrismaValue};
+use crate::prelude::{ModelRef, PrismaListValue, PrismaValue};
 use chrono::Utc;
 use std::collections::{btree_map::Keys, BTreeMap};
 
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct PrismaArgs {
     pub args: BTreeMap<String, PrismaValue>,
+    /// Operations on the current values of numeric fields, only applied when
+    /// updating.
+    pub operations: BTreeMap<String, UpdateOperation>,
+}
+
+/// A change to the value of a field in an update. All but `Set` compute the
+/// new value from the current one in the database, so concurrent updates of
+/// e.g. a counter don't overwrite each other.
+#[derive(Debug, PartialEq, Clone)]
+pub enum UpdateOperation {
+    Set(PrismaValue),
+    Increment(PrismaValue),
+    Decrement(PrismaValue),
+    Multiply(PrismaValue),
+    Divide(PrismaValue),
+}
+
+impl UpdateOperation {
+    pub fn value(&self) -> &PrismaValue {
+        match self {
+            UpdateOperation::Set(value) => value,
+            UpdateOperation::Increment(value) => value,
+            UpdateOperation::Decrement(value) => value,
+            UpdateOperation::Multiply(value) => value,
+            UpdateOperation::Divide(value) => value,
+        }
+    }
+}
+
+/// A change to the values of a scalar list field in an update. All but `Set`
+/// keep the other values and their order, without rewriting the list.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScalarListOperation {
+    /// Replaces all values.
+    Set(PrismaListValue),
+    /// Appends the values to the end.
+    Push(PrismaListValue),
+    /// Prepends the values to the start, in their order.
+    Unshift(PrismaListValue),
+    /// Removes the value at the zero-based index, if any.
+    RemoveAt(usize),
+    /// Removes every occurrence of the value.
+    RemoveValue(PrismaValue),
 }
 
 impl From<BTreeMap<String, PrismaValue>> for PrismaArgs {
     fn from(args: BTreeMap<String, PrismaValue>) -> Self {
-        Self { args }
+        Self {
+            args,
+            operations: BTreeMap::new(),
+        }
     }
 }
 
 impl PrismaArgs {
     pub fn new() -> Self {
-        Self { args: BTreeMap::new() }
+        Self::default()
     }
 
     pub fn insert<T, V>(&mut self, key: T, arg: V)
@@ -26,6 +72,26 @@ impl PrismaArgs {
         self.args.insert(key.into(), arg.into());
     }
 
+    /// Adds an operation on the field. Setting a value is the same as
+    /// inserting it.
+    pub fn insert_operation<T>(&mut self, key: T, operation: UpdateOperation)
+    where
+        T: Into<String>,
+    {
+        match operation {
+            UpdateOperation::Set(value) => {
+                self.args.insert(key.into(), value);
+            }
+            operation => {
+                self.operations.insert(key.into(), operation);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty() && self.operations.is_empty()
+    }
+
     pub fn has_arg_for(&self, field: &str) -> bool {
         self.args.contains_key(field)
     }
@@ -63,7 +129,7 @@ impl PrismaArgs {
     }
 
     pub fn update_datetimes(&mut self, model: ModelRef, list_causes_update: bool) {
-        if !self.args.is_empty() || list_causes_update {
+        if !self.is_empty() || list_causes_update {
             if let Some(field) = model.fields().updated_at() {
                 self.args
                     .insert(field.name.to_string(), PrismaValue::DateTime(Utc::now()));
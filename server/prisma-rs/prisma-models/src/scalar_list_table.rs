@@ -21,6 +21,10 @@ impl<'a> ScalarListTable<'a> {
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.table_name
+    }
+
     pub fn table(&self) -> Table {
         let schema = self.parent_field.schema();
         let database_name = schema.db_name.as_ref();
@@ -106,6 +106,16 @@ impl SelectedFields {
         self.scalar.iter().filter(|sf| sf.implicit).collect()
     }
 
+    /// Selected scalar list fields. They are not part of the columns,
+    /// as their values are stored in a separate table per field.
+    pub fn scalar_lists(&self) -> Vec<Arc<ScalarField>> {
+        self.scalar
+            .iter()
+            .filter(|sf| sf.field.is_list)
+            .map(|sf| sf.field.clone())
+            .collect()
+    }
+
     pub fn add_scalar(&mut self, field: Arc<ScalarField>, implicit: bool) {
         self.columns = OnceCell::new();
         self.scalar.push(SelectedScalarField { field, implicit });
@@ -0,0 +1,70 @@
+use prisma_models::{PrismaValue, ScalarField, ScalarFieldRef, TypeIdentifier};
+
+/// An aggregate function computed over the nodes of a query. Functions of a
+/// field skip nodes without a value.
+#[derive(Debug, Clone)]
+pub enum Aggregation {
+    Count,
+    Sum(ScalarFieldRef),
+    Avg(ScalarFieldRef),
+    Min(ScalarFieldRef),
+    Max(ScalarFieldRef),
+}
+
+impl Aggregation {
+    /// The name of the function, as selected in `aggregate`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregation::Count => "count",
+            Aggregation::Sum(_) => "sum",
+            Aggregation::Avg(_) => "avg",
+            Aggregation::Min(_) => "min",
+            Aggregation::Max(_) => "max",
+        }
+    }
+
+    pub fn field(&self) -> Option<&ScalarFieldRef> {
+        match self {
+            Aggregation::Count => None,
+            Aggregation::Sum(field) | Aggregation::Avg(field) | Aggregation::Min(field) | Aggregation::Max(field) => {
+                Some(field)
+            }
+        }
+    }
+
+    /// The type of the result. Averages are always floats, the other
+    /// functions of a field keep its type.
+    pub fn type_identifier(&self) -> TypeIdentifier {
+        match self {
+            Aggregation::Count => TypeIdentifier::Int,
+            Aggregation::Avg(_) => TypeIdentifier::Float,
+            Aggregation::Sum(field) | Aggregation::Min(field) | Aggregation::Max(field) => field.type_identifier,
+        }
+    }
+
+    /// Sums and averages need numbers.
+    pub fn is_numeric(field: &ScalarField) -> bool {
+        !field.is_list
+            && match field.type_identifier {
+                TypeIdentifier::Int | TypeIdentifier::Float => true,
+                _ => false,
+            }
+    }
+
+    /// Minimums and maximums need values with an order.
+    pub fn is_comparable(field: &ScalarField) -> bool {
+        !field.is_list && field.type_identifier != TypeIdentifier::Json
+    }
+}
+
+/// The results of the aggregations for a group of nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateResult {
+    /// The values of the grouping fields the nodes share, in their order.
+    /// Empty if not grouped.
+    pub group: Vec<PrismaValue>,
+
+    /// The results of the aggregations, in their order. Aggregations of a
+    /// field are null if no node has a value.
+    pub values: Vec<PrismaValue>,
+}
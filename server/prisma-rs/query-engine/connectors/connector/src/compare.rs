@@ -1,5 +1,5 @@
-use crate::filter::Filter;
-use prisma_models::PrismaValue;
+use crate::filter::{CountCondition, Filter};
+use prisma_models::{JsonPath, PrismaValue};
 
 /// Comparing methods for scalars.
 pub trait ScalarCompare {
@@ -35,6 +35,70 @@ pub trait ScalarCompare {
     where
         T: Into<PrismaValue>;
 
+    fn like<T>(&self, pattern: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn like_insensitive<T>(&self, pattern: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn search<T>(&self, query: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn contains_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn not_contains_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn starts_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn not_starts_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn ends_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn not_ends_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn like_sensitive<T>(&self, pattern: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn contains_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn not_contains_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn starts_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn not_starts_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn ends_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn not_ends_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
     fn less_than<T>(&self, val: T) -> Filter
     where
         T: Into<PrismaValue>;
@@ -52,6 +116,46 @@ pub trait ScalarCompare {
         T: Into<PrismaValue>;
 }
 
+/// Comparing methods for scalar lists.
+pub trait ScalarListCompare {
+    fn contains_element<T>(&self, value: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn contains_every_element<T>(&self, values: Vec<T>) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn contains_some_element<T>(&self, values: Vec<T>) -> Filter
+    where
+        T: Into<PrismaValue>;
+}
+
+/// Comparing methods for paths of JSON fields.
+pub trait JsonCompare {
+    fn json_path_equals<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn json_path_contains<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn json_path_contains_insensitive<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn json_path_contains_sensitive<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn json_array_contains<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>;
+
+    fn json_key_exists(&self, path: JsonPath) -> Filter;
+}
+
 pub trait RelationCompare {
     fn every_related<T>(&self, filter: T) -> Filter
     where
@@ -65,5 +169,9 @@ pub trait RelationCompare {
     where
         T: Into<Filter>;
 
+    fn related_count<T>(&self, filter: T, condition: CountCondition) -> Filter
+    where
+        T: Into<Filter>;
+
     fn one_relation_is_null(&self) -> Filter;
 }
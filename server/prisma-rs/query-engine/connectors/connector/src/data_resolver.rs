@@ -1,4 +1,4 @@
-use crate::{filter::NodeSelector, query_arguments::QueryArguments, ConnectorResult};
+use crate::{filter::NodeSelector, query_arguments::QueryArguments, AggregateResult, Aggregation, ConnectorResult};
 use prisma_models::prelude::*;
 use prisma_models::ScalarFieldRef;
 
@@ -32,6 +32,27 @@ pub trait DataResolver {
 
     fn count_by_model(&self, model: ModelRef, query_arguments: QueryArguments) -> ConnectorResult<usize>;
     fn count_by_table(&self, database: &str, table: &str) -> ConnectorResult<usize>;
+
+    /// Counts the related nodes of each parent matching the query arguments,
+    /// in the order of `from_node_ids`. Pagination is ignored.
+    fn count_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> ConnectorResult<Vec<usize>>;
+
+    /// Computes the aggregations over the nodes of the query, after filtering
+    /// and pagination. Without grouping fields all nodes form a single group,
+    /// otherwise the nodes are grouped by the values of the fields, ordered by
+    /// them.
+    fn aggregate(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        aggregations: &[Aggregation],
+        group_by: &[ScalarFieldRef],
+    ) -> ConnectorResult<Vec<AggregateResult>>;
 }
 
 pub struct ScalarListValues {
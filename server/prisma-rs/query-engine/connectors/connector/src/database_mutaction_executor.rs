@@ -8,10 +8,13 @@ use serde_json::Value;
 pub trait DatabaseMutactionExecutor {
     fn execute_raw(&self, _query: String) -> ConnectorResult<Value>;
 
+    /// Executes the mutaction and its nested mutactions in one transaction. The
+    /// result of the given mutaction is the last one. Nested mutactions are
+    /// executed in relation to the given parent.
     fn execute(
         &self,
         db_name: String,
         mutaction: DatabaseMutaction,
-        parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
+        parent_id: Option<GraphqlId>,
     ) -> ConnectorResult<DatabaseMutactionResults>;
 }
@@ -8,36 +8,49 @@ use std::fmt;
 #[derive(Debug)]
 pub struct NodeSelectorInfo {
     pub model: String,
-    pub field: String,
-    pub value: PrismaValue,
+    /// The selecting fields and their values, a single one unless the node
+    /// was selected by a multi-field unique constraint.
+    pub fields: Vec<(String, PrismaValue)>,
 }
 
 impl NodeSelectorInfo {
     pub fn for_id(model: ModelRef, value: &GraphqlId) -> Self {
         Self {
             model: model.name.clone(),
-            field: model.fields().id().name.clone(),
-            value: PrismaValue::from(value.clone()),
+            fields: vec![(model.fields().id().name.clone(), PrismaValue::from(value.clone()))],
         }
     }
 }
 
 impl fmt::Display for NodeSelectorInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "field {} in model {} with value {}",
-            self.model, self.field, self.value
-        )
+        match self.fields.as_slice() {
+            [(field, value)] => write!(f, "field {} in model {} with value {}", field, self.model, value),
+            fields => {
+                let names: Vec<&str> = fields.iter().map(|(field, _)| field.as_str()).collect();
+                let values: Vec<String> = fields.iter().map(|(_, value)| value.to_string()).collect();
+
+                write!(
+                    f,
+                    "fields ({}) in model {} with values ({})",
+                    names.join(", "),
+                    self.model,
+                    values.join(", ")
+                )
+            }
+        }
     }
 }
 
 impl From<&NodeSelector> for NodeSelectorInfo {
     fn from(ns: &NodeSelector) -> Self {
         Self {
-            model: ns.field.model().name.clone(),
-            field: ns.field.name.clone(),
-            value: ns.value.clone(),
+            model: ns.model().name.clone(),
+            fields: ns
+                .fields()
+                .into_iter()
+                .map(|(field, value)| (field.name.clone(), value.clone()))
+                .collect(),
         }
     }
 }
@@ -65,6 +78,9 @@ pub enum ConnectorError {
     #[fail(display = "Field cannot be null: {}", field)]
     FieldCannotBeNull { field: String },
 
+    #[fail(display = "Invalid update operation on field {}: {}", field, reason)]
+    InvalidUpdateOperation { field: String, reason: String },
+
     #[fail(display = "{}", _0)]
     DomainError(DomainError),
 
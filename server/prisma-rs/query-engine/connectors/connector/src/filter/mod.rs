@@ -12,6 +12,9 @@ pub use node_selector::*;
 pub use relation::*;
 pub use scalar::*;
 
+use prisma_models::{PrismaValue, ScalarField};
+use std::sync::Arc;
+
 #[derive(Debug, Clone)]
 pub enum Filter {
     And(Vec<Box<Filter>>),
@@ -71,9 +74,23 @@ impl From<bool> for Filter {
 
 impl From<NodeSelector> for Filter {
     fn from(node_selector: NodeSelector) -> Self {
-        Filter::Scalar(ScalarFilter {
-            field: node_selector.field,
-            condition: ScalarCondition::Equals(node_selector.value),
-        })
+        fn equals(field: Arc<ScalarField>, value: PrismaValue) -> Filter {
+            Filter::Scalar(ScalarFilter {
+                field,
+                condition: ScalarCondition::Equals(value),
+                mode: FilterMode::Default,
+            })
+        }
+
+        match node_selector {
+            NodeSelector::Field { field, value } => equals(field, value),
+            NodeSelector::Compound(selector) => Filter::and(
+                selector
+                    .into_fields()
+                    .into_iter()
+                    .map(|(field, value)| equals(field, value))
+                    .collect(),
+            ),
+        }
     }
 }
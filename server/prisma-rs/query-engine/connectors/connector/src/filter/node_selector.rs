@@ -1,10 +1,24 @@
 use prisma_models::prelude::*;
 use std::sync::Arc;
 
+/// Selects a single node by unique values.
 #[derive(Debug, Clone)]
-pub struct NodeSelector {
-    pub field: Arc<ScalarField>,
-    pub value: PrismaValue,
+pub enum NodeSelector {
+    /// The value of a unique field, e.g. the id.
+    Field {
+        field: Arc<ScalarField>,
+        value: PrismaValue,
+    },
+    /// The values of all fields of a multi-field unique constraint.
+    Compound(CompoundNodeSelector),
+}
+
+/// Selects a node by the values of the fields of a multi-field unique
+/// constraint, e.g. `(tenantId, slug)`, in the order of the constraint.
+/// Always has at least one field.
+#[derive(Debug, Clone)]
+pub struct CompoundNodeSelector {
+    fields: Vec<(Arc<ScalarField>, PrismaValue)>,
 }
 
 impl<T> From<(Arc<ScalarField>, T)> for NodeSelector
@@ -12,21 +26,115 @@ where
     T: Into<PrismaValue>,
 {
     fn from(tup: (Arc<ScalarField>, T)) -> NodeSelector {
-        NodeSelector {
+        NodeSelector::Field {
             field: tup.0,
             value: tup.1.into(),
         }
     }
 }
 
+impl From<CompoundNodeSelector> for NodeSelector {
+    fn from(selector: CompoundNodeSelector) -> NodeSelector {
+        NodeSelector::Compound(selector)
+    }
+}
+
 impl NodeSelector {
     pub fn new<T>(field: Arc<ScalarField>, value: T) -> Self
     where
         T: Into<PrismaValue>,
     {
-        Self {
+        NodeSelector::Field {
             field: field,
             value: value.into(),
         }
     }
+
+    pub fn model(&self) -> ModelRef {
+        match self {
+            NodeSelector::Field { field, .. } => field.model(),
+            NodeSelector::Compound(selector) => selector.model(),
+        }
+    }
+
+    /// The selecting fields and their values.
+    pub fn fields(&self) -> Vec<(&Arc<ScalarField>, &PrismaValue)> {
+        match self {
+            NodeSelector::Field { field, value } => vec![(field, value)],
+            NodeSelector::Compound(selector) => selector.fields().iter().map(|(field, value)| (field, value)).collect(),
+        }
+    }
+}
+
+impl CompoundNodeSelector {
+    /// A selector with the values of the fields of the unique constraint, in
+    /// its order.
+    ///
+    /// ```rust
+    /// # use connector::filter::*;
+    /// # use prisma_models::*;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// let user = schema.find_model("User").unwrap();
+    /// let constraint = user.find_unique_constraint("name_id").unwrap();
+    ///
+    /// let selector = CompoundNodeSelector::new(&user, constraint, vec!["Bob", "id1"]).unwrap();
+    /// let names: Vec<&str> = selector.fields().iter().map(|(field, _)| field.name.as_str()).collect();
+    ///
+    /// assert_eq!(vec!["name", "id"], names);
+    /// assert_eq!(PrismaValue::from("Bob"), selector.fields()[0].1);
+    ///
+    /// assert!(CompoundNodeSelector::new(&user, constraint, vec!["Bob"]).is_err());
+    /// ```
+    pub fn new<T>(model: &Model, constraint: &UniqueConstraint, values: Vec<T>) -> DomainResult<Self>
+    where
+        T: Into<PrismaValue>,
+    {
+        let fields = model.unique_constraint_fields(constraint)?;
+
+        if fields.len() != values.len() {
+            return Err(DomainError::InvalidNodeSelector {
+                model: model.name.clone(),
+                reason: format!(
+                    "unique constraint `{}` has {} fields, but {} values were given",
+                    constraint.name,
+                    fields.len(),
+                    values.len()
+                ),
+            });
+        }
+
+        let fields = fields.into_iter().zip(values.into_iter().map(Into::into)).collect();
+
+        Self::from_fields(model, fields)
+    }
+
+    /// A selector with the given fields and values, which have to be the
+    /// fields of a unique constraint of the model. Fails without fields.
+    pub fn from_fields(model: &Model, fields: Vec<(Arc<ScalarField>, PrismaValue)>) -> DomainResult<Self> {
+        if fields.is_empty() {
+            return Err(DomainError::InvalidNodeSelector {
+                model: model.name.clone(),
+                reason: String::from("a compound selector needs at least one field"),
+            });
+        }
+
+        Ok(Self { fields })
+    }
+
+    /// The selecting fields and their values.
+    pub fn fields(&self) -> &[(Arc<ScalarField>, PrismaValue)] {
+        &self.fields
+    }
+
+    pub fn into_fields(self) -> Vec<(Arc<ScalarField>, PrismaValue)> {
+        self.fields
+    }
+
+    pub fn model(&self) -> ModelRef {
+        self.fields[0].0.model()
+    }
 }
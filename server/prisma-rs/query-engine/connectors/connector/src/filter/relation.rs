@@ -21,6 +21,18 @@ pub enum RelationCondition {
     AtLeastOneRelatedNode,
     NoRelatedNode,
     ToOneRelatedNode, // TODO: This is needed for Mongo and should be discussed with Matthias
+    RelatedCount(CountCondition),
+}
+
+/// A comparison of the number of related records matching the nested filter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CountCondition {
+    Equals(usize),
+    NotEquals(usize),
+    LessThan(usize),
+    LessThanOrEquals(usize),
+    GreaterThan(usize),
+    GreaterThanOrEquals(usize),
 }
 
 impl RelationCondition {
@@ -63,6 +75,7 @@ impl RelationCompare for Arc<RelationField> {
     ///             Filter::Scalar(ScalarFilter {
     ///                 field: scalar_field,
     ///                 condition: ScalarCondition::Equals(scalar_val),
+    ///                 ..
     ///             }) => {
     ///                 assert_eq!(String::from("name"), scalar_field.name);
     ///                 assert_eq!(PrismaValue::from("Blog"), scalar_val);
@@ -114,6 +127,7 @@ impl RelationCompare for Arc<RelationField> {
     ///             Filter::Scalar(ScalarFilter {
     ///                 field: scalar_field,
     ///                 condition: ScalarCondition::Equals(scalar_val),
+    ///                 ..
     ///             }) => {
     ///                 assert_eq!(String::from("name"), scalar_field.name);
     ///                 assert_eq!(PrismaValue::from("Blog"), scalar_val);
@@ -165,6 +179,7 @@ impl RelationCompare for Arc<RelationField> {
     ///             Filter::Scalar(ScalarFilter {
     ///                 field: scalar_field,
     ///                 condition: ScalarCondition::Equals(scalar_val),
+    ///                 ..
     ///             }) => {
     ///                 assert_eq!(String::from("name"), scalar_field.name);
     ///                 assert_eq!(PrismaValue::from("Blog"), scalar_val);
@@ -186,6 +201,59 @@ impl RelationCompare for Arc<RelationField> {
         })
     }
 
+    /// The number of related records matching the filter satisfies the
+    /// condition.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let user = schema.find_model("User").unwrap();
+    /// # let site = schema.find_model("Site").unwrap();
+    /// #
+    /// let rel_field = user.fields().find_from_relation_fields("sites").unwrap();
+    /// let site_name = site.fields().find_from_scalar("name").unwrap();
+    /// let filter = rel_field.related_count(site_name.equals("Blog"), CountCondition::GreaterThan(5));
+    ///
+    /// match filter {
+    ///     Filter::Relation(RelationFilter {
+    ///         field: relation_field,
+    ///         nested_filter: nested,
+    ///         condition: condition,
+    ///     }) => {
+    ///         assert_eq!(String::from("sites"), relation_field.name);
+    ///         assert_eq!(RelationCondition::RelatedCount(CountCondition::GreaterThan(5)), condition);
+    ///
+    ///         match *nested {
+    ///             Filter::Scalar(ScalarFilter {
+    ///                 field: scalar_field,
+    ///                 condition: ScalarCondition::Equals(scalar_val),
+    ///                 ..
+    ///             }) => {
+    ///                 assert_eq!(String::from("name"), scalar_field.name);
+    ///                 assert_eq!(PrismaValue::from("Blog"), scalar_val);
+    ///             }
+    ///             _ => unreachable!()
+    ///         }
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn related_count<T>(&self, filter: T, condition: CountCondition) -> Filter
+    where
+        T: Into<Filter>,
+    {
+        Filter::from(RelationFilter {
+            field: Arc::clone(self),
+            nested_filter: Box::new(filter.into()),
+            condition: RelationCondition::RelatedCount(condition),
+        })
+    }
+
     /// One of the relations is `Null`.
     /// ```rust
     /// # use connector::{*, filter::*};
@@ -1,12 +1,13 @@
 use super::Filter;
-use crate::compare::ScalarCompare;
-use prisma_models::{PrismaValue, ScalarField};
+use crate::compare::{JsonCompare, ScalarCompare, ScalarListCompare};
+use prisma_models::{JsonPath, PrismaValue, ScalarField};
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct ScalarFilter {
     pub field: Arc<ScalarField>,
     pub condition: ScalarCondition,
+    pub mode: FilterMode,
 }
 
 #[derive(Debug, Clone)]
@@ -15,6 +16,28 @@ pub struct ScalarListFilter {
     pub condition: ScalarListCondition,
 }
 
+/// Case sensitivity of the string pattern conditions (`Contains`, `StartsWith`,
+/// `EndsWith`, their negations, `Like` and `JsonPathContains`). Other conditions
+/// always compare exactly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterMode {
+    /// Patterns match as the database matches them by default. In SQLite,
+    /// `LIKE` ignores the case of ASCII letters only.
+    Default,
+
+    /// Patterns ignore the case of all letters.
+    Insensitive,
+
+    /// Patterns match the case of all letters exactly.
+    Sensitive,
+}
+
+impl Default for FilterMode {
+    fn default() -> Self {
+        FilterMode::Default
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ScalarCondition {
     Equals(PrismaValue),
@@ -25,6 +48,22 @@ pub enum ScalarCondition {
     NotStartsWith(PrismaValue),
     EndsWith(PrismaValue),
     NotEndsWith(PrismaValue),
+    /// A raw pattern, where `%` matches any sequence of characters and `_`
+    /// any single character. Values of the other pattern conditions are
+    /// matched literally.
+    Like(PrismaValue),
+    /// A full-text search query, matched by the connector's search index
+    /// instead of the field value. Not affected by the `FilterMode`.
+    Search(PrismaValue),
+    /// The value at the path of a JSON field equals the given JSON value.
+    JsonPathEquals(JsonPath, PrismaValue),
+    /// The string at the path of a JSON field contains the given value.
+    JsonPathContains(JsonPath, PrismaValue),
+    /// The array at the path of a JSON field has the given JSON value as an
+    /// element.
+    JsonArrayContains(JsonPath, PrismaValue),
+    /// The path exists in the JSON field, even if it holds `null`.
+    JsonKeyExists(JsonPath),
     LessThan(PrismaValue),
     LessThanOrEquals(PrismaValue),
     GreaterThan(PrismaValue),
@@ -57,7 +96,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.equals("foo");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Equals(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Equals(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("foo"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -71,6 +110,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::Equals(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -90,7 +130,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_equals(false);
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEquals(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEquals(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from(false), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -104,6 +144,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotEquals(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -123,7 +164,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.contains("asdf");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Contains(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Contains(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("asdf"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -137,6 +178,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::Contains(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -156,7 +198,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_contains("asdf");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotContains(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotContains(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("asdf"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -170,6 +212,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotContains(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -189,7 +232,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.starts_with("qwert");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::StartsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::StartsWith(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("qwert"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -203,6 +246,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::StartsWith(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -222,7 +266,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_starts_with("qwert");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotStartsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotStartsWith(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("qwert"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -236,6 +280,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotStartsWith(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -255,7 +300,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.ends_with("musti");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::EndsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::EndsWith(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("musti"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -269,6 +314,7 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::EndsWith(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
@@ -288,7 +334,7 @@ impl ScalarCompare for Arc<ScalarField> {
     /// let filter = field.not_ends_with("naukio");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEndsWith(val) }) => {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEndsWith(val), mode: FilterMode::Default }) => {
     ///         assert_eq!(PrismaValue::from("naukio"), val);
     ///         assert_eq!(String::from("name"), field.name);
     ///     }
@@ -302,10 +348,11 @@ impl ScalarCompare for Arc<ScalarField> {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
             condition: ScalarCondition::NotEndsWith(val.into()),
+            mode: FilterMode::Default,
         })
     }
 
-    /// Field is less than the given value.
+    /// Field matches the given pattern.
     /// ```rust
     /// # use connector::{*, filter::*};
     /// # use prisma_models::*;
@@ -317,28 +364,29 @@ impl ScalarCompare for Arc<ScalarField> {
     /// # let schema = tmp.build(String::from("test"));
     /// # let model = schema.find_model("User").unwrap();
     /// #
-    /// let field = model.fields().find_from_scalar("id").unwrap();
-    /// let filter = field.less_than(10);
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.like("f_o%");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThan(val) }) => {
-    ///         assert_eq!(PrismaValue::from(10), val);
-    ///         assert_eq!(String::from("id"), field.name);
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Like(val), mode: FilterMode::Default }) => {
+    ///         assert_eq!(PrismaValue::from("f_o%"), val);
+    ///         assert_eq!(String::from("name"), field.name);
     ///     }
     ///     _ => unreachable!()
     /// }
     /// ```
-    fn less_than<T>(&self, val: T) -> Filter
+    fn like<T>(&self, pattern: T) -> Filter
     where
         T: Into<PrismaValue>,
     {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
-            condition: ScalarCondition::LessThan(val.into()),
+            condition: ScalarCondition::Like(pattern.into()),
+            mode: FilterMode::Default,
         })
     }
 
-    /// Field is less than or equals the given value.
+    /// Field matches the given pattern, ignoring case.
     /// ```rust
     /// # use connector::{*, filter::*};
     /// # use prisma_models::*;
@@ -350,28 +398,29 @@ impl ScalarCompare for Arc<ScalarField> {
     /// # let schema = tmp.build(String::from("test"));
     /// # let model = schema.find_model("User").unwrap();
     /// #
-    /// let field = model.fields().find_from_scalar("id").unwrap();
-    /// let filter = field.less_than_or_equals(10);
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.like_insensitive("F_o%");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThanOrEquals(val) }) => {
-    ///         assert_eq!(PrismaValue::from(10), val);
-    ///         assert_eq!(String::from("id"), field.name);
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Like(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("F_o%"), val);
+    ///         assert_eq!(String::from("name"), field.name);
     ///     }
     ///     _ => unreachable!()
     /// }
     /// ```
-    fn less_than_or_equals<T>(&self, val: T) -> Filter
+    fn like_insensitive<T>(&self, pattern: T) -> Filter
     where
         T: Into<PrismaValue>,
     {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
-            condition: ScalarCondition::LessThanOrEquals(val.into()),
+            condition: ScalarCondition::Like(pattern.into()),
+            mode: FilterMode::Insensitive,
         })
     }
 
-    /// Field is greater than the given value.
+    /// Field matches the given full-text search query.
     /// ```rust
     /// # use connector::{*, filter::*};
     /// # use prisma_models::*;
@@ -383,28 +432,29 @@ impl ScalarCompare for Arc<ScalarField> {
     /// # let schema = tmp.build(String::from("test"));
     /// # let model = schema.find_model("User").unwrap();
     /// #
-    /// let field = model.fields().find_from_scalar("id").unwrap();
-    /// let filter = field.greater_than(10);
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.search("foo OR bar");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThan(val) }) => {
-    ///         assert_eq!(PrismaValue::from(10), val);
-    ///         assert_eq!(String::from("id"), field.name);
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Search(val), .. }) => {
+    ///         assert_eq!(PrismaValue::from("foo OR bar"), val);
+    ///         assert_eq!(String::from("name"), field.name);
     ///     }
     ///     _ => unreachable!()
     /// }
     /// ```
-    fn greater_than<T>(&self, val: T) -> Filter
+    fn search<T>(&self, query: T) -> Filter
     where
         T: Into<PrismaValue>,
     {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
-            condition: ScalarCondition::GreaterThan(val.into()),
+            condition: ScalarCondition::Search(query.into()),
+            mode: FilterMode::Default,
         })
     }
 
-    /// Field is greater than or equals the given value.
+    /// Field contains the given value, ignoring case.
     /// ```rust
     /// # use connector::{*, filter::*};
     /// # use prisma_models::*;
@@ -416,24 +466,884 @@ impl ScalarCompare for Arc<ScalarField> {
     /// # let schema = tmp.build(String::from("test"));
     /// # let model = schema.find_model("User").unwrap();
     /// #
-    /// let field = model.fields().find_from_scalar("id").unwrap();
-    /// let filter = field.greater_than_or_equals(10);
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.contains_insensitive("Foo");
     ///
     /// match filter {
-    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThanOrEquals(val) }) => {
-    ///         assert_eq!(PrismaValue::from(10), val);
-    ///         assert_eq!(String::from("id"), field.name);
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Contains(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
     ///     }
     ///     _ => unreachable!()
     /// }
     /// ```
-    fn greater_than_or_equals<T>(&self, val: T) -> Filter
+    fn contains_insensitive<T>(&self, val: T) -> Filter
     where
         T: Into<PrismaValue>,
     {
         Filter::from(ScalarFilter {
             field: Arc::clone(self),
-            condition: ScalarCondition::GreaterThanOrEquals(val.into()),
+            condition: ScalarCondition::Contains(val.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// Field does not contain the given value, ignoring case.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.not_contains_insensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotContains(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn not_contains_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::NotContains(val.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// Field starts with the given value, ignoring case.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.starts_with_insensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::StartsWith(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn starts_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::StartsWith(val.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// Field does not start with the given value, ignoring case.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.not_starts_with_insensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotStartsWith(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn not_starts_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::NotStartsWith(val.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// Field ends with the given value, ignoring case.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.ends_with_insensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::EndsWith(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn ends_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::EndsWith(val.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// Field does not end with the given value, ignoring case.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.not_ends_with_insensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEndsWith(val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn not_ends_with_insensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::NotEndsWith(val.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// Field matches the given pattern, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.like_sensitive("F_o%");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Like(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("F_o%"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn like_sensitive<T>(&self, pattern: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::Like(pattern.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field contains the given value, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.contains_sensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::Contains(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn contains_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::Contains(val.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field does not contain the given value, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.not_contains_sensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotContains(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn not_contains_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::NotContains(val.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field starts with the given value, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.starts_with_sensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::StartsWith(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn starts_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::StartsWith(val.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field does not start with the given value, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.not_starts_with_sensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotStartsWith(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn not_starts_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::NotStartsWith(val.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field ends with the given value, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.ends_with_sensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::EndsWith(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn ends_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::EndsWith(val.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field does not end with the given value, matching case exactly.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("name").unwrap();
+    /// let filter = field.not_ends_with_sensitive("Foo");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::NotEndsWith(val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(PrismaValue::from("Foo"), val);
+    ///         assert_eq!(String::from("name"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn not_ends_with_sensitive<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::NotEndsWith(val.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// Field is less than the given value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("id").unwrap();
+    /// let filter = field.less_than(10);
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThan(val), mode: FilterMode::Default }) => {
+    ///         assert_eq!(PrismaValue::from(10), val);
+    ///         assert_eq!(String::from("id"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn less_than<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::LessThan(val.into()),
+            mode: FilterMode::Default,
+        })
+    }
+
+    /// Field is less than or equals the given value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("id").unwrap();
+    /// let filter = field.less_than_or_equals(10);
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::LessThanOrEquals(val), mode: FilterMode::Default }) => {
+    ///         assert_eq!(PrismaValue::from(10), val);
+    ///         assert_eq!(String::from("id"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn less_than_or_equals<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::LessThanOrEquals(val.into()),
+            mode: FilterMode::Default,
+        })
+    }
+
+    /// Field is greater than the given value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("id").unwrap();
+    /// let filter = field.greater_than(10);
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThan(val), mode: FilterMode::Default }) => {
+    ///         assert_eq!(PrismaValue::from(10), val);
+    ///         assert_eq!(String::from("id"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn greater_than<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::GreaterThan(val.into()),
+            mode: FilterMode::Default,
+        })
+    }
+
+    /// Field is greater than or equals the given value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("id").unwrap();
+    /// let filter = field.greater_than_or_equals(10);
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::GreaterThanOrEquals(val), mode: FilterMode::Default }) => {
+    ///         assert_eq!(PrismaValue::from(10), val);
+    ///         assert_eq!(String::from("id"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn greater_than_or_equals<T>(&self, val: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::GreaterThanOrEquals(val.into()),
+            mode: FilterMode::Default,
+        })
+    }
+}
+
+impl JsonCompare for Arc<ScalarField> {
+    /// The value at the path equals the given JSON value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("Profile").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let filter = field.json_path_equals(path.clone(), PrismaValue::Json(String::from("\"dark\"")));
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::JsonPathEquals(p, val), .. }) => {
+    ///         assert_eq!(path, p);
+    ///         assert_eq!(PrismaValue::Json(String::from("\"dark\"")), val);
+    ///         assert_eq!(String::from("settings"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn json_path_equals<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::JsonPathEquals(path, value.into()),
+            mode: FilterMode::Default,
+        })
+    }
+
+    /// The string at the path contains the given value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("Profile").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let filter = field.json_path_contains(path.clone(), "dar");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::JsonPathContains(p, val), .. }) => {
+    ///         assert_eq!(path, p);
+    ///         assert_eq!(PrismaValue::from("dar"), val);
+    ///         assert_eq!(String::from("settings"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn json_path_contains<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::JsonPathContains(path, value.into()),
+            mode: FilterMode::Default,
+        })
+    }
+
+    /// The string at the path contains the given value, ignoring the case of all letters.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("Profile").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let filter = field.json_path_contains_insensitive(path.clone(), "dar");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::JsonPathContains(p, val), mode: FilterMode::Insensitive }) => {
+    ///         assert_eq!(path, p);
+    ///         assert_eq!(PrismaValue::from("dar"), val);
+    ///         assert_eq!(String::from("settings"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn json_path_contains_insensitive<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::JsonPathContains(path, value.into()),
+            mode: FilterMode::Insensitive,
+        })
+    }
+
+    /// The string at the path contains the given value, matching the case of all letters.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("Profile").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let filter = field.json_path_contains_sensitive(path.clone(), "dar");
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::JsonPathContains(p, val), mode: FilterMode::Sensitive }) => {
+    ///         assert_eq!(path, p);
+    ///         assert_eq!(PrismaValue::from("dar"), val);
+    ///         assert_eq!(String::from("settings"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn json_path_contains_sensitive<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::JsonPathContains(path, value.into()),
+            mode: FilterMode::Sensitive,
+        })
+    }
+
+    /// The array at the path has the given JSON value as an element.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("Profile").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let filter = field.json_array_contains(path.clone(), PrismaValue::Json(String::from("1")));
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::JsonArrayContains(p, val), .. }) => {
+    ///         assert_eq!(path, p);
+    ///         assert_eq!(PrismaValue::Json(String::from("1")), val);
+    ///         assert_eq!(String::from("settings"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn json_array_contains<T>(&self, path: JsonPath, value: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::JsonArrayContains(path, value.into()),
+            mode: FilterMode::Default,
+        })
+    }
+
+    /// The path exists, even if it holds `null`.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("Profile").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let filter = field.json_key_exists(path.clone());
+    ///
+    /// match filter {
+    ///     Filter::Scalar(ScalarFilter { field: field, condition: ScalarCondition::JsonKeyExists(p), .. }) => {
+    ///         assert_eq!(path, p);
+    ///         assert_eq!(String::from("settings"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn json_key_exists(&self, path: JsonPath) -> Filter {
+        Filter::from(ScalarFilter {
+            field: Arc::clone(self),
+            condition: ScalarCondition::JsonKeyExists(path),
+            mode: FilterMode::Default,
+        })
+    }
+}
+
+impl ScalarListCompare for Arc<ScalarField> {
+    /// List contains the given value.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("cats").unwrap();
+    /// let filter = field.contains_element(1);
+    ///
+    /// match filter {
+    ///     Filter::ScalarList(ScalarListFilter { field: field, condition: ScalarListCondition::Contains(val) }) => {
+    ///         assert_eq!(PrismaValue::from(1), val);
+    ///         assert_eq!(String::from("cats"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn contains_element<T>(&self, value: T) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarListFilter {
+            field: Arc::clone(self),
+            condition: ScalarListCondition::Contains(value.into()),
+        })
+    }
+
+    /// List contains all the given values.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("cats").unwrap();
+    /// let filter = field.contains_every_element(vec![1, 2]);
+    ///
+    /// match filter {
+    ///     Filter::ScalarList(ScalarListFilter { field: field, condition: ScalarListCondition::ContainsEvery(vals) }) => {
+    ///         assert_eq!(vec![PrismaValue::from(1), PrismaValue::from(2)], vals);
+    ///         assert_eq!(String::from("cats"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn contains_every_element<T>(&self, values: Vec<T>) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarListFilter {
+            field: Arc::clone(self),
+            condition: ScalarListCondition::ContainsEvery(values.into_iter().map(Into::into).collect()),
+        })
+    }
+
+    /// List contains at least one of the given values.
+    /// ```rust
+    /// # use connector::{*, filter::*};
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("../sqlite-connector/test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let model = schema.find_model("User").unwrap();
+    /// #
+    /// let field = model.fields().find_from_scalar("cats").unwrap();
+    /// let filter = field.contains_some_element(vec![1, 2]);
+    ///
+    /// match filter {
+    ///     Filter::ScalarList(ScalarListFilter { field: field, condition: ScalarListCondition::ContainsSome(vals) }) => {
+    ///         assert_eq!(vec![PrismaValue::from(1), PrismaValue::from(2)], vals);
+    ///         assert_eq!(String::from("cats"), field.name);
+    ///     }
+    ///     _ => unreachable!()
+    /// }
+    /// ```
+    fn contains_some_element<T>(&self, values: Vec<T>) -> Filter
+    where
+        T: Into<PrismaValue>,
+    {
+        Filter::from(ScalarListFilter {
+            field: Arc::clone(self),
+            condition: ScalarListCondition::ContainsSome(values.into_iter().map(Into::into).collect()),
         })
     }
 }
@@ -6,11 +6,13 @@ pub mod error;
 pub mod filter;
 pub mod mutaction;
 
+mod aggregation;
 mod compare;
 mod data_resolver;
 mod database_mutaction_executor;
 mod query_arguments;
 
+pub use aggregation::*;
 pub use compare::*;
 pub use data_resolver::*;
 pub use database_mutaction_executor::*;
@@ -19,6 +19,15 @@ pub struct NestedCreateNode {
     pub nested_mutactions: NestedMutactions,
 }
 
+/// Creates many records of one model in batches. Nested mutations and scalar
+/// lists are not supported.
+#[derive(Debug, Clone)]
+pub struct CreateNodes {
+    pub model: ModelRef,
+    pub args: Vec<PrismaArgs>,
+    pub skip_duplicates: bool,
+}
+
 impl From<CreateNode> for DatabaseMutaction {
     fn from(cn: CreateNode) -> DatabaseMutaction {
         DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::CreateNode(cn))
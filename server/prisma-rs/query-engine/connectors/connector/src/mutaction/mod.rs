@@ -39,6 +39,7 @@ pub enum DatabaseMutaction {
 #[derive(Debug, Clone)]
 pub enum TopLevelDatabaseMutaction {
     CreateNode(CreateNode),
+    CreateNodes(CreateNodes),
     UpdateNode(UpdateNode),
     DeleteNode(DeleteNode),
     UpsertNode(UpsertNode),
@@ -73,6 +74,75 @@ pub struct NestedMutactions {
     pub delete_manys: Vec<NestedDeleteNodes>,
 }
 
+impl NestedMutactions {
+    /// All nested mutactions with their positions, in the order they are
+    /// executed.
+    pub fn database_mutactions(&self) -> impl Iterator<Item = (NestedPosition, NestedDatabaseMutaction)> + '_ {
+        fn positioned<'a, T, P, M>(
+            mutactions: &'a [T],
+            position: P,
+            mutaction: M,
+        ) -> impl Iterator<Item = (NestedPosition, NestedDatabaseMutaction)> + 'a
+        where
+            T: Clone,
+            P: Fn(usize) -> NestedPosition + 'a,
+            M: Fn(T) -> NestedDatabaseMutaction + 'a,
+        {
+            mutactions
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(move |(index, m)| (position(index), mutaction(m)))
+        }
+
+        positioned(
+            &self.creates,
+            NestedPosition::Create,
+            NestedDatabaseMutaction::CreateNode,
+        )
+        .chain(positioned(
+            &self.updates,
+            NestedPosition::Update,
+            NestedDatabaseMutaction::UpdateNode,
+        ))
+        .chain(positioned(
+            &self.upserts,
+            NestedPosition::Upsert,
+            NestedDatabaseMutaction::UpsertNode,
+        ))
+        .chain(positioned(
+            &self.deletes,
+            NestedPosition::Delete,
+            NestedDatabaseMutaction::DeleteNode,
+        ))
+        .chain(positioned(
+            &self.connects,
+            NestedPosition::Connect,
+            NestedDatabaseMutaction::Connect,
+        ))
+        .chain(positioned(
+            &self.disconnects,
+            NestedPosition::Disconnect,
+            NestedDatabaseMutaction::Disconnect,
+        ))
+        .chain(positioned(
+            &self.sets,
+            NestedPosition::Set,
+            NestedDatabaseMutaction::Set,
+        ))
+        .chain(positioned(
+            &self.update_manys,
+            NestedPosition::UpdateMany,
+            NestedDatabaseMutaction::UpdateNodes,
+        ))
+        .chain(positioned(
+            &self.delete_manys,
+            NestedPosition::DeleteMany,
+            NestedDatabaseMutaction::DeleteNodes,
+        ))
+    }
+}
+
 // SET
 
 #[derive(Debug, Clone)]
@@ -1,14 +1,39 @@
-use super::{DatabaseMutaction, DatabaseMutactionResultType};
+use super::*;
 use prisma_models::prelude::{GraphqlId, SingleNode};
 
+/// The results of a mutaction and of its nested mutactions, each with the
+/// path of positions leading from the executed mutaction to the mutaction of
+/// the result.
 #[derive(Default)]
 pub struct DatabaseMutactionResults {
-    results: Vec<DatabaseMutactionResult>,
+    results: Vec<(Vec<NestedPosition>, DatabaseMutactionResult)>,
+}
+
+/// The position of a nested mutaction among the nested mutactions of its
+/// parent, or how a result relates to the mutaction before it on the path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NestedPosition {
+    Create(usize),
+    Update(usize),
+    Upsert(usize),
+    Delete(usize),
+    Connect(usize),
+    Disconnect(usize),
+    Set(usize),
+    UpdateMany(usize),
+    DeleteMany(usize),
+    /// Into the nested mutactions of the create of an upsert.
+    UpsertCreate,
+    /// Into the nested mutactions of the update of an upsert.
+    UpsertUpdate,
+    /// A record deleted through a cascade of the mutaction.
+    Cascade,
 }
 
 #[derive(Clone)]
 pub enum Identifier {
     Id(GraphqlId),
+    Ids(Vec<GraphqlId>),
     Count(usize),
     Node(SingleNode),
     None,
@@ -29,9 +54,17 @@ impl DatabaseMutactionResult {
         }
     }
 
+    pub fn ids(&self) -> &[GraphqlId] {
+        match self.identifier {
+            Identifier::Ids(ref ids) => ids,
+            _ => panic!("No ids defined in DatabaseMutactionResult"),
+        }
+    }
+
     pub fn count(&self) -> usize {
         match self.identifier {
             Identifier::Count(count) => count,
+            Identifier::Ids(ref ids) => ids.len(),
             _ => panic!("No count defined in DatabaseMutactionResult"),
         }
     }
@@ -42,15 +75,61 @@ impl DatabaseMutactionResult {
             _ => panic!("No node defined in DatabaseMutactionResult"),
         }
     }
+
+    /// Nested mutactions of an executed create, update or upsert, depending on
+    /// the executed branch.
+    pub fn nested_mutactions(&self) -> Option<&NestedMutactions> {
+        match (&self.mutaction, self.typ) {
+            (DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::CreateNode(cn)), _) => Some(&cn.nested_mutactions),
+            (DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpdateNode(un)), _) => Some(&un.nested_mutactions),
+            (
+                DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpsertNode(ups)),
+                DatabaseMutactionResultType::Create,
+            ) => Some(&ups.create.nested_mutactions),
+            (DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpsertNode(ups)), _) => {
+                Some(&ups.update.nested_mutactions)
+            }
+            (DatabaseMutaction::Nested(NestedDatabaseMutaction::CreateNode(cn)), _) => Some(&cn.nested_mutactions),
+            (DatabaseMutaction::Nested(NestedDatabaseMutaction::UpdateNode(un)), _) => Some(&un.nested_mutactions),
+            (
+                DatabaseMutaction::Nested(NestedDatabaseMutaction::UpsertNode(ups)),
+                DatabaseMutactionResultType::Create,
+            ) => Some(&ups.create.nested_mutactions),
+            (DatabaseMutaction::Nested(NestedDatabaseMutaction::UpsertNode(ups)), _) => {
+                Some(&ups.update.nested_mutactions)
+            }
+            _ => None,
+        }
+    }
+
+    /// The executed branch of an upsert, holding its nested mutactions.
+    pub fn upsert_branch(&self) -> Option<NestedPosition> {
+        let is_upsert = match self.mutaction {
+            DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::UpsertNode(_)) => true,
+            DatabaseMutaction::Nested(NestedDatabaseMutaction::UpsertNode(_)) => true,
+            _ => false,
+        };
+
+        match (is_upsert, self.typ) {
+            (true, DatabaseMutactionResultType::Create) => Some(NestedPosition::UpsertCreate),
+            (true, _) => Some(NestedPosition::UpsertUpdate),
+            _ => None,
+        }
+    }
 }
 
 impl DatabaseMutactionResults {
     pub fn push(&mut self, result: DatabaseMutactionResult) {
-        self.results.push(result);
+        self.results.push((Vec::new(), result));
+    }
+
+    /// Adds the result of a record deleted through a cascade of the mutaction.
+    pub fn push_cascaded(&mut self, result: DatabaseMutactionResult) {
+        self.results.push((vec![NestedPosition::Cascade], result));
     }
 
     pub fn pop(&mut self) -> Option<DatabaseMutactionResult> {
-        self.results.pop()
+        self.results.pop().map(|(_, result)| result)
     }
 
     pub fn merge(&mut self, mut other_result: DatabaseMutactionResults) {
@@ -64,4 +143,16 @@ impl DatabaseMutactionResults {
             self.merge(mrs);
         }
     }
+
+    /// Puts the results below a parent, at the given position.
+    pub fn nest(&mut self, position: NestedPosition) {
+        for (path, _) in self.results.iter_mut() {
+            path.insert(0, position);
+        }
+    }
+
+    /// The results with their paths, in the order they were added.
+    pub fn with_paths(self) -> Vec<(Vec<NestedPosition>, DatabaseMutactionResult)> {
+        self.results
+    }
 }
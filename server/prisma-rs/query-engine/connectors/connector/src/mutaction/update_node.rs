@@ -5,7 +5,7 @@ use std::sync::Arc;
 pub trait SharedUpdateLogic {
     fn model(&self) -> ModelRef;
     fn non_list_args(&self) -> &PrismaArgs;
-    fn list_args(&self) -> &[(String, PrismaListValue)];
+    fn list_args(&self) -> &[(String, ScalarListOperation)];
     fn nested_mutactions(&self) -> &NestedMutactions;
 }
 
@@ -13,20 +13,20 @@ pub trait SharedUpdateLogic {
 pub struct UpdateNode {
     pub where_: NodeSelector,
     pub non_list_args: PrismaArgs,
-    pub list_args: Vec<(String, PrismaListValue)>,
+    pub list_args: Vec<(String, ScalarListOperation)>,
     pub nested_mutactions: NestedMutactions,
 }
 
 impl SharedUpdateLogic for UpdateNode {
     fn model(&self) -> ModelRef {
-        self.where_.field.model()
+        self.where_.model()
     }
 
     fn non_list_args(&self) -> &PrismaArgs {
         &self.non_list_args
     }
 
-    fn list_args(&self) -> &[(String, PrismaListValue)] {
+    fn list_args(&self) -> &[(String, ScalarListOperation)] {
         self.list_args.as_slice()
     }
 
@@ -40,7 +40,7 @@ pub struct NestedUpdateNode {
     pub relation_field: Arc<RelationField>,
     pub where_: Option<NodeSelector>,
     pub non_list_args: PrismaArgs,
-    pub list_args: Vec<(String, PrismaListValue)>,
+    pub list_args: Vec<(String, ScalarListOperation)>,
     pub nested_mutactions: NestedMutactions,
 }
 
@@ -53,7 +53,7 @@ impl SharedUpdateLogic for NestedUpdateNode {
         &self.non_list_args
     }
 
-    fn list_args(&self) -> &[(String, PrismaListValue)] {
+    fn list_args(&self) -> &[(String, ScalarListOperation)] {
         self.list_args.as_slice()
     }
 
@@ -67,7 +67,7 @@ pub struct UpdateNodes {
     pub model: ModelRef,
     pub filter: Filter,
     pub non_list_args: PrismaArgs,
-    pub list_args: Vec<(String, PrismaListValue)>,
+    pub list_args: Vec<(String, ScalarListOperation)>,
 }
 
 #[derive(Debug, Clone)]
@@ -75,5 +75,5 @@ pub struct NestedUpdateNodes {
     pub relation_field: Arc<RelationField>,
     pub filter: Option<Filter>,
     pub non_list_args: PrismaArgs,
-    pub list_args: Vec<(String, PrismaListValue)>,
+    pub list_args: Vec<(String, ScalarListOperation)>,
 }
@@ -9,7 +9,10 @@ pub struct QueryArguments {
     pub before: Option<GraphqlId>,
     pub last: Option<u32>,
     pub filter: Option<Filter>,
-    pub order_by: Option<OrderBy>,
+    /// Applied in turn, each ordering breaking the ties of the previous ones.
+    pub order_by: Vec<OrderBy>,
+    /// Keeps only the first node, in the order of the query, of each combination of values of the fields.
+    pub distinct: Vec<ScalarFieldRef>,
 }
 
 impl QueryArguments {
@@ -17,6 +20,25 @@ impl QueryArguments {
         self.last.or(self.first).or(self.skip).is_some()
     }
 
+    /// Paginated queries read one node more than requested, to tell whether more nodes exist.
+    /// Drops that node and restores the requested order for `last`, which is read backwards.
+    /// Returns the page and whether more nodes exist in the direction of pagination.
+    pub fn paginate<T>(&self, mut items: Vec<T>) -> (Vec<T>, bool) {
+        let has_more = match self.last.or(self.first) {
+            Some(count) if items.len() > count as usize => {
+                items.truncate(count as usize);
+                true
+            }
+            _ => false,
+        };
+
+        if self.last.is_some() {
+            items.reverse();
+        }
+
+        (items, has_more)
+    }
+
     pub fn window_limits(&self) -> (u32, u32) {
         let skip = self.skip.unwrap_or(0) + 1;
 
@@ -1,7 +1,7 @@
+use crate::ordering::OrderKey;
 use connector::QueryArguments;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
-use std::sync::Arc;
 
 #[derive(Clone, Copy)]
 enum CursorType {
@@ -12,67 +12,113 @@ enum CursorType {
 pub struct CursorCondition;
 
 impl CursorCondition {
+    /// The condition of the nodes beyond the `after` and `before` cursors in
+    /// the order of the query. Nodes without a value tie with each other.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("ATTACH DATABASE './test.db' AS 'test'", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE IF NOT EXISTS test.User (id Text, name Text);", NO_PARAMS).unwrap();
+    /// trans.execute(
+    ///     "INSERT INTO test.User (id, name) VALUES ('a', NULL), ('b', NULL), ('c', 'Bob'), ('d', 'Alice');",
+    ///     NO_PARAMS
+    /// ).unwrap();
+    ///
+    /// let model = schema.find_model("User").unwrap();
+    /// let name = model.fields().find_from_scalar("name").unwrap();
+    ///
+    /// let ids_after = |id: &str, nulls: Option<NullsOrder>| {
+    ///     let order_by = OrderBy {
+    ///         field: Arc::clone(&name),
+    ///         relations: Vec::new(),
+    ///         sort_order: SortOrder::Ascending,
+    ///         nulls,
+    ///         search: None,
+    ///         path: None,
+    ///         related_count: None,
+    ///     };
+    ///
+    ///     let args = QueryArguments {
+    ///         after: Some(GraphqlId::from(id)),
+    ///         order_by: vec![order_by],
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     Sqlite::ids_for(&trans, Arc::clone(&model), args).unwrap()
+    /// };
+    ///
+    /// let ids = |ids: &[&str]| -> Vec<GraphqlId> { ids.iter().map(|id| GraphqlId::from(*id)).collect() };
+    ///
+    /// // Without a value, nodes come first when ascending:
+    ///
+    /// assert_eq!(ids(&["b", "d", "c"]), ids_after("a", None));
+    /// assert_eq!(ids(&["d", "c"]), ids_after("b", None));
+    ///
+    /// // Or where placed explicitly:
+    ///
+    /// assert_eq!(ids(&["c", "a", "b"]), ids_after("d", Some(NullsOrder::Last)));
+    /// assert_eq!(ids(&["b"]), ids_after("a", Some(NullsOrder::Last)));
+    /// ```
     pub fn build(query_arguments: &QueryArguments, model: ModelRef) -> ConditionTree {
-        match (
-            query_arguments.before.as_ref(),
-            query_arguments.after.as_ref(),
-            query_arguments.order_by.as_ref(),
-        ) {
-            (None, None, _) => ConditionTree::NoCondition,
-            (before, after, order_by) => {
-                let field = match order_by {
-                    Some(order) => Arc::clone(&order.field),
-                    None => model.fields().id(),
-                };
+        match (query_arguments.before.as_ref(), query_arguments.after.as_ref()) {
+            (None, None) => ConditionTree::NoCondition,
+            (before, after) => {
+                let keys = OrderKey::all(&query_arguments.order_by);
+                let id_column = model.fields().id().as_column();
 
-                let sort_order: SortOrder = order_by.map(|order| order.sort_order).unwrap_or(SortOrder::Ascending);
+                // A node lies beyond the cursor if it does so in the first key
+                // differing from the cursor node, comparing the keys in order.
+                // Comparisons with `NULL` are never true, so missing values are
+                // compared explicitly: they tie with each other and sort before
+                // any value, as in the ordering of SQLite.
+                let cursor_for = |cursor_type: CursorType, id: &GraphqlId| {
+                    keys.iter().rev().fold(ConditionTree::NoCondition, |rest, key| {
+                        let row = key.of_row(None, &id_column);
+                        let node = key.of_node(id);
 
-                let cursor_for = |cursor_type: CursorType, id: GraphqlId| {
-                    let model_id = model.fields().id();
-                    let where_condition = model_id.as_column().equals(id.clone());
+                        let (smaller, larger) =
+                            match (cursor_type, key.sort_order()) {
+                                (CursorType::Before, SortOrder::Ascending)
+                                | (CursorType::After, SortOrder::Descending) => (row.clone(), node.clone()),
+                                (CursorType::Before, SortOrder::Descending)
+                                | (CursorType::After, SortOrder::Ascending) => (node.clone(), row.clone()),
+                            };
 
-                    let select_query = Select::from_table(model.table())
-                        .column(field.as_column())
-                        .so_that(ConditionTree::single(where_condition));
+                        let less: ConditionTree = smaller.clone().less_than(larger.clone()).into();
+                        let missing: ConditionTree = smaller.is_null().into();
+                        let present: ConditionTree = larger.is_not_null().into();
+                        let beyond = ConditionTree::or(less, ConditionTree::and(missing, present));
 
-                    let compare = match (cursor_type, sort_order) {
-                        (CursorType::Before, SortOrder::Ascending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().less_than(id))
-                            .or(field.as_column().less_than(select_query)),
-                        (CursorType::Before, SortOrder::Descending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().less_than(id))
-                            .or(field.as_column().greater_than(select_query)),
-                        (CursorType::After, SortOrder::Ascending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().greater_than(id))
-                            .or(field.as_column().greater_than(select_query)),
-                        (CursorType::After, SortOrder::Descending) => field
-                            .as_column()
-                            .equals(select_query.clone())
-                            .and(model_id.as_column().greater_than(id))
-                            .or(field.as_column().less_than(select_query)),
-                    };
+                        match rest {
+                            ConditionTree::NoCondition => beyond,
+                            rest => {
+                                let equal: ConditionTree = row.clone().equals(node.clone()).into();
+                                let row_missing: ConditionTree = row.is_null().into();
+                                let node_missing: ConditionTree = node.is_null().into();
 
-                    ConditionTree::single(compare)
+                                let tied = ConditionTree::or(equal, ConditionTree::and(row_missing, node_missing));
+                                ConditionTree::or(beyond, ConditionTree::and(tied, rest))
+                            }
+                        }
+                    })
                 };
 
                 let after_cursor = after
-                    .map(|id| {
-                        let id_val = id.clone();
-                        cursor_for(CursorType::After, id_val)
-                    })
+                    .map(|id| cursor_for(CursorType::After, id))
                     .unwrap_or(ConditionTree::NoCondition);
 
                 let before_cursor = before
-                    .map(|id| {
-                        let id_val = id.clone();
-                        cursor_for(CursorType::Before, id_val)
-                    })
+                    .map(|id| cursor_for(CursorType::Before, id))
                     .unwrap_or(ConditionTree::NoCondition);
 
                 ConditionTree::and(after_cursor, before_cursor)
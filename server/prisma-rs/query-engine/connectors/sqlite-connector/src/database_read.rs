@@ -95,7 +95,7 @@ pub trait DatabaseRead {
     /// # use prisma_models::*;
     /// # use rusqlite::{Connection, NO_PARAMS};
     /// # use sqlite_connector::*;
-    /// # use connector::{*, filter::{ScalarCondition, ScalarFilter, Filter}};
+    /// # use connector::{*, filter::{FilterMode, ScalarCondition, ScalarFilter, Filter}};
     /// # use prisma_query::ast::*;
     /// # use serde_json;
     /// # use std::{fs::File, sync::Arc};
@@ -117,11 +117,13 @@ pub trait DatabaseRead {
     /// let find_bob = ScalarFilter {
     ///     field: Arc::clone(&name_field),
     ///     condition: ScalarCondition::Equals(PrismaValue::from("Bob")),
+    ///     mode: FilterMode::Default,
     /// };
     ///
     /// let find_alice = ScalarFilter {
     ///     field: name_field,
     ///     condition: ScalarCondition::Equals(PrismaValue::from("Alice")),
+    ///     mode: FilterMode::Default,
     /// };
     ///
     /// let filter = Filter::or(vec![
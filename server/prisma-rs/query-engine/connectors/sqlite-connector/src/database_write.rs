@@ -2,11 +2,13 @@ mod create;
 mod delete;
 mod relation;
 mod update;
+mod upsert;
 
 pub use create::*;
 pub use delete::*;
 pub use relation::*;
 pub use update::*;
+pub use upsert::*;
 
 use connector::{
     mutaction::{DatabaseMutactionResults, NestedDatabaseMutaction, TopLevelDatabaseMutaction},
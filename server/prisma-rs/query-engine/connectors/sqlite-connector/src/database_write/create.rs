@@ -52,6 +52,102 @@ pub trait DatabaseCreate {
     where
         T: AsRef<str>;
 
+    /// Creates many new root records in batches, returning the ids of the
+    /// created records. With `skip_duplicates`, records violating a unique
+    /// constraint are not created and their ids are not returned.
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("ATTACH DATABASE './test.db' AS 'test'", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE IF NOT EXISTS test.User (id Text, name Text);", NO_PARAMS).unwrap();
+    /// let user = schema.find_model("User").unwrap();
+    ///
+    /// let mut bob = PrismaArgs::new();
+    /// bob.insert("name", "Bob");
+    ///
+    /// let mut alice = PrismaArgs::new();
+    /// alice.insert("name", "Alice");
+    ///
+    /// let ids = Sqlite::execute_create_many(&trans, Arc::clone(&user), &[bob, alice], false).unwrap();
+    ///
+    /// assert_eq!(2, ids.len());
+    ///
+    /// assert_eq!(
+    ///     2,
+    ///     Sqlite::count(&trans, user.table(), user.fields().id().as_column().in_selection(ids)).unwrap(),
+    /// );
+    /// ```
+    ///
+    /// Of the args with the same id, only the first is created:
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("ATTACH DATABASE './test.db' AS 'test'", NO_PARAMS).unwrap();
+    /// trans.execute("CREATE TABLE test.Account (id Text PRIMARY KEY, email Text UNIQUE);", NO_PARAMS).unwrap();
+    ///
+    /// let account = schema.find_model("Account").unwrap();
+    ///
+    /// let mut bob = PrismaArgs::new();
+    /// bob.insert("id", GraphqlId::from("account1"));
+    /// bob.insert("email", "bob@example.com");
+    ///
+    /// let mut alice = PrismaArgs::new();
+    /// alice.insert("id", GraphqlId::from("account1"));
+    /// alice.insert("email", "alice@example.com");
+    ///
+    /// let ids = Sqlite::execute_create_many(&trans, account, &[bob, alice], true).unwrap();
+    ///
+    /// assert_eq!(vec![GraphqlId::from("account1")], ids);
+    /// ```
+    ///
+    /// Violations of other constraints are not skipped:
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("ATTACH DATABASE ':memory:' AS 'test'", NO_PARAMS).unwrap();
+    /// trans.execute("CREATE TABLE test.Account (id Text PRIMARY KEY, email Text NOT NULL UNIQUE);", NO_PARAMS).unwrap();
+    ///
+    /// let account = schema.find_model("Account").unwrap();
+    ///
+    /// let mut bob = PrismaArgs::new();
+    /// bob.insert("id", GraphqlId::from("account1"));
+    ///
+    /// assert!(Sqlite::execute_create_many(&trans, account, &[bob], true).is_err());
+    /// ```
+    fn execute_create_many(
+        conn: &Transaction,
+        model: ModelRef,
+        args: &[PrismaArgs],
+        skip_duplicates: bool,
+    ) -> ConnectorResult<Vec<GraphqlId>>;
+
     /// Creates a new nested item related to a parent, including any associated
     /// list values, and is connected with the `parent_id` to the parent record.
     /// ```rust
@@ -6,15 +6,28 @@ use connector::{
 use prisma_models::{GraphqlId, ModelRef, ProjectRef, RelationFieldRef, SingleNode};
 use rusqlite::Transaction;
 
+/// A record deleted because a relation with `OnDelete::Cascade` led to it
+/// from another deleted record.
+#[derive(Clone)]
+pub struct CascadedDelete {
+    pub model: ModelRef,
+    pub node: SingleNode,
+}
+
 /// Functions to delete records from the database.
 ///
 /// The functions are transactional and will do a rollback is handled in case of
 /// an error.
+///
+/// Deleting a record deletes the records related to it through relations with
+/// `OnDelete::Cascade` in the same transaction, recursively. The cascaded
+/// records are returned together with the result of the delete.
 pub trait DatabaseDelete {
     /// A top level delete that removes one record. Violating any relations or a
     /// non-existing record will cause an error.
     ///
-    /// Will return the deleted record if the delete was successful.
+    /// Will return the deleted record and the cascaded records if the delete
+    /// was successful.
     /// ```rust
     /// # use prisma_models::*;
     /// # use rusqlite::{Connection, NO_PARAMS};
@@ -59,12 +72,15 @@ pub trait DatabaseDelete {
     ///
     /// assert_eq!(0, Sqlite::count(&trans, "User", ConditionTree::NoCondition).unwrap());
     /// ```
-    fn execute_delete(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode>;
+    fn execute_delete(
+        conn: &Transaction,
+        node_selector: &NodeSelector,
+    ) -> ConnectorResult<(SingleNode, Vec<CascadedDelete>)>;
 
     /// A top level delete that removes records matching the `Filter`. Violating
     /// any relations will cause an error.
     ///
-    /// Will return the number records deleted.
+    /// Will return the number records deleted and the cascaded records.
     /// ```rust
     /// # use prisma_models::*;
     /// # use rusqlite::{Connection, NO_PARAMS};
@@ -105,10 +121,16 @@ pub trait DatabaseDelete {
     /// let name_field = user.fields().find_from_scalar("name").unwrap();
     /// let filter = name_field.starts_with("Bob");
     ///
-    /// assert_eq!(2, Sqlite::execute_delete_many(&trans, user, &filter).unwrap());
+    /// let (count, _) = Sqlite::execute_delete_many(&trans, user, &filter).unwrap();
+    ///
+    /// assert_eq!(2, count);
     /// assert_eq!(1, Sqlite::count(&trans, "User", ConditionTree::NoCondition).unwrap());
     /// ```
-    fn execute_delete_many(conn: &Transaction, model: ModelRef, filter: &Filter) -> ConnectorResult<usize>;
+    fn execute_delete_many(
+        conn: &Transaction,
+        model: ModelRef,
+        filter: &Filter,
+    ) -> ConnectorResult<(usize, Vec<CascadedDelete>)>;
 
     /// A nested delete that removes one item related to the given `parent_id`.
     /// If no `RecordFinder` is given, will delete the first item from the
@@ -204,7 +226,7 @@ pub trait DatabaseDelete {
         nested_actions: &NestedActions,
         node_selector: &Option<NodeSelector>,
         relation_field: RelationFieldRef,
-    ) -> ConnectorResult<()>;
+    ) -> ConnectorResult<Vec<CascadedDelete>>;
 
     /// Removes nested items matching to filter, or if no filter is given, all
     /// nested items related to the given `parent_id`. An error will be thrown
@@ -287,7 +309,7 @@ pub trait DatabaseDelete {
         parent_id: &GraphqlId,
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
-    ) -> ConnectorResult<usize>;
+    ) -> ConnectorResult<(usize, Vec<CascadedDelete>)>;
 
     /// Truncates all tables from the project.
     fn execute_reset_data(conn: &Transaction, project: ProjectRef) -> ConnectorResult<()>;
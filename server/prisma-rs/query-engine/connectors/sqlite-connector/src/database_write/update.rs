@@ -48,7 +48,7 @@ pub trait DatabaseUpdate {
     ///     &trans,
     ///     &selector,
     ///     &args,
-    ///     &[("cats", vec![])],
+    ///     &[("cats", ScalarListOperation::Set(vec![]))],
     /// ).unwrap();
     ///
     /// let record = Sqlite::find_node(&trans, &selector).unwrap();
@@ -59,7 +59,7 @@ pub trait DatabaseUpdate {
         conn: &Transaction,
         node_selector: &NodeSelector,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>;
@@ -111,7 +111,7 @@ pub trait DatabaseUpdate {
     ///     Arc::clone(&user),
     ///     &finder,
     ///     &args,
-    ///     &[("cats", vec![])],
+    ///     &[("cats", ScalarListOperation::Set(vec![]))],
     /// ).unwrap();
     ///
     /// let id_field = user.fields().id();
@@ -125,7 +125,7 @@ pub trait DatabaseUpdate {
         model: ModelRef,
         filter: &Filter,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<usize>
     where
         T: AsRef<str>;
@@ -199,7 +199,7 @@ pub trait DatabaseUpdate {
     ///     &Some(NodeSelector::from((name_field, "A Cat Blog"))),
     ///     relation_field,
     ///     &args,
-    ///     &[("tags", vec![])],
+    ///     &[("tags", ScalarListOperation::Set(vec![]))],
     /// ).unwrap();
     ///
     /// assert_eq!(
@@ -218,7 +218,7 @@ pub trait DatabaseUpdate {
         node_selector: &Option<NodeSelector>,
         relation_field: RelationFieldRef,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>;
@@ -296,7 +296,7 @@ pub trait DatabaseUpdate {
     ///     &Some(filter),
     ///     relation_field,
     ///     &args,
-    ///     &[("cats", vec![])],
+    ///     &[("cats", ScalarListOperation::Set(vec![]))],
     /// ).unwrap();
     ///
     /// assert_eq!(2, count);
@@ -313,12 +313,13 @@ pub trait DatabaseUpdate {
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<usize>
     where
         T: AsRef<str>;
 
-    /// Updates list args related to the given records.
+    /// Updates list args related to the given records. Only `Set` rewrites the
+    /// list, the other operations keep the positions of the remaining values.
     /// ```rust
     /// # use prisma_models::*;
     /// # use rusqlite::{Connection, NO_PARAMS};
@@ -352,21 +353,44 @@ pub trait DatabaseUpdate {
     ///
     /// Sqlite::update_list_args(
     ///     &trans,
-    ///     &[user_id],
-    ///     user,
+    ///     &[user_id.clone()],
+    ///     Arc::clone(&user),
     ///     &[(
     ///         "cats",
-    ///         vec![PrismaValue::from("musti"), PrismaValue::from("naukio")]
+    ///         ScalarListOperation::Set(vec![PrismaValue::from("musti"), PrismaValue::from("naukio")])
     ///     )],
     /// ).unwrap();
     ///
-    /// assert_eq!(2, Sqlite::count(&trans, "User_cats", ConditionTree::default()).unwrap())
+    /// assert_eq!(2, Sqlite::count(&trans, "User_cats", ConditionTree::default()).unwrap());
+    ///
+    /// Sqlite::update_list_args(
+    ///     &trans,
+    ///     &[user_id],
+    ///     user,
+    ///     &[
+    ///         ("cats", ScalarListOperation::Push(vec![PrismaValue::from("miuku")])),
+    ///         ("cats", ScalarListOperation::Unshift(vec![PrismaValue::from("kisu"), PrismaValue::from("mirri")])),
+    ///         ("cats", ScalarListOperation::RemoveAt(1)),
+    ///         ("cats", ScalarListOperation::RemoveValue(PrismaValue::from("musti"))),
+    ///     ],
+    /// ).unwrap();
+    ///
+    /// let select = Select::from_table(("test", "User_cats"))
+    ///     .column("value")
+    ///     .order_by(Column::from("position").ascend());
+    ///
+    /// let cats = Sqlite::query(&trans, select, |row| {
+    ///     let value: String = row.get(0);
+    ///     Ok(value)
+    /// }).unwrap();
+    ///
+    /// assert_eq!(vec!["kisu", "naukio", "miuku"], cats);
     /// ```
     fn update_list_args<T>(
         conn: &Transaction,
         ids: &[GraphqlId],
         model: ModelRef,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<()>
     where
         T: AsRef<str>;
@@ -0,0 +1,129 @@
+use connector::{
+    mutaction::{DatabaseMutactionResultType, UpsertNode},
+    ConnectorResult,
+};
+use prisma_models::GraphqlId;
+use rusqlite::Transaction;
+
+/// Functions to create or update records in the database.
+pub trait DatabaseUpsert {
+    /// Creates the record of the upsert, or updates the record selected by
+    /// its `where_` if it exists. Returns the id of the record, and whether it
+    /// was created or updated. Done in a single statement where the schema
+    /// allows it, otherwise the record is looked up first.
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::{filter::*, mutaction::*};
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("ATTACH DATABASE './test.db' AS 'test'", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE IF NOT EXISTS test.User (id Text, name Text);", NO_PARAMS).unwrap();
+    /// # trans.execute("CREATE TABLE IF NOT EXISTS test.User_cats (nodeId Text, position Integer, value Text);", NO_PARAMS).unwrap();
+    /// let user = schema.find_model("User").unwrap();
+    /// let where_ = NodeSelector::from((user.fields().id(), GraphqlId::from("upsert1")));
+    ///
+    /// let mut create_args = PrismaArgs::new();
+    /// create_args.insert("id", GraphqlId::from("upsert1"));
+    /// create_args.insert("name", "Bob");
+    ///
+    /// let mut update_args = PrismaArgs::new();
+    /// update_args.insert("name", "Alice");
+    ///
+    /// let upsert = UpsertNode {
+    ///     where_: where_.clone(),
+    ///     create: CreateNode {
+    ///         model: Arc::clone(&user),
+    ///         non_list_args: create_args,
+    ///         list_args: Vec::new(),
+    ///         nested_mutactions: NestedMutactions::default(),
+    ///     },
+    ///     update: UpdateNode {
+    ///         where_,
+    ///         non_list_args: update_args,
+    ///         list_args: Vec::new(),
+    ///         nested_mutactions: NestedMutactions::default(),
+    ///     },
+    /// };
+    ///
+    /// let (id, typ) = Sqlite::execute_upsert(&trans, &upsert).unwrap();
+    ///
+    /// assert_eq!(GraphqlId::from("upsert1"), id);
+    /// assert_eq!(DatabaseMutactionResultType::Create, typ);
+    ///
+    /// let (id, typ) = Sqlite::execute_upsert(&trans, &upsert).unwrap();
+    ///
+    /// assert_eq!(GraphqlId::from("upsert1"), id);
+    /// assert_eq!(DatabaseMutactionResultType::Update, typ);
+    /// ```
+    ///
+    /// Selecting a unique field the create sets upserts in a single statement,
+    /// which may change the selected field:
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use rusqlite::{Connection, NO_PARAMS};
+    /// # use sqlite_connector::*;
+    /// # use connector::{filter::*, mutaction::*};
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// # let mut conn = Connection::open_in_memory().unwrap();
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let trans = conn.transaction().unwrap();
+    /// # trans.execute("ATTACH DATABASE './test.db' AS 'test'", NO_PARAMS).unwrap();
+    /// trans.execute("CREATE TABLE test.Account (id Text PRIMARY KEY, email Text UNIQUE);", NO_PARAMS).unwrap();
+    ///
+    /// let account = schema.find_model("Account").unwrap();
+    /// let email = account.fields().find_from_scalar("email").unwrap();
+    /// let where_ = NodeSelector::from((email, "bob@example.com"));
+    ///
+    /// let mut create_args = PrismaArgs::new();
+    /// create_args.insert("email", "bob@example.com");
+    ///
+    /// let mut upsert = UpsertNode {
+    ///     where_: where_.clone(),
+    ///     create: CreateNode {
+    ///         model: Arc::clone(&account),
+    ///         non_list_args: create_args,
+    ///         list_args: Vec::new(),
+    ///         nested_mutactions: NestedMutactions::default(),
+    ///     },
+    ///     update: UpdateNode {
+    ///         where_,
+    ///         non_list_args: PrismaArgs::new(),
+    ///         list_args: Vec::new(),
+    ///         nested_mutactions: NestedMutactions::default(),
+    ///     },
+    /// };
+    ///
+    /// assert!(MutationBuilder::upsert_node(&upsert).unwrap().is_some());
+    ///
+    /// let (created, typ) = Sqlite::execute_upsert(&trans, &upsert).unwrap();
+    /// assert_eq!(DatabaseMutactionResultType::Create, typ);
+    ///
+    /// let (id, typ) = Sqlite::execute_upsert(&trans, &upsert).unwrap();
+    /// assert_eq!(created, id);
+    /// assert_eq!(DatabaseMutactionResultType::Update, typ);
+    ///
+    /// upsert.update.non_list_args.insert("email", "robert@example.com");
+    ///
+    /// let (id, typ) = Sqlite::execute_upsert(&trans, &upsert).unwrap();
+    /// assert_eq!(created, id);
+    /// assert_eq!(DatabaseMutactionResultType::Update, typ);
+    ///
+    /// let email: String = trans.query_row("SELECT email FROM test.Account", NO_PARAMS, |row| row.get(0)).unwrap();
+    /// assert_eq!("robert@example.com", email);
+    /// ```
+    fn execute_upsert(
+        conn: &Transaction,
+        upsert: &UpsertNode,
+    ) -> ConnectorResult<(GraphqlId, DatabaseMutactionResultType)>;
+}
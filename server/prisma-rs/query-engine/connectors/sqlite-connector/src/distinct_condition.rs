@@ -0,0 +1,123 @@
+use crate::{filter_conversion::AliasedCondition, ordering::Ordering};
+use connector::QueryArguments;
+use prisma_models::prelude::*;
+use prisma_query::ast::*;
+use std::sync::Arc;
+
+/// Keeps the first node, in the order of the query, of each combination of
+/// values of the distinct fields. The nodes matching the filter are numbered
+/// per combination in a window, so the nodes kept don't depend on cursors and
+/// pagination.
+pub struct DistinctCondition;
+
+impl DistinctCondition {
+    const TABLE_ALIAS: &'static str = "distinct_nodes";
+    const RELATION_TABLE_ALIAS: &'static str = "distinct_relation";
+    const PARENT_ALIAS: &'static str = "distinct_parent";
+    const ROW_NUMBER_ALIAS: &'static str = "distinct_row_number";
+
+    pub fn build(query_arguments: &QueryArguments, model: ModelRef) -> ConditionTree {
+        if query_arguments.distinct.is_empty() {
+            return ConditionTree::NoCondition;
+        }
+
+        let nodes = Select::from_table(model.table()).so_that(Self::filter(query_arguments));
+        let nodes = Ordering::join_tables(nodes, &model, &query_arguments.order_by);
+
+        let first_nodes = Self::first_nodes(query_arguments, &model, nodes, None);
+
+        model.id_column().in_selection(first_nodes).into()
+    }
+
+    /// Related nodes are distinct per parent. The related nodes of the query
+    /// are joined to their parents through the relation table aliased as
+    /// `Relation::TABLE_ALIAS`.
+    pub fn for_related(
+        query_arguments: &QueryArguments,
+        from_field: &RelationField,
+        from_node_ids: &[GraphqlId],
+    ) -> ConditionTree {
+        if query_arguments.distinct.is_empty() {
+            return ConditionTree::NoCondition;
+        }
+
+        let model = from_field.related_model();
+        let relation = from_field.relation();
+
+        let parent_column = relation.column_for_relation_side(from_field.relation_side);
+        let child_column = relation.column_for_relation_side(from_field.relation_side.opposite());
+        let parent = parent_column.clone().table(Self::RELATION_TABLE_ALIAS);
+
+        let relation_table = relation.relation_table().alias(Self::RELATION_TABLE_ALIAS);
+        let joined = child_column.table(Self::RELATION_TABLE_ALIAS).equals(model.id_column());
+
+        let conditions = parent
+            .clone()
+            .in_selection(from_node_ids.to_owned())
+            .and(Self::filter(query_arguments));
+
+        let nodes = Select::from_table(model.table())
+            .inner_join(relation_table.on(joined))
+            .so_that(conditions);
+
+        let query_parent = parent_column.table(Relation::TABLE_ALIAS);
+        let first_nodes = Self::first_nodes(query_arguments, &model, nodes, Some((parent, query_parent)));
+
+        model.id_column().in_selection(first_nodes).into()
+    }
+
+    fn filter(query_arguments: &QueryArguments) -> ConditionTree {
+        query_arguments
+            .filter
+            .clone()
+            .map(|f| f.aliased_cond(None))
+            .unwrap_or(ConditionTree::NoCondition)
+    }
+
+    /// Numbers the nodes per combination of values and selects the ids of the
+    /// first ones. With a parent, given as its column in the numbered nodes
+    /// and in the query, the nodes are numbered per parent and the first ones
+    /// of the parent in the query are selected.
+    fn first_nodes(
+        query_arguments: &QueryArguments,
+        model: &ModelRef,
+        nodes: Select,
+        parent: Option<(Column, Column)>,
+    ) -> Select {
+        let id_field = model.fields().id();
+        let ordering = Ordering::for_model(Arc::clone(model), &query_arguments.order_by, false);
+
+        let row_number = ordering.into_iter().fold(row_number(), |acc, ord| acc.order_by(ord));
+
+        let row_number: Function = parent
+            .iter()
+            .map(|(column, _)| column.clone())
+            .chain(query_arguments.distinct.iter().map(|field| field.as_column()))
+            .fold(row_number, |acc, column| acc.partition_by(column))
+            .into();
+
+        let numbered = nodes
+            .column(id_field.as_column())
+            .value(row_number.alias(Self::ROW_NUMBER_ALIAS));
+
+        let first: ConditionTree = Column::from((Self::TABLE_ALIAS, Self::ROW_NUMBER_ALIAS))
+            .equals(1i64)
+            .into();
+
+        let (numbered, conditions) = match parent {
+            Some((column, query_column)) => {
+                let same_parent = Column::from((Self::TABLE_ALIAS, Self::PARENT_ALIAS)).equals(query_column);
+
+                (
+                    numbered.column(column.alias(Self::PARENT_ALIAS)),
+                    ConditionTree::and(first, same_parent),
+                )
+            }
+            None => (numbered, first),
+        };
+
+        Select::from_table(Table::from(numbered).alias(Self::TABLE_ALIAS))
+            .column((Self::TABLE_ALIAS, id_field.db_name()))
+            .so_that(conditions)
+    }
+}
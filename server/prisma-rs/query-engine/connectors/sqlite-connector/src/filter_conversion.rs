@@ -1,6 +1,11 @@
+use crate::{
+    escape_glob, escape_like, like_to_glob, raw_like_pattern, SearchIndex, JSON_ARRAY_CONTAINS_FUNCTION, JSON_FUNCTION,
+    LIKE_ESCAPE, LOWER_FUNCTION,
+};
 use connector::filter::*;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
+use serde_json::Value;
 
 #[derive(Clone, Copy, Debug)]
 /// A distinction in aliasing to separate the parent table and the joined data
@@ -159,6 +164,7 @@ impl AliasedCondition for Filter {
                 }
             },
             Filter::Scalar(filter) => filter.aliased_cond(alias),
+            Filter::ScalarList(filter) => filter.aliased_cond(alias),
             Filter::OneRelationIsNull(filter) => filter.aliased_cond(alias),
             Filter::Relation(filter) => filter.aliased_cond(alias),
             Filter::BoolFilter(b) => {
@@ -191,7 +197,8 @@ impl AliasedCondition for ScalarFilter {
     ///
     /// let sf = ScalarFilter {
     ///     field: Arc::clone(&field),
-    ///     condition: ScalarCondition::Equals(PrismaValue::from("foo"))
+    ///     condition: ScalarCondition::Equals(PrismaValue::from("foo")),
+    ///     mode: FilterMode::Default,
     /// };
     ///
     /// let expected: ConditionTree = (("test", "User"), "name").equals("foo").into();
@@ -201,6 +208,61 @@ impl AliasedCondition for ScalarFilter {
     ///
     /// let expected: ConditionTree = ("t0", "name").equals("foo").into();
     /// assert_eq!(expected, sf.aliased_cond(Some(Alias::default())));
+    ///
+    /// // Insensitive patterns compare both sides lowercased:
+    ///
+    /// let name = Column::from((("test", "User"), "name"));
+    ///
+    /// let expected: ConditionTree = function(LOWER_FUNCTION, vec![name.clone().into()])
+    ///     .like_escaped("%foo%", LIKE_ESCAPE)
+    ///     .into();
+    ///
+    /// assert_eq!(expected, field.contains_insensitive("Foo").aliased_cond(None));
+    ///
+    /// // Wildcards are escaped, unless given as a raw pattern:
+    ///
+    /// let expected: ConditionTree = name.clone().like_escaped("%50\\%%", LIKE_ESCAPE).into();
+    /// assert_eq!(expected, field.contains("50%").aliased_cond(None));
+    ///
+    /// let expected: ConditionTree = name.clone().like_escaped("f_o%", LIKE_ESCAPE).into();
+    /// assert_eq!(expected, field.like("f_o%").aliased_cond(None));
+    ///
+    /// // Sensitive patterns match with `GLOB`:
+    ///
+    /// let expected: ConditionTree = function("glob", vec!["*F[?]o*".into(), name.clone().into()]).equals(1).into();
+    /// assert_eq!(expected, field.contains_sensitive("F?o").aliased_cond(None));
+    ///
+    /// let expected: ConditionTree = function("glob", vec!["F?o*".into(), name.clone().into()]).equals(1).into();
+    /// assert_eq!(expected, field.like_sensitive("F_o%").aliased_cond(None));
+    ///
+    /// // Searches match the ids in the search index:
+    ///
+    /// let matches = SearchIndex::new(Arc::clone(&model)).matches(&field, "foo");
+    /// let expected: ConditionTree = (("test", "User"), "id").in_selection(matches).into();
+    ///
+    /// assert_eq!(expected, field.search("foo").aliased_cond(None));
+    ///
+    /// // JSON conditions compare the extracted value:
+    ///
+    /// let settings = schema.find_model("Profile").unwrap().fields().find_from_scalar("settings").unwrap();
+    /// let path: JsonPath = "$.theme".parse().unwrap();
+    /// let dark = PrismaValue::Json(String::from("\"dark\""));
+    ///
+    /// let document = function(JSON_FUNCTION, vec![Column::from((("test", "Profile"), "settings")).into()]);
+    /// let expected: ConditionTree = function("json_extract", vec![document.into(), "$.theme".into()])
+    ///     .equals("dark")
+    ///     .into();
+    ///
+    /// assert_eq!(expected, settings.json_path_equals(path.clone(), dark).aliased_cond(None));
+    ///
+    /// let text: ConditionTree = function("json_type", vec![document.clone().into(), "$.theme".into()])
+    ///     .equals("text")
+    ///     .into();
+    /// let theme = function("json_extract", vec![document.into(), "$.theme".into()]);
+    /// let contains: ConditionTree = function("glob", vec!["*d[?]rk*".into(), theme.into()]).equals(1).into();
+    ///
+    /// let expected = ConditionTree::and(text, contains);
+    /// assert_eq!(expected, settings.json_path_contains_sensitive(path, "d?rk").aliased_cond(None));
     /// ```
     fn aliased_cond(self, alias: Option<Alias>) -> ConditionTree {
         let column = match alias {
@@ -208,32 +270,335 @@ impl AliasedCondition for ScalarFilter {
             None => self.field.as_column(),
         };
 
-        let condition = match self.condition {
-            ScalarCondition::Equals(PrismaValue::Null) => column.is_null(),
-            ScalarCondition::NotEquals(PrismaValue::Null) => column.is_not_null(),
-            ScalarCondition::Equals(value) => column.equals(value),
-            ScalarCondition::NotEquals(value) => column.not_equals(value),
-            ScalarCondition::Contains(value) => column.like(format!("{}", value)),
-            ScalarCondition::NotContains(value) => column.not_like(format!("{}", value)),
-            ScalarCondition::StartsWith(value) => column.begins_with(format!("{}", value)),
-            ScalarCondition::NotStartsWith(value) => column.not_begins_with(format!("{}", value)),
-            ScalarCondition::EndsWith(value) => column.ends_into(format!("{}", value)),
-            ScalarCondition::NotEndsWith(value) => column.not_ends_into(format!("{}", value)),
-            ScalarCondition::LessThan(value) => column.less_than(value),
-            ScalarCondition::LessThanOrEquals(value) => column.less_than_or_equals(value),
-            ScalarCondition::GreaterThan(value) => column.greater_than(value),
-            ScalarCondition::GreaterThanOrEquals(value) => column.greater_than_or_equals(value),
+        // Values are matched literally with escaped wildcards, unless given as a
+        // raw pattern. Insensitive conditions lowercase both sides the way the
+        // Unicode-aware function of the connection does, sensitive ones match
+        // with `GLOB` and its wildcards instead.
+        let mode = self.mode;
+        let like = |pattern: String, negated: bool| pattern_condition(column.clone().into(), pattern, mode, negated);
+
+        let any = if mode == FilterMode::Sensitive { "*" } else { "%" };
+
+        let literal = |value: PrismaValue| match mode {
+            FilterMode::Sensitive => escape_glob(&format!("{}", value)),
+            _ => escape_like(&format!("{}", value)),
+        };
+
+        let raw = |value: PrismaValue| match mode {
+            FilterMode::Sensitive => like_to_glob(&format!("{}", value)),
+            _ => raw_like_pattern(&format!("{}", value)),
+        };
+
+        // Invalid JSON documents are read as `NULL`, matching nothing.
+        let document = || -> DatabaseValue { function(JSON_FUNCTION, vec![column.clone().into()]).into() };
+        let at_path = |name: &str, path: &JsonPath| function(name, vec![document(), path.to_string().into()]);
+
+        match self.condition {
+            ScalarCondition::Equals(PrismaValue::Null) => column.is_null().into(),
+            ScalarCondition::NotEquals(PrismaValue::Null) => column.is_not_null().into(),
+            ScalarCondition::Equals(value) => column.equals(value).into(),
+            ScalarCondition::NotEquals(value) => column.not_equals(value).into(),
+            ScalarCondition::Contains(value) => like(format!("{0}{1}{0}", any, literal(value)), false).into(),
+            ScalarCondition::NotContains(value) => like(format!("{0}{1}{0}", any, literal(value)), true).into(),
+            ScalarCondition::StartsWith(value) => like(format!("{}{}", literal(value), any), false).into(),
+            ScalarCondition::NotStartsWith(value) => like(format!("{}{}", literal(value), any), true).into(),
+            ScalarCondition::EndsWith(value) => like(format!("{}{}", any, literal(value)), false).into(),
+            ScalarCondition::NotEndsWith(value) => like(format!("{}{}", any, literal(value)), true).into(),
+            ScalarCondition::Like(value) => like(raw(value), false).into(),
+            ScalarCondition::Search(query) => {
+                let model = self.field.model();
+                let matches = SearchIndex::new(self.field.model()).matches(&self.field, &format!("{}", query));
+
+                let id_column = match alias {
+                    Some(ref alias) => model.id_column().table(alias.to_string(None)),
+                    None => model.id_column(),
+                };
+
+                id_column.in_selection(matches).into()
+            }
+            ScalarCondition::JsonPathEquals(path, value) => match JsonOperand::from(value) {
+                JsonOperand::Type(typ) => at_path("json_type", &path).equals(typ).into(),
+                JsonOperand::Value(value) => at_path("json_extract", &path).equals(value).into(),
+            },
+            ScalarCondition::JsonPathContains(path, value) => {
+                let text: ConditionTree = at_path("json_type", &path).equals("text").into();
+                let pattern = format!("{0}{1}{0}", any, literal(value));
+                let contains: ConditionTree =
+                    pattern_condition(at_path("json_extract", &path).into(), pattern, mode, false).into();
+
+                ConditionTree::and(text, contains)
+            }
+            ScalarCondition::JsonArrayContains(path, value) => {
+                let args = vec![column.into(), path.to_string().into(), json_element(value).into()];
+                function(JSON_ARRAY_CONTAINS_FUNCTION, args).equals(1).into()
+            }
+            ScalarCondition::JsonKeyExists(path) => at_path("json_type", &path).is_not_null().into(),
+            ScalarCondition::LessThan(value) => column.less_than(value).into(),
+            ScalarCondition::LessThanOrEquals(value) => column.less_than_or_equals(value).into(),
+            ScalarCondition::GreaterThan(value) => column.greater_than(value).into(),
+            ScalarCondition::GreaterThanOrEquals(value) => column.greater_than_or_equals(value).into(),
             ScalarCondition::In(values) => match values.split_first() {
-                Some((PrismaValue::Null, tail)) if tail.is_empty() => column.is_null(),
-                _ => column.in_selection(values),
+                Some((PrismaValue::Null, tail)) if tail.is_empty() => column.is_null().into(),
+                _ => column.in_selection(values).into(),
             },
             ScalarCondition::NotIn(values) => match values.split_first() {
-                Some((PrismaValue::Null, tail)) if tail.is_empty() => column.is_not_null(),
-                _ => column.not_in_selection(values),
+                Some((PrismaValue::Null, tail)) if tail.is_empty() => column.is_not_null().into(),
+                _ => column.not_in_selection(values).into(),
             },
+        }
+    }
+}
+
+/// `value LIKE ? ESCAPE '\'` of the pattern, or `NOT LIKE` if negated.
+/// Insensitive conditions lowercase the value and the pattern, sensitive ones
+/// take a `GLOB` pattern, matched by the built-in `glob(pattern, value)`.
+fn pattern_condition(value: DatabaseValue, pattern: String, mode: FilterMode, negated: bool) -> Compare {
+    match (mode, negated) {
+        (FilterMode::Default, false) => value.like_escaped(pattern, LIKE_ESCAPE),
+        (FilterMode::Default, true) => value.not_like_escaped(pattern, LIKE_ESCAPE),
+        (FilterMode::Insensitive, false) => {
+            function(LOWER_FUNCTION, vec![value]).like_escaped(pattern.to_lowercase(), LIKE_ESCAPE)
+        }
+        (FilterMode::Insensitive, true) => {
+            function(LOWER_FUNCTION, vec![value]).not_like_escaped(pattern.to_lowercase(), LIKE_ESCAPE)
+        }
+        (FilterMode::Sensitive, negated) => {
+            let matched = if negated { 0 } else { 1 };
+            function("glob", vec![pattern.into(), value]).equals(matched)
+        }
+    }
+}
+
+/// A value compared against the values of JSON documents. `json_extract`
+/// reads JSON scalars as SQL values, `true` and `false` as integers and
+/// `null` as `NULL`, so those are compared by their JSON type instead. Arrays
+/// and objects are read as minified JSON text, which `json` writes the
+/// compared value as.
+enum JsonOperand {
+    Type(&'static str),
+    Value(DatabaseValue),
+}
+
+impl From<PrismaValue> for JsonOperand {
+    fn from(value: PrismaValue) -> Self {
+        let json = match value {
+            PrismaValue::Json(json) => parse_json(json),
+            PrismaValue::Boolean(b) => Value::Bool(b),
+            PrismaValue::Null => Value::Null,
+            value => return JsonOperand::Value(value.into()),
         };
 
-        ConditionTree::single(condition)
+        match json {
+            Value::Null => JsonOperand::Type("null"),
+            Value::Bool(true) => JsonOperand::Type("true"),
+            Value::Bool(false) => JsonOperand::Type("false"),
+            Value::Number(n) => JsonOperand::Value(PrismaValue::Float(n.as_f64().unwrap_or_default()).into()),
+            Value::String(s) => JsonOperand::Value(PrismaValue::String(s).into()),
+            json => JsonOperand::Value(function("json", vec![json.to_string().into()]).into()),
+        }
+    }
+}
+
+/// The element searched in JSON arrays, written as JSON.
+fn json_element(value: PrismaValue) -> String {
+    let json = match value {
+        PrismaValue::Json(json) => parse_json(json),
+        PrismaValue::Boolean(b) => Value::Bool(b),
+        PrismaValue::Null => Value::Null,
+        PrismaValue::Int(i) => Value::from(i),
+        PrismaValue::Float(f) => Value::from(f),
+        value => Value::String(format!("{}", value)),
+    };
+
+    json.to_string()
+}
+
+/// Values given as JSON are compared as the JSON they hold, or as a string if
+/// they are invalid.
+fn parse_json(json: String) -> Value {
+    serde_json::from_str(&json).unwrap_or_else(|_| Value::String(json))
+}
+
+impl AliasedCondition for ScalarListFilter {
+    /// Conversion from a `ScalarListFilter` to a query condition tree. The
+    /// list values are matched in a subselect against the field's scalar list
+    /// table. Aliased when in a nested `SELECT`.
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use prisma_models::*;
+    /// # use connector::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// let schema = template.build(String::from("test"));
+    /// let user = schema.find_model("User").unwrap();
+    /// let field = user.fields().find_from_scalar("cats").unwrap();
+    ///
+    /// let sub_select = |cond: Compare| {
+    ///     Select::from_table(("test", "User_cats"))
+    ///         .column((("test", "User_cats"), "nodeId"))
+    ///         .so_that(cond)
+    /// };
+    ///
+    /// // List contains a value:
+    /// {
+    ///     let cond = field.contains_element(1).aliased_cond(None);
+    ///
+    ///     let expected: ConditionTree = (("test", "User"), "id")
+    ///         .in_selection(sub_select((("test", "User_cats"), "value").equals(1)))
+    ///         .into();
+    ///
+    ///     assert_eq!(expected, cond);
+    /// }
+    ///
+    /// // List contains every value:
+    /// {
+    ///     let cond = field.contains_every_element(vec![1, 2]).aliased_cond(Some(Alias::default()));
+    ///
+    ///     let first: ConditionTree = ("t0", "id")
+    ///         .in_selection(sub_select((("test", "User_cats"), "value").equals(1)))
+    ///         .into();
+    ///
+    ///     let second: ConditionTree = ("t0", "id")
+    ///         .in_selection(sub_select((("test", "User_cats"), "value").equals(2)))
+    ///         .into();
+    ///
+    ///     assert_eq!(ConditionTree::and(first, second), cond);
+    /// }
+    ///
+    /// // List contains some of the values:
+    /// {
+    ///     let cond = field.contains_some_element(vec![1, 2]).aliased_cond(None);
+    ///
+    ///     let expected: ConditionTree = (("test", "User"), "id")
+    ///         .in_selection(sub_select((("test", "User_cats"), "value").in_selection(vec![1, 2])))
+    ///         .into();
+    ///
+    ///     assert_eq!(expected, cond);
+    /// }
+    /// ```
+    ///
+    /// Nodes without values have an empty list, which contains none of the
+    /// values and every one of no values:
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use prisma_models::*;
+    /// # use connector::{filter::*, *};
+    /// # use prisma_query::{ast::*, visitor::{self, Visitor}};
+    /// # use rusqlite::Connection;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// let schema = template.build(String::from("test"));
+    /// let user = schema.find_model("User").unwrap();
+    /// let site = schema.find_model("Site").unwrap();
+    ///
+    /// let name = user.fields().find_from_scalar("name").unwrap();
+    /// let cats = user.fields().find_from_scalar("cats").unwrap();
+    /// let sites = user.fields().find_from_relation_fields("sites").unwrap();
+    /// let tags = site.fields().find_from_scalar("tags").unwrap();
+    ///
+    /// let conn = Connection::open_in_memory().unwrap();
+    /// conn.execute_batch(
+    ///     "ATTACH DATABASE ':memory:' AS test;
+    ///      CREATE TABLE test.User (id Text, name Text);
+    ///      CREATE TABLE test.User_cats (nodeId Text, position Integer, value Integer);
+    ///      CREATE TABLE test.Site (id Text, name Text);
+    ///      CREATE TABLE test.Site_tags (nodeId Text, position Integer, value Integer);
+    ///      CREATE TABLE test._UserToSites (A Text, B Text, id Text);
+    ///      INSERT INTO test.User VALUES ('a', 'Alice'), ('b', 'Bob'), ('c', 'Carol');
+    ///      INSERT INTO test.User_cats VALUES ('a', 1000, 1), ('a', 2000, 2), ('b', 1000, 2), ('b', 2000, 3);
+    ///      INSERT INTO test.Site VALUES ('blog', 'Blog'), ('shop', 'Shop');
+    ///      INSERT INTO test.Site_tags VALUES ('blog', 1000, 7);
+    ///      INSERT INTO test._UserToSites VALUES ('blog', 'a', 'r1'), ('shop', 'b', 'r2');",
+    /// )
+    /// .unwrap();
+    ///
+    /// let ids = |filter: Filter| -> Vec<String> {
+    ///     let select = Select::from_table(user.table())
+    ///         .column(user.id_column())
+    ///         .so_that(filter.aliased_cond(None))
+    ///         .order_by(user.id_column().ascend());
+    ///
+    ///     let (sql, params) = visitor::Sqlite::build(select);
+    ///     let mut stmt = conn.prepare(&sql).unwrap();
+    ///
+    ///     stmt.query_map(&params, |row| row.get(0)).unwrap().map(Result::unwrap).collect()
+    /// };
+    ///
+    /// assert_eq!(vec!["a", "b"], ids(cats.contains_element(2)));
+    /// assert!(ids(cats.contains_element(4)).is_empty());
+    ///
+    /// assert_eq!(vec!["a"], ids(cats.contains_every_element(vec![1, 2])));
+    /// assert_eq!(vec!["a", "b", "c"], ids(cats.contains_every_element(Vec::<i32>::new())));
+    ///
+    /// assert_eq!(vec!["a", "b"], ids(cats.contains_some_element(vec![1, 3])));
+    /// assert!(ids(cats.contains_some_element(Vec::<i32>::new())).is_empty());
+    ///
+    /// // Inside logical filters:
+    /// let bob = Filter::and(vec![cats.contains_element(2), name.equals("Bob")]);
+    /// assert_eq!(vec!["b"], ids(bob));
+    ///
+    /// let alice = Filter::or(vec![cats.contains_element(1), cats.contains_some_element(Vec::<i32>::new())]);
+    /// assert_eq!(vec!["a"], ids(alice));
+    ///
+    /// assert_eq!(vec!["c"], ids(Filter::not(vec![cats.contains_element(2)])));
+    /// assert!(ids(Filter::not(vec![cats.contains_every_element(Vec::<i32>::new())])).is_empty());
+    ///
+    /// // On related nodes, aliased in the relation's subselect:
+    /// assert_eq!(vec!["a"], ids(sites.at_least_one_related(tags.contains_element(7))));
+    /// assert_eq!(vec!["b", "c"], ids(sites.no_related(tags.contains_some_element(vec![7]))));
+    /// ```
+    fn aliased_cond(self, alias: Option<Alias>) -> ConditionTree {
+        let id_column = self.field.model().id_column();
+
+        let column = match alias {
+            Some(ref alias) => id_column.table(alias.to_string(None)),
+            None => id_column,
+        };
+
+        let list_table = self.field.scalar_list_table();
+
+        let sub_select = |condition: Compare| {
+            Select::from_table(list_table.table())
+                .column(list_table.node_id_column())
+                .so_that(condition)
+        };
+
+        match self.condition {
+            ScalarListCondition::Contains(value) => {
+                let sub_select = sub_select(list_table.value_column().equals(value));
+                ConditionTree::single(column.in_selection(sub_select))
+            }
+            ScalarListCondition::ContainsEvery(mut values) => match values.pop() {
+                None => ConditionTree::NoCondition,
+                Some(value) => {
+                    let right: ConditionTree = column
+                        .clone()
+                        .in_selection(sub_select(list_table.value_column().equals(value)))
+                        .into();
+
+                    values.into_iter().rev().fold(right, |acc, value| {
+                        let left: ConditionTree = column
+                            .clone()
+                            .in_selection(sub_select(list_table.value_column().equals(value)))
+                            .into();
+
+                        ConditionTree::and(left, acc)
+                    })
+                }
+            },
+            ScalarListCondition::ContainsSome(ref values) if values.is_empty() => ConditionTree::NegativeCondition,
+            ScalarListCondition::ContainsSome(values) => {
+                let sub_select = sub_select(list_table.value_column().in_selection(values));
+                ConditionTree::single(column.in_selection(sub_select))
+            }
+        }
     }
 }
 
@@ -303,6 +668,31 @@ impl AliasedCondition for RelationFilter {
     ///     assert_eq!(expected, cond);
     /// }
     ///
+    /// // More than five related records match:
+    /// {
+    ///     let cond = rf
+    ///         .clone()
+    ///         .related_count(site_name.clone().equals("Blog"), CountCondition::GreaterThan(5))
+    ///         .aliased_cond(None);
+    ///
+    ///     let join_data = ("test", "Site")
+    ///         .alias("j0")
+    ///         .on(("j0", "id").equals(Column::from(("t0", "A"))));
+    ///
+    ///     let parent: ConditionTree = ("t0", "B").equals(Column::from((("test", "User"), "id"))).into();
+    ///     let nested: ConditionTree = ("j0", "name").equals("Blog").into();
+    ///
+    ///     let related_count: DatabaseValue = Select::from_table(Table::from("_UserToSites").alias("t0"))
+    ///         .value(count(asterisk()))
+    ///         .inner_join(join_data)
+    ///         .so_that(ConditionTree::and(parent, nested))
+    ///         .into();
+    ///
+    ///     let expected: ConditionTree = related_count.greater_than(5i64).into();
+    ///
+    ///     assert_eq!(expected, cond);
+    /// }
+    ///
     /// // At least one related record matches:
     /// {
     ///     let cond = rf
@@ -333,14 +723,21 @@ impl AliasedCondition for RelationFilter {
             None => id,
         };
 
-        let condition = self.condition.clone();
-        let sub_select = self.aliased_sel(alias.map(|a| a.inc(AliasMode::Table)));
+        let sub_alias = alias.map(|a| a.inc(AliasMode::Table));
 
-        let comparison = match condition {
-            RelationCondition::EveryRelatedNode => column.not_in_selection(sub_select),
-            RelationCondition::NoRelatedNode => column.not_in_selection(sub_select),
-            RelationCondition::AtLeastOneRelatedNode => column.in_selection(sub_select),
-            RelationCondition::ToOneRelatedNode => column.in_selection(sub_select),
+        let comparison = match self.condition {
+            RelationCondition::RelatedCount(count) => related_count(self, column, count, sub_alias.unwrap_or_default()),
+            condition => {
+                let sub_select = self.aliased_sel(sub_alias);
+
+                match condition {
+                    RelationCondition::EveryRelatedNode => column.not_in_selection(sub_select),
+                    RelationCondition::NoRelatedNode => column.not_in_selection(sub_select),
+                    RelationCondition::AtLeastOneRelatedNode => column.in_selection(sub_select),
+                    RelationCondition::ToOneRelatedNode => column.in_selection(sub_select),
+                    RelationCondition::RelatedCount(_) => unreachable!(),
+                }
+            }
         };
 
         comparison.into()
@@ -359,14 +756,21 @@ impl AliasedSelect for RelationFilter {
 
         match *self.nested_filter {
             Filter::Relation(filter) => {
-                let sub_condition = filter.condition.clone();
-                let sub_select = filter.aliased_sel(Some(alias.inc(AliasMode::Table)));
-
-                let tree: ConditionTree = match sub_condition {
-                    RelationCondition::EveryRelatedNode => other_column.not_in_selection(sub_select),
-                    RelationCondition::NoRelatedNode => other_column.not_in_selection(sub_select),
-                    RelationCondition::AtLeastOneRelatedNode => other_column.in_selection(sub_select),
-                    RelationCondition::ToOneRelatedNode => other_column.in_selection(sub_select),
+                let sub_alias = alias.inc(AliasMode::Table);
+
+                let tree: ConditionTree = match filter.condition {
+                    RelationCondition::RelatedCount(count) => related_count(filter, other_column, count, sub_alias),
+                    sub_condition => {
+                        let sub_select = filter.aliased_sel(Some(sub_alias));
+
+                        match sub_condition {
+                            RelationCondition::EveryRelatedNode => other_column.not_in_selection(sub_select),
+                            RelationCondition::NoRelatedNode => other_column.not_in_selection(sub_select),
+                            RelationCondition::AtLeastOneRelatedNode => other_column.in_selection(sub_select),
+                            RelationCondition::ToOneRelatedNode => other_column.in_selection(sub_select),
+                            RelationCondition::RelatedCount(_) => unreachable!(),
+                        }
+                    }
                 }
                 .into();
 
@@ -403,6 +807,40 @@ impl AliasedSelect for RelationFilter {
     }
 }
 
+/// Compares the number of records related to the parent in `parent_id` that
+/// match the nested filter, counted in a correlated subselect so parents
+/// without related records count zero.
+fn related_count(filter: RelationFilter, parent_id: Column, condition: CountCondition, alias: Alias) -> Compare {
+    let table_alias = alias.to_string(Some(AliasMode::Table));
+    let join_alias = alias.to_string(Some(AliasMode::Join));
+
+    let relation = filter.field.relation();
+    let related = filter.field.related_model();
+
+    let this_column = filter.field.relation_column().table(table_alias.clone());
+    let other_column = filter.field.opposite_column().table(table_alias.clone());
+    let related_id = related.id_column().table(join_alias.clone());
+
+    let join = related.table().alias(join_alias).on(related_id.equals(other_column));
+    let parent: ConditionTree = this_column.equals(parent_id).into();
+    let nested = filter.nested_filter.aliased_cond(Some(alias.flip(AliasMode::Join)));
+
+    let related_count: DatabaseValue = Select::from_table(relation.relation_table().alias(table_alias))
+        .value(count(asterisk()))
+        .inner_join(join)
+        .so_that(ConditionTree::and(parent, nested))
+        .into();
+
+    match condition {
+        CountCondition::Equals(n) => related_count.equals(n as i64),
+        CountCondition::NotEquals(n) => related_count.not_equals(n as i64),
+        CountCondition::LessThan(n) => related_count.less_than(n as i64),
+        CountCondition::LessThanOrEquals(n) => related_count.less_than_or_equals(n as i64),
+        CountCondition::GreaterThan(n) => related_count.greater_than(n as i64),
+        CountCondition::GreaterThanOrEquals(n) => related_count.greater_than_or_equals(n as i64),
+    }
+}
+
 impl AliasedCondition for OneRelationIsNullFilter {
     /// Conversion from a `OneRelationIsNullFilter` to a query condition tree. Aliased when in a nested `SELECT`.
     ///
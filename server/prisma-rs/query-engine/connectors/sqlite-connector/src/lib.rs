@@ -16,10 +16,13 @@
 mod cursor_condition;
 mod database_read;
 mod database_write;
+mod distinct_condition;
 mod filter_conversion;
 mod mutaction;
 mod ordering;
 mod query_builder;
+mod schema_tables;
+mod search_index;
 mod sqlite;
 mod transactional_executor;
 
@@ -28,5 +31,7 @@ pub use database_write::*;
 pub use filter_conversion::*;
 pub use mutaction::*;
 pub use query_builder::SelectDefinition;
+pub use schema_tables::SchemaTables;
+pub use search_index::SearchIndex;
 pub use sqlite::*;
 pub use transactional_executor::*;
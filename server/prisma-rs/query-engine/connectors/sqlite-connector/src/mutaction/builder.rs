@@ -1,12 +1,13 @@
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
+use std::sync::Arc;
 
-use connector::{error::ConnectorError, ConnectorResult};
+use connector::{error::ConnectorError, filter::NodeSelector, mutaction::UpsertNode, ConnectorResult};
 
 pub struct MutationBuilder;
 
 impl MutationBuilder {
-    const PARAMETER_LIMIT: usize = 10000;
+    pub const PARAMETER_LIMIT: usize = 10000;
 
     pub fn create_node(model: ModelRef, mut args: PrismaArgs) -> (Insert, Option<GraphqlId>) {
         let model_id = model.fields().id();
@@ -112,6 +113,112 @@ impl MutationBuilder {
         Some(result)
     }
 
+    /// Batched inserts of many records of one model, generating the missing
+    /// ids and filling `createdAt` and `updatedAt`. Records setting the same
+    /// fields share multi-row statements, chunked under the parameter limit.
+    /// With `skip_duplicates`, records conflicting with a unique constraint
+    /// are ignored, other constraint violations still fail.
+    ///
+    /// Returns the statements with the ids of all records, or no ids when
+    /// they are auto-generated by the database.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use prisma_query::visitor::{self, Visitor};
+    /// # use sqlite_connector::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let user = schema.find_model("User").unwrap();
+    /// let mut bob = PrismaArgs::new();
+    /// bob.insert("id", GraphqlId::from("id1"));
+    /// bob.insert("name", "Bob");
+    ///
+    /// let mut alice = PrismaArgs::new();
+    /// alice.insert("name", "Alice");
+    ///
+    /// let (statements, ids) = MutationBuilder::create_nodes(user, vec![bob, alice], true);
+    /// assert_eq!(1, statements.len());
+    ///
+    /// let (sql, params) = visitor::Sqlite::build(statements.into_iter().next().unwrap());
+    ///
+    /// assert!(sql.ends_with("ON CONFLICT DO NOTHING"));
+    /// assert_eq!(4, params.len());
+    ///
+    /// assert_eq!(2, ids.len());
+    /// assert_eq!(GraphqlId::from("id1"), ids[0]);
+    /// ```
+    pub fn create_nodes(model: ModelRef, args: Vec<PrismaArgs>, skip_duplicates: bool) -> (Vec<Query>, Vec<GraphqlId>) {
+        let model_id = model.fields().id();
+        let fields = model.fields().scalar_non_list();
+
+        let mut ids = Vec::new();
+        let mut groups: Vec<(Vec<&str>, Vec<Vec<PrismaValue>>)> = Vec::new();
+
+        for mut args in args {
+            args.add_datetimes(Arc::clone(&model));
+
+            if !model_id.is_auto_generated {
+                let id = match args.take_field_value(&model_id.name) {
+                    Some(PrismaValue::GraphqlId(id)) => id,
+                    Some(PrismaValue::String(s)) => GraphqlId::String(s),
+                    Some(PrismaValue::Int(i)) => GraphqlId::Int(i as usize),
+                    Some(PrismaValue::Uuid(uuid)) => GraphqlId::UUID(uuid),
+                    _ => model.generate_id(),
+                };
+
+                args.insert(model_id.name.as_ref(), id.clone());
+                ids.push(id);
+            }
+
+            let (columns, values): (Vec<&str>, Vec<PrismaValue>) = fields
+                .iter()
+                .filter_map(|field| args.take_field_value(&field.name).map(|value| (field.db_name(), value)))
+                .unzip();
+
+            match groups.iter_mut().find(|(group_columns, _)| group_columns == &columns) {
+                Some((_, rows)) => rows.push(values),
+                None => groups.push((columns, vec![values])),
+            }
+        }
+
+        let mut statements = Vec::new();
+
+        for (columns, rows) in groups {
+            // Records without any values can't share a statement, and can't
+            // take a conflict clause either.
+            if columns.is_empty() {
+                for _ in rows {
+                    let insert: Insert = Insert::single_into(model.table()).into();
+                    statements.push(insert.into());
+                }
+
+                continue;
+            }
+
+            for chunk in rows.chunks(Self::PARAMETER_LIMIT / columns.len()) {
+                let insert: Insert = chunk
+                    .iter()
+                    .fold(Insert::multi_into(model.table(), columns.clone()), |acc, row| {
+                        acc.values(row.clone())
+                    })
+                    .into();
+
+                // `INSERT OR IGNORE` would also skip NOT NULL and CHECK violations.
+                let statement: Query = match skip_duplicates {
+                    true => insert.on_conflict(OnConflict::DoNothing).into(),
+                    false => insert.into(),
+                };
+
+                statements.push(statement);
+            }
+        }
+
+        (statements, ids)
+    }
+
     pub fn update_one(model: ModelRef, id: &GraphqlId, args: &PrismaArgs) -> ConnectorResult<Option<Update>> {
         Self::update_many(model, &[id; 1], args).map(|updates| updates.into_iter().next())
     }
@@ -125,7 +232,7 @@ impl MutationBuilder {
         let mut query = Update::table(model.table());
 
         for (name, value) in args.args.iter() {
-            let field = fields.find_from_scalar(&name).unwrap();
+            let field = fields.find_from_scalar(&name)?;
 
             if field.is_required && value.is_null() {
                 return Err(ConnectorError::FieldCannotBeNull {
@@ -149,6 +256,235 @@ impl MutationBuilder {
         Ok(result)
     }
 
+    /// Statements applying the operations on the current values of numeric
+    /// fields, e.g. `"count" = "count" + ?`, to the nodes.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use prisma_query::visitor::{self, Visitor};
+    /// # use sqlite_connector::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let user = schema.find_model("User").unwrap();
+    /// let id = GraphqlId::from("id1");
+    ///
+    /// let mut args = PrismaArgs::new();
+    /// args.operations.insert(String::from("name"), UpdateOperation::Set(PrismaValue::from("Bob")));
+    ///
+    /// let statements = MutationBuilder::update_operations(Arc::clone(&user), &[&id], &args).unwrap();
+    /// let (sql, params) = visitor::Sqlite::build(statements[0].clone());
+    ///
+    /// assert!(sql.starts_with(r#"UPDATE "test"."User" SET "name" = ?"#));
+    /// assert_eq!(2, params.len());
+    ///
+    /// // The operands count towards the parameter limit.
+    /// let ids: Vec<GraphqlId> = (0..MutationBuilder::PARAMETER_LIMIT).map(GraphqlId::from).collect();
+    /// let id_refs: Vec<&GraphqlId> = ids.iter().collect();
+    ///
+    /// let statements = MutationBuilder::update_operations(Arc::clone(&user), &id_refs, &args).unwrap();
+    /// let params = |i: usize| visitor::Sqlite::build(statements[i].clone()).1.len();
+    ///
+    /// assert_eq!(2, statements.len());
+    /// assert_eq!(MutationBuilder::PARAMETER_LIMIT, params(0));
+    /// assert_eq!(2, params(1));
+    ///
+    /// // Strings can't be incremented.
+    /// let mut args = PrismaArgs::new();
+    /// args.insert_operation("name", UpdateOperation::Increment(PrismaValue::Int(1)));
+    ///
+    /// assert!(MutationBuilder::update_operations(Arc::clone(&user), &[&id], &args).is_err());
+    ///
+    /// // Neither can fields the model doesn't have.
+    /// let mut args = PrismaArgs::new();
+    /// args.insert_operation("nickname", UpdateOperation::Increment(PrismaValue::Int(1)));
+    ///
+    /// assert!(MutationBuilder::update_operations(user, &[&id], &args).is_err());
+    /// ```
+    pub fn update_operations(model: ModelRef, ids: &[&GraphqlId], args: &PrismaArgs) -> ConnectorResult<Vec<Update>> {
+        if args.operations.is_empty() || ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let fields = model.fields();
+        let mut query = Update::table(model.table());
+
+        for (name, operation) in args.operations.iter() {
+            let field = fields.find_from_scalar(&name)?;
+            query = query.set(field.db_name(), Self::assignment(&field, operation)?);
+        }
+
+        let result: Vec<Update> = ids
+            .chunks(Self::PARAMETER_LIMIT - args.operations.len())
+            .map(|ids| {
+                query
+                    .clone()
+                    .so_that(fields.id().as_column().in_selection(ids.to_vec()))
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    /// A single `INSERT … ON CONFLICT … DO UPDATE` statement for the upsert,
+    /// creating the record, or updating the record holding the selected
+    /// unique values instead. SQLite checks for the conflict and writes in one
+    /// step, so concurrent upserts can't both create the record.
+    ///
+    /// Returns the statement with the id of the record if it is created.
+    /// `None` if the upsert can't be expressed as one statement, decided by
+    /// the schema: the selector must be the id, a unique field or a compound
+    /// unique constraint, the create has to set the selected values and an id
+    /// that isn't generated by the database, the update can change the
+    /// selected values only by setting them, and neither may change scalar
+    /// lists.
+    ///
+    /// A created record gets a rowid above the last one inserted by the
+    /// connection, so that the statement changes the last inserted rowid only
+    /// if it creates the record.
+    ///
+    /// ```rust
+    /// # use connector::{filter::*, mutaction::*};
+    /// # use prisma_models::*;
+    /// # use sqlite_connector::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let user = schema.find_model("User").unwrap();
+    /// let where_ = NodeSelector::from((user.fields().id(), GraphqlId::from("id1")));
+    ///
+    /// let mut args = PrismaArgs::new();
+    /// args.insert("name", "Bob");
+    ///
+    /// let mut upsert = UpsertNode {
+    ///     where_: where_.clone(),
+    ///     create: CreateNode {
+    ///         model: Arc::clone(&user),
+    ///         non_list_args: args.clone(),
+    ///         list_args: Vec::new(),
+    ///         nested_mutactions: NestedMutactions::default(),
+    ///     },
+    ///     update: UpdateNode {
+    ///         where_,
+    ///         non_list_args: args,
+    ///         list_args: Vec::new(),
+    ///         nested_mutactions: NestedMutactions::default(),
+    ///     },
+    /// };
+    ///
+    /// // Creating the user generates a new id, so it can't conflict on `id1`.
+    /// assert!(MutationBuilder::upsert_node(&upsert).unwrap().is_none());
+    ///
+    /// // Creating it with the selected id can:
+    /// upsert.create.non_list_args.insert("id", GraphqlId::from("id1"));
+    /// assert!(MutationBuilder::upsert_node(&upsert).unwrap().is_some());
+    ///
+    /// // Updating a field the model doesn't have is an error.
+    /// upsert.update.non_list_args.insert("nickname", "Bobby");
+    /// assert!(MutationBuilder::upsert_node(&upsert).is_err());
+    /// ```
+    pub fn upsert_node(upsert: &UpsertNode) -> ConnectorResult<Option<(Query, GraphqlId)>> {
+        let model = upsert.where_.model();
+        let model_id = model.fields().id();
+        let selected = upsert.where_.fields();
+
+        let create = &upsert.create.non_list_args;
+        let update = &upsert.update.non_list_args;
+
+        let unique = match upsert.where_ {
+            NodeSelector::Field { ref field, .. } => field.is_id() || field.is_unique,
+            NodeSelector::Compound(_) => true,
+        };
+
+        let expressible = unique
+            && !model_id.is_auto_generated
+            && upsert.create.list_args.is_empty()
+            && upsert.update.list_args.is_empty()
+            && selected.iter().all(|(field, value)| {
+                create.get_field_value(&field.name) == Some(value) && !update.operations.contains_key(&field.name)
+            });
+
+        let id = match create.get_field_value(&model_id.name) {
+            _ if !expressible => return Ok(None),
+            Some(PrismaValue::Null) | None => model.generate_id(),
+            Some(PrismaValue::GraphqlId(id)) => id.clone(),
+            _ => return Ok(None),
+        };
+
+        let mut create = create.clone();
+        create.insert(model_id.name.as_ref(), id.clone());
+
+        // A rowid above the last one inserted by the connection.
+        let last_rowid = function(
+            "max",
+            vec![
+                function(
+                    "coalesce",
+                    vec![function("max", vec![Column::from("rowid").into()]).into(), 0.into()],
+                )
+                .into(),
+                function("last_insert_rowid", Vec::new()).into(),
+            ],
+        );
+
+        let rowid = Select::from_table(model.table()).value(DatabaseValue::from(last_rowid) + 1);
+
+        let insert = model
+            .fields()
+            .scalar_non_list()
+            .iter()
+            .filter_map(|field| {
+                create
+                    .take_field_value(&field.name)
+                    .map(|value| (field.db_name(), value))
+            })
+            .fold(
+                Insert::single_into(model.table()).value("rowid", rowid),
+                |acc, (name, value)| acc.value(name, value),
+            );
+
+        let set_values = update
+            .args
+            .iter()
+            .map(|(name, value)| (name, UpdateOperation::Set(value.clone())));
+
+        let operations = update
+            .operations
+            .iter()
+            .map(|(name, operation)| (name, operation.clone()));
+
+        let mut on_update = Update::table(model.table());
+        let mut assigned = false;
+
+        for (name, operation) in set_values.chain(operations) {
+            let field = model.fields().find_from_scalar(name)?;
+
+            on_update = on_update.set(field.db_name(), Self::assignment(&field, &operation)?);
+            assigned = true;
+        }
+
+        // Without assignments the first selected value is set to itself, as
+        // `DO NOTHING` takes no conflict target and would skip any conflict.
+        if !assigned {
+            let (field, _) = &selected[0];
+            on_update = on_update.set(field.db_name(), Column::from(field.db_name()));
+        }
+
+        let target: Vec<Column> = selected
+            .iter()
+            .map(|(field, _)| Column::from(field.db_name()))
+            .collect();
+
+        let insert: Insert = insert.into();
+        let statement = insert.on_conflict(OnConflict::Update(on_update, target)).into();
+
+        Ok(Some((statement, id)))
+    }
+
     pub fn delete_many(model: ModelRef, ids: &[&GraphqlId]) -> Vec<Delete> {
         let mut deletes = Vec::new();
 
@@ -191,6 +527,92 @@ impl MutationBuilder {
         (deletes, inserts)
     }
 
+    /// Appends the values to the lists of the nodes, after their last
+    /// positions.
+    ///
+    /// ```rust
+    /// # use prisma_models::*;
+    /// # use prisma_query::visitor::{self, Visitor};
+    /// # use sqlite_connector::*;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// #
+    /// # let tmp: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// # let schema = tmp.build(String::from("test"));
+    /// # let user = schema.find_model("User").unwrap();
+    /// let cats = user.fields().find_from_scalar("cats").unwrap();
+    /// let values: PrismaListValue = (0..600).map(PrismaValue::Int).collect();
+    ///
+    /// let ids: Vec<GraphqlId> = (0..10).map(GraphqlId::from).collect();
+    /// let id_refs: Vec<&GraphqlId> = ids.iter().collect();
+    ///
+    /// let statements = MutationBuilder::push_scalar_list_values(&cats, &values, &id_refs);
+    ///
+    /// // A row for each value of each node, each taking five parameters.
+    /// assert_eq!(3, statements.len());
+    /// assert_eq!(MutationBuilder::PARAMETER_LIMIT, visitor::Sqlite::build(statements[0].clone()).1.len());
+    /// ```
+    pub fn push_scalar_list_values(
+        field: &ScalarField,
+        list_value: &PrismaListValue,
+        ids: &[&GraphqlId],
+    ) -> Vec<Insert> {
+        Self::insert_scalar_list_values(field, list_value.iter().collect(), "max", 0, 1000, ids)
+    }
+
+    /// Prepends the values to the lists of the nodes, before their first
+    /// positions. The values are inserted last to first to keep their order.
+    pub fn unshift_scalar_list_values(
+        field: &ScalarField,
+        list_value: &PrismaListValue,
+        ids: &[&GraphqlId],
+    ) -> Vec<Insert> {
+        Self::insert_scalar_list_values(field, list_value.iter().rev().collect(), "min", 2000, -1000, ids)
+    }
+
+    /// Removes the value at the zero-based index of the lists of the nodes.
+    pub fn remove_scalar_list_value_at(
+        scalar_list_table: &ScalarListTable,
+        index: usize,
+        ids: &[&GraphqlId],
+    ) -> Vec<Delete> {
+        ids.iter()
+            .map(|id| {
+                let position = Select::from_table(scalar_list_table.table())
+                    .column(scalar_list_table.position_column())
+                    .so_that(scalar_list_table.node_id_column().equals(*id))
+                    .order_by(scalar_list_table.position_column().ascend())
+                    .limit(1)
+                    .offset(index);
+
+                let condition = scalar_list_table
+                    .node_id_column()
+                    .equals(*id)
+                    .and(scalar_list_table.position_column().equals(position));
+
+                Delete::from_table(scalar_list_table.table()).so_that(condition)
+            })
+            .collect()
+    }
+
+    /// Removes every occurrence of the value from the lists of the nodes.
+    pub fn remove_scalar_list_value(
+        scalar_list_table: &ScalarListTable,
+        value: &PrismaValue,
+        ids: &[&GraphqlId],
+    ) -> Vec<Delete> {
+        ids.chunks(Self::PARAMETER_LIMIT)
+            .map(|chunk| {
+                let condition = scalar_list_table
+                    .node_id_column()
+                    .in_selection(chunk.to_vec())
+                    .and(scalar_list_table.value_column().equals(value.clone()));
+
+                Delete::from_table(scalar_list_table.table()).so_that(condition)
+            })
+            .collect()
+    }
+
     pub fn delete_scalar_list_values(scalar_list_table: &ScalarListTable, ids: &[&GraphqlId]) -> Vec<Delete> {
         Self::delete_in_chunks(scalar_list_table.table(), ids, |chunk| {
             ScalarListTable::NODE_ID_FIELD_NAME.in_selection(chunk.to_vec())
@@ -236,6 +658,112 @@ impl MutationBuilder {
         deletes
     }
 
+    /// The value the operation assigns to the field, e.g. `"count" + ?`.
+    fn assignment(field: &ScalarField, operation: &UpdateOperation) -> ConnectorResult<DatabaseValue> {
+        let current = || DatabaseValue::from(Column::from(field.db_name()));
+        let operand = Self::operand(field, operation)?;
+
+        let value = match operation {
+            UpdateOperation::Set(_) => operand.into(),
+            UpdateOperation::Increment(_) => current() + operand,
+            UpdateOperation::Decrement(_) => current() - operand,
+            UpdateOperation::Multiply(_) => current() * operand,
+            UpdateOperation::Divide(_) => current() / operand,
+        };
+
+        Ok(value)
+    }
+
+    /// Arithmetic needs a number of the field's type, or an integer for
+    /// float fields. Dividing by zero would set the value to `NULL` in SQLite.
+    fn operand(field: &ScalarField, operation: &UpdateOperation) -> ConnectorResult<PrismaValue> {
+        let invalid = |reason: &str| ConnectorError::InvalidUpdateOperation {
+            field: field.name.clone(),
+            reason: reason.into(),
+        };
+
+        let value = match operation {
+            UpdateOperation::Set(value) if field.is_required && value.is_null() => {
+                return Err(ConnectorError::FieldCannotBeNull {
+                    field: field.name.clone(),
+                })
+            }
+            UpdateOperation::Set(value) => return Ok(value.clone()),
+            operation => operation.value(),
+        };
+
+        match (field.type_identifier, value) {
+            (TypeIdentifier::Int, PrismaValue::Int(_)) => (),
+            (TypeIdentifier::Float, PrismaValue::Int(_)) => (),
+            (TypeIdentifier::Float, PrismaValue::Float(_)) => (),
+            (TypeIdentifier::Int, _) | (TypeIdentifier::Float, _) => return Err(invalid("Expected a number")),
+            _ => return Err(invalid("Only Int and Float fields support arithmetic")),
+        }
+
+        match (operation, value) {
+            (UpdateOperation::Divide(_), PrismaValue::Int(0)) => Err(invalid("Division by zero")),
+            (UpdateOperation::Divide(_), PrismaValue::Float(f)) if *f == 0.0 => Err(invalid("Division by zero")),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    /// Inserts the values into the lists of the nodes at the positions next
+    /// to the current `max` or `min` position, the nth value moved by n times
+    /// `step`, or counted from `empty` into empty lists. The positions are
+    /// read in the statement, before any of its rows is inserted, so
+    /// concurrent changes to the lists are kept.
+    fn insert_scalar_list_values(
+        field: &ScalarField,
+        values: Vec<&PrismaValue>,
+        aggregate: &str,
+        empty: i64,
+        step: i64,
+        ids: &[&GraphqlId],
+    ) -> Vec<Insert> {
+        let scalar_list_table = field.scalar_list_table();
+
+        let columns = vec![
+            ScalarListTable::NODE_ID_FIELD_NAME,
+            ScalarListTable::POSITION_FIELD_NAME,
+            ScalarListTable::VALUE_FIELD_NAME,
+        ];
+
+        let rows: Vec<(GraphqlId, DatabaseValue, PrismaValue)> = ids
+            .iter()
+            .flat_map(|id| {
+                let current = Select::from_table(scalar_list_table.table())
+                    .value(function(aggregate, vec![scalar_list_table.position_column().into()]))
+                    .so_that(scalar_list_table.node_id_column().equals(*id));
+
+                let position = function("coalesce", vec![current.into(), empty.into()]);
+
+                values.iter().enumerate().map(move |(i, value)| {
+                    let offset = step * (i as i64 + 1);
+                    (
+                        (*id).clone(),
+                        DatabaseValue::from(position.clone()) + offset,
+                        (*value).clone(),
+                    )
+                })
+            })
+            .collect();
+
+        // The id, the position of empty lists, the offset and the value, with
+        // the id again in the position of each row.
+        rows.chunks(Self::PARAMETER_LIMIT / 5)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .cloned()
+                    .fold(
+                        Insert::multi_into(scalar_list_table.table(), columns.clone()),
+                        |acc, row| acc.values(row),
+                    )
+                    .into()
+            })
+            .collect()
+    }
+
     fn delete_in_chunks<F>(table: Table, ids: &[&GraphqlId], conditions: F) -> Vec<Delete>
     where
         F: Fn(&[&GraphqlId]) -> Compare,
@@ -14,18 +14,39 @@ impl DeleteActions {
     /// connector, giving the connector the possibility to return an optional
     /// `GraphqlID` from the database, such as trying to read a row from the
     /// `SELECT`.
-    pub fn check_relation_violations<F>(model: ModelRef, ids: &[&GraphqlId], f: F) -> ConnectorResult<()>
+    ///
+    /// Records in `deleted` are removed in the same transaction, e.g. through a
+    /// cascade, and therefore don't prevent the deletion when requiring it.
+    pub fn check_relation_violations<F>(
+        model: ModelRef,
+        ids: &[&GraphqlId],
+        deleted: &[(ModelRef, Vec<GraphqlId>)],
+        f: F,
+    ) -> ConnectorResult<()>
     where
         F: Fn(Select) -> ConnectorResult<Option<GraphqlId>>,
     {
         for rf in model.schema().fields_requiring_model(model) {
             let relation = rf.relation();
+            let requiring_model = rf.model();
+
+            let deleted_ids: Vec<&GraphqlId> = deleted
+                .iter()
+                .filter(|(model, _)| model.name == requiring_model.name)
+                .flat_map(|(_, ids)| ids.iter())
+                .collect();
 
             let condition = rf
                 .opposite_column()
                 .in_selection(ids.to_vec())
                 .and(rf.relation_column().is_not_null());
 
+            let condition = if deleted_ids.is_empty() {
+                condition
+            } else {
+                ConditionTree::and(condition, rf.relation_column().not_in_selection(deleted_ids))
+            };
+
             let select = Select::from_table(relation.relation_table())
                 .column(rf.opposite_column())
                 .so_that(condition);
@@ -1,5 +1,7 @@
+use crate::{SearchIndex, JSON_FUNCTION};
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
+use std::sync::Arc;
 
 pub type OrderVec = Vec<(DatabaseValue, Option<Order>)>;
 
@@ -7,79 +9,269 @@ pub struct Ordering;
 
 /// Tooling for generating orderings for different query types.
 impl Ordering {
-    pub fn for_model(model: ModelRef, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec {
-        Self::by_fields(
-            order_by.map(|oby| oby.field.as_column()),
-            model.fields().id().as_column(),
-            order_by,
-            reverse,
-        )
+    pub fn for_model(model: ModelRef, order_by: &[OrderBy], reverse: bool) -> OrderVec {
+        Self::by_keys(order_by, None, model.fields().id().as_column(), reverse)
+    }
+
+    /// Orderings by relevance compare the rank of a table joined to the query
+    /// instead of the field. Returns the column and its sort order.
+    pub fn joined_column(order_by: &OrderBy) -> Option<(Column, SortOrder)> {
+        order_by.search.as_ref().map(|_| {
+            (
+                (SearchIndex::RANK_TABLE_ALIAS, SearchIndex::RANK_COLUMN).into(),
+                SearchIndex::rank_order(order_by.sort_order),
+            )
+        })
+    }
+
+    /// Joins the ranks of the matching nodes for relevance orderings.
+    pub fn join_tables(select: Select, model: &ModelRef, order_by: &[OrderBy]) -> Select {
+        order_by.iter().fold(select, |select, order_by| match order_by {
+            OrderBy {
+                field,
+                search: Some(query),
+                ..
+            } => {
+                let rank_id = Column::from((SearchIndex::RANK_TABLE_ALIAS, SearchIndex::NODE_ID_COLUMN));
+                let ranks = SearchIndex::new(Arc::clone(model)).ranked_matches(field, query);
+
+                select.inner_join(ranks.on(model.id_column().equals(rank_id)))
+            }
+            _ => select,
+        })
     }
 
-    pub fn internal<C>(second_field: C, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec
+    pub fn internal<C>(second_field: C, order_by: &[OrderBy], reverse: bool) -> OrderVec
     where
         C: Into<Column>,
     {
-        Self::by_fields(
-            order_by.map(|oby| oby.field.as_column()),
-            second_field.into(),
-            order_by,
-            reverse,
-        )
+        Self::by_keys(order_by, None, second_field.into(), reverse)
     }
 
     pub fn aliased_internal(
         alias: &str,
         secondary_alias: &str,
         secondary_field: &str,
-        order_by: Option<&OrderBy>,
+        order_by: &[OrderBy],
         reverse: bool,
     ) -> OrderVec {
-        Self::by_fields(
-            order_by.map(|oby| (alias, oby.field.db_name()).into()),
-            (secondary_alias, secondary_field).into(),
+        Self::by_keys(
             order_by,
+            Some(alias),
+            (secondary_alias, secondary_field).into(),
             reverse,
         )
     }
 
-    fn by_fields(
-        first_column: Option<Column>,
-        second_column: Column,
-        order_by: Option<&OrderBy>,
-        reverse: bool,
-    ) -> OrderVec {
-        let default_order = order_by
-            .as_ref()
-            .map(|order| order.sort_order)
-            .unwrap_or(SortOrder::Ascending);
+    /// Orders by the keys of the orderings, reversed when reading backwards.
+    /// The id column of the nodes breaks ties.
+    fn by_keys(order_by: &[OrderBy], alias: Option<&str>, id_column: Column, reverse: bool) -> OrderVec {
+        OrderKey::all(order_by)
+            .iter()
+            .map(|key| {
+                let value = key.of_row(alias, &id_column);
 
-        let ordering = match first_column {
-            Some(first) => {
-                if first != second_column {
-                    match (default_order, reverse) {
-                        (SortOrder::Ascending, true) => vec![first.descend(), second_column.descend()],
-                        (SortOrder::Descending, true) => vec![first.ascend(), second_column.descend()],
-                        (SortOrder::Ascending, false) => vec![first.ascend(), second_column.ascend()],
-                        (SortOrder::Descending, false) => vec![first.descend(), second_column.ascend()],
-                    }
-                } else {
-                    match (default_order, reverse) {
-                        (SortOrder::Ascending, true) => vec![second_column.descend()],
-                        (SortOrder::Descending, true) => vec![second_column.ascend()],
-                        (SortOrder::Ascending, false) => vec![second_column.ascend()],
-                        (SortOrder::Descending, false) => vec![second_column.descend()],
-                    }
+                match (key.sort_order(), reverse) {
+                    (SortOrder::Ascending, false) | (SortOrder::Descending, true) => value.ascend(),
+                    (SortOrder::Descending, false) | (SortOrder::Ascending, true) => value.descend(),
                 }
+            })
+            .collect()
+    }
+}
+
+/// A value nodes are ordered by. An ordering compares the values of its
+/// field, preceded by whether a value exists if nulls are placed explicitly.
+/// The node id comes last to break ties, unless the nodes are ordered by id
+/// already.
+pub enum OrderKey<'a> {
+    Presence(&'a OrderBy),
+    Value(&'a OrderBy),
+    Id,
+}
+
+impl<'a> OrderKey<'a> {
+    const RELATION_TABLE_ALIAS: &'static str = "order_relation";
+    const NODE_TABLE_ALIAS: &'static str = "order_node";
+    const COUNT_TABLE_ALIAS: &'static str = "order_count";
+
+    pub fn all(order_by: &'a [OrderBy]) -> Vec<Self> {
+        let mut keys = Vec::new();
+
+        for order in order_by {
+            // Only matching nodes are ranked, ranks and counts always exist.
+            if order.nulls.is_some() && order.search.is_none() && order.related_count.is_none() {
+                keys.push(OrderKey::Presence(order));
             }
-            _ => match (default_order, reverse) {
-                (SortOrder::Ascending, true) => vec![second_column.descend()],
-                (SortOrder::Descending, true) => vec![second_column.ascend()],
-                (SortOrder::Ascending, false) => vec![second_column.ascend()],
-                (SortOrder::Descending, false) => vec![second_column.descend()],
+
+            keys.push(OrderKey::Value(order));
+        }
+
+        let by_id = order_by.iter().any(|order| {
+            order.field.is_id() && order.relations.is_empty() && order.path.is_none() && order.related_count.is_none()
+        });
+
+        if !by_id {
+            keys.push(OrderKey::Id);
+        }
+
+        keys
+    }
+
+    pub fn sort_order(&self) -> SortOrder {
+        match self {
+            OrderKey::Presence(order) => match order.nulls {
+                Some(NullsOrder::First) => SortOrder::Ascending,
+                _ => SortOrder::Descending,
+            },
+            OrderKey::Value(order) => Ordering::joined_column(order)
+                .map(|(_, sort_order)| sort_order)
+                .unwrap_or(order.sort_order),
+            OrderKey::Id => SortOrder::Ascending,
+        }
+    }
+
+    /// The key of the node in the current row. Fields of the ordered model are
+    /// read from the aliased table if given, others are selected for the node
+    /// with the id in `id_column`.
+    pub fn of_row(&self, alias: Option<&str>, id_column: &Column) -> DatabaseValue {
+        match self {
+            OrderKey::Id => id_column.clone().into(),
+            OrderKey::Presence(order) => Self::presence(order, id_column.clone()).into(),
+            OrderKey::Value(OrderBy {
+                related_count: Some(rf),
+                ..
+            }) => Self::related_count(rf, id_column.clone()).into(),
+            OrderKey::Value(order) => match Ordering::joined_column(order) {
+                Some((column, _)) => column.into(),
+                None if order.relations.is_empty() => {
+                    let column = match alias {
+                        Some(alias) => Column::from((alias, order.field.db_name())),
+                        None => order.field.as_column(),
+                    };
+
+                    Self::at_path(order, column)
+                }
+                None => Self::value(order, id_column.clone()).into(),
             },
+        }
+    }
+
+    /// Selects the key of the node with the given id, to compare against in
+    /// cursor conditions.
+    pub fn of_node(&self, id: &GraphqlId) -> DatabaseValue {
+        match self {
+            OrderKey::Id => id.clone().into(),
+            OrderKey::Presence(order) => Self::presence(order, id.clone()).into(),
+            OrderKey::Value(OrderBy {
+                field,
+                search: Some(query),
+                ..
+            }) => SearchIndex::new(field.model()).rank_of(field, query, id).into(),
+            OrderKey::Value(OrderBy {
+                related_count: Some(rf),
+                ..
+            }) => Self::related_count(rf, id.clone()).into(),
+            OrderKey::Value(order) => Self::value(order, id.clone()).into(),
+        }
+    }
+
+    fn value<T>(order: &OrderBy, id: T) -> Select
+    where
+        T: Into<DatabaseValue>,
+    {
+        let (select, condition, value) = Self::locate(order, id);
+        select.value(value).so_that(condition)
+    }
+
+    /// Counts whether the value exists, `1` if it does and `0` if it is null
+    /// or a relation on the way is missing.
+    fn presence<T>(order: &OrderBy, id: T) -> Select
+    where
+        T: Into<DatabaseValue>,
+    {
+        let (select, condition, value) = Self::locate(order, id);
+
+        select
+            .value(count(asterisk()))
+            .so_that(condition.and(value.is_not_null()))
+    }
+
+    /// Counts the nodes related to the node with the given id through the
+    /// list relation field.
+    fn related_count<T>(rf: &RelationField, id: T) -> Select
+    where
+        T: Into<DatabaseValue>,
+    {
+        let this_column = rf.relation_column().table(Self::COUNT_TABLE_ALIAS);
+        let other_column = rf.opposite_column().table(Self::COUNT_TABLE_ALIAS);
+        let relation_table = rf.relation().relation_table().alias(Self::COUNT_TABLE_ALIAS);
+
+        Select::from_table(relation_table)
+            .value(count(asterisk()))
+            .so_that(this_column.equals(id).and(other_column.is_not_null()))
+    }
+
+    /// The tables holding the value of the ordered field for the node with the
+    /// given id, the condition selecting the node and the value.
+    /// Related fields follow the relations through their tables.
+    fn locate<T>(order: &OrderBy, id: T) -> (Select, Compare, DatabaseValue)
+    where
+        T: Into<DatabaseValue>,
+    {
+        let relation_alias = |i: usize| format!("{}_{}", Self::RELATION_TABLE_ALIAS, i);
+        let node_alias = |i: usize| format!("{}_{}", Self::NODE_TABLE_ALIAS, i);
+
+        let (select, condition) = match order.relations.first() {
+            None => {
+                let model = order.field.model();
+                let condition = model.fields().id().as_column().table(node_alias(0)).equals(id);
+
+                (Select::from_table(model.table().alias(node_alias(0))), condition)
+            }
+            Some(first) => {
+                let condition = first.relation_column().table(relation_alias(0)).equals(id);
+                let select = Select::from_table(first.relation().relation_table().alias(relation_alias(0)));
+
+                let select = order.relations.iter().enumerate().fold(select, |select, (i, rf)| {
+                    let select = match i {
+                        0 => select,
+                        _ => {
+                            let previous_id = rf.model().fields().id().as_column().table(node_alias(i - 1));
+                            let this_column = rf.relation_column().table(relation_alias(i));
+                            let relation_table = rf.relation().relation_table().alias(relation_alias(i));
+
+                            select.inner_join(relation_table.on(this_column.equals(previous_id)))
+                        }
+                    };
+
+                    let related = rf.related_model();
+                    let related_id = related.fields().id().as_column().table(node_alias(i));
+                    let other_column = rf.opposite_column().table(relation_alias(i));
+
+                    select.inner_join(related.table().alias(node_alias(i)).on(related_id.equals(other_column)))
+                });
+
+                (select, condition)
+            }
         };
 
-        ordering
+        let last = order.relations.len().max(1) - 1;
+        let column = order.field.as_column().table(node_alias(last));
+
+        (select, condition, Self::at_path(order, column))
+    }
+
+    /// The value at the JSON path of the ordering in the column, `NULL` if the
+    /// path is missing or the document is invalid. Other orderings compare the
+    /// column itself.
+    fn at_path(order: &OrderBy, column: Column) -> DatabaseValue {
+        match order.path {
+            Some(ref path) => {
+                let document = function(JSON_FUNCTION, vec![column.into()]);
+                function("json_extract", vec![document.into(), path.to_string().into()]).into()
+            }
+            None => column.into(),
+        }
     }
 }
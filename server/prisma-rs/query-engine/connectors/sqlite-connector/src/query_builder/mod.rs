@@ -1,9 +1,12 @@
 mod related_nodes;
 
-use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering};
+use crate::{
+    cursor_condition::CursorCondition, distinct_condition::DistinctCondition, filter_conversion::AliasedCondition,
+    ordering::Ordering,
+};
 use connector::{
     filter::{Filter, NodeSelector},
-    QueryArguments,
+    Aggregation, QueryArguments,
 };
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
@@ -42,42 +45,56 @@ impl SelectDefinition for Select {
 
 impl SelectDefinition for QueryArguments {
     fn into_select(self, model: ModelRef) -> Select {
-        let cursor: ConditionTree = CursorCondition::build(&self, Arc::clone(&model));
-        let order_by = self.order_by;
-        let ordering = Ordering::for_model(Arc::clone(&model), order_by.as_ref(), self.last.is_some());
-
-        let filter: ConditionTree = self
-            .filter
-            .map(|f| f.aliased_cond(None))
-            .unwrap_or(ConditionTree::NoCondition);
-
-        let conditions = match (filter, cursor) {
-            (ConditionTree::NoCondition, cursor) => cursor,
-            (filter, ConditionTree::NoCondition) => filter,
-            (filter, cursor) => ConditionTree::and(filter, cursor),
-        };
-
-        let (skip, limit) = match self.last.or(self.first) {
-            Some(c) => (self.skip.unwrap_or(0), Some(c + 1)), // +1 to see if there's more data
-            None => (self.skip.unwrap_or(0), None),
-        };
+        select_nodes(self, model, true)
+    }
+}
 
-        let select_ast = Select::from_table(model.table())
-            .so_that(conditions)
-            .offset(skip as usize);
+/// Selects the nodes of the query arguments. Paginated queries read one node
+/// more than requested if `read_ahead` is set, to see if there's more data.
+fn select_nodes(args: QueryArguments, model: ModelRef, read_ahead: bool) -> Select {
+    let cursor: ConditionTree = CursorCondition::build(&args, Arc::clone(&model));
+    let distinct: ConditionTree = DistinctCondition::build(&args, Arc::clone(&model));
+    let ordering = Ordering::for_model(Arc::clone(&model), &args.order_by, args.last.is_some());
 
-        let select_ast = ordering.into_iter().fold(select_ast, |acc, ord| acc.order_by(ord));
+    let filter: ConditionTree = args
+        .filter
+        .map(|f| f.aliased_cond(None))
+        .unwrap_or(ConditionTree::NoCondition);
 
-        match limit {
-            Some(limit) => select_ast.limit(limit as usize),
-            None => select_ast,
-        }
+    let conditions =
+        vec![filter, cursor, distinct]
+            .into_iter()
+            .fold(ConditionTree::NoCondition, |acc, condition| match (acc, condition) {
+                (ConditionTree::NoCondition, condition) => condition,
+                (acc, ConditionTree::NoCondition) => acc,
+                (acc, condition) => ConditionTree::and(acc, condition),
+            });
+
+    let (skip, limit) = match args.last.or(args.first) {
+        Some(c) if read_ahead => (args.skip.unwrap_or(0), Some(c + 1)),
+        Some(c) => (args.skip.unwrap_or(0), Some(c)),
+        None => (args.skip.unwrap_or(0), None),
+    };
+
+    let select_ast = Select::from_table(model.table())
+        .so_that(conditions)
+        .offset(skip as usize);
+
+    let select_ast = Ordering::join_tables(select_ast, &model, &args.order_by);
+    let select_ast = ordering.into_iter().fold(select_ast, |acc, ord| acc.order_by(ord));
+
+    match limit {
+        Some(limit) => select_ast.limit(limit as usize),
+        None => select_ast,
     }
 }
 
 pub struct QueryBuilder;
 
 impl QueryBuilder {
+    /// Alias of the nodes aggregated in `aggregate`.
+    const AGGREGATED_ALIAS: &'static str = "aggregated";
+
     pub fn get_nodes<T>(model: ModelRef, selected_fields: &SelectedFields, query: T) -> Select
     where
         T: SelectDefinition,
@@ -117,7 +134,10 @@ impl QueryBuilder {
             .column("nodeId")
             .column("position")
             .column("value")
-            .so_that(vhere);
+            .so_that(vhere)
+            // Values are grouped by node and must be in list order
+            .order_by(Column::from("nodeId").ascend())
+            .order_by(Column::from("position").ascend());
 
         query
     }
@@ -137,7 +157,83 @@ impl QueryBuilder {
         select_ast
     }
 
+    /// Selects the ids of the related nodes matching the query arguments
+    /// with the ids of their parents, ignoring pagination.
+    pub fn related_node_ids(
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> Select {
+        let selected_fields = SelectedFields::new(
+            vec![SelectedField::from(from_field.related_model().fields().id())],
+            Some(Arc::clone(&from_field)),
+        );
+
+        RelatedNodesQueryBuilder::new(from_field, from_node_ids, query_arguments, &selected_fields).without_pagination()
+    }
+
+    /// Selects the parent id of each related node matching the query arguments,
+    /// once per parent the node is related to. The nodes are counted per parent
+    /// from these ids.
+    pub fn count_related_nodes(
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> Select {
+        let nodes = Self::related_node_ids(from_field, from_node_ids, query_arguments);
+        let table = Table::from(nodes).alias("counted");
+
+        Select::from_table(table).column(("counted", SelectedFields::PARENT_MODEL_ALIAS))
+    }
+
     pub fn count_by_table(database: &str, table: &str) -> Select {
         Select::from_table((database, table)).value(count(asterisk()))
     }
+
+    /// Selects the fields to aggregate and group by of the nodes, reading the
+    /// requested page exactly.
+    pub fn aggregated_nodes(
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        aggregations: &[Aggregation],
+        group_by: &[ScalarFieldRef],
+    ) -> Select {
+        let mut fields = vec![model.fields().id()];
+
+        for field in group_by
+            .iter()
+            .chain(aggregations.iter().filter_map(Aggregation::field))
+        {
+            if !fields.iter().any(|f| f.name == field.name) {
+                fields.push(Arc::clone(field));
+            }
+        }
+
+        fields
+            .into_iter()
+            .fold(select_nodes(query_arguments, model, false), |acc, field| {
+                acc.column(field.as_column())
+            })
+    }
+
+    /// Aggregates the nodes selected by `aggregated_nodes`. Groups come first
+    /// in the selected columns, ordered by their values, followed by the
+    /// aggregations.
+    pub fn aggregate(nodes: Select, aggregations: &[Aggregation], group_by: &[ScalarFieldRef]) -> Select {
+        let column = |field: &ScalarFieldRef| Column::from((Self::AGGREGATED_ALIAS, field.db_name()));
+        let select = Select::from_table(Table::from(nodes).alias(Self::AGGREGATED_ALIAS));
+
+        let select = group_by.iter().fold(select, |acc, field| acc.column(column(field)));
+
+        let select = aggregations
+            .iter()
+            .fold(select, |acc, aggregation| match aggregation.field() {
+                Some(field) => acc.value(function(aggregation.name(), vec![column(field).into()])),
+                None => acc.value(count(asterisk())),
+            });
+
+        group_by.iter().fold(select, |acc, field| {
+            acc.group_by(column(field)).order_by(column(field).ascend())
+        })
+    }
 }
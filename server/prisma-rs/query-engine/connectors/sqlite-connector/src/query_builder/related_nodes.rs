@@ -1,4 +1,7 @@
-use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering};
+use crate::{
+    cursor_condition::CursorCondition, distinct_condition::DistinctCondition, filter_conversion::AliasedCondition,
+    ordering::Ordering,
+};
 use connector::QueryArguments;
 use prisma_models::prelude::*;
 use prisma_query::ast::{
@@ -14,8 +17,9 @@ pub struct RelatedNodesQueryBuilder<'a> {
     relation: Arc<Relation>,
     related_model: ModelRef,
     window_limits: (u32, u32),
-    order_by: Option<OrderBy>,
+    order_by: Vec<OrderBy>,
     cursor_condition: ConditionTree,
+    distinct_condition: ConditionTree,
     reverse_order: bool,
 }
 
@@ -33,9 +37,10 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
         let relation = from_field.relation();
         let related_model = from_field.related_model();
         let cursor_condition = CursorCondition::build(&query_arguments, related_model.clone());
+        let distinct_condition = DistinctCondition::for_related(&query_arguments, &from_field, from_node_ids);
         let window_limits = query_arguments.window_limits();
 
-        let order_by: Option<OrderBy> = query_arguments.order_by;
+        let order_by = query_arguments.order_by;
         let conditions: ConditionTree = query_arguments
             .filter
             .map(|f| f.aliased_cond(None))
@@ -53,6 +58,7 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
             window_limits,
             order_by,
             cursor_condition,
+            distinct_condition,
             reverse_order,
         }
     }
@@ -66,7 +72,7 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
             Self::BASE_TABLE_ALIAS,
             Self::BASE_TABLE_ALIAS,
             SelectedFields::RELATED_MODEL_ALIAS,
-            self.order_by.as_ref(),
+            &self.order_by,
             self.reverse_order,
         );
 
@@ -74,12 +80,17 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
         let conditions = relation_side_column
             .in_selection(self.from_node_ids.to_owned())
             .and(self.conditions)
-            .and(cursor_condition);
+            .and(cursor_condition)
+            .and(self.distinct_condition);
 
-        let base_with_conditions = match self.order_by {
-            Some(order_by) => base_query.column(order_by.field.as_column()).so_that(conditions),
-            None => base_query.so_that(conditions),
-        };
+        // The row numbers are ordered by the fields of the related model, so
+        // they are selected. Related fields are selected by the ordering.
+        let base_with_conditions = self
+            .order_by
+            .iter()
+            .filter(|order_by| order_by.relations.is_empty())
+            .fold(base_query, |acc, order_by| acc.column(order_by.field.as_column()))
+            .so_that(conditions);
 
         let row_number_part: Function = ordering
             .into_iter()
@@ -107,15 +118,12 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
             .clone()
             .in_selection(self.from_node_ids.to_owned())
             .and(cursor_condition)
-            .and(self.conditions);
+            .and(self.conditions)
+            .and(self.distinct_condition);
 
-        Ordering::internal(
-            opposite_relation_side_column,
-            self.order_by.as_ref(),
-            self.reverse_order,
-        )
-        .into_iter()
-        .fold(base_query.so_that(conditions), |acc, ord| acc.order_by(ord))
+        Ordering::internal(opposite_relation_side_column, &self.order_by, self.reverse_order)
+            .into_iter()
+            .fold(base_query.so_that(conditions), |acc, ord| acc.order_by(ord))
     }
 
     fn base_query(&self) -> Select {
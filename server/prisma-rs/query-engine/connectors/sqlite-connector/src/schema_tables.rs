@@ -0,0 +1,177 @@
+use crate::SearchIndex;
+use prisma_models::prelude::*;
+use std::sync::Arc;
+
+/// The tables of a schema: one per model, relation table and scalar list
+/// field, and the search indexes of the models declaring search. Table
+/// definitions can't be expressed with the query AST, so the statements are
+/// written out.
+///
+/// ```rust
+/// # use sqlite_connector::*;
+/// # use prisma_models::*;
+/// # use serde_json;
+/// # use std::fs::File;
+/// #
+/// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+/// let schema = template.build(String::from("test"));
+/// let statements = SchemaTables::new(schema).statements();
+///
+/// assert!(statements.contains(&String::from(
+///     r#"CREATE TABLE "test"."User" ("id" Text PRIMARY KEY, "name" Text, UNIQUE ("name", "id"))"#
+/// )));
+///
+/// assert!(statements.contains(&String::from(
+///     r#"CREATE TABLE "test"."Account" ("id" Text PRIMARY KEY, "email" Text UNIQUE)"#
+/// )));
+///
+/// assert!(statements.contains(&String::from(
+///     r#"CREATE TABLE "test"."_UserToSites" ("A" Text, "B" Text, "id" Text)"#
+/// )));
+///
+/// assert!(statements.contains(&String::from(
+///     r#"CREATE TABLE "test"."User_cats" ("nodeId" Text, "position" Integer, "value" Integer)"#
+/// )));
+///
+/// assert!(statements.contains(&String::from(
+///     r#"CREATE VIRTUAL TABLE "test"."Article_fts" USING fts5("nodeId" UNINDEXED, "title", "body")"#
+/// )));
+/// ```
+pub struct SchemaTables {
+    schema: SchemaRef,
+}
+
+impl SchemaTables {
+    pub fn new(schema: SchemaRef) -> Self {
+        Self { schema }
+    }
+
+    /// The statements creating all tables of the schema.
+    pub fn statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+
+        for model in self.schema.models() {
+            statements.push(self.model_table(model));
+
+            for field in model.fields().scalar_list() {
+                statements.push(self.scalar_list_table(&field));
+            }
+
+            if model.is_searchable {
+                statements.push(SearchIndex::new(Arc::clone(model)).create_table());
+            }
+        }
+
+        for relation in self.schema.relations().iter().filter(|r| r.is_relation_table()) {
+            statements.push(self.relation_table(relation));
+        }
+
+        statements
+    }
+
+    fn model_table(&self, model: &ModelRef) -> String {
+        let mut columns: Vec<String> = model
+            .fields()
+            .scalar_non_list()
+            .iter()
+            .map(|field| {
+                let constraint = match (field.is_id(), field.is_unique) {
+                    (true, _) => " PRIMARY KEY",
+                    (false, true) => " UNIQUE",
+                    (false, false) => "",
+                };
+
+                format!(
+                    "\"{}\" {}{}",
+                    field.db_name(),
+                    Self::column_type(field.type_identifier),
+                    constraint
+                )
+            })
+            .collect();
+
+        // Inline relations keep the id of the related node in a column of the
+        // model table.
+        for relation in self.schema.relations() {
+            match relation.inline_manifestation() {
+                Some(m) if m.in_table_of_model_name == model.name => {
+                    columns.push(format!("\"{}\" Text", m.referencing_column));
+                }
+                _ => (),
+            }
+        }
+
+        for constraint in model.unique_constraints.iter() {
+            let fields: Vec<String> = constraint
+                .fields
+                .iter()
+                .filter_map(|name| model.fields().find_from_scalar(name).ok())
+                .map(|field| format!("\"{}\"", field.db_name()))
+                .collect();
+
+            columns.push(format!("UNIQUE ({})", fields.join(", ")));
+        }
+
+        self.create_table(model.db_name(), columns)
+    }
+
+    fn scalar_list_table(&self, field: &ScalarField) -> String {
+        let columns = vec![
+            format!("\"{}\" Text", ScalarListTable::NODE_ID_FIELD_NAME),
+            format!("\"{}\" Integer", ScalarListTable::POSITION_FIELD_NAME),
+            format!(
+                "\"{}\" {}",
+                ScalarListTable::VALUE_FIELD_NAME,
+                Self::column_type(field.type_identifier)
+            ),
+        ];
+
+        self.create_table(field.scalar_list_table().name(), columns)
+    }
+
+    fn relation_table(&self, relation: &RelationRef) -> String {
+        let (table, model_a_column, model_b_column, id_column) = match relation.manifestation {
+            Some(RelationLinkManifestation::RelationTable(ref m)) => (
+                m.table.clone(),
+                m.model_a_column.as_str(),
+                m.model_b_column.as_str(),
+                m.id_column.as_ref().map(String::as_str),
+            ),
+            _ => (
+                format!("_{}", relation.name),
+                Relation::MODEL_A_DEFAULT_COLUMN,
+                Relation::MODEL_B_DEFAULT_COLUMN,
+                Some("id"),
+            ),
+        };
+
+        let mut columns = vec![
+            format!("\"{}\" Text", model_a_column),
+            format!("\"{}\" Text", model_b_column),
+        ];
+
+        if let Some(id_column) = id_column {
+            columns.push(format!("\"{}\" Text", id_column));
+        }
+
+        self.create_table(&table, columns)
+    }
+
+    fn create_table(&self, name: &str, columns: Vec<String>) -> String {
+        format!(
+            "CREATE TABLE \"{}\".\"{}\" ({})",
+            self.schema.db_name,
+            name,
+            columns.join(", ")
+        )
+    }
+
+    fn column_type(type_identifier: TypeIdentifier) -> &'static str {
+        match type_identifier {
+            TypeIdentifier::Int | TypeIdentifier::DateTime => "Integer",
+            TypeIdentifier::Float => "Real",
+            TypeIdentifier::Boolean => "Boolean",
+            _ => "Text",
+        }
+    }
+}
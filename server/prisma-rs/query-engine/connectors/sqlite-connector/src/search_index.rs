@@ -0,0 +1,226 @@
+use prisma_models::prelude::*;
+use prisma_query::ast::*;
+use std::sync::Arc;
+
+/// A full-text search index of a model, an FTS5 virtual table `{Model}_fts`
+/// holding the string fields of each node next to its id.
+///
+/// Models declaring search get their index with the tables of the schema, see
+/// [SchemaTables](struct.SchemaTables.html). Writes keep it in sync.
+///
+/// ```rust
+/// # use sqlite_connector::*;
+/// # use connector::{filter::*, *};
+/// # use prisma_models::*;
+/// # use serde_json;
+/// # use std::{fs::File, sync::Arc};
+/// #
+/// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+/// let schema = template.build(String::from("search_index"));
+/// let article = schema.find_model("Article").unwrap();
+/// let id = article.fields().id();
+/// let title = article.fields().find_from_scalar("title").unwrap();
+/// #
+/// # std::fs::create_dir_all("db").unwrap();
+/// # let _ = std::fs::remove_file("db/search_index.db");
+///
+/// let sqlite = Sqlite::new(1, false).unwrap();
+/// sqlite.create_tables(Arc::clone(&schema)).unwrap();
+///
+/// sqlite
+///     .with_transaction("search_index", |conn| {
+///         for (id, title) in &[("a", "Alice in Wonderland"), ("b", "Bob")] {
+///             let mut args = PrismaArgs::new();
+///             args.insert("id", GraphqlId::from(*id));
+///             args.insert("title", *title);
+///
+///             Sqlite::execute_create(conn, Arc::clone(&article), &args, &[] as &[(&str, PrismaListValue)])?;
+///         }
+///
+///         Ok(())
+///     })
+///     .unwrap();
+///
+/// let selected_fields = SelectedFields::new(vec![SelectedField::from(id)], None);
+/// let result = sqlite
+///     .get_nodes(Arc::clone(&article), QueryArguments::from(title.search("alice")), &selected_fields)
+///     .unwrap();
+///
+/// let ids: Vec<PrismaValue> = result.nodes.into_iter().map(|mut node| node.values.remove(0)).collect();
+/// assert_eq!(vec![PrismaValue::from("a")], ids);
+/// ```
+pub struct SearchIndex {
+    model: ModelRef,
+}
+
+impl SearchIndex {
+    pub const NODE_ID_COLUMN: &'static str = "nodeId";
+
+    /// The hidden column of FTS5 ordering by relevance, the best matches
+    /// having the lowest value.
+    pub const RANK_COLUMN: &'static str = "rank";
+
+    /// Alias of the ranked matches joined when ordering by relevance.
+    pub const RANK_TABLE_ALIAS: &'static str = "search_rank";
+
+    pub const PARAMETER_LIMIT: usize = 10000;
+
+    pub fn new(model: ModelRef) -> Self {
+        Self { model }
+    }
+
+    pub fn table_name(&self) -> String {
+        format!("{}_fts", self.model.db_name())
+    }
+
+    pub fn table(&self) -> Table {
+        let schema = self.model.schema();
+        Table::from((schema.db_name.as_str(), self.table_name().as_str()))
+    }
+
+    /// The indexed fields of the model.
+    pub fn fields(&self) -> Vec<Arc<ScalarField>> {
+        self.model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|field| field.type_identifier == TypeIdentifier::String)
+            .collect()
+    }
+
+    /// The statement creating the table. Virtual tables can't be expressed
+    /// with the query AST.
+    pub fn create_table(&self) -> String {
+        let columns: Vec<String> = self
+            .fields()
+            .iter()
+            .map(|field| format!(", \"{}\"", field.db_name()))
+            .collect();
+
+        format!(
+            "CREATE VIRTUAL TABLE \"{}\".\"{}\" USING fts5(\"{}\" UNINDEXED{})",
+            self.model.schema().db_name,
+            self.table_name(),
+            Self::NODE_ID_COLUMN,
+            columns.concat(),
+        )
+    }
+
+    /// Selects the id and the indexed fields of the given nodes.
+    pub fn source(&self, ids: &[&GraphqlId]) -> (Select, SelectedFields) {
+        let mut selected_fields = SelectedFields::default();
+        selected_fields.add_scalar(self.model.fields().id(), false);
+
+        for field in self.fields() {
+            selected_fields.add_scalar(field, false);
+        }
+
+        let select = Select::from_table(self.model.table()).so_that(self.model.id_column().in_selection(ids.to_vec()));
+
+        let select = selected_fields
+            .columns()
+            .into_iter()
+            .fold(select, |acc, col| acc.column(col.clone()));
+
+        (select, selected_fields)
+    }
+
+    /// Inserts a node read with the fields of [source](#method.source).
+    pub fn insert(&self, node: Node) -> Insert {
+        let mut values = node.values.into_iter();
+        let id = values.next().unwrap_or(PrismaValue::Null);
+
+        let insert = Insert::single_into(self.table()).value(Self::NODE_ID_COLUMN, id);
+
+        self.fields()
+            .iter()
+            .zip(values)
+            .fold(insert, |acc, (field, value)| acc.value(field.db_name(), value))
+    }
+
+    /// Removes the given nodes from the index.
+    pub fn delete(&self, ids: &[&GraphqlId]) -> Vec<Delete> {
+        ids.chunks(Self::PARAMETER_LIMIT)
+            .map(|chunk| {
+                let condition = Column::from(Self::NODE_ID_COLUMN).in_selection(chunk.to_vec());
+                Delete::from_table(self.table()).so_that(condition)
+            })
+            .collect()
+    }
+
+    /// Removes all nodes from the index.
+    pub fn truncate(&self) -> Delete {
+        Delete::from_table(self.table())
+    }
+
+    /// Relevance is ordered by rank in the opposite direction, descending
+    /// relevance being ascending rank.
+    pub fn rank_order(relevance_order: SortOrder) -> SortOrder {
+        match relevance_order {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+
+    /// Selects the ids of the nodes matching the query in the given field. Each
+    /// word of the query is written as an FTS5 string, so the query has no
+    /// syntax of its own and matches the nodes containing all of the words.
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use prisma_models::*;
+    /// # use prisma_query::ast::*;
+    /// # use serde_json;
+    /// # use std::fs::File;
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// let schema = template.build(String::from("test"));
+    /// let model = schema.find_model("Article").unwrap();
+    /// let field = model.fields().find_from_scalar("title").unwrap();
+    ///
+    /// let expected = Select::from_table(("test", "Article_fts"))
+    ///     .column("nodeId")
+    ///     .so_that("Article_fts".equals(r#"{title} : ("foo)" "OR" "say" """bar")"#));
+    ///
+    /// assert_eq!(expected, SearchIndex::new(model).matches(&field, r#"foo) OR say "bar"#));
+    /// ```
+    pub fn matches(&self, field: &ScalarField, query: &str) -> Select {
+        Select::from_table(self.table())
+            .column(Self::NODE_ID_COLUMN)
+            .so_that(self.match_condition(field, query))
+    }
+
+    /// The ids and the ranks of the nodes matching the query in the given
+    /// field, to join when ordering by relevance.
+    pub fn ranked_matches(&self, field: &ScalarField, query: &str) -> Table {
+        let select = self.matches(field, query).column(Self::RANK_COLUMN);
+        Table::from(select).alias(Self::RANK_TABLE_ALIAS)
+    }
+
+    /// Selects the rank of a single node for the query, to compare against in
+    /// cursor conditions.
+    pub fn rank_of(&self, field: &ScalarField, query: &str, id: &GraphqlId) -> Select {
+        let matching: ConditionTree = self.match_condition(field, query).into();
+        let node: ConditionTree = Column::from(Self::NODE_ID_COLUMN).equals(id.clone()).into();
+
+        Select::from_table(self.table())
+            .column(Self::RANK_COLUMN)
+            .so_that(ConditionTree::and(matching, node))
+    }
+
+    /// Comparing the hidden column named like the table runs a full-text query
+    /// in FTS5, limited to the field with a column filter.
+    fn match_condition(&self, field: &ScalarField, query: &str) -> Compare {
+        let mut strings: Vec<String> = query
+            .split_whitespace()
+            .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+            .collect();
+
+        // An empty string matches nothing.
+        if strings.is_empty() {
+            strings.push(String::from("\"\""));
+        }
+
+        Column::from(self.table_name().as_str()).equals(format!("{{{}}} : ({})", field.db_name(), strings.join(" ")))
+    }
+}
@@ -1,19 +1,37 @@
+mod functions;
 mod mutaction_executor;
 mod read;
 mod resolver;
+mod search;
 mod write;
 
-use crate::{Connection, TransactionalExecutor};
+use crate::{Connection, SchemaTables, TransactionalExecutor};
 use chrono::{DateTime, Utc};
 use connector::*;
 use prisma_models::prelude::*;
+use r2d2::CustomizeConnection;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{Row, Transaction, NO_PARAMS};
-use std::{collections::HashSet, env};
+use std::{collections::HashSet, env, sync::Arc};
 use uuid::Uuid;
 
+pub use functions::{
+    escape_glob, escape_like, like_to_glob, raw_like_pattern, JSON_ARRAY_CONTAINS_FUNCTION, JSON_FUNCTION, LIKE_ESCAPE,
+    LOWER_FUNCTION,
+};
+
 type Pool = r2d2::Pool<SqliteConnectionManager>;
 
+/// Registers the application-defined functions on new connections.
+#[derive(Debug)]
+struct FunctionRegistry;
+
+impl CustomizeConnection<rusqlite::Connection, rusqlite::Error> for FunctionRegistry {
+    fn on_acquire(&self, conn: &mut rusqlite::Connection) -> Result<(), rusqlite::Error> {
+        functions::register(conn)
+    }
+}
+
 pub struct Sqlite {
     pool: Pool,
     test_mode: bool,
@@ -58,11 +76,58 @@ impl Sqlite {
     pub fn new(connection_limit: u32, test_mode: bool) -> ConnectorResult<Sqlite> {
         let pool = r2d2::Pool::builder()
             .max_size(connection_limit)
+            .connection_customizer(Box::new(FunctionRegistry))
             .build(SqliteConnectionManager::memory())?;
 
         Ok(Sqlite { pool, test_mode })
     }
 
+    /// Registers the application-defined functions, such as the Unicode-aware
+    /// `prisma_lower`, to a connection not created through the pool.
+    ///
+    /// ```rust
+    /// # use rusqlite::Connection;
+    /// # use sqlite_connector::*;
+    /// let conn = Connection::open_in_memory().unwrap();
+    /// Sqlite::register_functions(&conn).unwrap();
+    ///
+    /// let matches = |sql: &str, pattern: String| -> bool {
+    ///     conn.query_row(sql, &[pattern], |row| row.get(0)).unwrap()
+    /// };
+    ///
+    /// // `LIKE` ignores the case of ASCII letters only:
+    ///
+    /// assert!(matches("SELECT 'Ärger' LIKE ?", String::from("Är%")));
+    /// assert!(!matches("SELECT 'Ärger' LIKE ?", String::from("är%")));
+    /// assert!(matches("SELECT 'Ärger' LIKE ?", String::from("%RGER")));
+    ///
+    /// // Lowercased on both sides, it ignores case:
+    ///
+    /// assert!(matches("SELECT prisma_lower('Ärger') LIKE prisma_lower(?)", String::from("är%")));
+    /// assert!(matches("SELECT prisma_lower('Ärger') LIKE prisma_lower(?)", String::from("%RGER")));
+    ///
+    /// // Wildcards can be escaped:
+    ///
+    /// let escaped = format!("%{}%", escape_like("r_er"));
+    ///
+    /// assert!(!matches("SELECT 'Ärger' LIKE ? ESCAPE '\\'", escaped));
+    /// assert!(matches("SELECT 'Ärger' LIKE ?", String::from("%r_er")));
+    /// ```
+    pub fn register_functions(conn: &rusqlite::Connection) -> ConnectorResult<()> {
+        Ok(functions::register(conn)?)
+    }
+
+    /// Creates the tables of the schema in its database.
+    pub fn create_tables(&self, schema: SchemaRef) -> ConnectorResult<()> {
+        self.with_transaction(&schema.db_name, |conn| {
+            for statement in SchemaTables::new(Arc::clone(&schema)).statements() {
+                conn.execute(&statement, NO_PARAMS)?;
+            }
+
+            Ok(())
+        })
+    }
+
     /// When querying and we haven't yet loaded the database, it'll be loaded on
     /// or created to `$SERVER_ROOT/db/{db_name}.db`.
     ///
@@ -0,0 +1,178 @@
+//! Application-defined SQL functions, registered on every new connection.
+//!
+//! The built-in `LIKE` of SQLite folds case for ASCII characters only and
+//! has no escape character unless an `ESCAPE` clause is given. Patterns of
+//! the default mode are matched by it as they always were, and insensitive
+//! conditions compare both sides folded with
+//! [LOWER_FUNCTION](constant.LOWER_FUNCTION.html), which lowercases all of
+//! Unicode unlike the built-in `lower`. Sensitive conditions match with the
+//! built-in `GLOB` instead, which compares exactly and takes patterns written
+//! by [escape_glob](fn.escape_glob.html) and [like_to_glob](fn.like_to_glob.html).
+//!
+//! The functions of the JSON extension fail on invalid documents, which
+//! [JSON_FUNCTION](constant.JSON_FUNCTION.html) reads as `NULL` instead, so
+//! they match nothing. Arrays are searched with
+//! [JSON_ARRAY_CONTAINS_FUNCTION](constant.JSON_ARRAY_CONTAINS_FUNCTION.html).
+
+use prisma_models::JsonPath;
+use rusqlite::{functions::Context, types::ValueRef, Connection};
+use serde_json::Value;
+
+/// The name of the Unicode-aware `lower` function.
+pub const LOWER_FUNCTION: &str = "prisma_lower";
+
+/// The name of the function returning the JSON document given, or `NULL` if it
+/// is invalid.
+pub const JSON_FUNCTION: &str = "prisma_json";
+
+/// The name of the function telling whether the array at the path of a JSON
+/// document, `prisma_json_array_contains(document, path, element)`, contains
+/// the element given as JSON. Numbers are equal by their value. `NULL` if the
+/// document is invalid or the path is missing.
+///
+/// ```rust
+/// # use sqlite_connector::*;
+/// # use rusqlite::Connection;
+/// let conn = Connection::open_in_memory().unwrap();
+/// Sqlite::register_functions(&conn).unwrap();
+///
+/// let contains = |document: &str, element: &str| -> Option<bool> {
+///     let sql = "SELECT prisma_json_array_contains(?, '$.tags', ?)";
+///     conn.query_row(sql, &[document, element], |row| row.get(0)).unwrap()
+/// };
+///
+/// assert_eq!(Some(true), contains("{\"tags\": [1, \"a\", true]}", "1.0"));
+/// assert_eq!(Some(true), contains("{\"tags\": [1, \"a\", true]}", "true"));
+/// assert_eq!(Some(false), contains("{\"tags\": [1, \"a\", true]}", "\"b\""));
+/// assert_eq!(Some(false), contains("{\"tags\": \"a\"}", "\"a\""));
+/// assert_eq!(None, contains("{\"other\": []}", "1"));
+/// assert_eq!(None, contains("oops", "1"));
+/// ```
+pub const JSON_ARRAY_CONTAINS_FUNCTION: &str = "prisma_json_array_contains";
+
+/// The escape character of `LIKE` patterns, given in their `ESCAPE` clause.
+pub const LIKE_ESCAPE: char = '\\';
+
+/// Registers the functions to the given connection.
+pub fn register(conn: &Connection) -> rusqlite::Result<()> {
+    conn.create_scalar_function(LOWER_FUNCTION, 1, true, |ctx: &Context| {
+        Ok(as_text(ctx.get_raw(0)).map(|value| value.to_lowercase()))
+    })?;
+
+    conn.create_scalar_function(JSON_FUNCTION, 1, true, |ctx: &Context| {
+        Ok(as_text(ctx.get_raw(0)).filter(|document| as_json(document).is_some()))
+    })?;
+
+    conn.create_scalar_function(JSON_ARRAY_CONTAINS_FUNCTION, 3, true, |ctx: &Context| {
+        let document = as_text(ctx.get_raw(0)).and_then(|document| as_json(&document));
+        let path = as_text(ctx.get_raw(1)).and_then(|path| path.parse::<JsonPath>().ok());
+        let element = as_text(ctx.get_raw(2)).and_then(|element| as_json(&element));
+
+        let contains = match (document, path, element) {
+            (Some(document), Some(path), Some(element)) => path.get(&document).map(|value| match value {
+                Value::Array(values) => values.iter().any(|value| json_equals(value, &element)),
+                _ => false,
+            }),
+            _ => None,
+        };
+
+        Ok(contains)
+    })
+}
+
+/// Escapes the `LIKE` wildcards and the escape character, so the value
+/// matches only literally.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c == '%' || c == '_' || c == LIKE_ESCAPE {
+            escaped.push(LIKE_ESCAPE);
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// Escapes only the escape character of a raw `LIKE` pattern, so its
+/// wildcards keep matching with an `ESCAPE` clause.
+///
+/// ```rust
+/// # use sqlite_connector::*;
+/// assert_eq!("C:\\\\_%", raw_like_pattern("C:\\_%"));
+/// ```
+pub fn raw_like_pattern(pattern: &str) -> String {
+    pattern.replace(LIKE_ESCAPE, &format!("{0}{0}", LIKE_ESCAPE))
+}
+
+/// Writes the `GLOB` wildcards as character classes, so the value matches
+/// only literally. `GLOB` has no escape character.
+///
+/// ```rust
+/// # use sqlite_connector::*;
+/// assert_eq!("50% [*]off[?] [[]x]", escape_glob("50% *off? [x]"));
+/// ```
+pub fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        push_glob_literal(&mut escaped, c);
+    }
+
+    escaped
+}
+
+/// Translates a raw `LIKE` pattern to a `GLOB` pattern: `%` matches any
+/// sequence of characters and `_` any single character, other characters
+/// match literally.
+///
+/// ```rust
+/// # use sqlite_connector::*;
+/// assert_eq!("F?o*[*]", like_to_glob("F_o%*"));
+/// ```
+pub fn like_to_glob(pattern: &str) -> String {
+    let mut glob = String::with_capacity(pattern.len());
+
+    for c in pattern.chars() {
+        match c {
+            '%' => glob.push('*'),
+            '_' => glob.push('?'),
+            c => push_glob_literal(&mut glob, c),
+        }
+    }
+
+    glob
+}
+
+fn push_glob_literal(glob: &mut String, c: char) {
+    if c == '*' || c == '?' || c == '[' {
+        glob.push('[');
+        glob.push(c);
+        glob.push(']');
+    } else {
+        glob.push(c);
+    }
+}
+
+fn as_json(document: &str) -> Option<Value> {
+    serde_json::from_str(document).ok()
+}
+
+fn json_equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64() == right.as_f64(),
+        (left, right) => left == right,
+    }
+}
+
+fn as_text(value: ValueRef) -> Option<String> {
+    match value {
+        ValueRef::Null => None,
+        ValueRef::Integer(i) => Some(i.to_string()),
+        ValueRef::Real(f) => Some(f.to_string()),
+        ValueRef::Text(s) => Some(s.to_string()),
+        ValueRef::Blob(b) => Some(String::from_utf8_lossy(b).into_owned()),
+    }
+}
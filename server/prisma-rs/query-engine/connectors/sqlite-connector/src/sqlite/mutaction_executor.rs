@@ -1,6 +1,7 @@
 use crate::{DatabaseWrite, Sqlite, TransactionalExecutor};
 use connector::{mutaction::*, ConnectorResult, DatabaseMutactionExecutor};
 use prisma_models::*;
+use rusqlite::Transaction;
 use serde_json::Value;
 
 impl DatabaseMutactionExecutor for Sqlite {
@@ -21,17 +22,50 @@ impl DatabaseMutactionExecutor for Sqlite {
         &self,
         db_name: String,
         mutaction: DatabaseMutaction,
-        parent_id: Option<GraphqlId>, // TODO: we don't need this when we handle the whole mutaction in here.
+        parent_id: Option<GraphqlId>,
     ) -> ConnectorResult<DatabaseMutactionResults> {
         self.with_transaction(&db_name, |conn| {
-            let mut results = DatabaseMutactionResults::default();
+            let results = match mutaction {
+                DatabaseMutaction::TopLevel(tlm) => Self::execute_toplevel(conn, tlm)?,
+                DatabaseMutaction::Nested(nm) => Self::execute_nested(conn, nm, parent_id.unwrap())?,
+            };
 
-            match mutaction {
-                DatabaseMutaction::TopLevel(tlm) => results.merge(Self::execute_toplevel(conn, tlm)?),
-                DatabaseMutaction::Nested(nm) => results.merge(Self::execute_nested(conn, nm, parent_id.unwrap())?),
+            Self::execute_nested_mutactions(conn, results)
+        })
+    }
+}
+
+impl Sqlite {
+    /// Executes the nested mutactions of the last result depth first, in the
+    /// same transaction, so a failing nested mutaction rolls back its parent.
+    /// The last result stays the last one, the results of the nested
+    /// mutactions are put at their positions below it.
+    fn execute_nested_mutactions(
+        conn: &Transaction,
+        mut results: DatabaseMutactionResults,
+    ) -> ConnectorResult<DatabaseMutactionResults> {
+        let result = match results.pop() {
+            Some(result) => result,
+            None => return Ok(results),
+        };
+
+        if let Some(nested) = result.nested_mutactions() {
+            for (position, mutaction) in nested.database_mutactions() {
+                let nested_results = Self::execute_nested(conn, mutaction, result.id().clone())?;
+                let mut nested_results = Self::execute_nested_mutactions(conn, nested_results)?;
+
+                nested_results.nest(position);
+
+                if let Some(branch) = result.upsert_branch() {
+                    nested_results.nest(branch);
+                }
+
+                results.merge(nested_results);
             }
+        }
 
-            Ok(results)
-        })
+        results.push(result);
+
+        Ok(results)
     }
 }
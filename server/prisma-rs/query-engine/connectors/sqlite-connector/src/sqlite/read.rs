@@ -68,14 +68,14 @@ impl DatabaseRead for Sqlite {
     }
 
     fn id_for(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<GraphqlId> {
-        let model = node_selector.field.model();
+        let model = node_selector.model();
         let opt_id = Self::ids_for(conn, model, node_selector.clone())?.into_iter().next();
 
         opt_id.ok_or_else(|| ConnectorError::NodeNotFoundForWhere(NodeSelectorInfo::from(node_selector)))
     }
 
     fn find_node(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
-        let model = node_selector.field.model();
+        let model = node_selector.model();
         let selected_fields = SelectedFields::from(Arc::clone(&model));
 
         let select = QueryBuilder::get_nodes(model, &selected_fields, node_selector);
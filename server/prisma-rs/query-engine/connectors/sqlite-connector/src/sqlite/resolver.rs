@@ -2,6 +2,8 @@ use crate::{query_builder::QueryBuilder, DatabaseRead, Sqlite, TransactionalExec
 use connector::{filter::NodeSelector, *};
 use itertools::Itertools;
 use prisma_models::*;
+use prisma_query::visitor::{self, Visitor};
+use std::collections::HashMap;
 
 impl DataResolver for Sqlite {
     fn get_node_by_where(
@@ -9,8 +11,8 @@ impl DataResolver for Sqlite {
         node_selector: &NodeSelector,
         selected_fields: &SelectedFields,
     ) -> ConnectorResult<Option<SingleNode>> {
-        let db_name = &node_selector.field.model().schema().db_name;
-        let query = QueryBuilder::get_nodes(node_selector.field.model(), selected_fields, node_selector);
+        let db_name = &node_selector.model().schema().db_name;
+        let query = QueryBuilder::get_nodes(node_selector.model(), selected_fields, node_selector);
         let field_names = selected_fields.names();
 
         let nodes = self.with_transaction(db_name, |conn| {
@@ -30,13 +32,13 @@ impl DataResolver for Sqlite {
     ) -> ConnectorResult<ManyNodes> {
         let db_name = &model.schema().db_name;
         let field_names = selected_fields.names();
-        let query = QueryBuilder::get_nodes(model, selected_fields, query_arguments);
 
         let nodes = self.with_transaction(db_name, |conn| {
+            let query = QueryBuilder::get_nodes(model, selected_fields, query_arguments);
             Self::query(conn, query, |row| Sqlite::read_row(row, selected_fields))
         })?;
 
-        Ok(ManyNodes { nodes, field_names })
+        Ok(ManyNodes::new(nodes, field_names))
     }
 
     fn get_related_nodes(
@@ -48,9 +50,10 @@ impl DataResolver for Sqlite {
     ) -> ConnectorResult<ManyNodes> {
         let db_name = &from_field.model().schema().db_name;
         let field_names = selected_fields.names();
-        let query = QueryBuilder::get_related_nodes(from_field, from_node_ids, query_arguments, selected_fields);
 
         let nodes = self.with_transaction(db_name, |conn| {
+            let query = QueryBuilder::get_related_nodes(from_field, from_node_ids, query_arguments, selected_fields);
+
             Self::query(conn, query, |row| {
                 let position = field_names.len();
 
@@ -61,15 +64,17 @@ impl DataResolver for Sqlite {
             })
         })?;
 
-        Ok(ManyNodes { nodes, field_names })
+        Ok(ManyNodes::new(nodes, field_names))
     }
 
     fn count_by_model(&self, model: ModelRef, query_arguments: QueryArguments) -> ConnectorResult<usize> {
         let db_name = &model.schema().db_name;
         let table = model.table();
-        let query = QueryBuilder::count_by_model(model, query_arguments);
 
-        self.with_transaction(db_name, |conn| Self::count(conn, table, query))
+        self.with_transaction(db_name, |conn| {
+            let query = QueryBuilder::count_by_model(model, query_arguments);
+            Self::count(conn, table, query)
+        })
     }
 
     fn count_by_table(&self, database: &str, table: &str) -> ConnectorResult<usize> {
@@ -77,6 +82,144 @@ impl DataResolver for Sqlite {
         self.with_transaction(database, |conn| Self::count(conn, table, query))
     }
 
+    fn count_related_nodes(
+        &self,
+        from_field: RelationFieldRef,
+        from_node_ids: &[GraphqlId],
+        query_arguments: QueryArguments,
+    ) -> ConnectorResult<Vec<usize>> {
+        let db_name = &from_field.model().schema().db_name;
+
+        let counts: HashMap<GraphqlId, usize> = self.with_transaction(db_name, |conn| {
+            let select = QueryBuilder::count_related_nodes(from_field, from_node_ids, query_arguments);
+            let (sql, params) = visitor::Sqlite::build(select);
+
+            let parent_ids = conn
+                .prepare(&sql)?
+                .query_map(&params, |row| row.get::<_, GraphqlId>(0))?
+                .collect::<Result<Vec<GraphqlId>, _>>()?;
+
+            let counts = parent_ids.into_iter().fold(HashMap::new(), |mut counts, parent_id| {
+                *counts.entry(parent_id).or_insert(0) += 1;
+                counts
+            });
+
+            Ok(counts)
+        })?;
+
+        Ok(from_node_ids
+            .iter()
+            .map(|id| counts.get(id).cloned().unwrap_or(0))
+            .collect())
+    }
+
+    /// Aggregates the nodes of the page the arguments select, per group of
+    /// nodes sharing the values of the grouping fields.
+    ///
+    /// ```rust
+    /// # use sqlite_connector::*;
+    /// # use connector::{filter::*, *};
+    /// # use prisma_models::*;
+    /// # use serde_json;
+    /// # use std::{fs::File, sync::Arc};
+    /// #
+    /// # let template: SchemaTemplate = serde_json::from_reader(File::open("./test_schema.json").unwrap()).unwrap();
+    /// let schema = template.build(String::from("aggregate"));
+    /// let product = schema.find_model("Product").unwrap();
+    /// let name = product.fields().find_from_scalar("name").unwrap();
+    /// let price = product.fields().find_from_scalar("price").unwrap();
+    /// #
+    /// # std::fs::create_dir_all("db").unwrap();
+    /// # let _ = std::fs::remove_file("db/aggregate.db");
+    ///
+    /// let sqlite = Sqlite::new(1, false).unwrap();
+    ///
+    /// sqlite
+    ///     .with_transaction("aggregate", |conn| {
+    ///         conn.execute_batch(
+    ///             "CREATE TABLE aggregate.Product (id Text PRIMARY KEY, name Text, price Real, stock Integer);
+    ///              INSERT INTO aggregate.Product (id, name, price) VALUES
+    ///                  ('a', 'Apple', 1.0), ('b', 'Apple', 2.0), ('c', 'Pear', 3.0), ('d', 'Pear', 5.0);",
+    ///         )?;
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let aggregations = vec![Aggregation::Count, Aggregation::Sum(Arc::clone(&price))];
+    /// let group = |name: &str, count: i32, sum: f64| AggregateResult {
+    ///     group: vec![PrismaValue::from(name)],
+    ///     values: vec![PrismaValue::from(count), PrismaValue::from(sum)],
+    /// };
+    ///
+    /// let all = sqlite
+    ///     .aggregate(Arc::clone(&product), QueryArguments::default(), &aggregations, &[Arc::clone(&name)])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(vec![group("Apple", 2, 3.0), group("Pear", 2, 8.0)], all);
+    ///
+    /// // Filters and pagination select the nodes before they are grouped:
+    /// let mut query_arguments = QueryArguments::from(price.greater_than(1.5));
+    /// query_arguments.first = Some(2);
+    ///
+    /// let page = sqlite
+    ///     .aggregate(Arc::clone(&product), query_arguments, &aggregations, &[Arc::clone(&name)])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(vec![group("Apple", 1, 2.0), group("Pear", 1, 3.0)], page);
+    ///
+    /// // Without groups, a single result aggregates all nodes:
+    /// let total = sqlite.aggregate(product, QueryArguments::default(), &aggregations, &[]).unwrap();
+    /// assert_eq!(vec![PrismaValue::from(4), PrismaValue::from(11.0)], total[0].values);
+    /// ```
+    fn aggregate(
+        &self,
+        model: ModelRef,
+        query_arguments: QueryArguments,
+        aggregations: &[Aggregation],
+        group_by: &[ScalarFieldRef],
+    ) -> ConnectorResult<Vec<AggregateResult>> {
+        // Nothing to select, a single group without values.
+        if aggregations.is_empty() && group_by.is_empty() {
+            return Ok(vec![AggregateResult {
+                group: Vec::new(),
+                values: Vec::new(),
+            }]);
+        }
+
+        let db_name = &model.schema().db_name;
+
+        self.with_transaction(db_name, |conn| {
+            let nodes = QueryBuilder::aggregated_nodes(model, query_arguments, aggregations, group_by);
+            let select = QueryBuilder::aggregate(nodes, aggregations, group_by);
+            let (sql, params) = visitor::Sqlite::build(select);
+
+            let results = conn
+                .prepare(&sql)?
+                .query_map(&params, |row| {
+                    let group = group_by
+                        .iter()
+                        .enumerate()
+                        .map(|(i, field)| Sqlite::fetch_value(field.type_identifier, row, i))
+                        .collect::<ConnectorResult<Vec<PrismaValue>>>()?;
+
+                    let values = aggregations
+                        .iter()
+                        .enumerate()
+                        .map(|(i, aggregation)| {
+                            Sqlite::fetch_value(aggregation.type_identifier(), row, group_by.len() + i)
+                        })
+                        .collect::<ConnectorResult<Vec<PrismaValue>>>()?;
+
+                    Ok(AggregateResult { group, values })
+                })?
+                .map(|row_res| -> ConnectorResult<AggregateResult> { Ok(row_res??) })
+                .collect::<ConnectorResult<Vec<AggregateResult>>>()?;
+
+            Ok(results)
+        })
+    }
+
     fn get_scalar_list_values_by_node_ids(
         &self,
         list_field: ScalarFieldRef,
@@ -0,0 +1,39 @@
+use crate::{DatabaseRead, DatabaseWrite, SearchIndex, Sqlite};
+use connector::ConnectorResult;
+use prisma_models::prelude::*;
+use prisma_query::ast::Insert;
+use rusqlite::Transaction;
+use std::sync::Arc;
+
+impl Sqlite {
+    /// Re-indexes the given nodes of a model declaring search after a write.
+    /// Deleted nodes are only removed from the index.
+    pub(crate) fn sync_search_index(conn: &Transaction, model: ModelRef, ids: &[&GraphqlId]) -> ConnectorResult<()> {
+        if !model.is_searchable || ids.is_empty() {
+            return Ok(());
+        }
+
+        let index = SearchIndex::new(model);
+
+        Self::execute_many(conn, index.delete(ids))?;
+
+        for chunk in ids.chunks(SearchIndex::PARAMETER_LIMIT) {
+            let (select, selected_fields) = index.source(chunk);
+            let nodes = Self::query(conn, select, |row| Sqlite::read_row(row, &selected_fields))?;
+            let inserts: Vec<Insert> = nodes.into_iter().map(|node| index.insert(node)).collect();
+
+            Self::execute_many(conn, inserts)?;
+        }
+
+        Ok(())
+    }
+
+    /// Empties the search indexes of the project.
+    pub(crate) fn truncate_search_indexes(conn: &Transaction, project: ProjectRef) -> ConnectorResult<()> {
+        for model in project.schema().models().iter().filter(|model| model.is_searchable) {
+            Self::execute_one(conn, SearchIndex::new(Arc::clone(model)).truncate())?;
+        }
+
+        Ok(())
+    }
+}
@@ -2,14 +2,16 @@ mod create;
 mod delete;
 mod relation;
 mod update;
+mod upsert;
 
 pub use create::*;
 pub use delete::*;
 pub use relation::*;
 pub use update::*;
+pub use upsert::*;
 
 use crate::*;
-use connector::{error::ConnectorError, mutaction::*, ConnectorResult};
+use connector::{filter::NodeSelector, mutaction::*, ConnectorResult};
 use prisma_models::GraphqlId;
 use prisma_query::{
     ast::Query,
@@ -35,6 +37,15 @@ impl DatabaseWrite for Sqlite {
                     mutaction: DatabaseMutaction::TopLevel(mutaction),
                 });
             }
+            TopLevelDatabaseMutaction::CreateNodes(ref cns) => {
+                let ids = Self::execute_create_many(conn, Arc::clone(&cns.model), &cns.args, cns.skip_duplicates)?;
+
+                results.push(DatabaseMutactionResult {
+                    identifier: Identifier::Ids(ids),
+                    typ: DatabaseMutactionResultType::Many,
+                    mutaction: DatabaseMutaction::TopLevel(mutaction),
+                });
+            }
             TopLevelDatabaseMutaction::UpdateNode(ref un) => {
                 let id = Self::execute_update(conn, &un.where_, &un.non_list_args, &un.list_args)?;
 
@@ -44,39 +55,15 @@ impl DatabaseWrite for Sqlite {
                     mutaction: DatabaseMutaction::TopLevel(mutaction),
                 });
             }
-            TopLevelDatabaseMutaction::UpsertNode(ref ups) => match Self::id_for(conn, &ups.where_) {
-                Err(_e @ ConnectorError::NodeNotFoundForWhere { .. }) => {
-                    let create = &ups.create;
-
-                    let id = Self::execute_create(
-                        conn,
-                        Arc::clone(&create.model),
-                        &create.non_list_args.clone(),
-                        &create.list_args,
-                    )?;
-
-                    results.push(DatabaseMutactionResult {
-                        identifier: Identifier::Id(id),
-                        typ: DatabaseMutactionResultType::Create,
-                        mutaction: DatabaseMutaction::TopLevel(mutaction),
-                    });
-                }
-                Ok(_) => {
-                    let id = Self::execute_update(
-                        conn,
-                        &ups.update.where_,
-                        &ups.update.non_list_args,
-                        &ups.update.list_args,
-                    )?;
-
-                    results.push(DatabaseMutactionResult {
-                        identifier: Identifier::Id(id),
-                        typ: DatabaseMutactionResultType::Update,
-                        mutaction: DatabaseMutaction::TopLevel(mutaction),
-                    });
-                }
-                Err(e) => return Err(e),
-            },
+            TopLevelDatabaseMutaction::UpsertNode(ref ups) => {
+                let (id, typ) = Self::execute_upsert(conn, ups)?;
+
+                results.push(DatabaseMutactionResult {
+                    identifier: Identifier::Id(id),
+                    typ,
+                    mutaction: DatabaseMutaction::TopLevel(mutaction),
+                });
+            }
             TopLevelDatabaseMutaction::UpdateNodes(ref uns) => {
                 let count = Self::execute_update_many(
                     conn,
@@ -95,7 +82,8 @@ impl DatabaseWrite for Sqlite {
                 results.push(result);
             }
             TopLevelDatabaseMutaction::DeleteNode(ref dn) => {
-                let node = Self::execute_delete(conn, &dn.where_)?;
+                let (node, cascaded) = Self::execute_delete(conn, &dn.where_)?;
+                push_cascaded(&mut results, cascaded);
 
                 results.push(DatabaseMutactionResult {
                     identifier: Identifier::Node(node),
@@ -104,7 +92,8 @@ impl DatabaseWrite for Sqlite {
                 });
             }
             TopLevelDatabaseMutaction::DeleteNodes(ref dns) => {
-                let count = Self::execute_delete_many(conn, Arc::clone(&dns.model), &dns.filter)?;
+                let (count, cascaded) = Self::execute_delete_many(conn, Arc::clone(&dns.model), &dns.filter)?;
+                push_cascaded(&mut results, cascaded);
 
                 results.push(DatabaseMutactionResult {
                     identifier: Identifier::Count(count),
@@ -265,7 +254,9 @@ impl DatabaseWrite for Sqlite {
                 results.push(result);
             }
             NestedDatabaseMutaction::DeleteNode(ref dn) => {
-                Self::execute_nested_delete(conn, &parent_id, dn, &dn.where_, Arc::clone(&dn.relation_field))?;
+                let cascaded =
+                    Self::execute_nested_delete(conn, &parent_id, dn, &dn.where_, Arc::clone(&dn.relation_field))?;
+                push_cascaded(&mut results, cascaded);
 
                 results.push(DatabaseMutactionResult {
                     identifier: Identifier::None,
@@ -274,8 +265,9 @@ impl DatabaseWrite for Sqlite {
                 });
             }
             NestedDatabaseMutaction::DeleteNodes(ref dns) => {
-                let count =
+                let (count, cascaded) =
                     Self::execute_nested_delete_many(conn, &parent_id, &dns.filter, Arc::clone(&dns.relation_field))?;
+                push_cascaded(&mut results, cascaded);
 
                 results.push(DatabaseMutactionResult {
                     identifier: Identifier::Count(count),
@@ -309,3 +301,21 @@ impl DatabaseWrite for Sqlite {
         Ok(())
     }
 }
+
+/// Reports every record deleted through a cascade as a deleted single node,
+/// before the result of the mutaction itself, which stays the last result.
+fn push_cascaded(results: &mut DatabaseMutactionResults, cascaded: Vec<CascadedDelete>) {
+    for CascadedDelete { model, node } in cascaded {
+        let id = node.get_id_value(Arc::clone(&model)).unwrap().clone();
+
+        let mutaction = TopLevelDatabaseMutaction::DeleteNode(DeleteNode {
+            where_: NodeSelector::new(model.fields().id(), id),
+        });
+
+        results.push_cascaded(DatabaseMutactionResult {
+            identifier: Identifier::Node(node),
+            typ: DatabaseMutactionResultType::Delete,
+            mutaction: DatabaseMutaction::TopLevel(mutaction),
+        });
+    }
+}
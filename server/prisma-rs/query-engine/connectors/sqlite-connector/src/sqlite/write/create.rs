@@ -4,8 +4,12 @@ use crate::{
 };
 use connector::ConnectorResult;
 use prisma_models::{GraphqlId, ModelRef, PrismaArgs, PrismaListValue, RelationFieldRef};
+use prisma_query::{
+    ast::*,
+    visitor::{self, Visitor},
+};
 use rusqlite::Transaction;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 impl DatabaseCreate for Sqlite {
     fn execute_create<T>(
@@ -35,9 +39,59 @@ impl DatabaseCreate for Sqlite {
             }
         }
 
+        Self::sync_search_index(conn, model, &[&id])?;
+
         Ok(id)
     }
 
+    fn execute_create_many(
+        conn: &Transaction,
+        model: ModelRef,
+        args: &[PrismaArgs],
+        skip_duplicates: bool,
+    ) -> ConnectorResult<Vec<GraphqlId>> {
+        let (inserts, ids) = MutationBuilder::create_nodes(Arc::clone(&model), args.to_vec(), skip_duplicates);
+
+        let existing = match skip_duplicates {
+            true => Self::existing_ids(conn, Arc::clone(&model), &ids)?,
+            false => HashSet::new(),
+        };
+
+        let auto_generated = model.fields().id().is_auto_generated;
+        let mut generated = Vec::new();
+
+        for insert in inserts {
+            let (sql, params) = visitor::Sqlite::build(insert);
+            let changes = conn.prepare(&sql)?.execute(&params)?;
+
+            // The database numbers the inserted records in sequence.
+            if auto_generated && changes > 0 {
+                let last_id = conn.last_insert_rowid() as usize;
+                generated.extend((last_id + 1 - changes..=last_id).map(GraphqlId::Int));
+            }
+        }
+
+        let created: Vec<GraphqlId> = if auto_generated {
+            generated
+        } else if skip_duplicates {
+            let inserted = Self::existing_ids(conn, Arc::clone(&model), &ids)?;
+            let mut seen = HashSet::new();
+
+            // Only the first of the args with the same id is inserted.
+            ids.into_iter()
+                .filter(|id| seen.insert(id.clone()))
+                .filter(|id| inserted.contains(id) && !existing.contains(id))
+                .collect()
+        } else {
+            ids
+        };
+
+        let id_refs: Vec<&GraphqlId> = created.iter().collect();
+        Self::sync_search_index(conn, model, id_refs.as_slice())?;
+
+        Ok(created)
+    }
+
     fn execute_nested_create<T>(
         conn: &Transaction,
         parent_id: &GraphqlId,
@@ -75,3 +129,22 @@ impl DatabaseCreate for Sqlite {
         }
     }
 }
+
+impl Sqlite {
+    /// The given ids that are already used by records of the model, looked
+    /// up in chunks under the parameter limit.
+    fn existing_ids(conn: &Transaction, model: ModelRef, ids: &[GraphqlId]) -> ConnectorResult<HashSet<GraphqlId>> {
+        let id_column = model.fields().id().as_column();
+        let mut existing = HashSet::new();
+
+        for chunk in ids.chunks(MutationBuilder::PARAMETER_LIMIT) {
+            let select = Select::from_table(model.table())
+                .column(id_column.clone())
+                .so_that(id_column.clone().in_selection(chunk.to_vec()));
+
+            existing.extend(Self::query(conn, select, Self::fetch_id)?);
+        }
+
+        Ok(existing)
+    }
+}
@@ -1,49 +1,44 @@
 use crate::{
     mutaction::{DeleteActions, MutationBuilder, NestedActions},
-    DatabaseDelete, DatabaseRead, DatabaseWrite, Sqlite,
+    query_builder::QueryBuilder,
+    CascadedDelete, DatabaseDelete, DatabaseRead, DatabaseWrite, Sqlite,
 };
 use connector::{
     error::{ConnectorError, NodeSelectorInfo},
     filter::{Filter, NodeSelector},
     ConnectorResult,
 };
-use prisma_models::{GraphqlId, ModelRef, ProjectRef, RelationFieldRef, SingleNode};
+use prisma_models::{GraphqlId, ModelRef, ProjectRef, RelationFieldRef, SelectedFields, SingleNode};
+use prisma_query::ast::*;
 use rusqlite::Transaction;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 impl DatabaseDelete for Sqlite {
-    fn execute_delete(conn: &Transaction, node_selector: &NodeSelector) -> ConnectorResult<SingleNode> {
-        let model = node_selector.field.model();
+    fn execute_delete(
+        conn: &Transaction,
+        node_selector: &NodeSelector,
+    ) -> ConnectorResult<(SingleNode, Vec<CascadedDelete>)> {
+        let model = node_selector.model();
         let node = Self::find_node(conn, node_selector)?;
 
         let id = node.get_id_value(Arc::clone(&model)).unwrap();
+        let cascaded = Self::delete_cascading(conn, model, &[id])?;
 
-        DeleteActions::check_relation_violations(Arc::clone(&model), &[id], |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
-            Ok(ids.into_iter().next())
-        })?;
-
-        let deletes = MutationBuilder::delete_many(model, &[id]);
-        Self::execute_many(conn, deletes)?;
-
-        Ok(node)
+        Ok((node, cascaded))
     }
 
-    fn execute_delete_many(conn: &Transaction, model: ModelRef, filter: &Filter) -> ConnectorResult<usize> {
+    fn execute_delete_many(
+        conn: &Transaction,
+        model: ModelRef,
+        filter: &Filter,
+    ) -> ConnectorResult<(usize, Vec<CascadedDelete>)> {
         let ids = Self::ids_for(conn, Arc::clone(&model), filter.clone())?;
         let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
         let count = ids.len();
 
-        DeleteActions::check_relation_violations(Arc::clone(&model), ids.as_slice(), |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
-            Ok(ids.into_iter().next())
-        })?;
-
-        let deletes = { MutationBuilder::delete_many(model, ids.as_slice()) };
+        let cascaded = Self::delete_cascading(conn, model, ids.as_slice())?;
 
-        Self::execute_many(conn, deletes)?;
-
-        Ok(count)
+        Ok((count, cascaded))
     }
 
     fn execute_nested_delete(
@@ -52,7 +47,7 @@ impl DatabaseDelete for Sqlite {
         actions: &NestedActions,
         node_selector: &Option<NodeSelector>,
         relation_field: RelationFieldRef,
-    ) -> ConnectorResult<()> {
+    ) -> ConnectorResult<Vec<CascadedDelete>> {
         if let Some(ref node_selector) = node_selector {
             Self::id_for(conn, node_selector)?;
         };
@@ -86,17 +81,7 @@ impl DatabaseDelete for Sqlite {
             check.call_box(ids.into_iter().next())?;
         }
 
-        let related_model = relation_field.related_model();
-
-        DeleteActions::check_relation_violations(related_model, &[&child_id; 1], |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
-            Ok(ids.into_iter().next())
-        })?;
-
-        let deletes = MutationBuilder::delete_many(relation_field.related_model(), &[&child_id]);
-        Self::execute_many(conn, deletes)?;
-
-        Ok(())
+        Self::delete_cascading(conn, relation_field.related_model(), &[&child_id])
     }
 
     fn execute_nested_delete_many(
@@ -104,32 +89,122 @@ impl DatabaseDelete for Sqlite {
         parent_id: &GraphqlId,
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
-    ) -> ConnectorResult<usize> {
+    ) -> ConnectorResult<(usize, Vec<CascadedDelete>)> {
         let ids = Self::get_ids_by_parents(conn, Arc::clone(&relation_field), vec![parent_id], filter.clone())?;
         let count = ids.len();
 
         let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-        let model = relation_field.model();
-
-        DeleteActions::check_relation_violations(model, ids.as_slice(), |select| {
-            let ids = Self::query(conn, select, Self::fetch_id)?;
-            Ok(ids.into_iter().next())
-        })?;
-
-        let deletes = MutationBuilder::delete_many(relation_field.related_model(), ids.as_slice());
-
-        Self::execute_many(conn, deletes)?;
+        let cascaded = Self::delete_cascading(conn, relation_field.related_model(), ids.as_slice())?;
 
-        Ok(count)
+        Ok((count, cascaded))
     }
 
     fn execute_reset_data(conn: &Transaction, project: ProjectRef) -> ConnectorResult<()> {
         Self::without_foreign_key_checks(conn, || {
-            let deletes = MutationBuilder::truncate_tables(project);
+            let deletes = MutationBuilder::truncate_tables(Arc::clone(&project));
 
             Self::execute_many(conn, deletes)
         })?;
 
+        Self::truncate_search_indexes(conn, project)?;
+
         Ok(())
     }
 }
+
+impl Sqlite {
+    /// Deletes the records and their scalar list values, together with every
+    /// record reachable from them through relations with `OnDelete::Cascade`.
+    /// Records required by a record that is not deleted cause a relation
+    /// violation. Returns the records deleted through the cascade.
+    fn delete_cascading(
+        conn: &Transaction,
+        model: ModelRef,
+        ids: &[&GraphqlId],
+    ) -> ConnectorResult<Vec<CascadedDelete>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let deleted = Self::collect_cascading(conn, model, ids)?;
+
+        for (model, ids) in deleted.iter() {
+            let ids: Vec<&GraphqlId> = ids.iter().collect();
+
+            DeleteActions::check_relation_violations(Arc::clone(model), ids.as_slice(), &deleted, |select| {
+                let ids = Self::query(conn, select, Self::fetch_id)?;
+                Ok(ids.into_iter().next())
+            })?;
+        }
+
+        let mut cascaded = Vec::new();
+
+        // Read in chunks under the parameter limit, like the deletes below.
+        for (model, ids) in deleted.iter().skip(1) {
+            let selected_fields = SelectedFields::from(Arc::clone(model));
+
+            for chunk in ids.chunks(MutationBuilder::PARAMETER_LIMIT) {
+                let select = {
+                    let condition = model.fields().id().as_column().in_selection(chunk.to_vec());
+                    let query = Select::from_table(model.table()).so_that(condition);
+
+                    QueryBuilder::get_nodes(Arc::clone(model), &selected_fields, query)
+                };
+
+                for node in Self::query(conn, select, |row| Self::read_row(row, &selected_fields))? {
+                    cascaded.push(CascadedDelete {
+                        model: Arc::clone(model),
+                        node: SingleNode::new(node, selected_fields.names()),
+                    });
+                }
+            }
+        }
+
+        // Dependents go first, the records they were reached from last.
+        for (model, ids) in deleted.iter().rev() {
+            let ids: Vec<&GraphqlId> = ids.iter().collect();
+
+            let deletes = MutationBuilder::delete_many(Arc::clone(model), ids.as_slice());
+            Self::execute_many(conn, deletes)?;
+            Self::sync_search_index(conn, Arc::clone(model), ids.as_slice())?;
+        }
+
+        Ok(cascaded)
+    }
+
+    /// Walks the cascading relations breadth-first, starting from the given
+    /// records, which form the first entry. Every record is visited once, so
+    /// cycles through self-relations end the walk.
+    fn collect_cascading(
+        conn: &Transaction,
+        model: ModelRef,
+        ids: &[&GraphqlId],
+    ) -> ConnectorResult<Vec<(ModelRef, Vec<GraphqlId>)>> {
+        let mut visited: HashSet<(String, GraphqlId)> =
+            ids.iter().map(|id| (model.name.clone(), (*id).clone())).collect();
+
+        let mut deleted = vec![(model, ids.iter().map(|id| (*id).clone()).collect::<Vec<GraphqlId>>())];
+        let mut next = 0;
+
+        while next < deleted.len() {
+            let (model, ids) = deleted[next].clone();
+            next += 1;
+
+            for rf in model.fields().cascading_relation() {
+                let related_model = rf.related_model();
+                let parent_ids: Vec<&GraphqlId> = ids.iter().collect();
+
+                let related_ids: Vec<GraphqlId> = Self::get_ids_by_parents(conn, rf, parent_ids, None::<Filter>)?
+                    .into_iter()
+                    .filter(|id| visited.insert((related_model.name.clone(), id.clone())))
+                    .collect();
+
+                if !related_ids.is_empty() {
+                    deleted.push((related_model, related_ids));
+                }
+            }
+        }
+
+        Ok(deleted)
+    }
+}
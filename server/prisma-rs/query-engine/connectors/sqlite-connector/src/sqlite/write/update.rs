@@ -3,7 +3,7 @@ use connector::{
     filter::{Filter, NodeSelector},
     ConnectorResult,
 };
-use prisma_models::{GraphqlId, ModelRef, PrismaArgs, PrismaListValue, RelationFieldRef};
+use prisma_models::{GraphqlId, ModelRef, PrismaArgs, RelationFieldRef, ScalarListOperation};
 use rusqlite::Transaction;
 use std::sync::Arc;
 
@@ -12,12 +12,12 @@ impl DatabaseUpdate for Sqlite {
         conn: &Transaction,
         node_selector: &NodeSelector,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>,
     {
-        let model = node_selector.field.model();
+        let model = node_selector.model();
         let id = Self::id_for(conn, node_selector)?;
         let updating = MutationBuilder::update_one(Arc::clone(&model), &id, non_list_args)?;
 
@@ -25,7 +25,11 @@ impl DatabaseUpdate for Sqlite {
             Self::execute_one(conn, update)?;
         }
 
+        let operations = MutationBuilder::update_operations(Arc::clone(&model), &[&id], non_list_args)?;
+        Self::execute_many(conn, operations)?;
+
         Self::update_list_args(conn, &[id.clone()], Arc::clone(&model), list_args)?;
+        Self::sync_search_index(conn, model, &[&id])?;
 
         Ok(id)
     }
@@ -35,21 +39,22 @@ impl DatabaseUpdate for Sqlite {
         model: ModelRef,
         filter: &Filter,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<usize>
     where
         T: AsRef<str>,
     {
         let ids = Self::ids_for(conn, Arc::clone(&model), filter.clone())?;
         let count = ids.len();
+        let id_refs: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
 
-        let updates = {
-            let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-            MutationBuilder::update_many(Arc::clone(&model), ids.as_slice(), non_list_args)?
-        };
+        let updates = MutationBuilder::update_many(Arc::clone(&model), id_refs.as_slice(), non_list_args)?;
+        let operations = MutationBuilder::update_operations(Arc::clone(&model), id_refs.as_slice(), non_list_args)?;
 
         Self::execute_many(conn, updates)?;
+        Self::execute_many(conn, operations)?;
         Self::update_list_args(conn, ids.as_slice(), Arc::clone(&model), list_args)?;
+        Self::sync_search_index(conn, model, id_refs.as_slice())?;
 
         Ok(count)
     }
@@ -60,7 +65,7 @@ impl DatabaseUpdate for Sqlite {
         node_selector: &Option<NodeSelector>,
         relation_field: RelationFieldRef,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<GraphqlId>
     where
         T: AsRef<str>,
@@ -81,21 +86,23 @@ impl DatabaseUpdate for Sqlite {
         filter: &Option<Filter>,
         relation_field: RelationFieldRef,
         non_list_args: &PrismaArgs,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<usize>
     where
         T: AsRef<str>,
     {
         let ids = Self::get_ids_by_parents(conn, Arc::clone(&relation_field), vec![parent_id], filter.clone())?;
         let count = ids.len();
+        let id_refs: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
 
-        let updates = {
-            let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-            MutationBuilder::update_many(relation_field.related_model(), ids.as_slice(), non_list_args)?
-        };
+        let related_model = relation_field.related_model();
+        let updates = MutationBuilder::update_many(Arc::clone(&related_model), id_refs.as_slice(), non_list_args)?;
+        let operations = MutationBuilder::update_operations(related_model, id_refs.as_slice(), non_list_args)?;
 
         Self::execute_many(conn, updates)?;
-        Self::update_list_args(conn, ids.as_slice(), relation_field.model(), list_args)?;
+        Self::execute_many(conn, operations)?;
+        Self::update_list_args(conn, ids.as_slice(), relation_field.related_model(), list_args)?;
+        Self::sync_search_index(conn, relation_field.related_model(), id_refs.as_slice())?;
 
         Ok(count)
     }
@@ -104,18 +111,42 @@ impl DatabaseUpdate for Sqlite {
         conn: &Transaction,
         ids: &[GraphqlId],
         model: ModelRef,
-        list_args: &[(T, PrismaListValue)],
+        list_args: &[(T, ScalarListOperation)],
     ) -> ConnectorResult<()>
     where
         T: AsRef<str>,
     {
-        for (field_name, list_value) in list_args {
+        let id_refs: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
+
+        for (field_name, operation) in list_args {
             let field = model.fields().find_from_scalar(field_name.as_ref()).unwrap();
             let table = field.scalar_list_table();
-            let (deletes, inserts) = MutationBuilder::update_scalar_list_values(&table, &list_value, ids.to_vec());
 
-            Self::execute_many(conn, deletes)?;
-            Self::execute_many(conn, inserts)?;
+            match operation {
+                ScalarListOperation::Set(list_value) => {
+                    let (deletes, inserts) =
+                        MutationBuilder::update_scalar_list_values(&table, list_value, ids.to_vec());
+
+                    Self::execute_many(conn, deletes)?;
+                    Self::execute_many(conn, inserts)?;
+                }
+                ScalarListOperation::Push(list_value) => {
+                    let inserts = MutationBuilder::push_scalar_list_values(&field, list_value, id_refs.as_slice());
+                    Self::execute_many(conn, inserts)?;
+                }
+                ScalarListOperation::Unshift(list_value) => {
+                    let inserts = MutationBuilder::unshift_scalar_list_values(&field, list_value, id_refs.as_slice());
+                    Self::execute_many(conn, inserts)?;
+                }
+                ScalarListOperation::RemoveAt(index) => {
+                    let deletes = MutationBuilder::remove_scalar_list_value_at(&table, *index, id_refs.as_slice());
+                    Self::execute_many(conn, deletes)?;
+                }
+                ScalarListOperation::RemoveValue(value) => {
+                    let deletes = MutationBuilder::remove_scalar_list_value(&table, value, id_refs.as_slice());
+                    Self::execute_many(conn, deletes)?;
+                }
+            }
         }
 
         Ok(())
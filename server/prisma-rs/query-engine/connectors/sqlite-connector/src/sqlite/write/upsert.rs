@@ -0,0 +1,90 @@
+use crate::{
+    mutaction::MutationBuilder, DatabaseCreate, DatabaseRead, DatabaseUpdate, DatabaseUpsert, DatabaseWrite, Sqlite,
+};
+use connector::{
+    error::ConnectorError,
+    filter::{CompoundNodeSelector, NodeSelector},
+    mutaction::{DatabaseMutactionResultType, UpsertNode},
+    ConnectorResult,
+};
+use prisma_models::{GraphqlId, PrismaValue};
+use rusqlite::Transaction;
+use std::sync::Arc;
+
+impl DatabaseUpsert for Sqlite {
+    fn execute_upsert(
+        conn: &Transaction,
+        upsert: &UpsertNode,
+    ) -> ConnectorResult<(GraphqlId, DatabaseMutactionResultType)> {
+        if let Some((statement, created_id)) = MutationBuilder::upsert_node(upsert)? {
+            // The bundled SQLite has no `RETURNING`. Inserting the record sets the
+            // last inserted rowid of the connection, updating it keeps the rowid.
+            let last_rowid = conn.last_insert_rowid();
+            Self::execute_one(conn, statement)?;
+
+            let (id, typ) = match conn.last_insert_rowid() == last_rowid {
+                true => (Self::updated_id(conn, upsert)?, DatabaseMutactionResultType::Update),
+                false => (created_id, DatabaseMutactionResultType::Create),
+            };
+
+            Self::sync_search_index(conn, upsert.where_.model(), &[&id])?;
+
+            return Ok((id, typ));
+        }
+
+        match Self::id_for(conn, &upsert.where_) {
+            Err(ConnectorError::NodeNotFoundForWhere { .. }) => {
+                let create = &upsert.create;
+                let id = Self::execute_create(
+                    conn,
+                    Arc::clone(&create.model),
+                    &create.non_list_args,
+                    &create.list_args,
+                )?;
+
+                Ok((id, DatabaseMutactionResultType::Create))
+            }
+            Ok(_) => {
+                let update = &upsert.update;
+                let id = Self::execute_update(conn, &update.where_, &update.non_list_args, &update.list_args)?;
+
+                Ok((id, DatabaseMutactionResultType::Update))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Sqlite {
+    /// The id of the record a single statement upsert updated. Known if it
+    /// was selected by its id, otherwise looked up by the selected values as
+    /// the update left them.
+    fn updated_id(conn: &Transaction, upsert: &UpsertNode) -> ConnectorResult<GraphqlId> {
+        let update = &upsert.update.non_list_args;
+
+        let mut fields: Vec<_> = upsert
+            .where_
+            .fields()
+            .into_iter()
+            .map(|(field, value)| {
+                let value = update.get_field_value(&field.name).unwrap_or(value);
+                (Arc::clone(field), value.clone())
+            })
+            .collect();
+
+        let selector = match upsert.where_ {
+            NodeSelector::Field { .. } => NodeSelector::from(fields.remove(0)),
+            NodeSelector::Compound(ref selector) => {
+                NodeSelector::from(CompoundNodeSelector::from_fields(&selector.model(), fields)?)
+            }
+        };
+
+        match selector {
+            NodeSelector::Field {
+                ref field,
+                value: PrismaValue::GraphqlId(ref id),
+            } if field.is_id() => Ok(id.clone()),
+            selector => Self::id_for(conn, &selector),
+        }
+    }
+}
@@ -16,11 +16,11 @@ mod single;
 use graphql_parser::query::{Field, Selection, Value};
 use inflector::Inflector;
 
-use crate::{CoreError, CoreResult, PrismaQuery};
+use crate::{filter, order_by, CoreError, CoreResult, PrismaQuery};
 use connector::{filter::NodeSelector, QueryArguments};
 use prisma_models::{
-    Field as ModelField, ModelRef, OrderBy, PrismaValue, RelationFieldRef, SchemaRef, SelectedField, SelectedFields,
-    SelectedScalarField, SortOrder,
+    Field as ModelField, GraphqlId, ModelRef, PrismaValue, RelationFieldRef, SchemaRef, SelectedField, SelectedFields,
+    SelectedScalarField,
 };
 
 use std::sync::Arc;
@@ -77,10 +77,7 @@ pub trait BuilderExt {
                 let field = model.fields().find_from_scalar(field_name).unwrap();
                 let value = Self::value_to_prisma_value(value);
 
-                Ok(NodeSelector {
-                    field: Arc::clone(&field),
-                    value: value,
-                })
+                Ok(NodeSelector::new(Arc::clone(&field), value))
             }
             _ => unimplemented!(),
         }
@@ -112,44 +109,25 @@ pub trait BuilderExt {
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
                         ("last", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
+                            Some(num) => Ok(QueryArguments { last: Some(num as u32), ..res }),
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
                         //("after", Value::String(s)) if s.is_uuid() => Ok(QueryArguments { after: Some(UuidString(s.clone()).into()), ..res }),
                         ("after", Value::String(s)) => Ok(QueryArguments { after: Some(s.clone().into()), ..res }),
                         ("after", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
+                            Some(num) => Ok(QueryArguments { after: Some(GraphqlId::from(num as usize)), ..res }),
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
                         //("before", Value::String(s)) if s.is_uuid() => Ok(QueryArguments { before: Some(UuidString(s.clone()).into()), ..res }),
                         ("before", Value::String(s)) => Ok(QueryArguments { before: Some(s.clone().into()), ..res }),
                         ("before", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
+                            Some(num) => Ok(QueryArguments { before: Some(GraphqlId::from(num as usize)), ..res }),
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
-                        ("orderby", Value::Enum(name)) => {
-                            let vec = name.split("_").collect::<Vec<&str>>();
-                            if vec.len() == 2 {
-                                model
-                                    .fields()
-                                    .find_from_scalar(vec[0])
-                                    .map(|val| QueryArguments {
-                                        order_by: Some(OrderBy {
-                                            field: Arc::clone(&val),
-                                            sort_order: match vec[1] {
-                                                "ASC" => SortOrder::Ascending,
-                                                "DESC" => SortOrder::Descending,
-                                                _ => unreachable!(),
-                                            },
-                                        }),
-                                        ..res
-                                    })
-                                    .map_err(|_| CoreError::QueryValidationError(format!("Unknown field `{}`", vec[0])))
-                            } else {
-                                Err(CoreError::QueryValidationError("...".into()))
-                            }
-                        }
-                        ("where", _) => panic!("lolnope"),
+                        ("orderBy", value) => order_by::extract_order_by(Arc::clone(&model), value)
+                            .map(|order_by| QueryArguments { order_by, ..res }),
+                        ("where", value) => filter::extract_filter(Arc::clone(&model), value)
+                            .map(|filter| QueryArguments { filter: Some(filter), ..res }),
                         (name, _) => Err(CoreError::QueryValidationError(format!("Unknown key: `{}`", name))),
                     }
                 } else {
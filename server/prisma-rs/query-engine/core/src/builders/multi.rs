@@ -41,6 +41,8 @@ impl<'f> BuilderExt for Builder<'f> {
             args,
             selected_fields,
             nested,
+            connection: None,
+            typename: None,
         })
     }
 }
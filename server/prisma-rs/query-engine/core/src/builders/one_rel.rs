@@ -40,6 +40,7 @@ impl<'f> BuilderExt for Builder<'f> {
             args,
             selected_fields,
             nested,
+            typename: None,
         })
     }
 }
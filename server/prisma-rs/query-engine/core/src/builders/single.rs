@@ -42,6 +42,7 @@ impl<'f> BuilderExt for Builder<'f> {
             selector,
             selected_fields,
             nested,
+            typename: None,
         })
     }
 }
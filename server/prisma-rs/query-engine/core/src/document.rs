@@ -0,0 +1,357 @@
+//! Preprocessing of GraphQL documents
+//!
+//! Selects the operation to execute, substitutes variables and inlines
+//! fragments, so that the query and mutation builders only ever see plain
+//! fields with literal arguments.
+
+use crate::{CoreError, CoreResult};
+use graphql_parser::query::*;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationKind {
+    Query,
+    Mutation,
+}
+
+/// An operation of a document with variables and fragments resolved
+#[derive(Debug)]
+pub struct ResolvedOperation {
+    pub kind: OperationKind,
+    pub selections: Vec<Selection>,
+}
+
+/// Picks the operation with the given name, or the only operation if no name is given,
+/// and resolves its selection set.
+pub fn resolve_operation(
+    document: &Document,
+    operation_name: Option<&str>,
+    variables: &BTreeMap<String, Value>,
+) -> CoreResult<ResolvedOperation> {
+    let operations: Vec<&OperationDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|d| match d {
+            Definition::Operation(op) => Some(op),
+            Definition::Fragment(_) => None,
+        })
+        .collect();
+
+    let operation = match operation_name {
+        Some(name) => operations
+            .into_iter()
+            .find(|op| self::operation_name(op) == Some(name))
+            .ok_or_else(|| CoreError::QueryValidationError(format!("Unknown operation named `{}`", name)))?,
+        None if operations.len() == 1 => operations[0],
+        None => {
+            return Err(CoreError::QueryValidationError(
+                "Must provide operation name if query contains multiple operations".into(),
+            ))
+        }
+    };
+
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|d| match d {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            Definition::Operation(_) => None,
+        })
+        .collect();
+
+    let (kind, variable_definitions, selection_set) = match operation {
+        OperationDefinition::SelectionSet(selection_set) => (OperationKind::Query, &[][..], selection_set),
+        OperationDefinition::Query(q) => (OperationKind::Query, &q.variable_definitions[..], &q.selection_set),
+        OperationDefinition::Mutation(m) => (OperationKind::Mutation, &m.variable_definitions[..], &m.selection_set),
+        OperationDefinition::Subscription(_) => {
+            return Err(CoreError::QueryValidationError("Subscriptions are not supported".into()))
+        }
+    };
+
+    let resolver = DocumentResolver {
+        fragments,
+        variables: resolve_variables(variable_definitions, variables)?,
+    };
+
+    let selections = resolver.resolve_selections(&selection_set.items, &mut vec![])?;
+
+    Ok(ResolvedOperation { kind, selections })
+}
+
+fn operation_name(operation: &OperationDefinition) -> Option<&str> {
+    match operation {
+        OperationDefinition::SelectionSet(_) => None,
+        OperationDefinition::Query(q) => q.name.as_ref().map(String::as_str),
+        OperationDefinition::Mutation(m) => m.name.as_ref().map(String::as_str),
+        OperationDefinition::Subscription(s) => s.name.as_ref().map(String::as_str),
+    }
+}
+
+/// Matches provided variables against the definitions of the operation,
+/// falling back to default values. Missing non-null variables are an error.
+fn resolve_variables(
+    definitions: &[VariableDefinition],
+    provided: &BTreeMap<String, Value>,
+) -> CoreResult<BTreeMap<String, Value>> {
+    definitions
+        .iter()
+        .map(|definition| {
+            let value = match (provided.get(&definition.name), &definition.default_value) {
+                (Some(value), _) => value.clone(),
+                (None, Some(default)) => default.clone(),
+                (None, None) => Value::Null,
+            };
+
+            match (&definition.var_type, &value) {
+                (Type::NonNullType(_), Value::Null) => Err(CoreError::QueryValidationError(format!(
+                    "Variable `${}` of required type `{}` was not provided",
+                    definition.name, definition.var_type
+                ))),
+                _ => Ok((definition.name.clone(), value)),
+            }
+        })
+        .collect()
+}
+
+struct DocumentResolver<'a> {
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    variables: BTreeMap<String, Value>,
+}
+
+impl<'a> DocumentResolver<'a> {
+    /// Flattens fragment spreads and inline fragments into plain fields and substitutes variables.
+    /// `visited` holds the fragments currently being resolved to detect cycles.
+    fn resolve_selections(&self, items: &'a [Selection], visited: &mut Vec<&'a str>) -> CoreResult<Vec<Selection>> {
+        let mut selections = vec![];
+
+        for item in items {
+            match item {
+                Selection::Field(field) if self.is_included(&field.directives)? => {
+                    let arguments = field
+                        .arguments
+                        .iter()
+                        .map(|(name, value)| self.substitute(value).map(|value| (name.clone(), value)))
+                        .collect::<CoreResult<Vec<_>>>()?;
+
+                    let items = self.resolve_selections(&field.selection_set.items, visited)?;
+
+                    selections.push(Selection::Field(Field {
+                        arguments,
+                        directives: vec![],
+                        selection_set: SelectionSet {
+                            items,
+                            ..field.selection_set.clone()
+                        },
+                        ..field.clone()
+                    }));
+                }
+                Selection::FragmentSpread(spread) if self.is_included(&spread.directives)? => {
+                    let name = spread.fragment_name.as_str();
+                    let fragment = self.fragments.get(name).ok_or_else(|| {
+                        CoreError::QueryValidationError(format!("Unknown fragment `{}`", name))
+                    })?;
+
+                    if visited.contains(&name) {
+                        return Err(CoreError::QueryValidationError(format!(
+                            "Fragment `{}` spreads itself",
+                            name
+                        )));
+                    }
+
+                    visited.push(name);
+                    selections.append(&mut self.resolve_selections(&fragment.selection_set.items, visited)?);
+                    visited.pop();
+                }
+                Selection::InlineFragment(fragment) if self.is_included(&fragment.directives)? => {
+                    selections.append(&mut self.resolve_selections(&fragment.selection_set.items, visited)?);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(selections)
+    }
+
+    /// Evaluates `@skip(if: ...)` and `@include(if: ...)`.
+    fn is_included(&self, directives: &[Directive]) -> CoreResult<bool> {
+        for directive in directives {
+            let condition = || match directive.arguments.iter().find(|(name, _)| name == "if") {
+                Some((_, value)) => match self.substitute(value)? {
+                    Value::Boolean(b) => Ok(b),
+                    _ => Err(CoreError::QueryValidationError(format!(
+                        "Argument `if` of directive `@{}` must be a Boolean",
+                        directive.name
+                    ))),
+                },
+                None => Err(CoreError::QueryValidationError(format!(
+                    "Directive `@{}` requires an argument `if`",
+                    directive.name
+                ))),
+            };
+
+            match directive.name.as_str() {
+                "skip" if condition()? => return Ok(false),
+                "include" if !condition()? => return Ok(false),
+                _ => (),
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn substitute(&self, value: &Value) -> CoreResult<Value> {
+        match value {
+            Value::Variable(name) => self
+                .variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| CoreError::QueryValidationError(format!("Variable `${}` is not defined", name))),
+            Value::List(values) => values
+                .iter()
+                .map(|value| self.substitute(value))
+                .collect::<CoreResult<Vec<Value>>>()
+                .map(Value::List),
+            Value::Object(obj) => obj
+                .iter()
+                .map(|(key, value)| self.substitute(value).map(|value| (key.clone(), value)))
+                .collect::<CoreResult<BTreeMap<String, Value>>>()
+                .map(Value::Object),
+            value => Ok(value.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(
+        query: &str,
+        operation_name: Option<&str>,
+        variables: &[(&str, Value)],
+    ) -> CoreResult<ResolvedOperation> {
+        let document = graphql_parser::parse_query(query).unwrap();
+        let variables = variables
+            .iter()
+            .map(|(name, value)| (String::from(*name), value.clone()))
+            .collect();
+
+        resolve_operation(&document, operation_name, &variables)
+    }
+
+    fn field_names(selections: &[Selection]) -> Vec<&str> {
+        selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(field) => field.name.as_str(),
+                _ => panic!("Expected only fields, got {:?}", selection),
+            })
+            .collect()
+    }
+
+    fn argument<'a>(selection: &'a Selection, name: &str) -> &'a Value {
+        match selection {
+            Selection::Field(field) => &field.arguments.iter().find(|(arg, _)| arg == name).unwrap().1,
+            _ => panic!("Expected a field, got {:?}", selection),
+        }
+    }
+
+    #[test]
+    fn selects_the_operation_by_name() {
+        let query = "query A { users { id } } mutation B { createUser(data: {}) { id } }";
+
+        let operation = resolve(query, Some("B"), &[]).unwrap();
+
+        assert_eq!(OperationKind::Mutation, operation.kind);
+        assert_eq!(vec!["createUser"], field_names(&operation.selections));
+
+        assert!(resolve(query, None, &[]).is_err());
+        assert!(resolve(query, Some("C"), &[]).is_err());
+    }
+
+    #[test]
+    fn substitutes_variables_in_nested_arguments() {
+        let query = "query($name: String!, $first: Int = 10) {
+            users(where: { OR: [{ name: $name }] }, first: $first) { id }
+        }";
+
+        let operation = resolve(query, None, &[("name", Value::String("Bob".into()))]).unwrap();
+        let users = &operation.selections[0];
+
+        let mut name = BTreeMap::new();
+        name.insert(String::from("name"), Value::String("Bob".into()));
+
+        let mut filter = BTreeMap::new();
+        filter.insert(String::from("OR"), Value::List(vec![Value::Object(name)]));
+
+        assert_eq!(&Value::Object(filter), argument(users, "where"));
+        assert_eq!(&Value::Int(10.into()), argument(users, "first"));
+    }
+
+    #[test]
+    fn missing_required_and_undefined_variables_are_errors() {
+        let query = "query($name: String!) { users(where: { name: $name }) { id } }";
+        assert!(resolve(query, None, &[]).is_err());
+
+        let query = "query { users(where: { name: $name }) { id } }";
+        assert!(resolve(query, None, &[]).is_err());
+
+        let query = "query($name: String) { users(where: { name: $name }) { id } }";
+        let operation = resolve(query, None, &[]).unwrap();
+
+        let mut filter = BTreeMap::new();
+        filter.insert(String::from("name"), Value::Null);
+
+        assert_eq!(&Value::Object(filter), argument(&operation.selections[0], "where"));
+    }
+
+    #[test]
+    fn inlines_fragments() {
+        let query = "
+            query { users { ...UserFields ... on User { name } } }
+            fragment UserFields on User { id ...Nested }
+            fragment Nested on User { sites { id } }
+        ";
+
+        let operation = resolve(query, None, &[]).unwrap();
+
+        match &operation.selections[0] {
+            Selection::Field(users) => {
+                assert_eq!(vec!["id", "sites", "name"], field_names(&users.selection_set.items))
+            }
+            selection => panic!("Expected a field, got {:?}", selection),
+        }
+    }
+
+    #[test]
+    fn unknown_and_cyclic_fragments_are_errors() {
+        assert!(resolve("query { users { ...Missing } }", None, &[]).is_err());
+
+        let query = "
+            query { users { ...A } }
+            fragment A on User { id ...B }
+            fragment B on User { name ...A }
+        ";
+
+        assert!(resolve(query, None, &[]).is_err());
+    }
+
+    #[test]
+    fn evaluates_skip_and_include() {
+        let query = "query($skip: Boolean!) {
+            users { id @skip(if: $skip) name @include(if: $skip) sites @include(if: false) { id } }
+        }";
+
+        let operation = resolve(query, None, &[("skip", Value::Boolean(true))]).unwrap();
+
+        match &operation.selections[0] {
+            Selection::Field(users) => assert_eq!(vec!["name"], field_names(&users.selection_set.items)),
+            selection => panic!("Expected a field, got {:?}", selection),
+        }
+    }
+
+    #[test]
+    fn subscriptions_are_not_supported() {
+        assert!(resolve("subscription { users { id } }", None, &[]).is_err());
+    }
+}
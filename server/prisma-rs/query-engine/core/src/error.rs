@@ -12,6 +12,9 @@ pub enum CoreError {
 
     #[fail(display = "Query validation error: {}", _0)]
     QueryValidationError(String),
+
+    #[fail(display = "Cannot return null for non-nullable field `{}`", _0)]
+    NullForNonNullableField(String),
 }
 
 impl From<ConnectorError> for CoreError {
@@ -0,0 +1,749 @@
+//! Conversion of GraphQL `where` inputs into connector filters
+
+use crate::{query_ast, CoreError, CoreResult};
+use connector::{filter::*, JsonCompare, RelationCompare, ScalarCompare, ScalarListCompare};
+use graphql_parser::query::Value;
+use prisma_models::{
+    DomainError, Field as ModelField, JsonPath, ModelRef, PrismaValue, RelationFieldRef, ScalarField, ScalarFieldRef,
+    TypeIdentifier,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterOperation {
+    Equals,
+    Not,
+    In,
+    NotIn,
+    LessThan,
+    LessThanOrEquals,
+    GreaterThan,
+    GreaterThanOrEquals,
+    Contains,
+    ContainsEvery,
+    ContainsSome,
+    NotContains,
+    StartsWith,
+    NotStartsWith,
+    EndsWith,
+    NotEndsWith,
+    Like,
+    Search,
+    JsonPath,
+    Every,
+    Some,
+    None,
+    Count,
+}
+
+/// Field name suffixes and their operations. Longer suffixes sharing an ending
+/// with shorter ones (`_not_in` and `_in`) have to come first.
+const SUFFIXES: &[(&str, FilterOperation)] = &[
+    ("_contains_every", FilterOperation::ContainsEvery),
+    ("_contains_some", FilterOperation::ContainsSome),
+    ("_not_starts_with", FilterOperation::NotStartsWith),
+    ("_not_ends_with", FilterOperation::NotEndsWith),
+    ("_not_contains", FilterOperation::NotContains),
+    ("_starts_with", FilterOperation::StartsWith),
+    ("_ends_with", FilterOperation::EndsWith),
+    ("_contains", FilterOperation::Contains),
+    ("_like", FilterOperation::Like),
+    ("_search", FilterOperation::Search),
+    ("_not_in", FilterOperation::NotIn),
+    ("_path", FilterOperation::JsonPath),
+    ("_every", FilterOperation::Every),
+    ("_some", FilterOperation::Some),
+    ("_none", FilterOperation::None),
+    ("_count", FilterOperation::Count),
+    ("_not", FilterOperation::Not),
+    ("_lte", FilterOperation::LessThanOrEquals),
+    ("_gte", FilterOperation::GreaterThanOrEquals),
+    ("_lt", FilterOperation::LessThan),
+    ("_gt", FilterOperation::GreaterThan),
+    ("_in", FilterOperation::In),
+];
+
+/// Appended to a string pattern filter to ignore case, e.g. `name_contains_insensitive`.
+const INSENSITIVE_SUFFIX: &str = "_insensitive";
+
+/// Appended to a string pattern filter to match case exactly, e.g. `name_contains_sensitive`.
+const SENSITIVE_SUFFIX: &str = "_sensitive";
+
+/// Converts a `where` input object of the given model into a `Filter`.
+/// All keys of the object must match, e.g. `{ name_contains: "a", age_gt: 18 }`.
+pub(crate) fn extract_filter(model: ModelRef, value: &Value) -> CoreResult<Filter> {
+    let obj = match value {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Expected a where input object for model `{}`",
+                model.name
+            )))
+        }
+    };
+
+    let mut filters = obj
+        .iter()
+        .map(|(key, value)| extract_key_filter(Arc::clone(&model), key, value))
+        .collect::<CoreResult<Vec<Filter>>>()?;
+
+    match filters.len() {
+        1 => Ok(filters.pop().unwrap()),
+        _ => Ok(Filter::and(filters)),
+    }
+}
+
+fn extract_key_filter(model: ModelRef, key: &str, value: &Value) -> CoreResult<Filter> {
+    let nested = || -> CoreResult<Vec<Filter>> {
+        as_list(value)
+            .into_iter()
+            .map(|value| extract_filter(Arc::clone(&model), value))
+            .collect()
+    };
+
+    match key {
+        "AND" => return Ok(Filter::and(nested()?)),
+        "OR" => return Ok(Filter::or(nested()?)),
+        "NOT" => return Ok(Filter::not(nested()?)),
+        _ => (),
+    };
+
+    let (base_key, mode) = match split_key(&model, key) {
+        None if key.ends_with(INSENSITIVE_SUFFIX) => {
+            (&key[..key.len() - INSENSITIVE_SUFFIX.len()], FilterMode::Insensitive)
+        }
+        None if key.ends_with(SENSITIVE_SUFFIX) => (&key[..key.len() - SENSITIVE_SUFFIX.len()], FilterMode::Sensitive),
+        _ => (key, FilterMode::Default),
+    };
+
+    let (field_name, operation) = split_key(&model, base_key).ok_or_else(|| {
+        CoreError::QueryValidationError(format!("Unknown filter `{}` on model `{}`", key, model.name))
+    })?;
+
+    match model.fields().find_from_all(field_name) {
+        Ok(ModelField::Scalar(field)) if mode != FilterMode::Default && !field.is_list => {
+            pattern_filter(Arc::clone(&field), operation, value, mode)
+        }
+        Ok(_) if mode == FilterMode::Insensitive => Err(CoreError::QueryValidationError(format!(
+            "Filter `{}` can't ignore case",
+            key
+        ))),
+        Ok(_) if mode == FilterMode::Sensitive => Err(CoreError::QueryValidationError(format!(
+            "Filter `{}` can't match case",
+            key
+        ))),
+        Ok(ModelField::Scalar(field)) if field.is_list => scalar_list_filter(Arc::clone(&field), operation, value),
+        Ok(ModelField::Scalar(field)) => scalar_filter(Arc::clone(&field), operation, value),
+        Ok(ModelField::Relation(field)) => relation_filter(Arc::clone(&field), operation, value),
+        Err(_) => Err(CoreError::QueryValidationError(format!(
+            "Unknown field `{}` on model `{}`",
+            field_name, model.name
+        ))),
+    }
+}
+
+/// Splits a filter key into the field name and the operation, e.g. `name_not_in` into `name` and `NotIn`.
+/// Field names may contain underscores, so a suffix only matches if the rest is a field of the model.
+fn split_key<'a>(model: &ModelRef, key: &'a str) -> Option<(&'a str, FilterOperation)> {
+    let is_field = |name: &str| model.fields().find_from_all(name).is_ok();
+
+    if is_field(key) {
+        return Some((key, FilterOperation::Equals));
+    }
+
+    SUFFIXES
+        .iter()
+        .filter(|(suffix, _)| key.ends_with(suffix))
+        .map(|(suffix, operation)| (&key[..key.len() - suffix.len()], *operation))
+        .find(|(field_name, _)| is_field(field_name))
+}
+
+fn scalar_filter(field: ScalarFieldRef, operation: FilterOperation, value: &Value) -> CoreResult<Filter> {
+    let single = || query_ast::coerce_value(&field, value);
+    let list = || -> CoreResult<Vec<PrismaValue>> {
+        as_list(value)
+            .into_iter()
+            .map(|value| query_ast::coerce_value(&field, value))
+            .collect()
+    };
+
+    let filter = match operation {
+        FilterOperation::Equals => field.equals(single()?),
+        FilterOperation::Not => field.not_equals(single()?),
+        FilterOperation::In => Filter::from(ScalarFilter {
+            field: Arc::clone(&field),
+            condition: ScalarCondition::In(list()?),
+            mode: FilterMode::Default,
+        }),
+        FilterOperation::NotIn => Filter::from(ScalarFilter {
+            field: Arc::clone(&field),
+            condition: ScalarCondition::NotIn(list()?),
+            mode: FilterMode::Default,
+        }),
+        FilterOperation::LessThan => field.less_than(single()?),
+        FilterOperation::LessThanOrEquals => field.less_than_or_equals(single()?),
+        FilterOperation::GreaterThan => field.greater_than(single()?),
+        FilterOperation::GreaterThanOrEquals => field.greater_than_or_equals(single()?),
+        FilterOperation::Contains => field.contains(single()?),
+        FilterOperation::NotContains => field.not_contains(single()?),
+        FilterOperation::StartsWith => field.starts_with(single()?),
+        FilterOperation::NotStartsWith => field.not_starts_with(single()?),
+        FilterOperation::EndsWith => field.ends_with(single()?),
+        FilterOperation::NotEndsWith => field.not_ends_with(single()?),
+        FilterOperation::Like => field.like(single()?),
+        FilterOperation::Search if !field.model().is_searchable => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Full-text search is not enabled on model `{}`",
+                field.model().name
+            )))
+        }
+        FilterOperation::Search if field.type_identifier == TypeIdentifier::String => field.search(single()?),
+        FilterOperation::Search => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Full-text search is only allowed on String fields, `{}` is not one",
+                field.name
+            )))
+        }
+        FilterOperation::JsonPath if field.type_identifier == TypeIdentifier::Json => {
+            json_path_filter(Arc::clone(&field), value)?
+        }
+        FilterOperation::JsonPath => {
+            return Err(CoreError::QueryValidationError(format!(
+                "JSON path filters are only allowed on Json fields, `{}` is not one",
+                field.name
+            )))
+        }
+        FilterOperation::ContainsEvery | FilterOperation::ContainsSome => {
+            return Err(CoreError::QueryValidationError(format!(
+                "List filters are not allowed on scalar field `{}`",
+                field.name
+            )))
+        }
+        FilterOperation::Every | FilterOperation::Some | FilterOperation::None | FilterOperation::Count => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Relation filters are not allowed on scalar field `{}`",
+                field.name
+            )))
+        }
+    };
+
+    Ok(filter)
+}
+
+/// Conditions on the value at a path of a Json field, e.g.
+/// `{ path: "$.tags", array_contains: "\"new\"" }`. All given conditions must match.
+fn json_path_filter(field: ScalarFieldRef, value: &Value) -> CoreResult<Filter> {
+    let obj = match value {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Filter `{}_path` expects a JSON path filter object",
+                field.name
+            )))
+        }
+    };
+
+    let path: JsonPath = match obj.get("path") {
+        Some(Value::String(path)) => path
+            .parse()
+            .map_err(|err: DomainError| CoreError::QueryValidationError(format!("{}", err)))?,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Filter `{}_path` requires a `path`",
+                field.name
+            )))
+        }
+    };
+
+    let mut filters = obj
+        .iter()
+        .filter(|(key, _)| key.as_str() != "path")
+        .map(|(key, value)| match (key.as_str(), value) {
+            ("equals", value) => Ok(field.json_path_equals(path.clone(), query_ast::coerce_value(&field, value)?)),
+            ("string_contains", Value::String(s)) => Ok(field.json_path_contains(path.clone(), s.as_str())),
+            ("string_contains_insensitive", Value::String(s)) => {
+                Ok(field.json_path_contains_insensitive(path.clone(), s.as_str()))
+            }
+            ("string_contains_sensitive", Value::String(s)) => {
+                Ok(field.json_path_contains_sensitive(path.clone(), s.as_str()))
+            }
+            ("array_contains", value) => {
+                Ok(field.json_array_contains(path.clone(), query_ast::coerce_value(&field, value)?))
+            }
+            ("exists", Value::Boolean(true)) => Ok(field.json_key_exists(path.clone())),
+            ("exists", Value::Boolean(false)) => Ok(Filter::not(vec![field.json_key_exists(path.clone())])),
+            (key, _) => Err(CoreError::QueryValidationError(format!(
+                "Invalid condition `{}` in filter `{}_path`",
+                key, field.name
+            ))),
+        })
+        .collect::<CoreResult<Vec<Filter>>>()?;
+
+    match filters.len() {
+        0 => Err(CoreError::QueryValidationError(format!(
+            "Filter `{}_path` requires a condition",
+            field.name
+        ))),
+        1 => Ok(filters.pop().unwrap()),
+        _ => Ok(Filter::and(filters)),
+    }
+}
+
+/// String pattern filters ignoring case or matching it exactly, as the mode
+/// of the filter requires.
+fn pattern_filter(
+    field: ScalarFieldRef,
+    operation: FilterOperation,
+    value: &Value,
+    mode: FilterMode,
+) -> CoreResult<Filter> {
+    let (sensitivity, action) = match mode {
+        FilterMode::Sensitive => ("Case-sensitive", "match case"),
+        _ => ("Case-insensitive", "ignore case"),
+    };
+
+    if field.type_identifier != TypeIdentifier::String {
+        return Err(CoreError::QueryValidationError(format!(
+            "{} filters are only allowed on String fields, `{}` is not one",
+            sensitivity, field.name
+        )));
+    }
+
+    let value = query_ast::coerce_value(&field, value)?;
+
+    let filter = match (operation, mode) {
+        (FilterOperation::Contains, FilterMode::Sensitive) => field.contains_sensitive(value),
+        (FilterOperation::NotContains, FilterMode::Sensitive) => field.not_contains_sensitive(value),
+        (FilterOperation::StartsWith, FilterMode::Sensitive) => field.starts_with_sensitive(value),
+        (FilterOperation::NotStartsWith, FilterMode::Sensitive) => field.not_starts_with_sensitive(value),
+        (FilterOperation::EndsWith, FilterMode::Sensitive) => field.ends_with_sensitive(value),
+        (FilterOperation::NotEndsWith, FilterMode::Sensitive) => field.not_ends_with_sensitive(value),
+        (FilterOperation::Like, FilterMode::Sensitive) => field.like_sensitive(value),
+        (FilterOperation::Contains, _) => field.contains_insensitive(value),
+        (FilterOperation::NotContains, _) => field.not_contains_insensitive(value),
+        (FilterOperation::StartsWith, _) => field.starts_with_insensitive(value),
+        (FilterOperation::NotStartsWith, _) => field.not_starts_with_insensitive(value),
+        (FilterOperation::EndsWith, _) => field.ends_with_insensitive(value),
+        (FilterOperation::NotEndsWith, _) => field.not_ends_with_insensitive(value),
+        (FilterOperation::Like, _) => field.like_insensitive(value),
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Only `contains`, `starts_with`, `ends_with` and `like` filters of field `{}` can {}",
+                field.name, action
+            )))
+        }
+    };
+
+    Ok(filter)
+}
+
+/// List fields can only be filtered by the elements they contain.
+fn scalar_list_filter(field: ScalarFieldRef, operation: FilterOperation, value: &Value) -> CoreResult<Filter> {
+    let list = || -> CoreResult<Vec<PrismaValue>> {
+        as_list(value)
+            .into_iter()
+            .map(|value| query_ast::coerce_value(&field, value))
+            .collect()
+    };
+
+    match (operation, value) {
+        (FilterOperation::Contains, Value::List(_)) => Err(CoreError::QueryValidationError(format!(
+            "Filter `{}_contains` expects a single element",
+            field.name
+        ))),
+        (FilterOperation::Contains, _) => Ok(field.contains_element(query_ast::coerce_value(&field, value)?)),
+        (FilterOperation::ContainsEvery, _) => Ok(field.contains_every_element(list()?)),
+        (FilterOperation::ContainsSome, _) => Ok(field.contains_some_element(list()?)),
+        _ => Err(CoreError::QueryValidationError(format!(
+            "Invalid filter on list field `{}`",
+            field.name
+        ))),
+    }
+}
+
+fn relation_filter(field: RelationFieldRef, operation: FilterOperation, value: &Value) -> CoreResult<Filter> {
+    let nested = || extract_filter(field.related_model(), value);
+
+    match (operation, value) {
+        (FilterOperation::Every, _) if field.is_list => Ok(field.every_related(nested()?)),
+        (FilterOperation::Some, _) if field.is_list => Ok(field.at_least_one_related(nested()?)),
+        (FilterOperation::None, _) if field.is_list => Ok(field.no_related(nested()?)),
+        (FilterOperation::Count, _) if field.is_list => related_count_filter(field, value),
+        (FilterOperation::Equals, Value::Null) if !field.is_list => Ok(field.one_relation_is_null()),
+        (FilterOperation::Equals, _) if !field.is_list => Ok(Filter::from(RelationFilter {
+            field: Arc::clone(&field),
+            nested_filter: Box::new(nested()?),
+            condition: RelationCondition::ToOneRelatedNode,
+        })),
+        _ => Err(CoreError::QueryValidationError(format!(
+            "Invalid filter on relation field `{}`",
+            field.name
+        ))),
+    }
+}
+
+/// Conditions on the number of related nodes matching the optional `where`
+/// input, e.g. `posts_count: { gt: 5, where: { published: true } }`. All given
+/// conditions must hold.
+fn related_count_filter(field: RelationFieldRef, value: &Value) -> CoreResult<Filter> {
+    let obj = match value {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Filter `{}_count` expects a count filter object",
+                field.name
+            )))
+        }
+    };
+
+    let nested = match obj.get("where") {
+        Some(value) => extract_filter(field.related_model(), value)?,
+        None => Filter::and(vec![]),
+    };
+
+    let mut filters = obj
+        .iter()
+        .filter(|(key, _)| key.as_str() != "where")
+        .map(|(key, value)| {
+            let condition: fn(usize) -> CountCondition = match key.as_str() {
+                "equals" => CountCondition::Equals,
+                "not" => CountCondition::NotEquals,
+                "lt" => CountCondition::LessThan,
+                "lte" => CountCondition::LessThanOrEquals,
+                "gt" => CountCondition::GreaterThan,
+                "gte" => CountCondition::GreaterThanOrEquals,
+                key => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Invalid condition `{}` in filter `{}_count`",
+                        key, field.name
+                    )))
+                }
+            };
+
+            let count = match value {
+                Value::Int(num) => num.as_i64().filter(|n| *n >= 0).map(|n| n as usize),
+                _ => None,
+            };
+
+            let count = count.ok_or_else(|| {
+                CoreError::QueryValidationError(format!(
+                    "Condition `{}` in filter `{}_count` expects a non-negative Int",
+                    key, field.name
+                ))
+            })?;
+
+            Ok(field.related_count(nested.clone(), condition(count)))
+        })
+        .collect::<CoreResult<Vec<Filter>>>()?;
+
+    match filters.len() {
+        0 => Err(CoreError::QueryValidationError(format!(
+            "Filter `{}_count` requires a condition",
+            field.name
+        ))),
+        1 => Ok(filters.pop().unwrap()),
+        _ => Ok(Filter::and(filters)),
+    }
+}
+
+/// `AND`, `OR`, `NOT` and `_in` filters accept either a single value or a list of values.
+fn as_list(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::List(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+/// The full-text search query of the field, if the filter requires it to
+/// match. Searches nested in `OR` or `NOT` don't count.
+pub(crate) fn search_query(filter: &Filter, field: &ScalarField) -> Option<String> {
+    match filter {
+        Filter::And(filters) => filters.iter().find_map(|filter| search_query(filter, field)),
+        Filter::Scalar(ScalarFilter {
+            field: searched,
+            condition: ScalarCondition::Search(query),
+            ..
+        }) if searched.name == field.name => Some(format!("{}", query)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+    use prisma_models::PrismaValue;
+
+    fn database(db_name: &str) -> TestDatabase {
+        let db = TestDatabase::new(db_name);
+
+        db.execute(
+            "mutation {
+                alice: createUser(data: { id: \"a\", name: \"Alice\", sites: { create: { id: \"s\", name: \"Blog\" } } }) { id }
+                bob: createUser(data: { id: \"b\", name: \"Bob\" }) { id }
+                carol: createUser(data: { id: \"c\", name: \"Carol\" }) { id }
+            }",
+        )
+        .unwrap();
+
+        db
+    }
+
+    fn names(db: &TestDatabase, filter: &str) -> Vec<PrismaValue> {
+        let query = format!("query {{ users(where: {}, orderBy: name_ASC) {{ name }} }}", filter);
+        db.find_all(&query, "name")
+    }
+
+    fn values(names: &[&str]) -> Vec<PrismaValue> {
+        names.iter().map(|name| PrismaValue::from(*name)).collect()
+    }
+
+    #[test]
+    fn scalar_filters() {
+        let db = database("filter_scalar");
+
+        assert_eq!(values(&["Bob"]), names(&db, "{ name: \"Bob\" }"));
+        assert_eq!(values(&["Alice", "Carol"]), names(&db, "{ name_not: \"Bob\" }"));
+        assert_eq!(values(&["Alice", "Carol"]), names(&db, "{ id_in: [\"a\", \"c\"] }"));
+        assert_eq!(values(&["Bob"]), names(&db, "{ id_not_in: [\"a\", \"c\"] }"));
+        assert_eq!(values(&["Bob", "Carol"]), names(&db, "{ name_gt: \"Alice\" }"));
+        assert_eq!(values(&["Alice", "Carol"]), names(&db, "{ name_contains: \"l\" }"));
+        assert_eq!(values(&["Carol"]), names(&db, "{ name_starts_with: \"C\" }"));
+        assert_eq!(values(&["Bob"]), names(&db, "{ name_ends_with: \"b\" }"));
+    }
+
+    #[test]
+    fn pattern_filters_ignore_the_case_of_ascii_letters_by_default() {
+        let db = database("filter_pattern_case");
+        db.execute("mutation { createUser(data: { id: \"e\", name: \"Émile\" }) { id } }")
+            .unwrap();
+
+        assert_eq!(values(&["Alice"]), names(&db, "{ name_contains: \"ALI\" }"));
+        assert_eq!(values(&["Carol"]), names(&db, "{ name_starts_with: \"c\" }"));
+        assert_eq!(values(&["Bob"]), names(&db, "{ name_ends_with: \"OB\" }"));
+        assert_eq!(values(&["Bob", "Carol"]), names(&db, "{ name_not_contains: \"I\" }"));
+
+        // Other letters are compared exactly, unless the filter is insensitive:
+        assert_eq!(values(&[]), names(&db, "{ name_starts_with: \"é\" }"));
+        assert_eq!(
+            values(&["Émile"]),
+            names(&db, "{ name_starts_with_insensitive: \"é\" }")
+        );
+    }
+
+    #[test]
+    fn sensitive_pattern_filters_match_the_case_of_all_letters() {
+        let db = database("filter_pattern_sensitive");
+        db.execute("mutation { createUser(data: { id: \"d\", name: \"a*li?[ce]\" }) { id } }")
+            .unwrap();
+
+        assert_eq!(values(&["Alice"]), names(&db, "{ name_contains_sensitive: \"Ali\" }"));
+        assert_eq!(values(&[]), names(&db, "{ name_contains_sensitive: \"ALI\" }"));
+        assert_eq!(values(&["Carol"]), names(&db, "{ name_starts_with_sensitive: \"C\" }"));
+        assert_eq!(values(&[]), names(&db, "{ name_ends_with_sensitive: \"B\" }"));
+        assert_eq!(
+            values(&["Alice", "Bob", "a*li?[ce]"]),
+            names(&db, "{ name_not_starts_with_sensitive: \"C\" }")
+        );
+
+        // Wildcards of values match literally, those of `like` patterns don't:
+        assert_eq!(
+            values(&["a*li?[ce]"]),
+            names(&db, "{ name_contains_sensitive: \"*li?[\" }")
+        );
+        assert_eq!(values(&["Alice"]), names(&db, "{ name_like_sensitive: \"A_i%\" }"));
+    }
+
+    #[test]
+    fn json_string_contains_matches_wildcards_of_the_value_literally() {
+        let db = TestDatabase::new("filter_json_string_contains");
+        db.execute(
+            "mutation {
+                a: createProfile(data: { id: \"a\", settings: \"{\\\"note\\\": \\\"50% off_Sale*\\\"}\" }) { id }
+                b: createProfile(data: { id: \"b\", settings: \"{\\\"note\\\": \\\"500 offXSale!\\\"}\" }) { id }
+            }",
+        )
+        .unwrap();
+
+        let ids = |condition: &str| {
+            let filter = format!("{{ settings_path: {{ path: \"$.note\", {} }} }}", condition);
+            let query = format!("query {{ profiles(where: {}, orderBy: id_ASC) {{ id }} }}", filter);
+            db.find_all(&query, "id")
+        };
+
+        // `%`, `_` and `*` would match `b` as wildcards of `LIKE` or `GLOB`:
+        for key in &[
+            "string_contains",
+            "string_contains_insensitive",
+            "string_contains_sensitive",
+        ] {
+            assert_eq!(values(&["a"]), ids(&format!("{}: \"0% off_Sale\"", key)));
+            assert_eq!(values(&["a"]), ids(&format!("{}: \"Sale*\"", key)));
+            assert_eq!(values(&[]), ids(&format!("{}: \"0%o\"", key)));
+        }
+
+        assert_eq!(values(&["a"]), ids("string_contains_insensitive: \"OFF_SALE*\""));
+        assert_eq!(values(&[]), ids("string_contains_sensitive: \"OFF_SALE*\""));
+    }
+
+    #[test]
+    fn all_keys_of_a_where_object_must_match() {
+        let db = database("filter_keys");
+
+        assert_eq!(
+            values(&["Carol"]),
+            names(&db, "{ name_contains: \"l\", name_starts_with: \"C\" }")
+        );
+    }
+
+    #[test]
+    fn logical_filters() {
+        let db = database("filter_logical");
+
+        assert_eq!(
+            values(&["Alice", "Bob"]),
+            names(&db, "{ OR: [{ name: \"Alice\" }, { name: \"Bob\" }] }")
+        );
+        assert_eq!(
+            values(&["Carol"]),
+            names(&db, "{ AND: [{ name_contains: \"l\" }, { NOT: { name: \"Alice\" } }] }")
+        );
+        assert_eq!(
+            values(&["Bob"]),
+            names(&db, "{ NOT: [{ name: \"Alice\" }, { name: \"Carol\" }] }")
+        );
+    }
+
+    #[test]
+    fn to_one_relation_filters() {
+        let db = database("filter_relation");
+
+        assert_eq!(values(&["Alice"]), names(&db, "{ sites: { name: \"Blog\" } }"));
+        assert_eq!(values(&["Bob", "Carol"]), names(&db, "{ sites: null }"));
+    }
+
+    #[test]
+    fn related_count_filters() {
+        let db = TestDatabase::new("filter_related_count");
+        db.execute(
+            "mutation {
+                a: createProduct(data: { id: \"a\", name: \"Apple\", price: 1.0, reviews: { create: [
+                    { id: \"r1\", author: \"Ann\", rating: 5 },
+                    { id: \"r2\", author: \"Ben\", rating: 5 },
+                    { id: \"r3\", author: \"Cid\", rating: 3 }
+                ] } }) { id }
+                b: createProduct(data: { id: \"b\", name: \"Banana\", price: 2.0 }) { id }
+                c: createProduct(data: { id: \"c\", name: \"Pear\", price: 3.0, reviews: { create: [
+                    { id: \"r4\", author: \"Ann\", rating: 4 },
+                    { id: \"r5\", author: \"Ben\", rating: 4 }
+                ] } }) { id }
+            }",
+        )
+        .unwrap();
+
+        let ids = |filter: &str| {
+            let query = format!("query {{ products(where: {}, orderBy: id_ASC) {{ id }} }}", filter);
+            db.find_all(&query, "id")
+        };
+
+        assert_eq!(values(&["a"]), ids("{ reviews_count: { gt: 2 } }"));
+        assert_eq!(values(&["a", "b"]), ids("{ reviews_count: { not: 2 } }"));
+        assert_eq!(values(&["a", "c"]), ids("{ reviews_count: { gte: 1, lte: 3 } }"));
+
+        // Nodes without related nodes count zero:
+        assert_eq!(values(&["b"]), ids("{ reviews_count: { equals: 0 } }"));
+        assert_eq!(
+            values(&["b", "c"]),
+            ids("{ reviews_count: { lt: 1, where: { rating_gt: 4 } } }")
+        );
+
+        assert_eq!(
+            values(&["a"]),
+            ids("{ reviews_count: { equals: 2, where: { rating: 5 } } }")
+        );
+        assert_eq!(
+            values(&["b", "c"]),
+            ids("{ OR: [{ reviews_count: { equals: 0 } }, { name: \"Pear\" }] }")
+        );
+    }
+
+    #[test]
+    fn invalid_related_count_filters_are_rejected() {
+        let db = TestDatabase::new("filter_related_count_invalid");
+
+        let is_rejected = |model: &str, filter: &str| {
+            let query = format!("query {{ {}(where: {}) {{ id }} }}", model, filter);
+            db.execute(&query).is_err()
+        };
+
+        assert!(is_rejected("products", "{ reviews_count: {} }"));
+        assert!(is_rejected("products", "{ reviews_count: { gt: -1 } }"));
+        assert!(is_rejected("products", "{ reviews_count: { between: 1 } }"));
+        assert!(is_rejected("products", "{ name_count: { gt: 1 } }"));
+        assert!(is_rejected("users", "{ sites_count: { gt: 1 } }"));
+    }
+
+    #[test]
+    fn search_filters_match_the_indexed_words_of_searchable_models() {
+        let db = TestDatabase::new("filter_search");
+        db.execute(
+            "mutation {
+                a: createArticle(data: { id: \"a\", title: \"Alice in Wonderland\" }) { id }
+                b: createArticle(data: { id: \"b\", title: \"Through the Looking-Glass\", body: \"Alice again\" }) { id }
+            }",
+        )
+        .unwrap();
+
+        let ids = |filter: &str| {
+            let query = format!("query {{ articles(where: {}, orderBy: id_ASC) {{ id }} }}", filter);
+            db.find_all(&query, "id")
+        };
+
+        assert_eq!(values(&["a"]), ids("{ title_search: \"alice\" }"));
+        assert_eq!(values(&["b"]), ids("{ body_search: \"alice\" }"));
+
+        // Writes keep the index in sync:
+        db.execute("mutation { updateArticle(where: { id: \"a\" }, data: { title: \"Wonderland\" }) { id } }")
+            .unwrap();
+        db.execute("mutation { deleteArticle(where: { id: \"b\" }) { id } }")
+            .unwrap();
+
+        assert_eq!(values(&[]), ids("{ title_search: \"alice\" }"));
+        assert_eq!(values(&["a"]), ids("{ title_search: \"wonderland\" }"));
+        assert_eq!(values(&[]), ids("{ body_search: \"alice\" }"));
+
+        let query = "query { users(where: { name_search: \"alice\" }) { id } }";
+        assert!(db.execute(query).is_err());
+    }
+
+    #[test]
+    fn single_record_queries_take_a_unique_where() {
+        let db = database("filter_single");
+
+        let name = db.find("query { user(where: { id: \"b\" }) { name } }", "name");
+
+        assert_eq!(Some(PrismaValue::from("Bob")), name);
+        assert_eq!(None, db.find("query { user(where: { id: \"x\" }) { name } }", "name"));
+    }
+
+    #[test]
+    fn unknown_filters_are_rejected() {
+        let db = database("filter_unknown");
+
+        let is_rejected = |filter: &str| {
+            let query = format!("query {{ users(where: {}) {{ name }} }}", filter);
+            db.execute(&query).is_err()
+        };
+
+        assert!(is_rejected("{ nickname: \"B\" }"));
+        assert!(is_rejected("{ name_between: \"B\" }"));
+    }
+
+    #[test]
+    fn negative_ids_are_rejected() {
+        let db = database("filter_negative_ids");
+
+        assert!(db.execute("query { users(where: { id: -1 }) { name } }").is_err());
+        assert!(db.execute("query { users(after: -1) { name } }").is_err());
+        assert!(db.execute("query { users(before: -1) { name } }").is_err());
+    }
+}
@@ -1,12 +1,23 @@
 #![deny(warnings)]
 
+mod document;
 mod error;
+mod filter;
+mod mutation_ast;
+mod mutation_executor;
+mod order_by;
 mod query_ast;
 mod query_executor;
 
 mod builders;
 
+#[cfg(test)]
+mod test_utils;
+
+pub use document::{resolve_operation, OperationKind, ResolvedOperation};
 pub use error::*;
+pub use mutation_ast::*;
+pub use mutation_executor::*;
 pub use query_ast::*;
 pub use query_executor::*;
 
@@ -0,0 +1,947 @@
+//! Prisma mutation AST module
+
+use crate::{filter, query_ast, CoreError, CoreResult, PrismaQuery, RootQueryBuilder};
+use connector::{
+    filter::{Filter, NodeSelector},
+    mutaction::*,
+};
+use graphql_parser::{self as gql, query::*};
+use inflector::Inflector;
+use prisma_models::{Field as ModelField, *};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct PrismaMutation {
+    pub name: String,
+    pub model: ModelRef,
+    pub mutaction: TopLevelDatabaseMutaction,
+    pub selection: MutationSelection,
+}
+
+/// Describes what is read back after a mutation has been executed.
+#[derive(Debug, Clone)]
+pub enum MutationSelection {
+    /// The mutated record, read with the given selection
+    Record {
+        selected_fields: SelectedFields,
+        nested: Vec<PrismaQuery>,
+        typename: Option<String>,
+    },
+
+    /// The number of affected records, for `createMany`, `updateMany` and `deleteMany`,
+    /// and whether `__typename` of the payload is selected
+    Count { typename: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MutationType {
+    Create,
+    Update,
+    Upsert,
+    Delete,
+    CreateMany,
+    UpdateMany,
+    DeleteMany,
+}
+
+impl MutationType {
+    /// Infers the mutation type from the field name, e.g. `createUser` or `deleteManyUsers`.
+    fn infer_root(model: &ModelRef, field: &gql::query::Field) -> Option<Self> {
+        let name = &model.name;
+        let plural = model.name.to_plural();
+
+        match field.name.as_str() {
+            n if n == format!("create{}", name) => Some(MutationType::Create),
+            n if n == format!("update{}", name) => Some(MutationType::Update),
+            n if n == format!("upsert{}", name) => Some(MutationType::Upsert),
+            n if n == format!("delete{}", name) => Some(MutationType::Delete),
+            n if n == format!("createMany{}", plural) => Some(MutationType::CreateMany),
+            n if n == format!("updateMany{}", plural) => Some(MutationType::UpdateMany),
+            n if n == format!("deleteMany{}", plural) => Some(MutationType::DeleteMany),
+            _ => None,
+        }
+    }
+
+    /// All arguments the mutation accepts.
+    fn arguments(self) -> &'static [&'static str] {
+        match self {
+            MutationType::Create => &["data"],
+            MutationType::Update => &["data", "where"],
+            MutationType::Upsert => &["where", "create", "update"],
+            MutationType::Delete => &["where"],
+            MutationType::CreateMany => &["data", "skipDuplicates"],
+            MutationType::UpdateMany => &["data", "where"],
+            MutationType::DeleteMany => &["where"],
+        }
+    }
+}
+
+/// Field name suffixes of the arithmetic update operations on Int and Float
+/// fields, e.g. `views_increment: 1`.
+const OPERATION_SUFFIXES: &[&str] = &["_increment", "_decrement", "_multiply", "_divide"];
+
+/// Values extracted from a `data` input object
+#[derive(Default)]
+struct DataInput {
+    non_list_args: PrismaArgs,
+    list_args: Vec<(String, ScalarListOperation)>,
+    nested_mutactions: NestedMutactions,
+}
+
+struct MutationBuilder<'a> {
+    schema: SchemaRef,
+    field: &'a gql::query::Field,
+    model: ModelRef,
+    mutation_type: MutationType,
+}
+
+impl<'a> MutationBuilder<'a> {
+    /// Finds the model and infers the mutation type for the given GraphQL field.
+    fn new(schema: SchemaRef, field: &'a gql::query::Field) -> CoreResult<Self> {
+        let inferred = schema
+            .models()
+            .iter()
+            .filter_map(|model| MutationType::infer_root(model, field).map(|mt| (Arc::clone(model), mt)))
+            .nth(0);
+
+        match inferred {
+            Some((model, mutation_type)) => Ok(Self {
+                schema,
+                field,
+                model,
+                mutation_type,
+            }),
+            None => Err(CoreError::QueryValidationError(format!(
+                "Model not found for mutation {}",
+                field.alias.as_ref().unwrap_or(&field.name)
+            ))),
+        }
+    }
+
+    fn build(self) -> CoreResult<PrismaMutation> {
+        self.validate_arguments()?;
+
+        let model = Arc::clone(&self.model);
+
+        let mutaction = match self.mutation_type {
+            MutationType::Create => TopLevelDatabaseMutaction::CreateNode(self.create_node("data")?),
+            MutationType::Update => TopLevelDatabaseMutaction::UpdateNode(self.update_node("data")?),
+            MutationType::Upsert => TopLevelDatabaseMutaction::UpsertNode(UpsertNode {
+                where_: query_ast::extract_node_selector(Arc::clone(&model), self.required_argument("where")?)?,
+                create: self.create_node("create")?,
+                update: self.update_node("update")?,
+            }),
+            MutationType::Delete => TopLevelDatabaseMutaction::DeleteNode(DeleteNode {
+                where_: query_ast::extract_node_selector(Arc::clone(&model), self.required_argument("where")?)?,
+            }),
+            MutationType::CreateMany => TopLevelDatabaseMutaction::CreateNodes(self.create_nodes()?),
+            MutationType::UpdateMany => {
+                let mut data = self.extract_data_argument("data", false)?;
+
+                data.non_list_args
+                    .update_datetimes(Arc::clone(&model), !data.list_args.is_empty());
+
+                TopLevelDatabaseMutaction::UpdateNodes(UpdateNodes {
+                    model: Arc::clone(&model),
+                    filter: self.extract_filter()?,
+                    non_list_args: data.non_list_args,
+                    list_args: data.list_args,
+                })
+            }
+            MutationType::DeleteMany => TopLevelDatabaseMutaction::DeleteNodes(DeleteNodes {
+                model: Arc::clone(&model),
+                filter: self.extract_filter()?,
+            }),
+        };
+
+        let selection = match self.mutation_type {
+            MutationType::CreateMany | MutationType::UpdateMany | MutationType::DeleteMany => self.count_selection()?,
+            _ => {
+                let (selected_fields, nested, typename) =
+                    query_ast::build_selection(Arc::clone(&self.schema), self.field, Arc::clone(&model))?;
+
+                MutationSelection::Record {
+                    selected_fields,
+                    nested,
+                    typename,
+                }
+            }
+        };
+
+        Ok(PrismaMutation {
+            name: self.field.alias.as_ref().unwrap_or(&self.field.name).clone(),
+            model,
+            mutaction,
+            selection,
+        })
+    }
+
+    fn create_node(&self, argument: &str) -> CoreResult<CreateNode> {
+        let mut data = self.extract_data_argument(argument, true)?;
+        data.non_list_args.add_datetimes(Arc::clone(&self.model));
+
+        Ok(CreateNode {
+            model: Arc::clone(&self.model),
+            non_list_args: data.non_list_args,
+            list_args: list_values(data.list_args),
+            nested_mutactions: data.nested_mutactions,
+        })
+    }
+
+    /// Batch creates take a list of records, without nested mutations or
+    /// scalar lists. The timestamps are filled in by the connector.
+    fn create_nodes(&self) -> CoreResult<CreateNodes> {
+        let args = as_list(self.required_argument("data")?)
+            .into_iter()
+            .map(|value| {
+                let data = extract_data(Arc::clone(&self.model), value, true)?;
+
+                if !data.list_args.is_empty() || !is_empty(&data.nested_mutactions) {
+                    return Err(CoreError::QueryValidationError(
+                        "Nested mutations and scalar lists are not allowed in batch creates".into(),
+                    ));
+                }
+
+                Ok(data.non_list_args)
+            })
+            .collect::<CoreResult<Vec<PrismaArgs>>>()?;
+
+        let skip_duplicates = match self.argument("skipDuplicates") {
+            Some(Value::Boolean(skip)) => *skip,
+            Some(Value::Null) | None => false,
+            Some(_) => {
+                return Err(CoreError::QueryValidationError(format!(
+                    "Expected a boolean for argument `skipDuplicates` on mutation `{}`",
+                    self.field.name
+                )))
+            }
+        };
+
+        Ok(CreateNodes {
+            model: Arc::clone(&self.model),
+            args,
+            skip_duplicates,
+        })
+    }
+
+    fn update_node(&self, argument: &str) -> CoreResult<UpdateNode> {
+        let where_ = query_ast::extract_node_selector(Arc::clone(&self.model), self.required_argument("where")?)?;
+        let mut data = self.extract_data_argument(argument, false)?;
+
+        data.non_list_args
+            .update_datetimes(Arc::clone(&self.model), !data.list_args.is_empty());
+
+        Ok(UpdateNode {
+            where_,
+            non_list_args: data.non_list_args,
+            list_args: data.list_args,
+            nested_mutactions: data.nested_mutactions,
+        })
+    }
+
+    fn extract_data_argument(&self, argument: &str, is_create: bool) -> CoreResult<DataInput> {
+        let data = extract_data(Arc::clone(&self.model), self.required_argument(argument)?, is_create)?;
+
+        match self.mutation_type {
+            MutationType::UpdateMany if !is_empty(&data.nested_mutactions) => Err(CoreError::QueryValidationError(
+                "Nested mutations are not allowed in batch updates".into(),
+            )),
+            _ => Ok(data),
+        }
+    }
+
+    fn extract_filter(&self) -> CoreResult<Filter> {
+        match self.argument("where") {
+            Some(value) => filter::extract_filter(Arc::clone(&self.model), value),
+            None => Ok(Filter::from(true)),
+        }
+    }
+
+    /// Batch mutations return a `BatchPayload`, of which only `count` can be selected.
+    fn count_selection(&self) -> CoreResult<MutationSelection> {
+        self.field.selection_set.items.iter().try_for_each(|item| match item {
+            Selection::Field(f) if f.name == "count" || f.name == query_ast::TYPENAME_FIELD => Ok(()),
+            Selection::Field(f) => Err(CoreError::QueryValidationError(format!(
+                "Selected field {} not found on type BatchPayload",
+                f.name
+            ))),
+            _ => Err(CoreError::QueryValidationError(
+                "Only fields can be selected on type BatchPayload".into(),
+            )),
+        })?;
+
+        let typename = self.field.selection_set.items.iter().any(|item| match item {
+            Selection::Field(f) => f.name == query_ast::TYPENAME_FIELD,
+            _ => false,
+        });
+
+        Ok(MutationSelection::Count { typename })
+    }
+
+    fn validate_arguments(&self) -> CoreResult<()> {
+        let allowed = self.mutation_type.arguments();
+
+        match self
+            .field
+            .arguments
+            .iter()
+            .find(|(name, _)| !allowed.contains(&name.as_str()))
+        {
+            Some((name, _)) => Err(CoreError::QueryValidationError(format!(
+                "Unknown argument `{}` on mutation `{}`",
+                name, self.field.name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn argument(&self, name: &str) -> Option<&'a Value> {
+        self.field
+            .arguments
+            .iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value)
+    }
+
+    fn required_argument(&self, name: &str) -> CoreResult<&'a Value> {
+        self.argument(name).ok_or_else(|| {
+            CoreError::QueryValidationError(format!(
+                "Required argument `{}` missing on mutation `{}`",
+                name, self.field.name
+            ))
+        })
+    }
+}
+
+/// Extracts scalar, scalar list and nested relation arguments from a `data` input object.
+fn extract_data(model: ModelRef, value: &Value, is_create: bool) -> CoreResult<DataInput> {
+    let obj = match value {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Expected an input object for model `{}`",
+                model.name
+            )))
+        }
+    };
+
+    let mut data = DataInput::default();
+
+    for (name, value) in obj.iter() {
+        match model.fields().find_from_all(name) {
+            Ok(ModelField::Scalar(field)) if field.is_list => {
+                let operation = match value {
+                    Value::Object(list_obj) if list_obj.len() == 1 => {
+                        let (operation, value) = list_obj.iter().next().unwrap();
+                        list_operation(&field, operation, value, is_create)?
+                    }
+                    _ => None,
+                };
+
+                match operation {
+                    Some(operation) => data.list_args.push((name.clone(), operation)),
+                    None if is_create => {
+                        return Err(CoreError::QueryValidationError(format!(
+                            "Expected `{{ set: [...] }}` for list field `{}`",
+                            name
+                        )))
+                    }
+                    None => {
+                        return Err(CoreError::QueryValidationError(format!(
+                            "Expected one of `set`, `push`, `unshift`, `removeAt` or `removeValue` for list field `{}`",
+                            name
+                        )))
+                    }
+                }
+            }
+            Ok(ModelField::Scalar(field)) => {
+                data.non_list_args
+                    .insert(name.as_str(), query_ast::coerce_value(&field, value)?);
+            }
+            Ok(ModelField::Relation(field)) => {
+                extract_nested(&mut data.nested_mutactions, Arc::clone(&field), value, is_create)?;
+            }
+            Err(_) => match split_operation(&model, name) {
+                Some((field, suffix)) if !is_create => {
+                    if data.non_list_args.operations.contains_key(&field.name) {
+                        return Err(CoreError::QueryValidationError(format!(
+                            "Only one operation is allowed on field `{}`",
+                            field.name
+                        )));
+                    }
+
+                    let operand = query_ast::coerce_value(&field, value)?;
+
+                    data.non_list_args
+                        .insert_operation(field.name.as_str(), update_operation(suffix, operand));
+                }
+                _ => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Unknown field `{}` on model `{}`",
+                        name, model.name
+                    )))
+                }
+            },
+        }
+    }
+
+    if let Some(name) = data
+        .non_list_args
+        .operations
+        .keys()
+        .find(|name| data.non_list_args.has_arg_for(name))
+    {
+        return Err(CoreError::QueryValidationError(format!(
+            "Field `{}` can't be set and changed by an operation at once",
+            name
+        )));
+    }
+
+    Ok(data)
+}
+
+/// Converts one operation of a list field input, e.g. `{ push: [...] }`.
+/// Creates only allow to `set` the values.
+fn list_operation(
+    field: &ScalarField,
+    operation: &str,
+    value: &Value,
+    is_create: bool,
+) -> CoreResult<Option<ScalarListOperation>> {
+    let values = || -> CoreResult<PrismaListValue> {
+        match query_ast::coerce_value(field, value)? {
+            PrismaValue::List(values) => Ok(values),
+            PrismaValue::Null => Ok(vec![]),
+            value => Ok(vec![value]),
+        }
+    };
+
+    let operation = match operation {
+        "set" => ScalarListOperation::Set(values()?),
+        _ if is_create => return Ok(None),
+        "push" => ScalarListOperation::Push(values()?),
+        "unshift" => ScalarListOperation::Unshift(values()?),
+        "removeAt" => match value {
+            Value::Int(index) => match index.as_i64() {
+                Some(index) if index >= 0 => ScalarListOperation::RemoveAt(index as usize),
+                _ => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Expected a non-negative index for `removeAt` on list field `{}`",
+                        field.name
+                    )))
+                }
+            },
+            _ => {
+                return Err(CoreError::QueryValidationError(format!(
+                    "Expected an index for `removeAt` on list field `{}`",
+                    field.name
+                )))
+            }
+        },
+        "removeValue" => match value {
+            Value::List(_) => {
+                return Err(CoreError::QueryValidationError(format!(
+                    "Expected a single value for `removeValue` on list field `{}`",
+                    field.name
+                )))
+            }
+            value => ScalarListOperation::RemoveValue(query_ast::coerce_value(field, value)?),
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(operation))
+}
+
+/// The values of the list fields of a create, which only allows to `set` them.
+fn list_values(list_args: Vec<(String, ScalarListOperation)>) -> Vec<(String, PrismaListValue)> {
+    list_args
+        .into_iter()
+        .map(|(name, operation)| match operation {
+            ScalarListOperation::Set(values) => (name, values),
+            _ => unreachable!("Creates only set list values"),
+        })
+        .collect()
+}
+
+/// Splits e.g. `views_increment` into the Int or Float field and the suffix of
+/// the operation.
+fn split_operation(model: &ModelRef, name: &str) -> Option<(Arc<ScalarField>, &'static str)> {
+    OPERATION_SUFFIXES.iter().find_map(|suffix| {
+        if !name.ends_with(suffix) {
+            return None;
+        }
+
+        match model.fields().find_from_scalar(&name[..name.len() - suffix.len()]) {
+            Ok(ref field) if field.is_list => None,
+            Ok(field) => match field.type_identifier {
+                TypeIdentifier::Int | TypeIdentifier::Float => Some((field, *suffix)),
+                _ => None,
+            },
+            Err(_) => None,
+        }
+    })
+}
+
+fn update_operation(suffix: &str, operand: PrismaValue) -> UpdateOperation {
+    match suffix {
+        "_increment" => UpdateOperation::Increment(operand),
+        "_decrement" => UpdateOperation::Decrement(operand),
+        "_multiply" => UpdateOperation::Multiply(operand),
+        "_divide" => UpdateOperation::Divide(operand),
+        _ => unreachable!("Unknown operation suffix {}", suffix),
+    }
+}
+
+/// Extracts nested mutactions for a relation field, e.g. `posts: { create: [...], connect: [...] }`.
+/// Nested updates, upserts and deletes of a list relation select the related
+/// records with a `where`, the ones of a to-one relation act on the related record.
+fn extract_nested(
+    nested: &mut NestedMutactions,
+    field: RelationFieldRef,
+    value: &Value,
+    top_is_create: bool,
+) -> CoreResult<()> {
+    let obj = match value {
+        Value::Object(obj) => obj,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Expected an input object for relation field `{}`",
+                field.name
+            )))
+        }
+    };
+
+    let related_model = field.related_model();
+
+    for (operation, value) in obj.iter() {
+        match operation.as_str() {
+            "create" => {
+                for value in as_list(value) {
+                    nested.creates.push(nested_create(&field, value, top_is_create)?);
+                }
+            }
+            "connect" => {
+                for value in as_list(value) {
+                    nested.connects.push(NestedConnect {
+                        relation_field: Arc::clone(&field),
+                        where_: query_ast::extract_node_selector(Arc::clone(&related_model), value)?,
+                        top_is_create,
+                    });
+                }
+            }
+            "disconnect" if !top_is_create => match value {
+                Value::Boolean(true) if !field.is_list => nested.disconnects.push(NestedDisconnect {
+                    relation_field: Arc::clone(&field),
+                    where_: None,
+                }),
+                Value::Boolean(false) if !field.is_list => (),
+                _ if field.is_list => {
+                    for value in as_list(value) {
+                        nested.disconnects.push(NestedDisconnect {
+                            relation_field: Arc::clone(&field),
+                            where_: Some(query_ast::extract_node_selector(Arc::clone(&related_model), value)?),
+                        });
+                    }
+                }
+                _ => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Expected a boolean for `disconnect` on relation field `{}`",
+                        field.name
+                    )))
+                }
+            },
+            "set" if !top_is_create && field.is_list => {
+                let wheres = as_list(value)
+                    .into_iter()
+                    .map(|value| query_ast::extract_node_selector(Arc::clone(&related_model), value))
+                    .collect::<CoreResult<Vec<_>>>()?;
+
+                nested.sets.push(NestedSet {
+                    relation_field: Arc::clone(&field),
+                    wheres,
+                });
+            }
+            "update" if !top_is_create => {
+                for value in as_list(value) {
+                    let (where_, data) = match field.is_list {
+                        true => (
+                            Some(nested_where(&field, operation, value)?),
+                            required_nested_argument(&field, operation, value, "data")?,
+                        ),
+                        false => (None, value),
+                    };
+
+                    nested.updates.push(nested_update(&field, where_, data)?);
+                }
+            }
+            "upsert" if !top_is_create => {
+                for value in as_list(value) {
+                    let where_ = match field.is_list {
+                        true => Some(nested_where(&field, operation, value)?),
+                        false => None,
+                    };
+
+                    let create = required_nested_argument(&field, operation, value, "create")?;
+                    let update = required_nested_argument(&field, operation, value, "update")?;
+
+                    nested.upserts.push(NestedUpsertNode {
+                        relation_field: Arc::clone(&field),
+                        where_: where_.clone(),
+                        create: nested_create(&field, create, false)?,
+                        update: nested_update(&field, where_, update)?,
+                    });
+                }
+            }
+            "delete" if !top_is_create => match value {
+                Value::Boolean(true) if !field.is_list => nested.deletes.push(NestedDeleteNode {
+                    relation_field: Arc::clone(&field),
+                    where_: None,
+                }),
+                Value::Boolean(false) if !field.is_list => (),
+                _ if field.is_list => {
+                    for value in as_list(value) {
+                        nested.deletes.push(NestedDeleteNode {
+                            relation_field: Arc::clone(&field),
+                            where_: Some(query_ast::extract_node_selector(Arc::clone(&related_model), value)?),
+                        });
+                    }
+                }
+                _ => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Expected a boolean for `delete` on relation field `{}`",
+                        field.name
+                    )))
+                }
+            },
+            "updateMany" if !top_is_create && field.is_list => {
+                for value in as_list(value) {
+                    let filter = match nested_argument(&field, operation, value, "where")? {
+                        Some(where_) => Some(filter::extract_filter(Arc::clone(&related_model), where_)?),
+                        None => None,
+                    };
+
+                    let data = required_nested_argument(&field, operation, value, "data")?;
+                    let mut data = extract_data(Arc::clone(&related_model), data, false)?;
+
+                    if !is_empty(&data.nested_mutactions) {
+                        return Err(CoreError::QueryValidationError(
+                            "Nested mutations are not allowed in batch updates".into(),
+                        ));
+                    }
+
+                    data.non_list_args
+                        .update_datetimes(Arc::clone(&related_model), !data.list_args.is_empty());
+
+                    nested.update_manys.push(NestedUpdateNodes {
+                        relation_field: Arc::clone(&field),
+                        filter,
+                        non_list_args: data.non_list_args,
+                        list_args: data.list_args,
+                    });
+                }
+            }
+            "deleteMany" if !top_is_create && field.is_list => {
+                for value in as_list(value) {
+                    nested.delete_manys.push(NestedDeleteNodes {
+                        relation_field: Arc::clone(&field),
+                        filter: Some(filter::extract_filter(Arc::clone(&related_model), value)?),
+                    });
+                }
+            }
+            _ => {
+                return Err(CoreError::QueryValidationError(format!(
+                    "Unsupported nested mutation `{}` on relation field `{}`",
+                    operation, field.name
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn nested_create(field: &RelationFieldRef, value: &Value, top_is_create: bool) -> CoreResult<NestedCreateNode> {
+    let related_model = field.related_model();
+    let mut data = extract_data(Arc::clone(&related_model), value, true)?;
+    data.non_list_args.add_datetimes(related_model);
+
+    Ok(NestedCreateNode {
+        relation_field: Arc::clone(field),
+        non_list_args: data.non_list_args,
+        list_args: list_values(data.list_args),
+        top_is_create,
+        nested_mutactions: data.nested_mutactions,
+    })
+}
+
+fn nested_update(
+    field: &RelationFieldRef,
+    where_: Option<NodeSelector>,
+    value: &Value,
+) -> CoreResult<NestedUpdateNode> {
+    let related_model = field.related_model();
+    let mut data = extract_data(Arc::clone(&related_model), value, false)?;
+
+    data.non_list_args
+        .update_datetimes(related_model, !data.list_args.is_empty());
+
+    Ok(NestedUpdateNode {
+        relation_field: Arc::clone(field),
+        where_,
+        non_list_args: data.non_list_args,
+        list_args: data.list_args,
+        nested_mutactions: data.nested_mutactions,
+    })
+}
+
+/// The `where` of a nested update or upsert on a list relation.
+fn nested_where(field: &RelationFieldRef, operation: &str, value: &Value) -> CoreResult<NodeSelector> {
+    let where_ = required_nested_argument(field, operation, value, "where")?;
+    query_ast::extract_node_selector(field.related_model(), where_)
+}
+
+/// A field of a nested operation input, e.g. the `data` of `update: { where: {...}, data: {...} }`.
+fn nested_argument<'v>(
+    field: &RelationFieldRef,
+    operation: &str,
+    value: &'v Value,
+    name: &str,
+) -> CoreResult<Option<&'v Value>> {
+    match value {
+        Value::Object(obj) => Ok(obj.get(name)),
+        _ => Err(CoreError::QueryValidationError(format!(
+            "Expected an input object for `{}` on relation field `{}`",
+            operation, field.name
+        ))),
+    }
+}
+
+fn required_nested_argument<'v>(
+    field: &RelationFieldRef,
+    operation: &str,
+    value: &'v Value,
+    name: &str,
+) -> CoreResult<&'v Value> {
+    nested_argument(field, operation, value, name)?.ok_or_else(|| {
+        CoreError::QueryValidationError(format!(
+            "Required field `{}` missing in `{}` on relation field `{}`",
+            name, operation, field.name
+        ))
+    })
+}
+
+/// Nested inputs accept either a single value or a list of values.
+fn as_list(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::List(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+fn is_empty(nested: &NestedMutactions) -> bool {
+    nested.creates.is_empty()
+        && nested.updates.is_empty()
+        && nested.upserts.is_empty()
+        && nested.deletes.is_empty()
+        && nested.connects.is_empty()
+        && nested.disconnects.is_empty()
+        && nested.sets.is_empty()
+        && nested.update_manys.is_empty()
+        && nested.delete_manys.is_empty()
+}
+
+impl RootQueryBuilder {
+    pub(crate) fn build_mutation(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<PrismaMutation>> {
+        query_ast::data_fields(root_fields)
+            .map(|root_field| self.build_mutation_field(root_field))
+            .collect()
+    }
+
+    /// Builds the mutation of a single root field of a resolved `mutation` operation.
+    pub fn build_mutation_field(&self, root_field: &gql::query::Field) -> CoreResult<PrismaMutation> {
+        MutationBuilder::new(Arc::clone(&self.schema), root_field)?.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn nested_of_update(data: &str) -> CoreResult<NestedMutactions> {
+        let query = format!(
+            "mutation {{ updateUser(where: {{ id: \"user\" }}, data: {}) {{ id }} }}",
+            data
+        );
+
+        match mutation(schema("mutation_ast"), &query)?.mutaction {
+            TopLevelDatabaseMutaction::UpdateNode(un) => Ok(un.nested_mutactions),
+            mutaction => panic!("Expected an update, got {:?}", mutaction),
+        }
+    }
+
+    fn assert_name(args: &PrismaArgs, name: &str) {
+        assert_eq!(Some(&PrismaValue::from(name)), args.get_field_value("name"));
+    }
+
+    #[test]
+    fn nested_update_of_a_to_one_relation() {
+        let nested = nested_of_update("{ sites: { update: { name: \"Updated\" } } }").unwrap();
+
+        assert_eq!(1, nested.updates.len());
+        assert!(nested.updates[0].where_.is_none());
+        assert_name(&nested.updates[0].non_list_args, "Updated");
+    }
+
+    #[test]
+    fn nested_upsert_of_a_to_one_relation() {
+        let nested =
+            nested_of_update("{ sites: { upsert: { create: { name: \"Created\" }, update: { name: \"Updated\" } } } }")
+                .unwrap();
+
+        assert_eq!(1, nested.upserts.len());
+
+        let upsert = &nested.upserts[0];
+
+        assert!(upsert.where_.is_none());
+        assert!(!upsert.create.top_is_create);
+        assert_name(&upsert.create.non_list_args, "Created");
+        assert_name(&upsert.update.non_list_args, "Updated");
+    }
+
+    #[test]
+    fn nested_upsert_requires_both_branches() {
+        assert!(nested_of_update("{ sites: { upsert: { create: { name: \"Created\" } } } }").is_err());
+    }
+
+    #[test]
+    fn nested_delete_of_a_to_one_relation() {
+        let nested = nested_of_update("{ sites: { delete: true } }").unwrap();
+
+        assert_eq!(1, nested.deletes.len());
+        assert!(nested.deletes[0].where_.is_none());
+
+        let nested = nested_of_update("{ sites: { delete: false } }").unwrap();
+
+        assert!(nested.deletes.is_empty());
+    }
+
+    #[test]
+    fn batch_operations_are_rejected_on_a_to_one_relation() {
+        assert!(nested_of_update("{ sites: { deleteMany: [{ name: \"Site\" }] } }").is_err());
+        assert!(nested_of_update("{ sites: { updateMany: [{ data: { name: \"Site\" } }] } }").is_err());
+    }
+
+    #[test]
+    fn nested_updates_are_rejected_in_creates() {
+        let query = "mutation { createUser(data: { name: \"User\", sites: { update: { name: \"Site\" } } }) { id } }";
+
+        assert!(mutation(schema("mutation_ast"), query).is_err());
+    }
+
+    #[test]
+    fn nested_mutations_are_rejected_in_batch_updates() {
+        let query = "mutation { updateManyUsers(data: { sites: { delete: true } }) { count } }";
+
+        assert!(mutation(schema("mutation_ast"), query).is_err());
+    }
+
+    #[test]
+    fn arithmetic_operations_on_non_numeric_fields_are_unknown() {
+        let query = "mutation { updateUser(where: { id: \"user\" }, data: { name_increment: 1 }) { id } }";
+
+        assert!(mutation(schema("mutation_ast"), query).is_err());
+    }
+
+    #[test]
+    fn fields_take_one_value_or_operation_in_updates() {
+        let update = |data: &str| {
+            let query = format!(
+                "mutation {{ updateProduct(where: {{ id: \"a\" }}, data: {}) {{ id }} }}",
+                data
+            );
+
+            mutation(schema("mutation_ast"), &query)
+        };
+
+        let args = match update("{ stock_increment: 1, price: 2.0 }").unwrap().mutaction {
+            TopLevelDatabaseMutaction::UpdateNode(un) => un.non_list_args,
+            mutaction => panic!("Expected an update, got {:?}", mutaction),
+        };
+
+        assert_eq!(Some(&PrismaValue::Float(2.0)), args.get_field_value("price"));
+        assert_eq!(
+            Some(&UpdateOperation::Increment(PrismaValue::Int(1))),
+            args.operations.get("stock")
+        );
+
+        assert!(update("{ stock: 1, stock_increment: 1 }").is_err());
+        assert!(update("{ stock_increment: 1, stock_multiply: 2 }").is_err());
+    }
+
+    #[test]
+    fn numeric_operations_are_rejected_in_creates() {
+        let query = "mutation { createProduct(data: { name: \"Apple\", price: 1.0, stock_increment: 1 }) { id } }";
+
+        assert!(mutation(schema("mutation_ast"), query).is_err());
+    }
+
+    #[test]
+    fn list_fields_take_one_operation_in_updates_and_only_set_in_creates() {
+        let update = |operation: &str| {
+            let query = format!(
+                "mutation {{ updateUser(where: {{ id: \"user\" }}, data: {{ cats: {} }}) {{ id }} }}",
+                operation
+            );
+
+            mutation(schema("mutation_ast"), &query)
+        };
+
+        let list_args = match update("{ unshift: [1, 2] }").unwrap().mutaction {
+            TopLevelDatabaseMutaction::UpdateNode(un) => un.list_args,
+            mutaction => panic!("Expected an update, got {:?}", mutaction),
+        };
+
+        assert_eq!(
+            vec![(
+                String::from("cats"),
+                ScalarListOperation::Unshift(vec![PrismaValue::Int(1), PrismaValue::Int(2)])
+            )],
+            list_args
+        );
+
+        assert!(update("{ push: [1], removeAt: 0 }").is_err());
+        assert!(update("{ removeAt: -1 }").is_err());
+        assert!(update("{ removeValue: [1] }").is_err());
+        assert!(update("{ append: [1] }").is_err());
+
+        let create = "mutation { createUser(data: { name: \"User\", cats: { push: [1] } }) { id } }";
+
+        assert!(mutation(schema("mutation_ast"), create).is_err());
+    }
+
+    #[test]
+    fn batch_creates_take_records_without_relations_and_lists() {
+        let create_many = |arguments: &str| {
+            let query = format!("mutation {{ createManyUsers({}) {{ count }} }}", arguments);
+            mutation(schema("mutation_ast"), &query)
+        };
+
+        match create_many("data: [{ name: \"A\" }, { name: \"B\" }]")
+            .unwrap()
+            .mutaction
+        {
+            TopLevelDatabaseMutaction::CreateNodes(cns) => {
+                assert_eq!(2, cns.args.len());
+                assert_name(&cns.args[1], "B");
+                assert!(!cns.skip_duplicates);
+            }
+            mutaction => panic!("Expected a batch create, got {:?}", mutaction),
+        }
+
+        assert!(create_many("data: { name: \"A\", cats: { set: [1] } }").is_err());
+        assert!(create_many("data: { name: \"A\", sites: { create: { name: \"Site\" } } }").is_err());
+        assert!(create_many("data: { name: \"A\" }, skipDuplicates: 1").is_err());
+    }
+}
@@ -0,0 +1,752 @@
+use crate::{
+    mutation_ast::*, CoreError, CoreResult, PrismaQuery, PrismaQueryResult, QueryExecutor, RecordQuery,
+    SinglePrismaQueryResult,
+};
+use connector::{filter::NodeSelector, mutaction::*, DatabaseMutactionExecutor};
+use prisma_models::{Node, PrismaValue, SingleNode};
+use std::sync::Arc;
+
+pub struct MutationExecutor {
+    pub db_name: String,
+    pub database_executor: Arc<DatabaseMutactionExecutor + Send + Sync + 'static>,
+}
+
+impl MutationExecutor {
+    /// Executes the mutations serially, reading back the selection of each mutation
+    /// before the next one is executed.
+    pub fn execute(
+        &self,
+        mutations: &[PrismaMutation],
+        query_executor: &QueryExecutor,
+    ) -> CoreResult<Vec<PrismaQueryResult>> {
+        let mut results = vec![];
+
+        for mutation in mutations {
+            results.append(&mut self.execute_mutation(mutation, query_executor)?);
+        }
+
+        Ok(results)
+    }
+
+    fn execute_mutation(
+        &self,
+        mutation: &PrismaMutation,
+        query_executor: &QueryExecutor,
+    ) -> CoreResult<Vec<PrismaQueryResult>> {
+        match (&mutation.mutaction, &mutation.selection) {
+            // The selection has to be read before the record is gone.
+            (TopLevelDatabaseMutaction::DeleteNode(dn), MutationSelection::Record { .. }) => {
+                let query = Self::record_query(mutation, dn.where_.clone());
+                let results = query_executor.execute(&[query])?;
+
+                self.execute_toplevel(mutation.mutaction.clone())?;
+
+                Ok(results)
+            }
+            (_, MutationSelection::Record { .. }) => {
+                let result = self.execute_toplevel(mutation.mutaction.clone())?;
+                let id = result.id().clone();
+
+                let selector = NodeSelector::new(mutation.model.fields().id(), id);
+                let query = Self::record_query(mutation, selector);
+
+                query_executor.execute(&[query])
+            }
+            (_, MutationSelection::Count { typename }) => {
+                let result = self.execute_toplevel(mutation.mutaction.clone())?;
+                let node = Node::new(vec![PrismaValue::Int(result.count() as i32)]);
+                let count = SingleNode::new(node, vec![String::from("count")]);
+                let typename = if *typename {
+                    Some(String::from("BatchPayload"))
+                } else {
+                    None
+                };
+
+                Ok(vec![PrismaQueryResult::Single(
+                    SinglePrismaQueryResult::without_selection(mutation.name.clone(), count, typename),
+                )])
+            }
+        }
+    }
+
+    /// Executes the mutaction with all of its nested mutactions in one transaction.
+    fn execute_toplevel(&self, mutaction: TopLevelDatabaseMutaction) -> CoreResult<DatabaseMutactionResult> {
+        let mut results =
+            self.database_executor
+                .execute(self.db_name.clone(), DatabaseMutaction::TopLevel(mutaction), None)?;
+
+        results
+            .pop()
+            .ok_or_else(|| CoreError::QueryValidationError("Mutaction returned no result".into()))
+    }
+
+    fn record_query(mutation: &PrismaMutation, selector: NodeSelector) -> PrismaQuery {
+        match mutation.selection {
+            MutationSelection::Record {
+                ref selected_fields,
+                ref nested,
+                ref typename,
+            } => PrismaQuery::RecordQuery(RecordQuery {
+                name: mutation.name.clone(),
+                selector,
+                selected_fields: selected_fields.clone(),
+                nested: nested.clone(),
+                typename: typename.clone(),
+            }),
+            MutationSelection::Count { .. } => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::*, CoreResult, PrismaQueryResult};
+    use connector::{filter::NodeSelector, mutaction::*};
+    use prisma_models::{GraphqlId, PrismaValue};
+
+    const CREATE_USER: &str = "mutation {
+        createUser(data: { id: \"user\", name: \"User\", sites: { create: { id: \"site\", name: \"Site\" } } }) { id }
+    }";
+
+    fn site_name(db: &TestDatabase, id: &str) -> Option<PrismaValue> {
+        let query = format!("query {{ site(where: {{ id: \"{}\" }}) {{ name }} }}", id);
+        db.find(&query, "name")
+    }
+
+    fn user_name(db: &TestDatabase) -> Option<PrismaValue> {
+        db.find("query { user(where: { id: \"user\" }) { name } }", "name")
+    }
+
+    fn update_sites(db: &TestDatabase, sites: &str) {
+        let query = format!(
+            "mutation {{ updateUser(where: {{ id: \"user\" }}, data: {{ sites: {} }}) {{ id }} }}",
+            sites
+        );
+
+        db.execute(&query).unwrap();
+    }
+
+    #[test]
+    fn nested_create_and_update() {
+        let db = TestDatabase::new("mutation_executor_update");
+        db.execute(CREATE_USER).unwrap();
+
+        assert_eq!(Some(PrismaValue::from("Site")), site_name(&db, "site"));
+
+        update_sites(&db, "{ update: { name: \"Updated\" } }");
+
+        assert_eq!(Some(PrismaValue::from("Updated")), site_name(&db, "site"));
+    }
+
+    #[test]
+    fn nested_upsert_updates_an_existing_and_creates_a_missing_record() {
+        let db = TestDatabase::new("mutation_executor_upsert");
+        db.execute(CREATE_USER).unwrap();
+
+        let upsert = "{ upsert: { create: { id: \"other\", name: \"Other\" }, update: { name: \"Upserted\" } } }";
+
+        update_sites(&db, upsert);
+
+        assert_eq!(Some(PrismaValue::from("Upserted")), site_name(&db, "site"));
+        assert_eq!(None, site_name(&db, "other"));
+
+        update_sites(&db, "{ delete: true }");
+
+        assert_eq!(None, site_name(&db, "site"));
+
+        update_sites(&db, upsert);
+
+        assert_eq!(Some(PrismaValue::from("Other")), site_name(&db, "other"));
+    }
+
+    #[test]
+    fn selection_of_a_deleted_record_is_read_before_the_delete() {
+        let db = TestDatabase::new("mutation_executor_delete");
+        db.execute(CREATE_USER).unwrap();
+
+        let deleted = db.find("mutation { deleteUser(where: { id: \"user\" }) { name } }", "name");
+
+        assert_eq!(Some(PrismaValue::from("User")), deleted);
+        assert_eq!(None, user_name(&db));
+    }
+
+    #[test]
+    fn batch_mutations_return_the_count() {
+        let db = TestDatabase::new("mutation_executor_count");
+        db.execute(CREATE_USER).unwrap();
+
+        let count = db.find(
+            "mutation { updateManyUsers(where: { id: \"user\" }, data: { name: \"Renamed\" }) { count } }",
+            "count",
+        );
+
+        assert_eq!(Some(PrismaValue::Int(1)), count);
+        assert_eq!(Some(PrismaValue::from("Renamed")), user_name(&db));
+    }
+
+    fn user_by_name_id(name: &str, id: &str) -> String {
+        format!("{{ name_id: {{ name: \"{}\", id: \"{}\" }} }}", name, id)
+    }
+
+    #[test]
+    fn records_are_selected_by_compound_unique_constraints() {
+        let db = TestDatabase::new("mutation_executor_compound");
+        db.execute(CREATE_USER).unwrap();
+
+        let find = |name: &str, id: &str| {
+            let query = format!("query {{ user(where: {}) {{ name }} }}", user_by_name_id(name, id));
+            db.find(&query, "name")
+        };
+
+        assert_eq!(Some(PrismaValue::from("User")), find("User", "user"));
+        assert_eq!(None, find("Other", "user"));
+
+        let update = format!(
+            "mutation {{ updateUser(where: {}, data: {{ name: \"Renamed\" }}) {{ id }} }}",
+            user_by_name_id("User", "user")
+        );
+        db.execute(&update).unwrap();
+
+        assert_eq!(Some(PrismaValue::from("Renamed")), user_name(&db));
+        assert!(db.execute(&update).is_err());
+
+        let upsert = format!(
+            "mutation {{ upsertUser(where: {}, create: {{ id: \"other\", name: \"Other\" }}, update: {{ name: \"Upserted\" }}) {{ id }} }}",
+            user_by_name_id("Other", "other")
+        );
+
+        db.execute(&upsert).unwrap();
+        assert_eq!(Some(PrismaValue::from("Other")), find("Other", "other"));
+
+        db.execute(&upsert).unwrap();
+        assert_eq!(Some(PrismaValue::from("Upserted")), find("Upserted", "other"));
+
+        let delete = format!(
+            "mutation {{ deleteUser(where: {}) {{ name }} }}",
+            user_by_name_id("Renamed", "user")
+        );
+
+        assert_eq!(Some(PrismaValue::from("Renamed")), db.find(&delete, "name"));
+        assert_eq!(None, user_name(&db));
+    }
+
+    #[test]
+    fn related_records_are_connected_by_compound_unique_constraints() {
+        let db = TestDatabase::new("mutation_executor_compound_connect");
+        db.execute(
+            "mutation {
+                a: createUser(data: { id: \"a\", name: \"Alice\" }) { id }
+                b: createSite(data: { id: \"s\", name: \"Site\" }) { id }
+            }",
+        )
+        .unwrap();
+
+        let connect = format!(
+            "mutation {{ updateSite(where: {{ id: \"s\" }}, data: {{ user: {{ connect: {} }} }}) {{ id }} }}",
+            user_by_name_id("Alice", "a")
+        );
+        db.execute(&connect).unwrap();
+
+        let sites = db.find_all("query { sites(where: { user: { id: \"a\" } }) { id } }", "id");
+
+        assert_eq!(vec![PrismaValue::from("s")], sites);
+    }
+
+    #[test]
+    fn compound_selectors_need_the_values_of_all_fields() {
+        let db = TestDatabase::new("mutation_executor_compound_invalid");
+        db.execute(CREATE_USER).unwrap();
+
+        let is_rejected = |selector: &str| {
+            let query = format!("query {{ user(where: {}) {{ id }} }}", selector);
+            db.execute(&query).is_err()
+        };
+
+        assert!(is_rejected("{ name_id: { name: \"User\" } }"));
+        assert!(is_rejected("{ name_id: { name: \"User\", site: \"site\" } }"));
+        assert!(is_rejected("{ name_id: { name: \"User\", id: \"user\", other: 1 } }"));
+        assert!(is_rejected("{ name_id: \"User\" }"));
+        assert!(is_rejected("{ id_name: { name: \"User\", id: \"user\" } }"));
+    }
+
+    /// A tree of folders, deleting a folder deletes its children and files.
+    fn folder_database(db_name: &str) -> TestDatabase {
+        let db = TestDatabase::new(db_name);
+
+        db.execute(
+            "mutation {
+                root: createFolder(data: {
+                    id: \"root\",
+                    name: \"Root\",
+                    labels: { set: [1, 2] },
+                    files: { create: { id: \"todo\", name: \"TODO\" } },
+                    children: { create: [
+                        {
+                            id: \"docs\",
+                            name: \"Docs\",
+                            files: { create: { id: \"readme\", name: \"README\" } },
+                            children: { create: { id: \"drafts\", name: \"Drafts\", labels: { set: [3] } } }
+                        },
+                        { id: \"pics\", name: \"Pics\" }
+                    ] }
+                }) { id }
+                other: createFolder(data: { id: \"other\", name: \"Other\", files: { create: { id: \"notes\", name: \"Notes\" } } }) { id }
+            }",
+        )
+        .unwrap();
+
+        db
+    }
+
+    fn ids(db: &TestDatabase, field: &str) -> Vec<PrismaValue> {
+        db.find_all(&format!("query {{ {}(orderBy: id_ASC) {{ id }} }}", field), "id")
+    }
+
+    fn values(ids: &[&str]) -> Vec<PrismaValue> {
+        ids.iter().map(|id| PrismaValue::from(*id)).collect()
+    }
+
+    #[test]
+    fn deletes_cascade_to_related_records_and_their_scalar_lists() {
+        let db = folder_database("mutation_executor_cascade");
+
+        let deleted = db.find("mutation { deleteFolder(where: { id: \"docs\" }) { name } }", "name");
+
+        assert_eq!(Some(PrismaValue::from("Docs")), deleted);
+        assert_eq!(values(&["other", "pics", "root"]), ids(&db, "folders"));
+        assert_eq!(values(&["notes", "todo"]), ids(&db, "files"));
+
+        // The list values of a cascaded record are gone with it.
+        db.execute("mutation { createFolder(data: { id: \"drafts\", name: \"Drafts\" }) { id } }")
+            .unwrap();
+
+        let labels = |id: &str| {
+            db.find(
+                &format!("query {{ folder(where: {{ id: \"{}\" }}) {{ labels }} }}", id),
+                "labels",
+            )
+        };
+
+        assert_eq!(Some(PrismaValue::List(Vec::new())), labels("drafts"));
+        assert_eq!(
+            Some(PrismaValue::List(vec![PrismaValue::Int(1), PrismaValue::Int(2)])),
+            labels("root")
+        );
+    }
+
+    #[test]
+    fn batch_and_nested_deletes_cascade() {
+        let db = folder_database("mutation_executor_cascade_many");
+
+        let count = db.find(
+            "mutation { deleteManyFolders(where: { name: \"Root\" }) { count } }",
+            "count",
+        );
+
+        assert_eq!(Some(PrismaValue::Int(1)), count);
+        assert_eq!(values(&["other"]), ids(&db, "folders"));
+        assert_eq!(values(&["notes"]), ids(&db, "files"));
+
+        let db = folder_database("mutation_executor_cascade_nested");
+
+        db.execute("mutation { updateFolder(where: { id: \"root\" }, data: { children: { delete: { id: \"docs\" } } }) { id } }")
+            .unwrap();
+
+        assert_eq!(values(&["other", "pics", "root"]), ids(&db, "folders"));
+        assert_eq!(values(&["notes", "todo"]), ids(&db, "files"));
+
+        db.execute("mutation { updateFolder(where: { id: \"root\" }, data: { children: { deleteMany: { name: \"Pics\" } } }) { id } }")
+            .unwrap();
+
+        assert_eq!(values(&["other", "root"]), ids(&db, "folders"));
+    }
+
+    #[test]
+    fn cascades_through_cyclic_relations_visit_each_record_once() {
+        let db = folder_database("mutation_executor_cascade_cycle");
+
+        // Root becomes a child of its own grandchild.
+        db.execute("mutation { updateFolder(where: { id: \"root\" }, data: { parent: { connect: { id: \"drafts\" } } }) { id } }")
+            .unwrap();
+        db.execute("mutation { deleteFolder(where: { id: \"docs\" }) { id } }")
+            .unwrap();
+
+        assert_eq!(values(&["other"]), ids(&db, "folders"));
+        assert_eq!(values(&["notes"]), ids(&db, "files"));
+    }
+
+    #[test]
+    fn cascaded_records_are_reported_in_the_results() {
+        let db = folder_database("mutation_executor_cascade_results");
+        let folder = db.schema.find_model("Folder").unwrap();
+
+        let mutaction = TopLevelDatabaseMutaction::DeleteNode(DeleteNode {
+            where_: NodeSelector::new(folder.fields().id(), GraphqlId::from("docs")),
+        });
+
+        let results = db
+            .mutation_executor
+            .database_executor
+            .execute(
+                db.mutation_executor.db_name.clone(),
+                DatabaseMutaction::TopLevel(mutaction),
+                None,
+            )
+            .unwrap();
+
+        let cascaded: Vec<(String, PrismaValue)> = results
+            .with_paths()
+            .into_iter()
+            .filter(|(path, _)| path == &[NestedPosition::Cascade])
+            .map(|(_, result)| {
+                let model = match result.mutaction {
+                    DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::DeleteNode(ref dn)) => dn.where_.model(),
+                    _ => panic!("Expected the delete of a cascaded record"),
+                };
+
+                (model.name.clone(), result.node().get_field_value("id").unwrap().clone())
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                (String::from("Folder"), PrismaValue::from("drafts")),
+                (String::from("File"), PrismaValue::from("readme")),
+            ],
+            cascaded
+        );
+    }
+
+    fn product_values(db: &TestDatabase, id: &str) -> (Option<PrismaValue>, Option<PrismaValue>) {
+        let query = format!("query {{ product(where: {{ id: \"{}\" }}) {{ price stock }} }}", id);
+        (db.find(&query, "price"), db.find(&query, "stock"))
+    }
+
+    fn update_product(db: &TestDatabase, data: &str) -> CoreResult<Vec<PrismaQueryResult>> {
+        db.execute(&format!(
+            "mutation {{ updateProduct(where: {{ id: \"a\" }}, data: {}) {{ id }} }}",
+            data
+        ))
+    }
+
+    #[test]
+    fn numeric_fields_are_updated_relative_to_their_current_values() {
+        let db = TestDatabase::new("mutation_executor_operations");
+        db.execute(
+            "mutation {
+                a: createProduct(data: { id: \"a\", name: \"Apple\", price: 2.0, stock: 10 }) { id }
+                b: createProduct(data: { id: \"b\", name: \"Pear\", price: 1.0, stock: 1 }) { id }
+            }",
+        )
+        .unwrap();
+
+        update_product(&db, "{ stock_increment: 5, price_multiply: 3 }").unwrap();
+        assert_eq!(
+            (Some(PrismaValue::Float(6.0)), Some(PrismaValue::Int(15))),
+            product_values(&db, "a")
+        );
+
+        // Int fields are divided without remainder, Float fields take Int operands.
+        update_product(&db, "{ stock_divide: 2, price_divide: 4 }").unwrap();
+        assert_eq!(
+            (Some(PrismaValue::Float(1.5)), Some(PrismaValue::Int(7))),
+            product_values(&db, "a")
+        );
+
+        update_product(&db, "{ name: \"Green Apple\", stock_decrement: 8, price_increment: 1 }").unwrap();
+        assert_eq!(
+            (Some(PrismaValue::Float(2.5)), Some(PrismaValue::Int(-1))),
+            product_values(&db, "a")
+        );
+
+        let count = db.find(
+            "mutation { updateManyProducts(data: { stock_increment: 2 }) { count } }",
+            "count",
+        );
+
+        assert_eq!(Some(PrismaValue::Int(2)), count);
+        assert_eq!(Some(PrismaValue::Int(1)), product_values(&db, "a").1);
+        assert_eq!(Some(PrismaValue::Int(3)), product_values(&db, "b").1);
+    }
+
+    #[test]
+    fn nested_updates_apply_numeric_operations() {
+        let db = TestDatabase::new("mutation_executor_nested_operations");
+        db.execute(
+            "mutation { createProduct(data: { id: \"a\", name: \"Apple\", price: 1.0, reviews: { create: [
+                { id: \"r1\", author: \"Ann\", rating: 4 },
+                { id: \"r2\", author: \"Ben\", rating: 2 }
+            ] } }) { id } }",
+        )
+        .unwrap();
+
+        let rating = |id: &str| {
+            db.find(
+                &format!("query {{ review(where: {{ id: \"{}\" }}) {{ rating }} }}", id),
+                "rating",
+            )
+        };
+
+        update_product(
+            &db,
+            "{ reviews: { update: { where: { id: \"r1\" }, data: { rating_decrement: 3 } } } }",
+        )
+        .unwrap();
+
+        assert_eq!(Some(PrismaValue::Int(1)), rating("r1"));
+        assert_eq!(Some(PrismaValue::Int(2)), rating("r2"));
+
+        update_product(&db, "{ reviews: { updateMany: { data: { rating_multiply: 2 } } } }").unwrap();
+
+        assert_eq!(Some(PrismaValue::Int(2)), rating("r1"));
+        assert_eq!(Some(PrismaValue::Int(4)), rating("r2"));
+    }
+
+    #[test]
+    fn failed_numeric_operations_change_nothing() {
+        let db = TestDatabase::new("mutation_executor_failed_operations");
+        db.execute("mutation { createProduct(data: { id: \"a\", name: \"Apple\", price: 2.0, stock: 10 }) { id } }")
+            .unwrap();
+
+        assert!(update_product(&db, "{ stock_increment: 1, price_divide: 0 }").is_err());
+        assert!(update_product(&db, "{ stock_divide: 0.0 }").is_err());
+        assert!(update_product(&db, "{ stock_increment: 1.5 }").is_err());
+
+        assert_eq!(
+            (Some(PrismaValue::Float(2.0)), Some(PrismaValue::Int(10))),
+            product_values(&db, "a")
+        );
+    }
+
+    fn ints(values: &[i32]) -> Option<PrismaValue> {
+        Some(PrismaValue::List(values.iter().map(|v| PrismaValue::Int(*v)).collect()))
+    }
+
+    fn cats(db: &TestDatabase, id: &str) -> Option<PrismaValue> {
+        db.find(
+            &format!("query {{ user(where: {{ id: \"{}\" }}) {{ cats }} }}", id),
+            "cats",
+        )
+    }
+
+    #[test]
+    fn scalar_list_operations_keep_the_order_of_the_remaining_values() {
+        let db = TestDatabase::new("mutation_executor_list_operations");
+        db.execute("mutation { createUser(data: { id: \"a\", name: \"A\", cats: { set: [1, 2, 3] } }) { id } }")
+            .unwrap();
+
+        let update = |operation: &str| {
+            let query = format!(
+                "mutation {{ updateUser(where: {{ id: \"a\" }}, data: {{ cats: {} }}) {{ id }} }}",
+                operation
+            );
+
+            db.execute(&query).unwrap();
+            cats(&db, "a")
+        };
+
+        assert_eq!(ints(&[1, 2, 3, 4, 5]), update("{ push: [4, 5] }"));
+        assert_eq!(ints(&[7, 8, 1, 2, 3, 4, 5]), update("{ unshift: [7, 8] }"));
+        assert_eq!(ints(&[7, 8, 1, 2, 3, 4, 5, 1]), update("{ push: 1 }"));
+        assert_eq!(ints(&[7, 1, 2, 3, 4, 5, 1]), update("{ removeAt: 1 }"));
+        assert_eq!(ints(&[7, 2, 3, 4, 5]), update("{ removeValue: 1 }"));
+        assert_eq!(ints(&[7, 2, 3, 4, 5]), update("{ removeAt: 10 }"));
+        assert_eq!(ints(&[2, 3, 4, 5]), update("{ removeAt: 0 }"));
+        assert_eq!(ints(&[2, 3, 4, 5, 6]), update("{ push: 6 }"));
+        assert_eq!(ints(&[9]), update("{ set: [9] }"));
+        assert_eq!(ints(&[]), update("{ set: [] }"));
+        assert_eq!(ints(&[0]), update("{ unshift: [0] }"));
+    }
+
+    #[test]
+    fn scalar_list_operations_take_more_values_than_a_compound_select() {
+        let db = TestDatabase::new("mutation_executor_list_operations_large");
+        db.execute("mutation { createUser(data: { id: \"a\", name: \"A\", cats: { set: [0] } }) { id } }")
+            .unwrap();
+
+        let values: Vec<i32> = (1..=600).collect();
+        let list: Vec<String> = values.iter().map(ToString::to_string).collect();
+
+        let query = format!(
+            "mutation {{ updateUser(where: {{ id: \"a\" }}, data: {{ cats: {{ push: [{}] }} }}) {{ id }} }}",
+            list.join(", ")
+        );
+
+        db.execute(&query).unwrap();
+
+        let expected: Vec<i32> = std::iter::once(0).chain(values).collect();
+        assert_eq!(ints(&expected), cats(&db, "a"));
+    }
+
+    #[test]
+    fn scalar_list_operations_apply_to_each_updated_record() {
+        let db = TestDatabase::new("mutation_executor_list_operations_many");
+        db.execute(
+            "mutation {
+                a: createUser(data: { id: \"a\", name: \"A\", cats: { set: [1, 2] }, sites: { create: { id: \"s\", name: \"S\", tags: { set: [1] } } } }) { id }
+                b: createUser(data: { id: \"b\", name: \"B\" }) { id }
+            }",
+        )
+        .unwrap();
+
+        let count = db.find(
+            "mutation { updateManyUsers(data: { cats: { push: [3] } }) { count } }",
+            "count",
+        );
+
+        assert_eq!(Some(PrismaValue::Int(2)), count);
+        assert_eq!(ints(&[1, 2, 3]), cats(&db, "a"));
+        assert_eq!(ints(&[3]), cats(&db, "b"));
+
+        db.execute("mutation { updateManyUsers(where: { id: \"a\" }, data: { cats: { unshift: [0] } }) { count } }")
+            .unwrap();
+
+        assert_eq!(ints(&[0, 1, 2, 3]), cats(&db, "a"));
+        assert_eq!(ints(&[3]), cats(&db, "b"));
+
+        db.execute("mutation { updateUser(where: { id: \"a\" }, data: { sites: { update: { tags: { push: [2] } } } }) { id } }")
+            .unwrap();
+
+        let tags = db.find("query { site(where: { id: \"s\" }) { tags } }", "tags");
+
+        assert_eq!(ints(&[1, 2]), tags);
+    }
+
+    #[test]
+    fn batch_creates_generate_ids_and_return_the_count() {
+        let db = TestDatabase::new("mutation_executor_create_many");
+
+        let count = db.find(
+            "mutation { createManyProducts(data: [
+                { name: \"Apple\", price: 1.0 },
+                { name: \"Pear\", price: 2.0, stock: 3 }
+            ]) { count } }",
+            "count",
+        );
+
+        assert_eq!(Some(PrismaValue::Int(2)), count);
+        assert_eq!(
+            values(&["Apple", "Pear"]),
+            db.find_all("query { products(orderBy: name_ASC) { name } }", "name")
+        );
+
+        let product_ids = ids(&db, "products");
+        assert_ne!(product_ids[0], product_ids[1]);
+
+        // More values than fit into one statement are inserted in chunks.
+        let data: Vec<String> = (0..3000)
+            .map(|i| format!("{{ name: \"Product {}\", price: 1.0, stock: {} }}", i, i))
+            .collect();
+
+        let query = format!(
+            "mutation {{ createManyProducts(data: [{}]) {{ count }} }}",
+            data.join(", ")
+        );
+
+        assert_eq!(Some(PrismaValue::Int(3000)), db.find(&query, "count"));
+        assert_eq!(3002, ids(&db, "products").len());
+    }
+
+    #[test]
+    fn batch_creates_skip_duplicates_only_if_asked() {
+        let db = TestDatabase::new("mutation_executor_create_many_duplicates");
+        db.execute("mutation { createAccount(data: { id: \"a\", email: \"a@example.com\" }) { id } }")
+            .unwrap();
+
+        let duplicates = "[
+            { id: \"b\", email: \"b@example.com\" },
+            { id: \"a\", email: \"other@example.com\" },
+            { id: \"c\", email: \"a@example.com\" }
+        ]";
+
+        // The whole batch fails on a duplicate, nothing is created.
+        let query = format!("mutation {{ createManyAccounts(data: {}) {{ count }} }}", duplicates);
+
+        assert!(db.execute(&query).is_err());
+        assert_eq!(values(&["a"]), ids(&db, "accounts"));
+
+        let query = format!(
+            "mutation {{ createManyAccounts(data: {}, skipDuplicates: true) {{ count }} }}",
+            duplicates
+        );
+
+        assert_eq!(Some(PrismaValue::Int(1)), db.find(&query, "count"));
+        assert_eq!(values(&["a", "b"]), ids(&db, "accounts"));
+
+        let email = db.find("query { account(where: { id: \"a\" }) { email } }", "email");
+        assert_eq!(Some(PrismaValue::from("a@example.com")), email);
+    }
+
+    fn upsert_account(db: &TestDatabase, update: &str) -> Option<PrismaValue> {
+        let query = format!(
+            "mutation {{ upsertAccount(where: {{ email: \"a@example.com\" }}, create: {{ email: \"a@example.com\" }}, update: {}) {{ id }} }}",
+            update
+        );
+
+        db.find(&query, "id")
+    }
+
+    #[test]
+    fn upserts_by_a_unique_field_create_a_record_once() {
+        let db = TestDatabase::new("mutation_executor_upsert_unique");
+
+        let created = upsert_account(&db, "{}");
+
+        assert!(created.is_some());
+        assert_eq!(vec![created.clone().unwrap()], ids(&db, "accounts"));
+
+        // Upserting again keeps the record and its generated id.
+        assert_eq!(created, upsert_account(&db, "{}"));
+        assert_eq!(vec![created.clone().unwrap()], ids(&db, "accounts"));
+
+        // The selected field can be updated as well.
+        assert_eq!(created, upsert_account(&db, "{ email: \"b@example.com\" }"));
+
+        let email = db.find("query { accounts { email } }", "email");
+        assert_eq!(Some(PrismaValue::from("b@example.com")), email);
+    }
+
+    #[test]
+    fn upserts_create_after_inserts_into_other_tables() {
+        let db = TestDatabase::new("mutation_executor_upsert_after_insert");
+
+        db.execute("mutation { createAccount(data: { email: \"b@example.com\" }) { id } }")
+            .unwrap();
+
+        // The nested create only runs if the upsert is reported as a create.
+        db.execute(
+            "mutation { upsertUser(
+                where: { id: \"user\" },
+                create: { id: \"user\", name: \"User\", sites: { create: { id: \"site\", name: \"Site\" } } },
+                update: { name: \"Upserted\" }
+            ) { id } }",
+        )
+        .unwrap();
+
+        assert_eq!(Some(PrismaValue::from("User")), user_name(&db));
+        assert_eq!(Some(PrismaValue::from("Site")), site_name(&db, "site"));
+    }
+
+    #[test]
+    fn upserts_apply_their_nested_mutations_after_the_record_is_written() {
+        let db = TestDatabase::new("mutation_executor_upsert_nested");
+
+        let upsert = "mutation { upsertUser(
+            where: { id: \"user\" },
+            create: { id: \"user\", name: \"User\", cats: { set: [1] }, sites: { create: { id: \"site\", name: \"Site\" } } },
+            update: { name: \"Upserted\", cats: { push: 2 }, sites: { update: { name: \"Updated\" } } }
+        ) { id } }";
+
+        db.execute(upsert).unwrap();
+
+        assert_eq!(Some(PrismaValue::from("User")), user_name(&db));
+        assert_eq!(Some(PrismaValue::from("Site")), site_name(&db, "site"));
+        assert_eq!(ints(&[1]), cats(&db, "user"));
+
+        db.execute(upsert).unwrap();
+
+        assert_eq!(Some(PrismaValue::from("Upserted")), user_name(&db));
+        assert_eq!(Some(PrismaValue::from("Updated")), site_name(&db, "site"));
+        assert_eq!(ints(&[1, 2]), cats(&db, "user"));
+    }
+}
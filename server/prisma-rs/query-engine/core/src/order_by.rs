@@ -0,0 +1,267 @@
+//! Conversion of GraphQL `orderBy` arguments into model orderings
+
+use crate::{CoreError, CoreResult};
+use graphql_parser::query::Value;
+use prisma_models::{ModelRef, NullsOrder, OrderBy, RelationFieldRef, ScalarFieldRef, SortOrder};
+
+/// Enum value suffixes and the orderings they stand for. Relevance orderings
+/// are descending, the best matches first.
+const SUFFIXES: &[(&str, SortOrder, Option<NullsOrder>)] = &[
+    ("_ASC_NULLS_FIRST", SortOrder::Ascending, Some(NullsOrder::First)),
+    ("_ASC_NULLS_LAST", SortOrder::Ascending, Some(NullsOrder::Last)),
+    ("_DESC_NULLS_FIRST", SortOrder::Descending, Some(NullsOrder::First)),
+    ("_DESC_NULLS_LAST", SortOrder::Descending, Some(NullsOrder::Last)),
+    ("_ASC", SortOrder::Ascending, None),
+    ("_DESC", SortOrder::Descending, None),
+    (RELEVANCE_SUFFIX, SortOrder::Descending, None),
+];
+
+const RELEVANCE_SUFFIX: &str = "_RELEVANCE";
+
+/// Appended to a list relation field to order by the number of related nodes,
+/// e.g. `posts_COUNT_DESC`.
+const COUNT_SUFFIX: &str = "_COUNT";
+
+/// Converts the `orderBy` argument of the given model, a single ordering like
+/// `name_ASC` or a list of orderings applied in turn.
+///
+/// Relevance orderings get an empty search query, to be filled in from the
+/// `{field}_search` filter.
+pub(crate) fn extract_order_by(model: ModelRef, value: &Value) -> CoreResult<Vec<OrderBy>> {
+    match value {
+        Value::List(values) => values.iter().map(|value| extract_ordering(&model, value)).collect(),
+        value => extract_ordering(&model, value).map(|order_by| vec![order_by]),
+    }
+}
+
+fn extract_ordering(model: &ModelRef, value: &Value) -> CoreResult<OrderBy> {
+    let name = match value {
+        Value::Enum(name) => name,
+        _ => {
+            return Err(CoreError::QueryValidationError(format!(
+                "Expected an ordering of model `{}`",
+                model.name
+            )))
+        }
+    };
+
+    let unknown = || CoreError::QueryValidationError(format!("Unknown ordering `{}` of model `{}`", name, model.name));
+
+    let (suffix, sort_order, nulls) = SUFFIXES
+        .iter()
+        .find(|(suffix, _, _)| name.ends_with(suffix))
+        .ok_or_else(unknown)?;
+
+    let base = &name[..name.len() - suffix.len()];
+    let by_relevance = *suffix == RELEVANCE_SUFFIX;
+
+    let (relations, field) = match resolve_field(model, base) {
+        Some(resolved) => resolved,
+        None if nulls.is_none() && !by_relevance => {
+            return related_count_ordering(model, base, *sort_order).ok_or_else(unknown)
+        }
+        None => return Err(unknown()),
+    };
+
+    if by_relevance && !relations.is_empty() {
+        return Err(CoreError::QueryValidationError(format!(
+            "Ordering `{}` can't order by the relevance of a related field",
+            name
+        )));
+    }
+
+    Ok(OrderBy {
+        field,
+        relations,
+        sort_order: *sort_order,
+        nulls: *nulls,
+        search: if by_relevance { Some(String::new()) } else { None },
+        path: None,
+        related_count: None,
+    })
+}
+
+/// Orders by the number of nodes related through a list relation field of the
+/// model, named like `posts_COUNT`. Ties are broken by the node id as usual.
+fn related_count_ordering(model: &ModelRef, name: &str, sort_order: SortOrder) -> Option<OrderBy> {
+    if !name.ends_with(COUNT_SUFFIX) {
+        return None;
+    }
+
+    let rf = model
+        .fields()
+        .find_from_relation_fields(&name[..name.len() - COUNT_SUFFIX.len()])
+        .ok()
+        .filter(|rf| rf.is_list)?;
+
+    Some(OrderBy {
+        field: model.fields().id(),
+        relations: Vec::new(),
+        sort_order,
+        nulls: None,
+        search: None,
+        path: None,
+        related_count: Some(rf),
+    })
+}
+
+/// Resolves a scalar field of the model, or of a model related through to-one
+/// relation fields prefixed to its name, e.g. `author_name`. Field names may
+/// contain underscores, so every relation field matching the start is tried.
+fn resolve_field(model: &ModelRef, name: &str) -> Option<(Vec<RelationFieldRef>, ScalarFieldRef)> {
+    if let Ok(field) = model.fields().find_from_scalar(name) {
+        return if field.is_list { None } else { Some((Vec::new(), field)) };
+    }
+
+    model
+        .fields()
+        .relation()
+        .into_iter()
+        .filter(|rf| !rf.is_list && name.starts_with(&format!("{}_", rf.name)))
+        .find_map(|rf| {
+            let (mut relations, field) = resolve_field(&rf.related_model(), &name[rf.name.len() + 1..])?;
+            relations.insert(0, rf);
+
+            Some((relations, field))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+    use prisma_models::PrismaValue;
+
+    fn database(db_name: &str) -> TestDatabase {
+        let db = TestDatabase::new(db_name);
+
+        db.execute(
+            "mutation {
+                a: createUser(data: { id: \"a\", name: \"Bob\", sites: { create: { id: \"s1\", name: \"Zoo\" } } }) { id }
+                b: createUser(data: { id: \"b\", name: \"Alice\", sites: { create: { id: \"s2\", name: \"Art\" } } }) { id }
+                c: createUser(data: { id: \"c\", name: \"Bob\" }) { id }
+                d: createUser(data: { id: \"d\" }) { id }
+            }",
+        )
+        .unwrap();
+
+        db
+    }
+
+    fn ids(db: &TestDatabase, arguments: &str) -> Vec<PrismaValue> {
+        db.find_all(&format!("query {{ users({}) {{ id }} }}", arguments), "id")
+    }
+
+    fn values(ids: &[&str]) -> Vec<PrismaValue> {
+        ids.iter().map(|id| PrismaValue::from(*id)).collect()
+    }
+
+    #[test]
+    fn orderings_are_applied_in_turn_and_ties_broken_by_id() {
+        let db = database("order_by_multiple");
+
+        assert_eq!(values(&["b", "a", "c", "d"]), ids(&db, "orderBy: name_ASC_NULLS_LAST"));
+        assert_eq!(
+            values(&["b", "c", "a", "d"]),
+            ids(&db, "orderBy: [name_ASC_NULLS_LAST, id_DESC]")
+        );
+        assert_eq!(
+            values(&["d", "a", "c", "b"]),
+            ids(&db, "orderBy: [name_DESC_NULLS_FIRST]")
+        );
+    }
+
+    #[test]
+    fn nulls_are_placed_as_requested_in_both_directions() {
+        let db = database("order_by_nulls");
+
+        assert_eq!(values(&["d", "b", "a", "c"]), ids(&db, "orderBy: name_ASC_NULLS_FIRST"));
+        assert_eq!(values(&["b", "a", "c", "d"]), ids(&db, "orderBy: name_ASC_NULLS_LAST"));
+        assert_eq!(
+            values(&["d", "a", "c", "b"]),
+            ids(&db, "orderBy: name_DESC_NULLS_FIRST")
+        );
+        assert_eq!(values(&["a", "c", "b", "d"]), ids(&db, "orderBy: name_DESC_NULLS_LAST"));
+    }
+
+    #[test]
+    fn nodes_are_ordered_by_fields_of_to_one_related_nodes() {
+        let db = database("order_by_related");
+
+        assert_eq!(
+            values(&["b", "a", "c", "d"]),
+            ids(&db, "orderBy: sites_name_ASC_NULLS_LAST")
+        );
+        assert_eq!(
+            values(&["c", "d", "a", "b"]),
+            ids(&db, "orderBy: sites_name_DESC_NULLS_FIRST")
+        );
+        assert_eq!(
+            values(&["b", "c", "a", "d"]),
+            ids(&db, "orderBy: [name_ASC_NULLS_LAST, sites_name_ASC_NULLS_FIRST]")
+        );
+    }
+
+    #[test]
+    fn cursors_page_through_compound_orderings() {
+        let db = database("order_by_cursors");
+        let order_by = "orderBy: [name_ASC_NULLS_LAST, id_DESC]";
+
+        assert_eq!(values(&["a", "d"]), ids(&db, &format!("{}, after: \"c\"", order_by)));
+        assert_eq!(
+            values(&["a"]),
+            ids(&db, &format!("{}, after: \"c\", first: 1", order_by))
+        );
+        assert_eq!(values(&["b", "c"]), ids(&db, &format!("{}, before: \"a\"", order_by)));
+        assert_eq!(
+            values(&["c"]),
+            ids(&db, &format!("{}, before: \"a\", last: 1", order_by))
+        );
+        assert_eq!(
+            values(&["c", "d"]),
+            ids(&db, "orderBy: sites_name_ASC_NULLS_LAST, after: \"a\"")
+        );
+    }
+
+    #[test]
+    fn nodes_are_ordered_by_the_number_of_related_nodes() {
+        let db = TestDatabase::new("order_by_related_count");
+        db.execute(
+            "mutation {
+                a: createProduct(data: { id: \"a\", name: \"Apple\", price: 1.0, reviews: { create: [
+                    { id: \"r1\", author: \"Ann\", rating: 5 },
+                    { id: \"r2\", author: \"Ben\", rating: 4 },
+                    { id: \"r3\", author: \"Cid\", rating: 3 }
+                ] } }) { id }
+                b: createProduct(data: { id: \"b\", name: \"Banana\", price: 2.0 }) { id }
+                c: createProduct(data: { id: \"c\", name: \"Pear\", price: 3.0, reviews: { create: [
+                    { id: \"r4\", author: \"Ann\", rating: 4 },
+                    { id: \"r5\", author: \"Ben\", rating: 2 }
+                ] } }) { id }
+                d: createProduct(data: { id: \"d\", name: \"Plum\", price: 4.0 }) { id }
+            }",
+        )
+        .unwrap();
+
+        let product_ids = |arguments: &str| db.find_all(&format!("query {{ products({}) {{ id }} }}", arguments), "id");
+
+        assert_eq!(values(&["b", "d", "c", "a"]), product_ids("orderBy: reviews_COUNT_ASC"));
+        assert_eq!(
+            values(&["a", "c", "b", "d"]),
+            product_ids("orderBy: reviews_COUNT_DESC")
+        );
+        assert_eq!(
+            values(&["a", "c", "d", "b"]),
+            product_ids("orderBy: [reviews_COUNT_DESC, id_DESC]")
+        );
+
+        // Cursors compare the number of related nodes of the cursor node:
+        assert_eq!(
+            values(&["b", "d"]),
+            product_ids("orderBy: reviews_COUNT_DESC, after: \"c\"")
+        );
+        assert_eq!(
+            values(&["c"]),
+            product_ids("orderBy: reviews_COUNT_DESC, before: \"b\", last: 1")
+        );
+    }
+}
@@ -1,11 +1,29 @@
 //! Prisma query AST module
 
-use crate::{CoreError, CoreResult};
-use connector::{filter::NodeSelector, QueryArguments};
+use crate::{
+    document::{self, OperationKind},
+    filter, order_by, CoreError, CoreResult, PrismaMutation,
+};
+use chrono::{DateTime, Utc};
+use connector::{
+    filter::{CompoundNodeSelector, NodeSelector},
+    Aggregation, QueryArguments,
+};
 use graphql_parser::{self as gql, query::*};
 use inflector::Inflector;
 use prisma_models::{Field as ModelField, *};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
+use uuid::Uuid;
+
+/// A top-level operation of a GraphQL document
+#[derive(Debug, Clone)]
+pub enum Operation {
+    /// Queries of a `query` operation, executed independently of each other
+    Read(Vec<PrismaQuery>),
+
+    /// Mutations of a `mutation` operation, executed serially in document order
+    Write(Vec<PrismaMutation>),
+}
 
 #[derive(Debug, Clone)]
 pub enum PrismaQuery {
@@ -21,6 +39,9 @@ pub struct RecordQuery {
     pub selector: NodeSelector,
     pub selected_fields: SelectedFields,
     pub nested: Vec<PrismaQuery>,
+
+    /// The type name `__typename` resolves to, if selected
+    pub typename: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +51,10 @@ pub struct MultiRecordQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<PrismaQuery>,
+    pub connection: Option<ConnectionSelection>,
+
+    /// The type name `__typename` resolves to, if selected
+    pub typename: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +64,9 @@ pub struct RelatedRecordQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<PrismaQuery>,
+
+    /// The type name `__typename` resolves to, if selected
+    pub typename: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,12 +76,54 @@ pub struct MultiRelatedRecordQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<PrismaQuery>,
+    pub connection: Option<ConnectionSelection>,
+
+    /// The type name `__typename` resolves to, if selected
+    pub typename: Option<String>,
+}
+
+/// The selection of a connection field (e.g. `usersConnection`), which wraps the nodes in edges.
+/// Selected fields and nested queries of the query are the ones of `edges { node }`.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionSelection {
+    /// Selected fields of `edges`, if selected
+    pub edges: Option<Vec<String>>,
+
+    /// Selected fields of `pageInfo`, if selected
+    pub page_info: Option<Vec<String>>,
+
+    /// Aggregations of `aggregate`, e.g. `count` or `sum { price }`, in selection order, if selected
+    pub aggregate: Option<Vec<Aggregation>>,
+
+    /// Name of the model of the nodes, the connection types are named after, e.g. `UserEdge`
+    pub model_name: String,
+
+    /// Whether `__typename` is selected on the connection itself
+    pub typename: bool,
+
+    /// Whether `__typename` is selected on `aggregate`
+    pub aggregate_typename: bool,
+}
+
+impl ConnectionSelection {
+    /// Suffix of connection fields
+    pub const SUFFIX: &'static str = "Connection";
+}
+
+/// Meta field selectable on every type, resolved to the name of the type instead of being queried
+pub const TYPENAME_FIELD: &str = "__typename";
+
+/// Meta fields, e.g. `__typename` or `__schema`, are resolved against the GraphQL schema instead of
+/// querying data. Root meta fields are left out of the built operation.
+pub fn is_meta_field(field: &gql::query::Field) -> bool {
+    field.name.starts_with("__")
 }
 
 pub struct RootQueryBuilder {
     pub query: Document,
     pub schema: SchemaRef,
     pub operation_name: Option<String>,
+    pub variables: BTreeMap<String, Value>,
 }
 
 #[derive(Debug)]
@@ -71,6 +141,13 @@ impl QueryType {
             Some(QueryType::Single(Arc::clone(&model)))
         } else if model.name.to_camel_case().to_plural() == field.name {
             Some(QueryType::Multiple(Arc::clone(&model)))
+        } else if format!(
+            "{}{}",
+            model.name.to_camel_case().to_plural(),
+            ConnectionSelection::SUFFIX
+        ) == field.name
+        {
+            Some(QueryType::Multiple(Arc::clone(&model)))
         } else {
             None
         }
@@ -92,6 +169,11 @@ type BuilderResult<T> = Option<CoreResult<T>>;
 struct QueryBuilder<'a> {
     schema: SchemaRef,
     field: &'a gql::query::Field,
+
+    /// Selections the fields and nested queries are built from.
+    /// For connections, this is the selection of `edges { node }`, if any.
+    selections: &'a [Selection],
+    connection: BuilderResult<ConnectionSelection>,
     query_type: BuilderResult<QueryType>,
     name: Option<String>,
     selector: BuilderResult<NodeSelector>,
@@ -99,6 +181,7 @@ struct QueryBuilder<'a> {
     args: BuilderResult<QueryArguments>,
     parent_field: Option<RelationFieldRef>,
     nested: BuilderResult<Vec<QueryBuilder<'a>>>,
+    typename: Option<String>,
 }
 
 impl<'a> QueryBuilder<'a> {
@@ -106,6 +189,8 @@ impl<'a> QueryBuilder<'a> {
         Self {
             schema,
             field,
+            selections: &field.selection_set.items,
+            connection: None,
             query_type: None,
             name: None,
             selector: None,
@@ -113,6 +198,7 @@ impl<'a> QueryBuilder<'a> {
             args: None,
             parent_field: None,
             nested: None,
+            typename: None,
         }
     }
 
@@ -147,12 +233,90 @@ impl<'a> QueryBuilder<'a> {
         self
     }
 
+    /// Connection fields are named after the plural or relation field they wrap
+    fn is_connection(&self) -> bool {
+        match (&self.query_type, &self.parent_field) {
+            (Some(Ok(QueryType::Multiple(m))), None) => self.field.name != m.name.to_camel_case().to_plural(),
+            (Some(Ok(QueryType::ManyRelation(_))), Some(parent)) => self.field.name != parent.name,
+            _ => false,
+        }
+    }
+
+    /// Splits the selection of a connection field into the connection parts
+    /// and the selection of its nodes, which fields and nested queries are built from.
+    fn extract_connection(mut self) -> Self {
+        if !self.is_connection() {
+            return self;
+        }
+
+        let field: &'a gql::query::Field = self.field;
+        let model = match self.query_type {
+            Some(Ok(ref qt)) => qt.model(),
+            _ => return self,
+        };
+
+        let mut connection = ConnectionSelection {
+            model_name: model.name.clone(),
+            ..ConnectionSelection::default()
+        };
+        let mut node_selections: &'a [Selection] = &[];
+
+        for selected in connection_fields(&field.selection_set.items) {
+            let names = connection_fields(&selected.selection_set.items)
+                .map(|f| f.name.clone())
+                .collect();
+
+            match selected.name.as_str() {
+                "edges" => {
+                    if let Some(node) = connection_fields(&selected.selection_set.items).find(|f| f.name == "node") {
+                        node_selections = &node.selection_set.items;
+                    }
+
+                    connection.edges = Some(names);
+                }
+                "pageInfo" => connection.page_info = Some(names),
+                "aggregate" => match extract_aggregations(&model, selected) {
+                    Ok(aggregations) => {
+                        connection.aggregate = Some(aggregations);
+                        connection.aggregate_typename = names.iter().any(|name| name == TYPENAME_FIELD);
+                    }
+                    Err(err) => {
+                        self.connection = Some(Err(err));
+                        return self;
+                    }
+                },
+                TYPENAME_FIELD => connection.typename = true,
+                _ => (),
+            }
+        }
+
+        // Relation connections are aggregated per parent, by counting the related nodes
+        let aggregates_fields = connection
+            .aggregate
+            .iter()
+            .flatten()
+            .any(|aggregation| aggregation.field().is_some());
+
+        if self.parent_field.is_some() && aggregates_fields {
+            self.connection = Some(Err(CoreError::QueryValidationError(format!(
+                "Only `count` can be aggregated on relation field `{}`",
+                field.name
+            ))));
+
+            return self;
+        }
+
+        self.selections = node_selections;
+        self.connection = Some(Ok(connection));
+        self
+    }
+
     fn process_arguments(mut self) -> Self {
         match self.query_type {
             Some(Ok(QueryType::Single(ref m))) => self.selector = Some(self.extract_node_selector(Arc::clone(m))),
             Some(Ok(QueryType::Multiple(ref m))) => self.args = Some(self.extract_query_args(Arc::clone(m))),
-            Some(Ok(QueryType::OneRelation(ref m))) => self.args = Some(self.extract_query_args(Arc::clone(m))),
-            Some(Ok(QueryType::ManyRelation(ref m))) => self.args = Some(self.extract_query_args(Arc::clone(m))),
+            Some(Ok(QueryType::OneRelation(ref m))) => self.args = Some(self.extract_nested_query_args(Arc::clone(m))),
+            Some(Ok(QueryType::ManyRelation(ref m))) => self.args = Some(self.extract_nested_query_args(Arc::clone(m))),
             _ => {
                 //FIXME: This is really not ideal, where do we store the error in this case?
                 // This, and many other places, actually point to a separated query builder for many and single
@@ -165,19 +329,12 @@ impl<'a> QueryBuilder<'a> {
     }
 
     fn extract_node_selector(&self, model: ModelRef) -> CoreResult<NodeSelector> {
-        let (_, value) = self.field.arguments.first().expect("no arguments found"); // FIXME: this expects at least one query arg...
-        match value {
-            Value::Object(obj) => {
-                let (field_name, value) = obj.iter().next().expect("object was empty");
-                let field = model.fields().find_from_scalar(field_name).unwrap();
-                let value = Self::value_to_prisma_value(value);
-
-                Ok(NodeSelector {
-                    field: Arc::clone(&field),
-                    value: value,
-                })
-            }
-            _ => unimplemented!(),
+        match self.field.arguments.iter().find(|(name, _)| name == "where") {
+            Some((_, value)) => extract_node_selector(model, value),
+            None => Err(CoreError::QueryValidationError(format!(
+                "Required argument `where` missing on field `{}`",
+                self.field.name
+            ))),
         }
     }
 
@@ -198,71 +355,144 @@ impl<'a> QueryBuilder<'a> {
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
                         ("last", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
+                            Some(num) => Ok(QueryArguments { last: Some(num as u32), ..res }),
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
                         //("after", Value::String(s)) if s.is_uuid() => Ok(QueryArguments { after: Some(UuidString(s.clone()).into()), ..res }),
                         ("after", Value::String(s)) => Ok(QueryArguments { after: Some(s.clone().into()), ..res }),
                         ("after", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
+                            Some(num) if num >= 0 => Ok(QueryArguments { after: Some(GraphqlId::from(num as usize)), ..res }),
+                            Some(_) => Err(CoreError::QueryValidationError("Ids can't be negative".into())),
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
                         //("before", Value::String(s)) if s.is_uuid() => Ok(QueryArguments { before: Some(UuidString(s.clone()).into()), ..res }),
                         ("before", Value::String(s)) => Ok(QueryArguments { before: Some(s.clone().into()), ..res }),
                         ("before", Value::Int(num)) => match num.as_i64() {
-                            Some(num) => Ok(QueryArguments { first: Some(num as u32), ..res }),
+                            Some(num) if num >= 0 => Ok(QueryArguments { before: Some(GraphqlId::from(num as usize)), ..res }),
+                            Some(_) => Err(CoreError::QueryValidationError("Ids can't be negative".into())),
                             None => Err(CoreError::QueryValidationError("Invalid number povided".into())),
                         },
-                        ("orderby", Value::Enum(name)) => {
-                            let vec = name.split("_").collect::<Vec<&str>>();
-                            if vec.len() == 2 {
-                                model
-                                    .fields()
-                                    .find_from_scalar(vec[0])
-                                    .map(|val| QueryArguments {
-                                        order_by: Some(OrderBy {
-                                            field: Arc::clone(&val),
-                                            sort_order: match vec[1] {
-                                                "ASC" => SortOrder::Ascending,
-                                                "DESC" => SortOrder::Descending,
-                                                _ => unreachable!(),
-                                            },
-                                        }),
-                                        ..res
-                                    })
-                                    .map_err(|_| CoreError::QueryValidationError(format!("Unknown field `{}`", vec[0])))
-                            } else {
-                                Err(CoreError::QueryValidationError("...".into()))
-                            }
-                        }
-                        ("where", _) => panic!("lolnope"),
+                        ("orderBy", value) => order_by::extract_order_by(Arc::clone(&model), value)
+                            .map(|order_by| QueryArguments { order_by, ..res }),
+                        ("where", value) => filter::extract_filter(Arc::clone(&model), value)
+                            .map(|filter| QueryArguments { filter: Some(filter), ..res }),
+                        ("distinct", value) => extract_distinct(Arc::clone(&model), value)
+                            .map(|distinct| QueryArguments { distinct, ..res }),
+                        // Resolved once the order field is known
+                        ("orderByPath", Value::String(_)) => Ok(res),
                         (name, _) => Err(CoreError::QueryValidationError(format!("Unknown key: `{}`", name))),
                     }
                 } else {
                     result
                 }
             })
+            .and_then(|args| self.resolve_relevance_order(args))
+            .and_then(|args| self.resolve_path_order(args))
     }
 
-    // Todo: From trait somewhere?
-    fn value_to_prisma_value(val: &Value) -> PrismaValue {
-        match val {
-            Value::String(s) => PrismaValue::String(s.clone()),
-            Value::Int(i) => PrismaValue::Int(i.as_i64().unwrap() as i32),
-            _ => unimplemented!(),
+    /// Relation queries can't order by relevance or JSON paths, the ranks and
+    /// values are only joined to top-level queries.
+    fn extract_nested_query_args(&self, model: ModelRef) -> CoreResult<QueryArguments> {
+        self.extract_query_args(model).and_then(|args| {
+            if args.order_by.iter().any(|order_by| order_by.search.is_some()) {
+                Err(CoreError::QueryValidationError(format!(
+                    "Ordering by relevance is not supported on relation field `{}`",
+                    self.field.name
+                )))
+            } else if args.order_by.iter().any(|order_by| order_by.path.is_some()) {
+                Err(CoreError::QueryValidationError(format!(
+                    "Ordering by a JSON path is not supported on relation field `{}`",
+                    self.field.name
+                )))
+            } else {
+                Ok(args)
+            }
+        })
+    }
+
+    /// Ordering by `{field}_RELEVANCE` ranks the nodes for the query of the
+    /// `{field}_search` filter, which is therefore required. The ranks of a
+    /// single field can be joined.
+    fn resolve_relevance_order(&self, mut args: QueryArguments) -> CoreResult<QueryArguments> {
+        let filter = args.filter.as_ref();
+        let mut by_relevance = args.order_by.iter_mut().filter(|order_by| order_by.search.is_some());
+
+        if let Some(order_by) = by_relevance.next() {
+            match filter.and_then(|filter| filter::search_query(filter, &order_by.field)) {
+                Some(query) => order_by.search = Some(query),
+                None => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Ordering by `{name}_RELEVANCE` requires a `{name}_search` filter",
+                        name = order_by.field.name
+                    )))
+                }
+            }
+        }
+
+        if by_relevance.next().is_some() {
+            return Err(CoreError::QueryValidationError(
+                "Only one field can be ordered by relevance".into(),
+            ));
         }
+
+        Ok(args)
+    }
+
+    /// `orderByPath` orders the Json field given in `orderBy` by the value at
+    /// the path, e.g. `orderBy: settings_ASC, orderByPath: "$.theme"`.
+    fn resolve_path_order(&self, mut args: QueryArguments) -> CoreResult<QueryArguments> {
+        let path = self
+            .field
+            .arguments
+            .iter()
+            .find_map(|(name, value)| match (name.as_str(), value) {
+                ("orderByPath", Value::String(path)) => Some(path),
+                _ => None,
+            });
+
+        let mut json_orders: Vec<&mut OrderBy> = args
+            .order_by
+            .iter_mut()
+            .filter(|order_by| order_by.field.type_identifier == TypeIdentifier::Json && order_by.relations.is_empty())
+            .collect();
+
+        match (path, json_orders.as_mut_slice()) {
+            (None, _) => (),
+            (Some(path), [order_by]) => {
+                let path = path
+                    .parse()
+                    .map_err(|err: DomainError| CoreError::QueryValidationError(format!("{}", err)))?;
+
+                order_by.path = Some(path);
+            }
+            (Some(_), _) => {
+                return Err(CoreError::QueryValidationError(
+                    "`orderByPath` requires ordering by exactly one Json field of the model".into(),
+                ))
+            }
+        }
+
+        Ok(args)
     }
 
     fn map_selected_scalar_fields(mut self) -> Self {
         if let Some(Ok(ref qt)) = self.query_type {
             let model = qt.model();
+
+            // `__typename` isn't queried, but resolved to the model name
+            if connection_fields(self.selections).any(|f| f.name == TYPENAME_FIELD) {
+                self.typename = Some(model.name.clone());
+            }
+
             let selected_fields = self
-                .field
-                .selection_set
-                .items
+                .selections
                 .iter()
                 .filter_map(|i| {
                     if let Selection::Field(f) = i {
+                        if f.name == TYPENAME_FIELD {
+                            return None;
+                        }
+
                         // We have to make sure the selected field exists in some form.
                         let field = model.fields().find_from_all(&f.name);
                         match field {
@@ -272,14 +502,14 @@ impl<'a> QueryBuilder<'a> {
                             }))),
                             // Relation fields are not handled here, but in nested queries
                             Ok(ModelField::Relation(_field)) => None,
+                            _ if find_connection_field(&model, &f.name).is_some() => None,
                             _ => Some(Err(CoreError::QueryValidationError(format!(
                                 "Selected field {} not found on model {}",
                                 f.name, model.name,
                             )))),
                         }
                     } else {
-                        // Todo: We only support selecting fields at the moment.
-                        unimplemented!()
+                        unreachable!("Fragments are inlined before building")
                     }
                 })
                 .collect::<CoreResult<Vec<SelectedField>>>();
@@ -305,12 +535,14 @@ impl<'a> QueryBuilder<'a> {
             let model = qt.model();
 
             let nested_queries: CoreResult<Vec<QueryBuilder>> = self
-                .field
-                .selection_set
-                .items
+                .selections
                 .iter()
                 .filter_map(|i| {
                     if let Selection::Field(f) = i {
+                        if f.name == TYPENAME_FIELD {
+                            return None;
+                        }
+
                         let field = model.fields().find_from_all(&f.name);
                         match field {
                             Ok(ModelField::Scalar(_field)) => None,
@@ -325,14 +557,25 @@ impl<'a> QueryBuilder<'a> {
 
                                 Some(Ok(qb))
                             }
-                            _ => Some(Err(CoreError::QueryValidationError(format!(
-                                "Selected field {} not found on model {}",
-                                f.name, model.name,
-                            )))),
+                            _ => match find_connection_field(&model, &f.name) {
+                                Some(field) => {
+                                    let qb = QueryBuilder::new(Arc::clone(&self.schema), f)
+                                        .infer_query_type(Some(field))
+                                        .extract_connection()
+                                        .process_arguments()
+                                        .map_selected_scalar_fields()
+                                        .collect_nested_queries();
+
+                                    Some(Ok(qb))
+                                }
+                                None => Some(Err(CoreError::QueryValidationError(format!(
+                                    "Selected field {} not found on model {}",
+                                    f.name, model.name,
+                                )))),
+                            },
                         }
                     } else {
-                        // Todo: We only support selecting fields at the moment.
-                        unimplemented!()
+                        unreachable!("Fragments are inlined before building")
                     }
                 })
                 .collect();
@@ -362,6 +605,13 @@ impl<'a> QueryBuilder<'a> {
             .map(|qb| qb.get())
             .collect::<CoreResult<Vec<PrismaQuery>>>()?;
 
+        let connection = match self.connection {
+            Some(connection) => Some(connection?),
+            None => None,
+        };
+
+        let typename = self.typename;
+
         // todo this needs some DRYing
         match self.query_type {
             Some(qt) => match qt? {
@@ -376,6 +626,7 @@ impl<'a> QueryBuilder<'a> {
                         selector: selector,
                         selected_fields: selected_fields,
                         nested: nested_queries,
+                        typename,
                     }))
                 }
                 QueryType::Multiple(model) => {
@@ -389,6 +640,8 @@ impl<'a> QueryBuilder<'a> {
                         model,
                         selected_fields,
                         nested: nested_queries,
+                        connection,
+                        typename,
                     }))
                 }
                 QueryType::OneRelation(_model) => {
@@ -409,6 +662,7 @@ impl<'a> QueryBuilder<'a> {
                         selected_fields: selected_fields,
                         args: args,
                         nested: nested_queries,
+                        typename,
                     }))
                 }
                 QueryType::ManyRelation(_model) => {
@@ -429,6 +683,8 @@ impl<'a> QueryBuilder<'a> {
                         selected_fields: selected_fields,
                         args: args,
                         nested: nested_queries,
+                        connection,
+                        typename,
                     }))
                 }
             },
@@ -438,47 +694,281 @@ impl<'a> QueryBuilder<'a> {
 }
 
 impl RootQueryBuilder {
-    // FIXME: Find op name and only execute op!
-    pub fn build(self) -> CoreResult<Vec<PrismaQuery>> {
-        self.query
-            .definitions
-            .iter()
-            .map(|d| match d {
-                // Query without the explicit "query" before the selection set
-                Definition::Operation(OperationDefinition::SelectionSet(SelectionSet { span: _, items })) => {
-                    self.build_query(&items)
+    /// Builds the operation selected by `operation_name`, with variables and fragments resolved.
+    pub fn build(self) -> CoreResult<Operation> {
+        let operation = document::resolve_operation(
+            &self.query,
+            self.operation_name.as_ref().map(String::as_str),
+            &self.variables,
+        )?;
+
+        match operation.kind {
+            OperationKind::Query => self.build_query(&operation.selections).map(Operation::Read),
+            OperationKind::Mutation => self.build_mutation(&operation.selections).map(Operation::Write),
+        }
+    }
+
+    fn build_query(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<PrismaQuery>> {
+        data_fields(root_fields)
+            .map(|root_field| self.build_query_field(root_field))
+            .collect()
+    }
+
+    /// Builds the query of a single root field of a resolved `query` operation.
+    pub fn build_query_field(&self, root_field: &gql::query::Field) -> CoreResult<PrismaQuery> {
+        // First query-level fields map to a model in our schema, either a plural or singular
+        QueryBuilder::new(Arc::clone(&self.schema), root_field)
+            .infer_query_type(None)
+            .extract_connection()
+            .process_arguments()
+            .map_selected_scalar_fields()
+            .collect_nested_queries()
+            .get() // Q: Since we never really give any args, and we always have to call these fns, we should just to it internally and call .get
+    }
+}
+
+/// Builds the selected fields, nested queries and selected type name of a field returning a single record
+/// of the given model, e.g. the payload of a mutation.
+pub(crate) fn build_selection(
+    schema: SchemaRef,
+    field: &gql::query::Field,
+    model: ModelRef,
+) -> CoreResult<(SelectedFields, Vec<PrismaQuery>, Option<String>)> {
+    let builder = QueryBuilder {
+        query_type: Some(Ok(QueryType::Single(model))),
+        ..QueryBuilder::new(schema, field)
+    }
+    .map_selected_scalar_fields()
+    .collect_nested_queries();
+
+    let selected_fields = builder.selected_fields.unwrap_or(Err(CoreError::QueryValidationError(
+        "Selected fields required but not found".into(),
+    )))?;
+
+    let nested = builder
+        .nested
+        .unwrap_or(Err(CoreError::QueryValidationError(
+            "Required nested queries not found".into(),
+        )))?
+        .into_iter()
+        .map(|qb| qb.get())
+        .collect::<CoreResult<Vec<PrismaQuery>>>()?;
+
+    Ok((selected_fields, nested, builder.typename))
+}
+
+/// Finds the list relation field a nested connection field wraps, e.g. `posts` for `postsConnection`
+fn find_connection_field(model: &ModelRef, name: &str) -> Option<RelationFieldRef> {
+    if !name.ends_with(ConnectionSelection::SUFFIX) {
+        return None;
+    }
+
+    let name = &name[..name.len() - ConnectionSelection::SUFFIX.len()];
+
+    model
+        .fields()
+        .find_from_relation_fields(name)
+        .ok()
+        .filter(|field| field.is_list)
+}
+
+/// Extracts the fields of the `distinct` argument, a field or a list of fields, e.g. `distinct: [country, status]`.
+fn extract_distinct(model: ModelRef, value: &Value) -> CoreResult<Vec<ScalarFieldRef>> {
+    let values = match value {
+        Value::List(values) => values.iter().collect(),
+        value => vec![value],
+    };
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::Enum(name) => model
+                .fields()
+                .find_from_scalar(name)
+                .ok()
+                .filter(|field| !field.is_list)
+                .ok_or_else(|| {
+                    CoreError::QueryValidationError(format!(
+                        "Unknown distinct field `{}` on model `{}`",
+                        name, model.name
+                    ))
+                }),
+            _ => Err(CoreError::QueryValidationError(format!(
+                "Expected a field of model `{}` to be distinct by",
+                model.name
+            ))),
+        })
+        .collect()
+}
+
+/// Extracts the aggregations of an `aggregate` selection, e.g. `count sum { price }`
+fn extract_aggregations(model: &ModelRef, aggregate: &gql::query::Field) -> CoreResult<Vec<Aggregation>> {
+    let mut aggregations = Vec::new();
+
+    for function in connection_fields(&aggregate.selection_set.items) {
+        if function.name == TYPENAME_FIELD {
+            continue;
+        }
+
+        if function.name == "count" {
+            aggregations.push(Aggregation::Count);
+            continue;
+        }
+
+        for selected in connection_fields(&function.selection_set.items).filter(|f| f.name != TYPENAME_FIELD) {
+            let field = model.fields().find_from_scalar(&selected.name).map_err(|_| {
+                CoreError::QueryValidationError(format!("Unknown field `{}` on model `{}`", selected.name, model.name))
+            })?;
+
+            let aggregation = match function.name.as_str() {
+                "sum" if Aggregation::is_numeric(&field) => Aggregation::Sum(field),
+                "avg" if Aggregation::is_numeric(&field) => Aggregation::Avg(field),
+                "min" if Aggregation::is_comparable(&field) => Aggregation::Min(field),
+                "max" if Aggregation::is_comparable(&field) => Aggregation::Max(field),
+                name => {
+                    return Err(CoreError::QueryValidationError(format!(
+                        "Field `{}` on model `{}` can't be aggregated with `{}`",
+                        field.name, model.name, name
+                    )))
                 }
+            };
 
-                // Regular query
-                Definition::Operation(OperationDefinition::Query(Query {
-                    position: _,
-                    name: _,
-                    variable_definitions: _,
-                    directives: _,
-                    selection_set,
-                })) => self.build_query(&selection_set.items),
-                _ => unimplemented!(),
-            })
-            .collect::<CoreResult<Vec<Vec<PrismaQuery>>>>() // Collect all the "query trees"
-            .map(|v| v.into_iter().flatten().collect())
+            aggregations.push(aggregation);
+        }
     }
 
-    fn build_query(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<PrismaQuery>> {
-        root_fields
+    Ok(aggregations)
+}
+
+/// The root fields of an operation that aren't meta fields
+pub(crate) fn data_fields(root_fields: &[Selection]) -> impl Iterator<Item = &gql::query::Field> {
+    connection_fields(root_fields).filter(|field| !is_meta_field(field))
+}
+
+fn connection_fields(selections: &[Selection]) -> impl Iterator<Item = &gql::query::Field> {
+    selections.iter().map(|selection| match selection {
+        Selection::Field(field) => field,
+        _ => unreachable!("Fragments are inlined before building"),
+    })
+}
+
+/// Extracts a `NodeSelector` from a unique where input, e.g. `{ id: "..." }`, or
+/// `{ tenantId_slug: { tenantId: "...", slug: "..." } }` for a multi-field unique
+/// constraint.
+pub(crate) fn extract_node_selector(model: ModelRef, value: &Value) -> CoreResult<NodeSelector> {
+    match value {
+        Value::Object(obj) if obj.len() == 1 => {
+            let (field_name, value) = obj.iter().next().unwrap();
+
+            if let Ok(constraint) = model.find_unique_constraint(field_name) {
+                return extract_compound_selector(&model, constraint, value);
+            }
+
+            let field = model.fields().find_from_scalar(field_name).map_err(|_| {
+                CoreError::QueryValidationError(format!("Unknown field `{}` on model `{}`", field_name, model.name))
+            })?;
+
+            if !field.is_unique && !field.is_id() {
+                return Err(CoreError::QueryValidationError(format!(
+                    "Field `{}` on model `{}` is not unique",
+                    field_name, model.name
+                )));
+            }
+
+            let value = coerce_value(&field, value)?;
+            Ok(NodeSelector::new(field, value))
+        }
+        _ => Err(CoreError::QueryValidationError(format!(
+            "Expected an object with exactly one unique field of model `{}`",
+            model.name
+        ))),
+    }
+}
+
+/// The values of all fields of the unique constraint select the node.
+fn extract_compound_selector(model: &Model, constraint: &UniqueConstraint, value: &Value) -> CoreResult<NodeSelector> {
+    let invalid = || {
+        CoreError::QueryValidationError(format!(
+            "Expected an object with exactly the fields {} of unique constraint `{}` on model `{}`",
+            constraint.fields.join(", "),
+            constraint.name,
+            model.name
+        ))
+    };
+
+    let obj = match value {
+        Value::Object(obj) if obj.len() == constraint.fields.len() => obj,
+        _ => return Err(invalid()),
+    };
+
+    let fields = model
+        .unique_constraint_fields(constraint)?
+        .into_iter()
+        .map(|field| {
+            let value = obj.get(&field.name).ok_or_else(invalid)?;
+            let value = coerce_value(&field, value)?;
+
+            Ok((field, value))
+        })
+        .collect::<CoreResult<Vec<_>>>()?;
+
+    Ok(NodeSelector::from(CompoundNodeSelector::from_fields(model, fields)?))
+}
+
+/// Converts a GraphQL input value into a `PrismaValue` of the type of the given field.
+/// List fields accept lists of values of the field type.
+pub(crate) fn coerce_value(field: &ScalarField, value: &Value) -> CoreResult<PrismaValue> {
+    match value {
+        Value::List(values) if field.is_list => values
             .iter()
-            .map(|item| {
-                // First query-level fields map to a model in our schema, either a plural or singular
-                match item {
-                    Selection::Field(root_field) => QueryBuilder::new(Arc::clone(&self.schema), root_field)
-                        .infer_query_type(None)
-                        .process_arguments()
-                        .map_selected_scalar_fields()
-                        .collect_nested_queries()
-                        .get(), // Q: Since we never really give any args, and we always have to call these fns, we should just to it internally and call .get
-                    _ => unimplemented!(),
-                }
-            })
-            .collect()
+            .map(|value| coerce_scalar_value(field, value))
+            .collect::<CoreResult<Vec<PrismaValue>>>()
+            .map(PrismaValue::List),
+        value => coerce_scalar_value(field, value),
+    }
+}
+
+fn coerce_scalar_value(field: &ScalarField, value: &Value) -> CoreResult<PrismaValue> {
+    let invalid = || {
+        CoreError::QueryValidationError(format!(
+            "Invalid value for field `{}` of type `{}`",
+            field.name,
+            field.type_identifier.user_friendly_type_name()
+        ))
+    };
+
+    let as_int = |num: &Number| {
+        num.as_i64()
+            .filter(|i| *i >= i64::from(std::i32::MIN) && *i <= i64::from(std::i32::MAX))
+            .map(|i| i as i32)
+            .ok_or_else(invalid)
+    };
+
+    match (field.type_identifier, value) {
+        (_, Value::Null) => Ok(PrismaValue::Null),
+        (_, Value::Variable(name)) => Err(CoreError::QueryValidationError(format!(
+            "Variable `${}` is not defined",
+            name
+        ))),
+        (TypeIdentifier::String, Value::String(s)) => Ok(PrismaValue::String(s.clone())),
+        (TypeIdentifier::Int, Value::Int(num)) => as_int(num).map(PrismaValue::Int),
+        (TypeIdentifier::Float, Value::Float(f)) => Ok(PrismaValue::Float(*f)),
+        (TypeIdentifier::Float, Value::Int(num)) => as_int(num).map(|i| PrismaValue::Float(f64::from(i))),
+        (TypeIdentifier::Boolean, Value::Boolean(b)) => Ok(PrismaValue::Boolean(*b)),
+        (TypeIdentifier::Enum, Value::Enum(e)) | (TypeIdentifier::Enum, Value::String(e)) => {
+            Ok(PrismaValue::Enum(e.clone()))
+        }
+        (TypeIdentifier::Json, Value::String(s)) => Ok(PrismaValue::Json(s.clone())),
+        (TypeIdentifier::DateTime, Value::String(s)) => DateTime::parse_from_rfc3339(s)
+            .map(|dt| PrismaValue::DateTime(dt.with_timezone(&Utc)))
+            .map_err(|_| invalid()),
+        (TypeIdentifier::GraphQLID, Value::String(s)) => Ok(PrismaValue::GraphqlId(GraphqlId::String(s.clone()))),
+        (TypeIdentifier::GraphQLID, Value::Int(num)) => match as_int(num)? {
+            i if i >= 0 => Ok(PrismaValue::GraphqlId(GraphqlId::Int(i as usize))),
+            _ => Err(invalid()),
+        },
+        (TypeIdentifier::UUID, Value::String(s)) => Uuid::parse_str(s).map(PrismaValue::Uuid).map_err(|_| invalid()),
+        _ => Err(invalid()),
     }
 }
 
@@ -1,47 +1,114 @@
-use crate::{query_ast, CoreResult};
-use connector::DataResolver;
-use prisma_models::{GraphqlId, ManyNodes, SelectedFields, SingleNode};
+use crate::{query_ast, CoreError, CoreResult};
+use connector::{DataResolver, QueryArguments};
+use prisma_models::{GraphqlId, ManyNodes, ModelRef, PrismaValue, RelationFieldRef, SelectedFields, SingleNode};
 use query_ast::*;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PrismaQueryResult {
     Single(SinglePrismaQueryResult),
     Multi(MultiPrismaQueryResult),
+    Failed(FailedPrismaQueryResult),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SinglePrismaQueryResult {
     pub name: String,
     pub result: Option<SingleNode>,
     pub nested: Vec<PrismaQueryResult>,
 
+    /// The type name `__typename` resolves to, if selected
+    pub typename: Option<String>,
+
     /// Used for filtering implicit fields in result node
     selected_fields: SelectedFields,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MultiPrismaQueryResult {
     pub name: String,
     pub result: ManyNodes,
-    pub nested: Vec<PrismaQueryResult>,
+
+    /// Nested results of each node, in the order of the nodes
+    pub nested: Vec<Vec<PrismaQueryResult>>,
+
+    /// Set if the nodes were queried through a connection field
+    pub connection: Option<ConnectionResult>,
+
+    /// The type name `__typename` of the nodes resolves to, if selected
+    pub typename: Option<String>,
 
     /// Used for filtering implicit fields in result nodes
     selected_fields: SelectedFields,
 }
 
+/// A nested query that couldn't be resolved for a parent. The error is
+/// reported at the position of the query in the response.
+#[derive(Debug, Clone)]
+pub struct FailedPrismaQueryResult {
+    pub name: String,
+    pub parent_field: RelationFieldRef,
+
+    /// Set if the nodes were queried through a connection field
+    pub connection: bool,
+
+    /// Shared by all parents, nested queries being resolved once for all of them
+    pub error: Arc<CoreError>,
+}
+
+/// The parts of a connection besides its nodes
+#[derive(Debug, Clone)]
+pub struct ConnectionResult {
+    pub selection: ConnectionSelection,
+
+    /// Cursors of the nodes, which are their ids
+    pub cursors: Vec<GraphqlId>,
+
+    /// Results of the selected aggregations over all nodes matching the filter, if `aggregate` is selected
+    pub aggregates: Option<Vec<PrismaValue>>,
+}
+
 impl PrismaQueryResult {
     /// Filters implicitly selected fields from the result set.
     pub fn filter(self) -> Self {
         match self {
             PrismaQueryResult::Single(s) => PrismaQueryResult::Single(s.filter()),
             PrismaQueryResult::Multi(m) => PrismaQueryResult::Multi(m.filter()),
+            PrismaQueryResult::Failed(f) => PrismaQueryResult::Failed(f),
+        }
+    }
+
+    /// Whether the field of a nested query can be `null` in the response. To-one
+    /// relations can unless they are required, lists of related nodes always
+    /// can and connections can't. Root fields are nullable as the schema says.
+    pub fn is_nullable(&self) -> bool {
+        let (parent_field, connection) = match self {
+            PrismaQueryResult::Single(s) => (s.selected_fields.from_field.as_ref(), false),
+            PrismaQueryResult::Multi(m) => (m.selected_fields.from_field.as_ref(), m.connection.is_some()),
+            PrismaQueryResult::Failed(f) => (Some(&f.parent_field), f.connection),
+        };
+
+        match parent_field {
+            Some(field) if field.is_list => !connection,
+            Some(field) => !field.is_required,
+            None => true,
         }
     }
 }
 
 // Q: Best pattern here? Mix of in place mutation and recreating result
 impl SinglePrismaQueryResult {
+    /// Creates a result that isn't backed by a model selection, e.g. the count of a batch mutation.
+    pub(crate) fn without_selection(name: String, result: SingleNode, typename: Option<String>) -> Self {
+        Self {
+            name,
+            result: Some(result),
+            nested: vec![],
+            typename,
+            selected_fields: SelectedFields::default(),
+        }
+    }
+
     /// Filters implicitly selected fields in-place in the result node and field names.
     /// Traverses nested result tree.
     pub fn filter(self) -> Self {
@@ -67,7 +134,24 @@ impl SinglePrismaQueryResult {
     }
 }
 
+impl FailedPrismaQueryResult {
+    fn new(name: &str, parent_field: &RelationFieldRef, connection: bool, error: CoreError) -> Self {
+        Self {
+            name: name.to_owned(),
+            parent_field: Arc::clone(parent_field),
+            connection,
+            error: Arc::new(error),
+        }
+    }
+}
+
 impl MultiPrismaQueryResult {
+    /// Nodes of related nodes can't be `null` in the response, the ones of root
+    /// fields can.
+    pub fn has_nullable_nodes(&self) -> bool {
+        self.selected_fields.from_field.is_none()
+    }
+
     /// Filters implicitly selected fields in-place in the result nodes and field names.
     /// Traverses nested result tree.
     pub fn filter(mut self) -> Self {
@@ -100,7 +184,11 @@ impl MultiPrismaQueryResult {
             .collect();
 
         let result = ManyNodes { nodes, ..self.result };
-        let nested = self.nested.into_iter().map(|nested| nested.filter()).collect();
+        let nested = self
+            .nested
+            .into_iter()
+            .map(|nested| nested.into_iter().map(|nested| nested.filter()).collect())
+            .collect();
 
         Self { result, nested, ..self }
     }
@@ -113,121 +201,395 @@ pub struct QueryExecutor {
 impl QueryExecutor {
     // WIP
     pub fn execute(&self, queries: &[PrismaQuery]) -> CoreResult<Vec<PrismaQueryResult>> {
-        self.execute_internal(queries, vec![])
-    }
-
-    #[allow(unused_variables)]
-    fn execute_internal(
-        &self,
-        queries: &[PrismaQuery],
-        parent_ids: Vec<GraphqlId>,
-    ) -> CoreResult<Vec<PrismaQueryResult>> {
         let mut results = vec![];
+
         for query in queries {
             match query {
                 PrismaQuery::RecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
-
-                    let result = self
-                        .data_resolver
-                        .get_node_by_where(&query.selector, &selected_fields)?;
-
-                    match result {
+                    let model = query.selector.model();
+                    let selected_fields =
+                        Self::inject_required_fields(Arc::clone(&model), query.selected_fields.clone());
+
+                    let result = match self.data_resolver.get_node_by_where(&query.selector, &selected_fields)? {
+                        Some(node) => Some(self.resolve_scalar_list_fields_single(
+                            Arc::clone(&model),
+                            &selected_fields,
+                            node,
+                        )?),
+                        None => None,
+                    };
+
+                    // A missing node is still answered, as `null`
+                    let nested = match result {
                         Some(ref node) => {
-                            let model = Arc::clone(&query.selector.field.model());
                             let ids = vec![node.get_id_value(model)?.clone()];
-
-                            let nested = self.execute_internal(&query.nested, ids)?;
-                            let result = SinglePrismaQueryResult {
-                                name: query.name.clone(),
-                                result,
-                                nested,
-                                selected_fields,
-                            };
-                            results.push(PrismaQueryResult::Single(result));
+                            self.execute_nested(&query.nested, &ids).pop().unwrap_or_else(Vec::new)
                         }
-                        None => (),
-                    }
-                }
-                PrismaQuery::MultiRecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
+                        None => vec![],
+                    };
 
-                    let result =
-                        self.data_resolver
-                            .get_nodes(Arc::clone(&query.model), query.args.clone(), &selected_fields)?;
-
-                    // FIXME: Rewrite to not panic and also in a more functional way!
-                    let ids = result.get_id_values(Arc::clone(&query.model))?;
-                    let nested = result.nodes.iter().fold(vec![], |mut vec, node| {
-                        vec.append(&mut self.execute_internal(&query.nested, ids.clone()).unwrap());
-                        vec
-                    });
-
-                    results.push(PrismaQueryResult::Multi(MultiPrismaQueryResult {
+                    results.push(PrismaQueryResult::Single(SinglePrismaQueryResult {
                         name: query.name.clone(),
                         result,
                         nested,
+                        typename: query.typename.clone(),
                         selected_fields,
                     }));
                 }
-                PrismaQuery::RelatedRecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
-
-                    let result = self.data_resolver.get_related_nodes(
-                        Arc::clone(&query.parent_field),
-                        &parent_ids,
-                        query.args.clone(),
-                        &selected_fields,
-                    )?;
-
-                    // FIXME: Required fields need to return Errors, non-required can be ignored!
-                    if let Some(node) = result.into_single_node() {
-                        let ids = vec![node.get_id_value(query.parent_field.related_model())?.clone()];
-                        let nested = self.execute_internal(&query.nested, ids)?;
-                        let result = SinglePrismaQueryResult {
-                            name: query.name.clone(),
-                            result: Some(node),
-                            nested,
-                            selected_fields,
-                        };
-                        results.push(PrismaQueryResult::Single(result));
-                    }
-                }
-                PrismaQuery::MultiRelatedRecordQuery(query) => {
-                    let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
-
-                    let result = self.data_resolver.get_related_nodes(
-                        Arc::clone(&query.parent_field),
-                        &parent_ids,
-                        query.args.clone(),
-                        &selected_fields,
-                    )?;
-
-                    // FIXME: Rewrite to not panic and also in a more functional way!
-                    let ids = result.get_id_values(Arc::clone(&query.parent_field.related_model()))?;
-                    let nested = result.nodes.iter().fold(vec![], |mut vec, node| {
-                        vec.append(&mut self.execute_internal(&query.nested, ids.clone()).unwrap());
-                        vec
-                    });
+                PrismaQuery::MultiRecordQuery(query) => {
+                    let model = Arc::clone(&query.model);
+                    let selected_fields =
+                        Self::inject_required_fields(Arc::clone(&model), query.selected_fields.clone());
+
+                    let mut result =
+                        self.data_resolver
+                            .get_nodes(Arc::clone(&model), query.args.clone(), &selected_fields)?;
+
+                    self.resolve_scalar_list_fields(Arc::clone(&model), &selected_fields, &mut result)?;
+
+                    let ids = result.get_id_values(Arc::clone(&model))?;
+                    let nested = self.execute_nested(&query.nested, &ids);
+                    let (result, nested) = Self::paginate(&query.args, result, nested);
+
+                    let connection = match query.connection {
+                        Some(ref selection) => {
+                            let aggregates = match selection.aggregate {
+                                Some(ref aggregations) => {
+                                    let args = Self::count_arguments(&query.args);
+                                    let groups =
+                                        self.data_resolver
+                                            .aggregate(Arc::clone(&model), args, aggregations, &[])?;
+
+                                    groups.into_iter().next().map(|group| group.values)
+                                }
+                                None => None,
+                            };
+
+                            Some(Self::connection_result(selection, model, &result, aggregates)?)
+                        }
+                        None => None,
+                    };
 
                     results.push(PrismaQueryResult::Multi(MultiPrismaQueryResult {
                         name: query.name.clone(),
                         result,
                         nested,
+                        connection,
+                        typename: query.typename.clone(),
                         selected_fields,
                     }));
                 }
+                _ => unreachable!("Relation queries are only executed as nested queries"),
             }
         }
 
         Ok(results)
     }
 
+    /// Executes each nested query once for all parents.
+    /// Returns the nested results of every parent, in the order of `parent_ids`.
+    /// A failing query fails for all parents, without failing the parents themselves.
+    fn execute_nested(&self, queries: &[PrismaQuery], parent_ids: &[GraphqlId]) -> Vec<Vec<PrismaQueryResult>> {
+        let mut results: Vec<Vec<PrismaQueryResult>> = parent_ids.iter().map(|_| vec![]).collect();
+
+        for query in queries {
+            let (name, parent_field, connection, query_results) = match query {
+                PrismaQuery::RelatedRecordQuery(query) => (
+                    &query.name,
+                    &query.parent_field,
+                    false,
+                    self.execute_related_record(query, parent_ids),
+                ),
+                PrismaQuery::MultiRelatedRecordQuery(query) => (
+                    &query.name,
+                    &query.parent_field,
+                    query.connection.is_some(),
+                    self.execute_multi_related_record(query, parent_ids),
+                ),
+                _ => unreachable!("Root queries can't be nested"),
+            };
+
+            let query_results = query_results.unwrap_or_else(|error| {
+                let failed = FailedPrismaQueryResult::new(name, parent_field, connection, error);
+                parent_ids
+                    .iter()
+                    .map(|_| PrismaQueryResult::Failed(failed.clone()))
+                    .collect()
+            });
+
+            for (parent_results, result) in results.iter_mut().zip(query_results) {
+                parent_results.push(result);
+            }
+        }
+
+        results
+    }
+
+    /// The related node of each parent. A required relation without a node fails.
+    fn execute_related_record(
+        &self,
+        query: &RelatedRecordQuery,
+        parent_ids: &[GraphqlId],
+    ) -> CoreResult<Vec<PrismaQueryResult>> {
+        let model = query.parent_field.related_model();
+        let selected_fields = Self::inject_required_fields(model, query.selected_fields.clone());
+        let groups = self.execute_related(
+            &query.parent_field,
+            &query.args,
+            &selected_fields,
+            &query.nested,
+            parent_ids,
+        )?;
+
+        let results = groups
+            .into_iter()
+            .map(|(result, nested)| match result.into_single_node() {
+                None if query.parent_field.is_required => {
+                    let field = &query.parent_field;
+                    let error = CoreError::NullForNonNullableField(format!("{}.{}", field.model().name, field.name));
+
+                    PrismaQueryResult::Failed(FailedPrismaQueryResult::new(&query.name, field, false, error))
+                }
+                result => PrismaQueryResult::Single(SinglePrismaQueryResult {
+                    name: query.name.clone(),
+                    result,
+                    nested: nested.into_iter().next().unwrap_or_else(Vec::new),
+                    typename: query.typename.clone(),
+                    selected_fields: selected_fields.clone(),
+                }),
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    /// The related nodes of each parent, paginated for each of them.
+    fn execute_multi_related_record(
+        &self,
+        query: &MultiRelatedRecordQuery,
+        parent_ids: &[GraphqlId],
+    ) -> CoreResult<Vec<PrismaQueryResult>> {
+        let model = query.parent_field.related_model();
+        let selected_fields = Self::inject_required_fields(Arc::clone(&model), query.selected_fields.clone());
+        let groups = self.execute_related(
+            &query.parent_field,
+            &query.args,
+            &selected_fields,
+            &query.nested,
+            parent_ids,
+        )?;
+
+        // Relation connections only aggregate counts
+        let counts = match query.connection {
+            Some(ref selection) if selection.aggregate.is_some() => {
+                Some(self.count_related(&query.parent_field, &query.args, parent_ids)?)
+            }
+            _ => None,
+        };
+
+        let mut results = vec![];
+
+        for (position, (result, nested)) in groups.into_iter().enumerate() {
+            // Pagination applies to the nodes of each parent
+            let (result, nested) = Self::paginate(&query.args, result, nested);
+
+            let connection = match query.connection {
+                Some(ref selection) => {
+                    let aggregates = match (&selection.aggregate, &counts) {
+                        (Some(aggregations), Some(counts)) => {
+                            Some(vec![PrismaValue::Int(counts[position] as i32); aggregations.len()])
+                        }
+                        _ => None,
+                    };
+
+                    Some(Self::connection_result(
+                        selection,
+                        Arc::clone(&model),
+                        &result,
+                        aggregates,
+                    )?)
+                }
+                None => None,
+            };
+
+            results.push(PrismaQueryResult::Multi(MultiPrismaQueryResult {
+                name: query.name.clone(),
+                result,
+                nested,
+                connection,
+                typename: query.typename.clone(),
+                selected_fields: selected_fields.clone(),
+            }));
+        }
+
+        Ok(results)
+    }
+
+    /// Resolves the related nodes of all parents with a single query, then their nested queries.
+    /// Nodes are grouped back to their parents by the parent id they were read with,
+    /// a node related to several parents is part of each group.
+    fn execute_related(
+        &self,
+        parent_field: &RelationFieldRef,
+        args: &QueryArguments,
+        selected_fields: &SelectedFields,
+        nested: &[PrismaQuery],
+        parent_ids: &[GraphqlId],
+    ) -> CoreResult<Vec<(ManyNodes, Vec<Vec<PrismaQueryResult>>)>> {
+        let model = parent_field.related_model();
+        let mut result =
+            self.data_resolver
+                .get_related_nodes(Arc::clone(parent_field), parent_ids, args.clone(), selected_fields)?;
+
+        self.resolve_scalar_list_fields(Arc::clone(&model), selected_fields, &mut result)?;
+
+        let ids = result.get_id_values(model)?;
+        let nested = self.execute_nested(nested, &ids);
+
+        let mut positions: HashMap<&GraphqlId, Vec<usize>> = HashMap::new();
+        for (position, id) in parent_ids.iter().enumerate() {
+            positions.entry(id).or_insert_with(Vec::new).push(position);
+        }
+
+        let mut groups: Vec<(ManyNodes, Vec<Vec<PrismaQueryResult>>)> = parent_ids
+            .iter()
+            .map(|_| {
+                let nodes = ManyNodes::new(vec![], result.field_names.clone());
+                (nodes, vec![])
+            })
+            .collect();
+
+        for (node, nested) in result.nodes.into_iter().zip(nested) {
+            let parent_positions = node.parent_id.as_ref().and_then(|id| positions.get(id));
+
+            for &position in parent_positions.into_iter().flatten() {
+                groups[position].0.nodes.push(node.clone());
+                groups[position].1.push(nested.clone());
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Counts the related nodes matching the filter of each parent, in the order of `parent_ids`
+    fn count_related(
+        &self,
+        parent_field: &RelationFieldRef,
+        args: &QueryArguments,
+        parent_ids: &[GraphqlId],
+    ) -> CoreResult<Vec<usize>> {
+        let counts = self.data_resolver.count_related_nodes(
+            Arc::clone(parent_field),
+            parent_ids,
+            Self::count_arguments(args),
+        )?;
+
+        Ok(counts)
+    }
+
+    /// Aggregates count all nodes matching the filter, regardless of pagination.
+    /// Distinct nodes are the first ones in the order of the query.
+    fn count_arguments(args: &QueryArguments) -> QueryArguments {
+        QueryArguments {
+            filter: args.filter.clone(),
+            order_by: args.order_by.clone(),
+            distinct: args.distinct.clone(),
+            ..QueryArguments::default()
+        }
+    }
+
+    /// Drops the node read to tell whether there are more pages, together with its nested results,
+    /// see `QueryArguments::paginate`.
+    fn paginate(
+        args: &QueryArguments,
+        result: ManyNodes,
+        nested: Vec<Vec<PrismaQueryResult>>,
+    ) -> (ManyNodes, Vec<Vec<PrismaQueryResult>>) {
+        let items: Vec<_> = result.nodes.into_iter().zip(nested).collect();
+        let (items, has_more) = args.paginate(items);
+        let (nodes, nested) = items.into_iter().unzip();
+
+        let result = ManyNodes {
+            nodes,
+            has_next_page: has_more && args.last.is_none(),
+            has_previous_page: has_more && args.last.is_some(),
+            ..result
+        };
+
+        (result, nested)
+    }
+
+    fn connection_result(
+        selection: &ConnectionSelection,
+        model: ModelRef,
+        result: &ManyNodes,
+        aggregates: Option<Vec<PrismaValue>>,
+    ) -> CoreResult<ConnectionResult> {
+        Ok(ConnectionResult {
+            selection: selection.clone(),
+            cursors: result.get_id_values(model)?,
+            aggregates,
+        })
+    }
+
+    /// Resolves the selected scalar list fields of the nodes with one call per field
+    /// and appends their values to the nodes, in list order.
+    fn resolve_scalar_list_fields(
+        &self,
+        model: ModelRef,
+        selected_fields: &SelectedFields,
+        result: &mut ManyNodes,
+    ) -> CoreResult<()> {
+        let list_fields = selected_fields.scalar_lists();
+
+        if list_fields.is_empty() {
+            return Ok(());
+        }
+
+        let ids = result.get_id_values(model)?;
+
+        // Field names of related nodes end with relation columns that have no values,
+        // list values are placed right after the values read from the row.
+        let offset = selected_fields.type_identifiers().len();
+
+        for (i, field) in list_fields.into_iter().enumerate() {
+            let values: HashMap<GraphqlId, Vec<PrismaValue>> = self
+                .data_resolver
+                .get_scalar_list_values_by_node_ids(Arc::clone(&field), ids.clone())?
+                .into_iter()
+                .map(|list| (list.node_id, list.values))
+                .collect();
+
+            // Nodes without any values have an empty list.
+            // The same node may occur more than once, once for each parent it's related to.
+            result.field_names.insert(offset + i, field.name.clone());
+            result.nodes.iter_mut().zip(ids.iter()).for_each(|(node, id)| {
+                let list = values.get(id).cloned().unwrap_or_else(Vec::new);
+                node.values.push(PrismaValue::List(list));
+            });
+        }
+
+        Ok(())
+    }
+
+    fn resolve_scalar_list_fields_single(
+        &self,
+        model: ModelRef,
+        selected_fields: &SelectedFields,
+        node: SingleNode,
+    ) -> CoreResult<SingleNode> {
+        let mut result = ManyNodes::new(vec![node.node], node.field_names);
+
+        self.resolve_scalar_list_fields(model, selected_fields, &mut result)?;
+
+        Ok(result.into_single_node().expect("The node was resolved before"))
+    }
+
     /// Injects fields required for querying, if they're not already in the selection set.
     /// Currently, required fields for every query are:
     /// - ID field
-    fn inject_required_fields(mut selected_fields: SelectedFields) -> SelectedFields {
-        let id_field = selected_fields.model().fields().id();
+    fn inject_required_fields(model: ModelRef, mut selected_fields: SelectedFields) -> SelectedFields {
+        let id_field = model.fields().id();
         if let None = selected_fields.scalar.iter().find(|f| f.field.name == id_field.name) {
             selected_fields.add_scalar(id_field.into(), true);
         };
@@ -0,0 +1,95 @@
+//! Helpers for the tests of the query and mutation builders and executors,
+//! running against the schema of `test_schema.json` in the SQLite connector.
+
+use crate::{
+    CoreResult, MutationExecutor, Operation, PrismaMutation, PrismaQueryResult, QueryExecutor, RootQueryBuilder,
+};
+use prisma_models::{PrismaValue, SchemaRef, SchemaTemplate};
+use sqlite_connector::Sqlite;
+use std::{collections::BTreeMap, fs::File, sync::Arc};
+
+pub fn schema(db_name: &str) -> SchemaRef {
+    let template: SchemaTemplate =
+        serde_json::from_reader(File::open("../connectors/sqlite-connector/test_schema.json").unwrap()).unwrap();
+
+    template.build(String::from(db_name))
+}
+
+/// Builds the operation of the given document, without variables.
+pub fn build(schema: SchemaRef, query: &str) -> CoreResult<Operation> {
+    RootQueryBuilder {
+        query: graphql_parser::parse_query(query).unwrap(),
+        schema,
+        operation_name: None,
+        variables: BTreeMap::new(),
+    }
+    .build()
+}
+
+/// Builds the single mutation of the given document.
+pub fn mutation(schema: SchemaRef, query: &str) -> CoreResult<PrismaMutation> {
+    match build(schema, query)? {
+        Operation::Write(mut mutations) => Ok(mutations.remove(0)),
+        Operation::Read(_) => panic!("Expected a mutation"),
+    }
+}
+
+/// A new, empty database with the tables of the schema, and executors on it.
+pub struct TestDatabase {
+    pub schema: SchemaRef,
+    pub query_executor: QueryExecutor,
+    pub mutation_executor: MutationExecutor,
+}
+
+impl TestDatabase {
+    pub fn new(db_name: &str) -> Self {
+        let _ = std::fs::create_dir_all("db");
+        let _ = std::fs::remove_file(format!("db/{}.db", db_name));
+
+        let schema = schema(db_name);
+        let sqlite = Arc::new(Sqlite::new(1, false).unwrap());
+        sqlite.create_tables(Arc::clone(&schema)).unwrap();
+
+        Self {
+            schema,
+            query_executor: QueryExecutor {
+                data_resolver: Arc::clone(&sqlite),
+            },
+            mutation_executor: MutationExecutor {
+                db_name: String::from(db_name),
+                database_executor: sqlite,
+            },
+        }
+    }
+
+    /// Builds and executes the operation of the given document.
+    pub fn execute(&self, query: &str) -> CoreResult<Vec<PrismaQueryResult>> {
+        match build(Arc::clone(&self.schema), query)? {
+            Operation::Read(queries) => self.query_executor.execute(&queries),
+            Operation::Write(mutations) => self.mutation_executor.execute(&mutations, &self.query_executor),
+        }
+    }
+
+    /// A field of each record returned by a query of multiple records.
+    pub fn find_all(&self, query: &str, field: &str) -> Vec<PrismaValue> {
+        match self.execute(query).unwrap().pop() {
+            Some(PrismaQueryResult::Multi(result)) => result
+                .result
+                .nodes
+                .iter()
+                .map(|node| node.get_field_value(&result.result.field_names, field).unwrap().clone())
+                .collect(),
+            result => panic!("Expected multiple records, got {:?}", result),
+        }
+    }
+
+    /// A field of the record returned by a query of a single record, if it was found.
+    pub fn find(&self, query: &str, field: &str) -> Option<PrismaValue> {
+        match self.execute(query).unwrap().pop() {
+            Some(PrismaQueryResult::Single(result)) => {
+                result.result.map(|node| node.get_field_value(field).unwrap().clone())
+            }
+            result => panic!("Expected a single record, got {:?}", result),
+        }
+    }
+}
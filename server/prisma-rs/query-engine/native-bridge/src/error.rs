@@ -45,10 +45,23 @@ impl From<serde_json::error::Error> for BridgeError {
 
 impl From<NodeSelectorInfo> for protobuf::prisma::NodeSelector {
     fn from(info: NodeSelectorInfo) -> Self {
+        let mut fields = info
+            .fields
+            .into_iter()
+            .map(|(field_name, value)| protobuf::prisma::SelectorField {
+                field_name,
+                value: value.into(),
+            });
+
+        // Node selectors select by at least one field, an empty one is
+        // reported with an empty field instead of failing the response.
+        let first = fields.next().unwrap_or_default();
+
         Self {
             model_name: info.model,
-            field_name: info.field,
-            value: info.value.into(),
+            field_name: first.field_name,
+            value: first.value,
+            compound_fields: fields.collect(),
         }
     }
 }
@@ -91,13 +104,7 @@ impl From<BridgeError> for protobuf::prisma::error::Value {
             }
 
             BridgeError::ConnectorError(ConnectorError::NodeNotFoundForWhere(info)) => {
-                let node_selector = protobuf::prisma::NodeSelector {
-                    model_name: info.model,
-                    field_name: info.field,
-                    value: info.value.into(),
-                };
-
-                protobuf::prisma::error::Value::NodeNotFoundForWhere(node_selector)
+                protobuf::prisma::error::Value::NodeNotFoundForWhere(protobuf::prisma::NodeSelector::from(info))
             }
 
             BridgeError::ConnectorError(ConnectorError::NodesNotConnected {
@@ -55,5 +55,6 @@ data_interface!(
     execute_raw,
     count_by_model,
     count_by_table,
+    aggregate,
     execute_mutaction
 );
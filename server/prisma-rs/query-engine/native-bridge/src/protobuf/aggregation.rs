@@ -0,0 +1,22 @@
+use crate::{
+    protobuf::prisma::{self, aggregation::Function},
+    BridgeResult,
+};
+use connector::Aggregation;
+use prisma_models::ModelRef;
+
+/// Functions besides `count` need the name of a scalar field.
+pub fn into_model_aggregation(model: &ModelRef, aggregation: prisma::Aggregation) -> BridgeResult<Aggregation> {
+    let field_name = aggregation.field.clone().unwrap_or_default();
+    let field = || model.fields().find_from_scalar(&field_name);
+
+    let aggregation = match aggregation.function() {
+        Function::Count => Aggregation::Count,
+        Function::Sum => Aggregation::Sum(field()?),
+        Function::Avg => Aggregation::Avg(field()?),
+        Function::Min => Aggregation::Min(field()?),
+        Function::Max => Aggregation::Max(field()?),
+    };
+
+    Ok(aggregation)
+}
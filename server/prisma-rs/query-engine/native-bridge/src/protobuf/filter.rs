@@ -1,68 +1,63 @@
 use super::prisma as pb;
+use crate::{BridgeError, BridgeResult};
 use connector::filter::*;
 use prisma_models::prelude::*;
 
 pub trait IntoFilter {
-    fn into_filter(self, model: ModelRef) -> Filter;
+    fn into_filter(self, model: ModelRef) -> BridgeResult<Filter>;
 }
 
 impl IntoFilter for pb::Filter {
-    fn into_filter(self, model: ModelRef) -> Filter {
-        match self.type_.unwrap() {
+    fn into_filter(self, model: ModelRef) -> BridgeResult<Filter> {
+        let type_ = self
+            .type_
+            .ok_or(BridgeError::InvalidConnectionArguments("Filters need a type"))?;
+
+        let filter = match type_ {
             pb::filter::Type::And(mut and) => match and.filters.len() {
-                1 => and.filters.pop().unwrap().into_filter(model),
-                _ => {
-                    let filters = and
-                        .filters
-                        .into_iter()
-                        .map(|filter| Box::new(filter.into_filter(model.clone())))
-                        .collect();
-
-                    Filter::And(filters)
-                }
+                1 => and.filters.pop().unwrap().into_filter(model)?,
+                _ => Filter::And(into_boxed_filters(and.filters, model)?),
             },
             pb::filter::Type::Or(mut or) => match or.filters.len() {
-                1 => or.filters.pop().unwrap().into_filter(model),
-                _ => {
-                    let filters = or
-                        .filters
-                        .into_iter()
-                        .map(|filter| Box::new(filter.into_filter(model.clone())))
-                        .collect();
-
-                    Filter::Or(filters)
-                }
+                1 => or.filters.pop().unwrap().into_filter(model)?,
+                _ => Filter::Or(into_boxed_filters(or.filters, model)?),
             },
-            pb::filter::Type::Not(not) => Filter::Not(
-                not.filters
-                    .into_iter()
-                    .map(|filter| Box::new(filter.into_filter(model.clone())))
-                    .collect(),
-            ),
-            pb::filter::Type::Scalar(scalar) => scalar.into_filter(model),
-            pb::filter::Type::ScalarList(scalar_list) => scalar_list.into_filter(model),
+            pb::filter::Type::Not(not) => Filter::Not(into_boxed_filters(not.filters, model)?),
+            pb::filter::Type::Scalar(scalar) => scalar.into_filter(model)?,
+            pb::filter::Type::ScalarList(scalar_list) => scalar_list.into_filter(model)?,
             pb::filter::Type::OneRelationIsNull(relation_field) => {
                 let field = model
                     .fields()
-                    .find_from_relation_fields(relation_field.field.as_ref())
-                    .unwrap();
+                    .find_from_relation_fields(relation_field.field.as_ref())?;
 
                 Filter::OneRelationIsNull(OneRelationIsNullFilter { field })
             }
-            pb::filter::Type::Relation(relation_filter) => relation_filter.into_filter(model),
+            pb::filter::Type::Relation(relation_filter) => relation_filter.into_filter(model)?,
             pb::filter::Type::NodeSubscription(_) => Filter::NodeSubscription,
             pb::filter::Type::BoolFilter(boo) => Filter::BoolFilter(boo),
-        }
+        };
+
+        Ok(filter)
     }
 }
 
 impl IntoFilter for pb::ScalarFilter {
-    fn into_filter(self, model: ModelRef) -> Filter {
+    fn into_filter(self, model: ModelRef) -> BridgeResult<Filter> {
         use pb::scalar_filter::Condition::*;
 
-        let field = model.fields().find_from_scalar(self.field.as_ref()).unwrap();
+        let field = model.fields().find_from_scalar(self.field.as_ref())?;
 
-        let condition = match self.condition.unwrap() {
+        let mode = match self.mode() {
+            pb::scalar_filter::FilterMode::Default => FilterMode::Default,
+            pb::scalar_filter::FilterMode::Insensitive => FilterMode::Insensitive,
+            pb::scalar_filter::FilterMode::Sensitive => FilterMode::Sensitive,
+        };
+
+        let condition = self.condition.ok_or(BridgeError::InvalidConnectionArguments(
+            "Scalar filters need a condition",
+        ))?;
+
+        let condition = match condition {
             Equals(value) => ScalarCondition::Equals(value.into()),
             NotEquals(value) => ScalarCondition::NotEquals(value.into()),
             Contains(value) => ScalarCondition::Contains(value.into()),
@@ -71,6 +66,33 @@ impl IntoFilter for pb::ScalarFilter {
             NotStartsWith(value) => ScalarCondition::NotStartsWith(value.into()),
             EndsWith(value) => ScalarCondition::EndsWith(value.into()),
             NotEndsWith(value) => ScalarCondition::NotEndsWith(value.into()),
+            Like(value) => ScalarCondition::Like(value.into()),
+            Search(value) => ScalarCondition::Search(value.into()),
+            Json(json) => {
+                use pb::json_filter::Condition::*;
+
+                let path: JsonPath = json.path.parse()?;
+
+                let condition = json
+                    .condition
+                    .ok_or(BridgeError::InvalidConnectionArguments("JSON filters need a condition"))?;
+
+                match condition {
+                    PathEquals(value) => ScalarCondition::JsonPathEquals(path, value.into()),
+                    PathContains(value) => ScalarCondition::JsonPathContains(path, value.into()),
+                    ArrayContains(value) => ScalarCondition::JsonArrayContains(path, value.into()),
+                    KeyExists(true) => ScalarCondition::JsonKeyExists(path),
+                    KeyExists(false) => {
+                        let exists = ScalarFilter {
+                            field,
+                            condition: ScalarCondition::JsonKeyExists(path),
+                            mode,
+                        };
+
+                        return Ok(Filter::not(vec![Filter::Scalar(exists)]));
+                    }
+                }
+            }
             LessThan(value) => ScalarCondition::LessThan(value.into()),
             LessThanOrEquals(value) => ScalarCondition::LessThanOrEquals(value.into()),
             GreaterThan(value) => ScalarCondition::GreaterThan(value.into()),
@@ -79,17 +101,21 @@ impl IntoFilter for pb::ScalarFilter {
             NotIn(mc) => ScalarCondition::NotIn(mc.values.into_iter().map(|value| value.into()).collect()),
         };
 
-        Filter::Scalar(ScalarFilter { field, condition })
+        Ok(Filter::Scalar(ScalarFilter { field, condition, mode }))
     }
 }
 
 impl IntoFilter for pb::ScalarListFilter {
-    fn into_filter(self, model: ModelRef) -> Filter {
+    fn into_filter(self, model: ModelRef) -> BridgeResult<Filter> {
         use pb::scalar_list_condition::Condition::*;
 
-        let field = model.fields().find_from_scalar(self.field.as_ref()).unwrap();
+        let field = model.fields().find_from_scalar(self.field.as_ref())?;
 
-        let condition = match self.condition.condition.unwrap() {
+        let condition = self.condition.condition.ok_or(BridgeError::InvalidConnectionArguments(
+            "Scalar list filters need a condition",
+        ))?;
+
+        let condition = match condition {
             Contains(value) => ScalarListCondition::Contains(value.into()),
             ContainsEvery(values) => {
                 ScalarListCondition::ContainsEvery(values.values.into_iter().map(|value| value.into()).collect())
@@ -99,32 +125,54 @@ impl IntoFilter for pb::ScalarListFilter {
             }
         };
 
-        Filter::ScalarList(ScalarListFilter { field, condition })
+        Ok(Filter::ScalarList(ScalarListFilter { field, condition }))
     }
 }
 
 impl IntoFilter for pb::RelationFilter {
-    fn into_filter(self, model: ModelRef) -> Filter {
+    fn into_filter(self, model: ModelRef) -> BridgeResult<Filter> {
         let condition = self.condition().clone();
 
-        let field = model
-            .fields()
-            .find_from_relation_fields(self.field.field.as_ref())
-            .unwrap();
-
-        let nested_filter: Box<Filter> = Box::new((*self.nested_filter).into_filter(field.related_model()));
+        let field = model.fields().find_from_relation_fields(self.field.field.as_ref())?;
+        let nested_filter: Box<Filter> = Box::new((*self.nested_filter).into_filter(field.related_model())?);
 
         let condition = match condition {
             pb::relation_filter::Condition::EveryRelatedNode => RelationCondition::EveryRelatedNode,
             pb::relation_filter::Condition::AtLeastOneRelatedNode => RelationCondition::AtLeastOneRelatedNode,
             pb::relation_filter::Condition::NoRelatedNode => RelationCondition::NoRelatedNode,
             pb::relation_filter::Condition::ToOneRelatedNode => RelationCondition::ToOneRelatedNode,
+            pb::relation_filter::Condition::RelatedCount => {
+                use pb::relation_filter::related_count::Comparison;
+
+                let related_count = self.related_count.ok_or(BridgeError::InvalidConnectionArguments(
+                    "Related count filters need a count to compare with",
+                ))?;
+                let count = related_count.count as usize;
+
+                let count_condition = match related_count.comparison() {
+                    Comparison::Equals => CountCondition::Equals(count),
+                    Comparison::NotEquals => CountCondition::NotEquals(count),
+                    Comparison::LessThan => CountCondition::LessThan(count),
+                    Comparison::LessThanOrEquals => CountCondition::LessThanOrEquals(count),
+                    Comparison::GreaterThan => CountCondition::GreaterThan(count),
+                    Comparison::GreaterThanOrEquals => CountCondition::GreaterThanOrEquals(count),
+                };
+
+                RelationCondition::RelatedCount(count_condition)
+            }
         };
 
-        Filter::Relation(RelationFilter {
+        Ok(Filter::Relation(RelationFilter {
             field,
             nested_filter,
             condition,
-        })
+        }))
     }
 }
+
+fn into_boxed_filters(filters: Vec<pb::Filter>, model: ModelRef) -> BridgeResult<Vec<Box<Filter>>> {
+    filters
+        .into_iter()
+        .map(|filter| Ok(Box::new(filter.into_filter(model.clone())?)))
+        .collect()
+}
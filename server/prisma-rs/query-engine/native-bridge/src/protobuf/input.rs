@@ -1,9 +1,11 @@
+mod aggregate;
 mod count_by_model;
 mod count_by_table;
 mod get_nodes;
 mod get_related_nodes;
 mod get_scalar_list_values;
 
+pub use aggregate::*;
 pub use count_by_model::*;
 pub use count_by_table::*;
 pub use get_nodes::*;
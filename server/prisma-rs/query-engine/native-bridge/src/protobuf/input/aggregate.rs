@@ -0,0 +1,7 @@
+use crate::protobuf::{prelude::*, BridgeResult, InputValidation};
+
+impl InputValidation for AggregateInput {
+    fn validate(&self) -> BridgeResult<()> {
+        Self::validate_args(&self.query_arguments)
+    }
+}
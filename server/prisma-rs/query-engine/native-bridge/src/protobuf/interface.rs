@@ -1,9 +1,9 @@
-use super::query_arguments::into_model_query_arguments;
+use super::{aggregation::into_model_aggregation, query_arguments::into_model_query_arguments};
 use crate::{
     protobuf::{mutaction::*, prelude::*, InputValidation},
     BridgeError, BridgeResult, ExternalInterface,
 };
-use connector::{error::ConnectorError, filter::NodeSelector, DataResolver, DatabaseMutactionExecutor};
+use connector::{error::ConnectorError, Aggregation, DataResolver, DatabaseMutactionExecutor};
 use prisma_common::config::*;
 use prisma_models::prelude::*;
 use prost::Message;
@@ -78,9 +78,7 @@ impl ExternalInterface for ProtoBufInterface {
             let model = project.schema().find_model(&input.model_name)?;
             let selected_fields = input.selected_fields.into_selected_fields(model.clone(), None);
 
-            let value: PrismaValue = input.value.into();
-            let field = model.fields().find_from_scalar(&input.field_name)?;
-            let node_selector = NodeSelector { field, value };
+            let node_selector = convert_selector_fields(&model, &input.field_name, input.value, input.compound_fields)?;
 
             let query_result = self.data_resolver.get_node_by_where(&node_selector, &selected_fields)?;
 
@@ -108,7 +106,7 @@ impl ExternalInterface for ProtoBufInterface {
 
             let model = project.schema().find_model(&input.model_name)?;
             let selected_fields = input.selected_fields.into_selected_fields(model.clone(), None);
-            let query_arguments = into_model_query_arguments(model.clone(), input.query_arguments);
+            let query_arguments = into_model_query_arguments(model.clone(), input.query_arguments)?;
 
             let query_result = self.data_resolver.get_nodes(model, query_arguments, &selected_fields)?;
             let (nodes, fields) = (query_result.nodes, query_result.field_names);
@@ -147,7 +145,7 @@ impl ExternalInterface for ProtoBufInterface {
             let query_result = self.data_resolver.get_related_nodes(
                 from_field,
                 &from_node_ids,
-                into_model_query_arguments(Arc::clone(&related_model), input.query_arguments),
+                into_model_query_arguments(Arc::clone(&related_model), input.query_arguments)?,
                 &selected_fields,
             )?;
 
@@ -209,7 +207,7 @@ impl ExternalInterface for ProtoBufInterface {
             let project: ProjectRef = project_template.into();
             let model = project.schema().find_model(&input.model_name)?;
 
-            let query_arguments = into_model_query_arguments(model.clone(), input.query_arguments);
+            let query_arguments = into_model_query_arguments(model.clone(), input.query_arguments)?;
             let count = self.data_resolver.count_by_model(model, query_arguments)?;
 
             let response = RpcResponse::ok(count);
@@ -221,6 +219,41 @@ impl ExternalInterface for ProtoBufInterface {
         })
     }
 
+    fn aggregate(&self, payload: &mut [u8]) -> Vec<u8> {
+        Self::protobuf_result(|| {
+            let input = AggregateInput::decode(payload)?;
+            input.validate()?;
+
+            let project_template: ProjectTemplate = serde_json::from_reader(input.project_json.as_slice())?;
+            let project: ProjectRef = project_template.into();
+            let model = project.schema().find_model(&input.model_name)?;
+
+            let aggregations = input
+                .aggregations
+                .into_iter()
+                .map(|aggregation| into_model_aggregation(&model, aggregation))
+                .collect::<BridgeResult<Vec<Aggregation>>>()?;
+
+            let group_by = input
+                .group_by
+                .iter()
+                .map(|name| model.fields().find_from_scalar(name))
+                .collect::<DomainResult<Vec<ScalarFieldRef>>>()?;
+
+            let query_arguments = into_model_query_arguments(model.clone(), input.query_arguments)?;
+            let groups = self
+                .data_resolver
+                .aggregate(model, query_arguments, &aggregations, &group_by)?;
+
+            let response = RpcResponse::ok(groups);
+
+            let mut response_payload = Vec::new();
+            response.encode(&mut response_payload).unwrap();
+
+            Ok(response_payload)
+        })
+    }
+
     fn count_by_table(&self, payload: &mut [u8]) -> Vec<u8> {
         Self::protobuf_result(|| {
             let input = CountByTableInput::decode(payload)?;
@@ -269,15 +302,14 @@ impl ExternalInterface for ProtoBufInterface {
             let project: ProjectRef = project_template.into();
 
             let parent_id = input.parent_id.clone().map(GraphqlId::from);
-            let mutaction = convert_mutaction(input, Arc::clone(&project));
+            let mutaction = convert_mutaction(input, Arc::clone(&project))?;
             let db_name = project.schema().db_name.to_string();
 
-            let mut results = self
+            let results = self
                 .database_mutaction_executor
                 .execute(db_name, mutaction, parent_id)?;
-            let result = results.pop().expect("no mutaction results returned");
 
-            let response = RpcResponse::ok_mutaction(convert_mutaction_result(result));
+            let response = RpcResponse::ok_mutaction(convert_mutaction_results(results));
             let mut response_payload = Vec::new();
 
             response.encode(&mut response_payload).unwrap();
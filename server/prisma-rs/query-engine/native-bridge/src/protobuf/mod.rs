@@ -1,3 +1,4 @@
+mod aggregation;
 mod envelope;
 mod filter;
 mod input;
@@ -15,6 +16,7 @@ pub use mutaction::*;
 
 use crate::{BridgeError, BridgeResult};
 use chrono::prelude::*;
+use connector::AggregateResult;
 use prelude::*;
 use prisma_models::prelude::*;
 use std::sync::Arc;
@@ -48,6 +50,22 @@ impl From<prisma::ScalarListValuesResult> for prisma::Result {
     }
 }
 
+impl From<Vec<AggregateResult>> for prisma::Result {
+    fn from(res: Vec<AggregateResult>) -> prisma::Result {
+        let groups = res
+            .into_iter()
+            .map(|group| prisma::AggregateGroup {
+                group: group.group.into_iter().map(|v| v.into()).collect(),
+                values: group.values.into_iter().map(|v| v.into()).collect(),
+            })
+            .collect();
+
+        prisma::Result {
+            value: Some(result::Value::AggregateResult(prisma::AggregateResult { groups })),
+        }
+    }
+}
+
 impl RpcResponse {
     pub fn header() -> Header {
         Header {
@@ -81,11 +99,11 @@ impl RpcResponse {
         }
     }
 
-    pub fn ok_mutaction(result: crate::protobuf::prisma::DatabaseMutactionResult) -> RpcResponse {
+    pub fn ok_mutaction(results: crate::protobuf::prisma::DatabaseMutactionResults) -> RpcResponse {
         RpcResponse {
             header: Self::header(),
             response: Some(rpc::Response::Result(prisma::Result {
-                value: Some(result::Value::MutactionResult(result)),
+                value: Some(result::Value::MutactionResults(results)),
             })),
         }
     }
@@ -109,6 +127,15 @@ impl From<prisma::order_by::SortOrder> for SortOrder {
     }
 }
 
+impl From<prisma::order_by::NullsOrder> for NullsOrder {
+    fn from(no: prisma::order_by::NullsOrder) -> NullsOrder {
+        match no {
+            prisma::order_by::NullsOrder::First => NullsOrder::First,
+            prisma::order_by::NullsOrder::Last => NullsOrder::Last,
+        }
+    }
+}
+
 impl From<ValueContainer> for PrismaValue {
     fn from(container: ValueContainer) -> PrismaValue {
         use prisma::value_container as vc;
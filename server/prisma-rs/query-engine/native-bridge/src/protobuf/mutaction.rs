@@ -1,272 +1,357 @@
 use super::filter::IntoFilter;
-use connector::{filter::NodeSelector, mutaction::*};
+use crate::{BridgeError, BridgeResult};
+use connector::{
+    filter::{CompoundNodeSelector, NodeSelector},
+    mutaction::*,
+};
 use prisma_models::prelude::*;
 use std::sync::Arc;
 
-pub fn convert_mutaction(m: crate::protobuf::prisma::DatabaseMutaction, project: ProjectRef) -> DatabaseMutaction {
+pub fn convert_mutaction(
+    m: crate::protobuf::prisma::DatabaseMutaction,
+    project: ProjectRef,
+) -> BridgeResult<DatabaseMutaction> {
     use crate::protobuf::prisma::database_mutaction;
-    match m.type_.unwrap() {
-        database_mutaction::Type::Create(x) => DatabaseMutaction::TopLevel(convert_create_envelope(x, project)),
-        database_mutaction::Type::Update(x) => DatabaseMutaction::TopLevel(convert_update_envelope(x, project)),
-        database_mutaction::Type::Upsert(x) => DatabaseMutaction::TopLevel(convert_upsert(x, project)),
-        database_mutaction::Type::Delete(x) => DatabaseMutaction::TopLevel(convert_delete(x, project)),
+
+    let type_ = m
+        .type_
+        .ok_or(BridgeError::InvalidConnectionArguments("Mutactions need a type"))?;
+
+    let mutaction = match type_ {
+        database_mutaction::Type::Create(x) => DatabaseMutaction::TopLevel(convert_create_envelope(x, project)?),
+        database_mutaction::Type::CreateNodes(x) => DatabaseMutaction::TopLevel(convert_create_nodes(x, project)?),
+        database_mutaction::Type::Update(x) => DatabaseMutaction::TopLevel(convert_update_envelope(x, project)?),
+        database_mutaction::Type::Upsert(x) => DatabaseMutaction::TopLevel(convert_upsert(x, project)?),
+        database_mutaction::Type::Delete(x) => DatabaseMutaction::TopLevel(convert_delete(x, project)?),
         database_mutaction::Type::Reset(x) => DatabaseMutaction::TopLevel(convert_reset(x, project)),
-        database_mutaction::Type::DeleteNodes(x) => DatabaseMutaction::TopLevel(convert_delete_nodes(x, project)),
-        database_mutaction::Type::UpdateNodes(x) => DatabaseMutaction::TopLevel(convert_update_nodes(x, project)),
+        database_mutaction::Type::DeleteNodes(x) => DatabaseMutaction::TopLevel(convert_delete_nodes(x, project)?),
+        database_mutaction::Type::UpdateNodes(x) => DatabaseMutaction::TopLevel(convert_update_nodes(x, project)?),
         database_mutaction::Type::NestedConnect(x) => {
-            DatabaseMutaction::Nested(convert_nested_connect_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_connect_envelope(x, project)?)
         }
         database_mutaction::Type::NestedDisconnect(x) => {
-            DatabaseMutaction::Nested(convert_nested_disconnect_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_disconnect_envelope(x, project)?)
         }
-        database_mutaction::Type::NestedSet(x) => DatabaseMutaction::Nested(convert_nested_set_envelope(x, project)),
+        database_mutaction::Type::NestedSet(x) => DatabaseMutaction::Nested(convert_nested_set_envelope(x, project)?),
         database_mutaction::Type::NestedCreate(x) => {
-            DatabaseMutaction::Nested(convert_nested_create_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_create_envelope(x, project)?)
         }
         database_mutaction::Type::NestedUpdate(x) => {
-            DatabaseMutaction::Nested(convert_nested_update_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_update_envelope(x, project)?)
         }
         database_mutaction::Type::NestedUpsert(x) => {
-            DatabaseMutaction::Nested(convert_nested_upsert_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_upsert_envelope(x, project)?)
         }
         database_mutaction::Type::NestedDelete(x) => {
-            DatabaseMutaction::Nested(convert_nested_delete_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_delete_envelope(x, project)?)
         }
         database_mutaction::Type::NestedUpdateNodes(x) => {
-            DatabaseMutaction::Nested(convert_nested_update_nodes_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_update_nodes_envelope(x, project)?)
         }
         database_mutaction::Type::NestedDeleteNodes(x) => {
-            DatabaseMutaction::Nested(convert_nested_delete_nodes_envelope(x, project))
+            DatabaseMutaction::Nested(convert_nested_delete_nodes_envelope(x, project)?)
         }
-    }
+    };
+
+    Ok(mutaction)
 }
 
 pub fn convert_create_envelope(
     m: crate::protobuf::prisma::CreateNode,
     project: ProjectRef,
-) -> TopLevelDatabaseMutaction {
-    TopLevelDatabaseMutaction::CreateNode(convert_create(m, project))
+) -> BridgeResult<TopLevelDatabaseMutaction> {
+    Ok(TopLevelDatabaseMutaction::CreateNode(convert_create(m, project)?))
 }
 
-pub fn convert_create(m: crate::protobuf::prisma::CreateNode, project: ProjectRef) -> CreateNode {
-    let model = project.schema().find_model(&m.model_name).unwrap();
-    CreateNode {
+pub fn convert_create(m: crate::protobuf::prisma::CreateNode, project: ProjectRef) -> BridgeResult<CreateNode> {
+    let model = project.schema().find_model(&m.model_name)?;
+
+    Ok(CreateNode {
+        non_list_args: convert_prisma_args(m.non_list_args, &model)?,
         model: model,
-        non_list_args: convert_prisma_args(m.non_list_args),
         list_args: convert_list_args(m.list_args),
-        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
-    }
+        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project))?,
+    })
+}
+
+pub fn convert_create_nodes(
+    m: crate::protobuf::prisma::CreateNodes,
+    project: ProjectRef,
+) -> BridgeResult<TopLevelDatabaseMutaction> {
+    let model = project.schema().find_model(&m.model_name)?;
+
+    let create_nodes = CreateNodes {
+        args: m
+            .args
+            .into_iter()
+            .map(|args| convert_prisma_args(args, &model))
+            .collect::<BridgeResult<_>>()?,
+        model,
+        skip_duplicates: m.skip_duplicates.unwrap_or(false),
+    };
+
+    Ok(TopLevelDatabaseMutaction::CreateNodes(create_nodes))
 }
 
 pub fn convert_nested_mutactions(
     m: crate::protobuf::prisma::NestedMutactions,
     project: ProjectRef,
-) -> NestedMutactions {
-    NestedMutactions {
+) -> BridgeResult<NestedMutactions> {
+    Ok(NestedMutactions {
         creates: m
             .creates
             .into_iter()
             .map(|m| convert_nested_create(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         updates: m
             .updates
             .into_iter()
             .map(|m| convert_nested_update(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         upserts: m
             .upserts
             .into_iter()
             .map(|m| convert_nested_upsert(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         deletes: m
             .deletes
             .into_iter()
             .map(|m| convert_nested_delete(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         connects: m
             .connects
             .into_iter()
             .map(|m| convert_nested_connect(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         disconnects: m
             .disconnects
             .into_iter()
             .map(|m| convert_nested_disconnect(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         sets: m
             .sets
             .into_iter()
             .map(|m| convert_nested_set(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         update_manys: m
             .update_manys
             .into_iter()
             .map(|m| convert_nested_update_nodes(m, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
         delete_manys: m
             .delete_manys
             .into_iter()
             .map(|m| convert_nested_delete_nodes(m, Arc::clone(&project)))
-            .collect(),
-    }
+            .collect::<BridgeResult<_>>()?,
+    })
 }
 
 pub fn convert_nested_create_envelope(
     m: crate::protobuf::prisma::NestedCreateNode,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::CreateNode(convert_nested_create(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::CreateNode(convert_nested_create(m, project)?))
 }
 
-pub fn convert_nested_create(m: crate::protobuf::prisma::NestedCreateNode, project: ProjectRef) -> NestedCreateNode {
-    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name);
+pub fn convert_nested_create(
+    m: crate::protobuf::prisma::NestedCreateNode,
+    project: ProjectRef,
+) -> BridgeResult<NestedCreateNode> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
 
-    NestedCreateNode {
+    Ok(NestedCreateNode {
+        non_list_args: convert_prisma_args(m.non_list_args, &relation_field.related_model())?,
         relation_field: relation_field,
-        non_list_args: convert_prisma_args(m.non_list_args),
         list_args: convert_list_args(m.list_args),
         top_is_create: m.top_is_create,
-        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
-    }
+        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project))?,
+    })
 }
 
 pub fn convert_update_envelope(
     m: crate::protobuf::prisma::UpdateNode,
     project: ProjectRef,
-) -> TopLevelDatabaseMutaction {
-    TopLevelDatabaseMutaction::UpdateNode(convert_update(m, project))
+) -> BridgeResult<TopLevelDatabaseMutaction> {
+    Ok(TopLevelDatabaseMutaction::UpdateNode(convert_update(m, project)?))
 }
 
-pub fn convert_update(m: crate::protobuf::prisma::UpdateNode, project: ProjectRef) -> UpdateNode {
-    UpdateNode {
-        where_: convert_node_select(m.where_, Arc::clone(&project)),
-        non_list_args: convert_prisma_args(m.non_list_args),
-        list_args: convert_list_args(m.list_args),
-        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
-    }
+pub fn convert_update(m: crate::protobuf::prisma::UpdateNode, project: ProjectRef) -> BridgeResult<UpdateNode> {
+    let where_ = convert_node_select(m.where_, Arc::clone(&project))?;
+
+    Ok(UpdateNode {
+        non_list_args: convert_prisma_args(m.non_list_args, &where_.model())?,
+        where_,
+        list_args: convert_list_operations(m.list_args, m.list_operations)?,
+        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project))?,
+    })
 }
 
 pub fn convert_nested_update_envelope(
     m: crate::protobuf::prisma::NestedUpdateNode,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::UpdateNode(convert_nested_update(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::UpdateNode(convert_nested_update(m, project)?))
 }
 
-pub fn convert_nested_update(m: crate::protobuf::prisma::NestedUpdateNode, project: ProjectRef) -> NestedUpdateNode {
-    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name);
-    NestedUpdateNode {
+pub fn convert_nested_update(
+    m: crate::protobuf::prisma::NestedUpdateNode,
+    project: ProjectRef,
+) -> BridgeResult<NestedUpdateNode> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
+
+    Ok(NestedUpdateNode {
+        where_: convert_optional_node_select(m.where_, Arc::clone(&project))?,
+        non_list_args: convert_prisma_args(m.non_list_args, &relation_field.related_model())?,
         relation_field: relation_field,
-        where_: m.where_.map(|w| convert_node_select(w, Arc::clone(&project))),
-        non_list_args: convert_prisma_args(m.non_list_args),
-        list_args: convert_list_args(m.list_args),
-        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
-    }
+        list_args: convert_list_operations(m.list_args, m.list_operations)?,
+        nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project))?,
+    })
 }
 
-pub fn convert_update_nodes(m: crate::protobuf::prisma::UpdateNodes, project: ProjectRef) -> TopLevelDatabaseMutaction {
-    let model = project.schema().find_model(&m.model_name).unwrap();
+pub fn convert_update_nodes(
+    m: crate::protobuf::prisma::UpdateNodes,
+    project: ProjectRef,
+) -> BridgeResult<TopLevelDatabaseMutaction> {
+    let model = project.schema().find_model(&m.model_name)?;
+
     let update_nodes = UpdateNodes {
         model: Arc::clone(&model),
-        filter: m.filter.into_filter(model),
-        non_list_args: convert_prisma_args(m.non_list_args),
-        list_args: convert_list_args(m.list_args),
+        non_list_args: convert_prisma_args(m.non_list_args, &model)?,
+        filter: m.filter.into_filter(model)?,
+        list_args: convert_list_operations(m.list_args, m.list_operations)?,
     };
-    TopLevelDatabaseMutaction::UpdateNodes(update_nodes)
+
+    Ok(TopLevelDatabaseMutaction::UpdateNodes(update_nodes))
 }
 
 pub fn convert_nested_update_nodes_envelope(
     m: crate::protobuf::prisma::NestedUpdateNodes,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::UpdateNodes(convert_nested_update_nodes(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::UpdateNodes(convert_nested_update_nodes(
+        m, project,
+    )?))
 }
 
 pub fn convert_nested_update_nodes(
     m: crate::protobuf::prisma::NestedUpdateNodes,
     project: ProjectRef,
-) -> NestedUpdateNodes {
-    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name);
-    NestedUpdateNodes {
+) -> BridgeResult<NestedUpdateNodes> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
+
+    let filter = match m.filter {
+        Some(filter) => Some(filter.into_filter(relation_field.related_model())?),
+        None => None,
+    };
+
+    Ok(NestedUpdateNodes {
         relation_field: Arc::clone(&relation_field),
-        filter: m.filter.map(|f| f.into_filter(relation_field.related_model())),
-        non_list_args: convert_prisma_args(m.non_list_args),
-        list_args: convert_list_args(m.list_args),
-    }
+        filter,
+        non_list_args: convert_prisma_args(m.non_list_args, &relation_field.related_model())?,
+        list_args: convert_list_operations(m.list_args, m.list_operations)?,
+    })
 }
 
-pub fn convert_upsert(m: crate::protobuf::prisma::UpsertNode, project: ProjectRef) -> TopLevelDatabaseMutaction {
+pub fn convert_upsert(
+    m: crate::protobuf::prisma::UpsertNode,
+    project: ProjectRef,
+) -> BridgeResult<TopLevelDatabaseMutaction> {
     let upsert_node = UpsertNode {
-        where_: convert_node_select(m.where_, Arc::clone(&project)),
-        create: convert_create(m.create, Arc::clone(&project)),
-        update: convert_update(m.update, project),
+        where_: convert_node_select(m.where_, Arc::clone(&project))?,
+        create: convert_create(m.create, Arc::clone(&project))?,
+        update: convert_update(m.update, project)?,
     };
-    TopLevelDatabaseMutaction::UpsertNode(upsert_node)
+
+    Ok(TopLevelDatabaseMutaction::UpsertNode(upsert_node))
 }
 
 pub fn convert_nested_upsert_envelope(
     m: crate::protobuf::prisma::NestedUpsertNode,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::UpsertNode(convert_nested_upsert(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::UpsertNode(convert_nested_upsert(m, project)?))
 }
 
-pub fn convert_nested_upsert(m: crate::protobuf::prisma::NestedUpsertNode, project: ProjectRef) -> NestedUpsertNode {
-    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name);
-    NestedUpsertNode {
+pub fn convert_nested_upsert(
+    m: crate::protobuf::prisma::NestedUpsertNode,
+    project: ProjectRef,
+) -> BridgeResult<NestedUpsertNode> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
+
+    Ok(NestedUpsertNode {
         relation_field: relation_field,
-        where_: m.where_.map(|w| convert_node_select(w, Arc::clone(&project))),
-        create: convert_nested_create(m.create, Arc::clone(&project)),
-        update: convert_nested_update(m.update, Arc::clone(&project)),
-    }
+        where_: convert_optional_node_select(m.where_, Arc::clone(&project))?,
+        create: convert_nested_create(m.create, Arc::clone(&project))?,
+        update: convert_nested_update(m.update, Arc::clone(&project))?,
+    })
 }
 
-pub fn convert_delete(m: crate::protobuf::prisma::DeleteNode, project: ProjectRef) -> TopLevelDatabaseMutaction {
+pub fn convert_delete(
+    m: crate::protobuf::prisma::DeleteNode,
+    project: ProjectRef,
+) -> BridgeResult<TopLevelDatabaseMutaction> {
     let delete_node = DeleteNode {
-        where_: convert_node_select(m.where_, project),
+        where_: convert_node_select(m.where_, project)?,
     };
-    TopLevelDatabaseMutaction::DeleteNode(delete_node)
+
+    Ok(TopLevelDatabaseMutaction::DeleteNode(delete_node))
 }
 
 pub fn convert_nested_delete_envelope(
     m: crate::protobuf::prisma::NestedDeleteNode,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::DeleteNode(convert_nested_delete(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::DeleteNode(convert_nested_delete(m, project)?))
 }
 
-pub fn convert_nested_delete(m: crate::protobuf::prisma::NestedDeleteNode, project: ProjectRef) -> NestedDeleteNode {
-    NestedDeleteNode {
-        relation_field: find_relation_field(Arc::clone(&project), m.model_name, m.field_name),
-        where_: m.where_.map(|w| convert_node_select(w, project)),
-    }
+pub fn convert_nested_delete(
+    m: crate::protobuf::prisma::NestedDeleteNode,
+    project: ProjectRef,
+) -> BridgeResult<NestedDeleteNode> {
+    Ok(NestedDeleteNode {
+        relation_field: find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?,
+        where_: convert_optional_node_select(m.where_, project)?,
+    })
 }
 
-pub fn convert_delete_nodes(m: crate::protobuf::prisma::DeleteNodes, project: ProjectRef) -> TopLevelDatabaseMutaction {
-    let model = project.schema().find_model(&m.model_name).unwrap();
+pub fn convert_delete_nodes(
+    m: crate::protobuf::prisma::DeleteNodes,
+    project: ProjectRef,
+) -> BridgeResult<TopLevelDatabaseMutaction> {
+    let model = project.schema().find_model(&m.model_name)?;
+
     let delete_nodes = DeleteNodes {
         model: Arc::clone(&model),
-        filter: m.filter.into_filter(model),
+        filter: m.filter.into_filter(model)?,
     };
-    TopLevelDatabaseMutaction::DeleteNodes(delete_nodes)
+
+    Ok(TopLevelDatabaseMutaction::DeleteNodes(delete_nodes))
 }
 
 pub fn convert_nested_delete_nodes_envelope(
     m: crate::protobuf::prisma::NestedDeleteNodes,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::DeleteNodes(convert_nested_delete_nodes(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::DeleteNodes(convert_nested_delete_nodes(
+        m, project,
+    )?))
 }
 
 pub fn convert_nested_delete_nodes(
     m: crate::protobuf::prisma::NestedDeleteNodes,
     project: ProjectRef,
-) -> NestedDeleteNodes {
-    let relation_field = find_relation_field(project, m.model_name, m.field_name);
-    NestedDeleteNodes {
+) -> BridgeResult<NestedDeleteNodes> {
+    let relation_field = find_relation_field(project, m.model_name, m.field_name)?;
+
+    let filter = match m.filter {
+        Some(filter) => Some(filter.into_filter(relation_field.related_model())?),
+        None => None,
+    };
+
+    Ok(NestedDeleteNodes {
         relation_field: Arc::clone(&relation_field),
-        filter: m.filter.map(|f| f.into_filter(relation_field.related_model())),
-    }
+        filter,
+    })
 }
 
 pub fn convert_reset(_: crate::protobuf::prisma::ResetData, project: ProjectRef) -> TopLevelDatabaseMutaction {
@@ -277,90 +362,137 @@ pub fn convert_reset(_: crate::protobuf::prisma::ResetData, project: ProjectRef)
 pub fn convert_nested_connect_envelope(
     m: crate::protobuf::prisma::NestedConnect,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::Connect(convert_nested_connect(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::Connect(convert_nested_connect(m, project)?))
 }
 
-pub fn convert_nested_connect(m: crate::protobuf::prisma::NestedConnect, project: ProjectRef) -> NestedConnect {
-    let relation_field = project
-        .schema()
-        .find_model(&m.model_name)
-        .unwrap()
-        .fields()
-        .find_from_relation_fields(&m.field_name)
-        .unwrap();
+pub fn convert_nested_connect(
+    m: crate::protobuf::prisma::NestedConnect,
+    project: ProjectRef,
+) -> BridgeResult<NestedConnect> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
 
-    NestedConnect {
+    Ok(NestedConnect {
         relation_field: relation_field,
-        where_: convert_node_select(m.where_, project),
+        where_: convert_node_select(m.where_, project)?,
         top_is_create: m.top_is_create,
-    }
+    })
 }
 
 pub fn convert_nested_disconnect_envelope(
     m: crate::protobuf::prisma::NestedDisconnect,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::Disconnect(convert_nested_disconnect(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::Disconnect(convert_nested_disconnect(
+        m, project,
+    )?))
 }
 
 pub fn convert_nested_disconnect(
     m: crate::protobuf::prisma::NestedDisconnect,
     project: ProjectRef,
-) -> NestedDisconnect {
-    let relation_field = project
-        .schema()
-        .find_model(&m.model_name)
-        .unwrap()
-        .fields()
-        .find_from_relation_fields(&m.field_name)
-        .unwrap();
-
-    NestedDisconnect {
+) -> BridgeResult<NestedDisconnect> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
+
+    Ok(NestedDisconnect {
         relation_field: relation_field,
-        where_: m.where_.map(|w| convert_node_select(w, project)),
-    }
+        where_: convert_optional_node_select(m.where_, project)?,
+    })
 }
 
 pub fn convert_nested_set_envelope(
     m: crate::protobuf::prisma::NestedSet,
     project: ProjectRef,
-) -> NestedDatabaseMutaction {
-    NestedDatabaseMutaction::Set(convert_nested_set(m, project))
+) -> BridgeResult<NestedDatabaseMutaction> {
+    Ok(NestedDatabaseMutaction::Set(convert_nested_set(m, project)?))
 }
 
-pub fn convert_nested_set(m: crate::protobuf::prisma::NestedSet, project: ProjectRef) -> NestedSet {
-    let relation_field = project
-        .schema()
-        .find_model(&m.model_name)
-        .unwrap()
-        .fields()
-        .find_from_relation_fields(&m.field_name)
-        .unwrap();
+pub fn convert_nested_set(m: crate::protobuf::prisma::NestedSet, project: ProjectRef) -> BridgeResult<NestedSet> {
+    let relation_field = find_relation_field(Arc::clone(&project), m.model_name, m.field_name)?;
 
-    NestedSet {
+    Ok(NestedSet {
         relation_field: relation_field,
         wheres: m
             .wheres
             .into_iter()
             .map(|w| convert_node_select(w, Arc::clone(&project)))
-            .collect(),
+            .collect::<BridgeResult<_>>()?,
+    })
+}
+
+pub fn convert_node_select(
+    selector: crate::protobuf::prisma::NodeSelector,
+    project: ProjectRef,
+) -> BridgeResult<NodeSelector> {
+    let model = project.schema().find_model(&selector.model_name)?;
+    let node_selector =
+        convert_selector_fields(&model, &selector.field_name, selector.value, selector.compound_fields)?;
+
+    Ok(node_selector)
+}
+
+/// Converts the selector of a nested mutaction, if it has one.
+pub fn convert_optional_node_select(
+    selector: Option<crate::protobuf::prisma::NodeSelector>,
+    project: ProjectRef,
+) -> BridgeResult<Option<NodeSelector>> {
+    match selector {
+        Some(selector) => Ok(Some(convert_node_select(selector, project)?)),
+        None => Ok(None),
     }
 }
 
-pub fn convert_node_select(selector: crate::protobuf::prisma::NodeSelector, project: ProjectRef) -> NodeSelector {
-    let model = project.schema().find_model(&selector.model_name).unwrap();
-    let field = model.fields().find_from_scalar(&selector.field_name).unwrap();
-    let value: PrismaValue = selector.value.into();
-    NodeSelector { field, value }
+/// Selects by the field, or by all fields if further fields of a multi-field
+/// unique constraint follow.
+pub fn convert_selector_fields(
+    model: &Model,
+    field_name: &str,
+    value: crate::protobuf::prisma::ValueContainer,
+    compound_fields: Vec<crate::protobuf::prisma::SelectorField>,
+) -> DomainResult<NodeSelector> {
+    let field = model.fields().find_from_scalar(field_name)?;
+    let value: PrismaValue = value.into();
+
+    if compound_fields.is_empty() {
+        return Ok(NodeSelector::new(field, value));
+    }
+
+    let mut fields = vec![(field, value)];
+
+    for compound in compound_fields {
+        let field = model.fields().find_from_scalar(&compound.field_name)?;
+        fields.push((field, PrismaValue::from(compound.value)));
+    }
+
+    Ok(NodeSelector::from(CompoundNodeSelector::from_fields(model, fields)?))
 }
 
-pub fn convert_prisma_args(proto: crate::protobuf::prisma::PrismaArgs) -> PrismaArgs {
+/// Converts the arguments of a write to the model. The fields of the update
+/// operations must be scalar fields of the model.
+pub fn convert_prisma_args(proto: crate::protobuf::prisma::PrismaArgs, model: &Model) -> BridgeResult<PrismaArgs> {
+    use crate::protobuf::prisma::update_operation::Kind;
+
     let mut result = PrismaArgs::default();
     for arg in proto.args {
         result.insert(arg.key, arg.value);
     }
-    result
+    for operation in proto.operations {
+        let kind = operation.kind();
+        let value = PrismaValue::from(operation.value);
+
+        let update = match kind {
+            Kind::Set => UpdateOperation::Set(value),
+            Kind::Increment => UpdateOperation::Increment(value),
+            Kind::Decrement => UpdateOperation::Decrement(value),
+            Kind::Multiply => UpdateOperation::Multiply(value),
+            Kind::Divide => UpdateOperation::Divide(value),
+        };
+
+        model.fields().find_from_scalar(&operation.field)?;
+        result.insert_operation(operation.field, update);
+    }
+
+    Ok(result)
 }
 
 pub fn convert_list_args(proto: crate::protobuf::prisma::PrismaArgs) -> Vec<(String, PrismaListValue)> {
@@ -373,51 +505,128 @@ pub fn convert_list_args(proto: crate::protobuf::prisma::PrismaArgs) -> Vec<(Str
     result
 }
 
-pub fn find_relation_field(project: ProjectRef, model: String, field: String) -> Arc<RelationField> {
-    project
-        .schema()
-        .find_model(&model)
-        .unwrap()
-        .fields()
-        .find_from_relation_fields(&field)
-        .unwrap()
-}
+pub fn convert_list_operations(
+    list_args: crate::protobuf::prisma::PrismaArgs,
+    operations: Vec<crate::protobuf::prisma::ScalarListOperation>,
+) -> BridgeResult<Vec<(String, ScalarListOperation)>> {
+    use crate::protobuf::prisma::scalar_list_operation::Kind;
+
+    let mut result: Vec<(String, ScalarListOperation)> = convert_list_args(list_args)
+        .into_iter()
+        .map(|(field, values)| (field, ScalarListOperation::Set(values)))
+        .collect();
+
+    for operation in operations {
+        let kind = operation.kind();
+
+        let value = operation
+            .value
+            .ok_or(BridgeError::InvalidConnectionArguments("List operations need a value"));
 
-pub fn convert_mutaction_result(result: DatabaseMutactionResult) -> crate::protobuf::prisma::DatabaseMutactionResult {
+        let list_operation = match kind {
+            Kind::Set => ScalarListOperation::Set(value?.into()),
+            Kind::Push => ScalarListOperation::Push(value?.into()),
+            Kind::Unshift => ScalarListOperation::Unshift(value?.into()),
+            Kind::RemoveAt => {
+                let index = operation.index.ok_or(BridgeError::InvalidConnectionArguments(
+                    "Removing at a position needs an index",
+                ))?;
+
+                ScalarListOperation::RemoveAt(index as usize)
+            }
+            Kind::RemoveValue => ScalarListOperation::RemoveValue(value?.into()),
+        };
+
+        result.push((operation.field, list_operation));
+    }
+
+    Ok(result)
+}
+
+pub fn find_relation_field(project: ProjectRef, model: String, field: String) -> DomainResult<Arc<RelationField>> {
+    let model = project.schema().find_model(&model)?;
+    model.fields().find_from_relation_fields(&field)
+}
+
+/// Converts the results of a mutaction and its nested mutactions, keeping the
+/// positions of the nested mutactions so the results can be matched with them.
+pub fn convert_mutaction_results(
+    results: DatabaseMutactionResults,
+) -> crate::protobuf::prisma::DatabaseMutactionResults {
+    let results = results
+        .with_paths()
+        .into_iter()
+        .map(|(path, result)| {
+            let cascaded_model = match path.last() {
+                Some(NestedPosition::Cascade) => match result.mutaction {
+                    DatabaseMutaction::TopLevel(TopLevelDatabaseMutaction::DeleteNode(ref dn)) => {
+                        Some(dn.where_.model().name.clone())
+                    }
+                    _ => unreachable!("Records are deleted through a cascade"),
+                },
+                _ => None,
+            };
+
+            let path = path
+                .into_iter()
+                .filter_map(|position| {
+                    let (field, index) = match position {
+                        NestedPosition::Create(index) => ("creates", index),
+                        NestedPosition::Update(index) => ("updates", index),
+                        NestedPosition::Upsert(index) => ("upserts", index),
+                        NestedPosition::Delete(index) => ("deletes", index),
+                        NestedPosition::Connect(index) => ("connects", index),
+                        NestedPosition::Disconnect(index) => ("disconnects", index),
+                        NestedPosition::Set(index) => ("sets", index),
+                        NestedPosition::UpdateMany(index) => ("update_manys", index),
+                        NestedPosition::DeleteMany(index) => ("delete_manys", index),
+                        NestedPosition::UpsertCreate => ("create", 0),
+                        NestedPosition::UpsertUpdate => ("update", 0),
+                        NestedPosition::Cascade => return None,
+                    };
+
+                    Some(crate::protobuf::prisma::NestedPosition {
+                        field: field.to_string(),
+                        index: index as u32,
+                    })
+                })
+                .collect();
+
+            crate::protobuf::prisma::DatabaseMutactionResult {
+                type_: Some(convert_mutaction_result_type(result)),
+                path,
+                cascaded_model,
+            }
+        })
+        .collect();
+
+    crate::protobuf::prisma::DatabaseMutactionResults { results }
+}
+
+fn convert_mutaction_result_type(
+    result: DatabaseMutactionResult,
+) -> crate::protobuf::prisma::database_mutaction_result::Type {
     use crate::protobuf::prisma::database_mutaction_result;
 
     match result.typ {
         DatabaseMutactionResultType::Create => {
             let result = crate::protobuf::prisma::IdResult { id: result.id().into() };
-            let typ = database_mutaction_result::Type::Create(result);
-
-            crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
+            database_mutaction_result::Type::Create(result)
         }
         DatabaseMutactionResultType::Update => {
             let result = crate::protobuf::prisma::IdResult { id: result.id().into() };
-            let typ = database_mutaction_result::Type::Update(result);
-
-            crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
+            database_mutaction_result::Type::Update(result)
         }
         DatabaseMutactionResultType::Delete => {
             let result = crate::protobuf::prisma::NodeResult::from(result.node().clone());
-            let typ = database_mutaction_result::Type::Delete(result);
-
-            crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
+            database_mutaction_result::Type::Delete(result)
         }
         DatabaseMutactionResultType::Many => {
             let result = crate::protobuf::prisma::ManyNodesResult {
                 count: result.count() as u32,
             };
-            let typ = database_mutaction_result::Type::Many(result);
-            crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
+            database_mutaction_result::Type::Many(result)
         }
-        DatabaseMutactionResultType::Unit => {
-            let result = crate::protobuf::prisma::Unit {};
-            let typ = database_mutaction_result::Type::Unit(result);
-            crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
-        }
-
-        // x => panic!("can't handle result type {:?}", x),
+        DatabaseMutactionResultType::Unit => database_mutaction_result::Type::Unit(crate::protobuf::prisma::Unit {}),
     }
 }
@@ -1,10 +1,36 @@
-use prisma_models::{ModelRef, OrderBy};
+use crate::{protobuf::prisma::order_by::NullsOrder, BridgeResult};
+use prisma_models::{JsonPath, ModelRef, OrderBy};
 
-pub fn into_model_order_by(model: ModelRef, ord: crate::protobuf::prisma::OrderBy) -> OrderBy {
-    let field = model.fields().find_from_scalar(&ord.scalar_field).unwrap();
+pub fn into_model_order_by(model: ModelRef, ord: crate::protobuf::prisma::OrderBy) -> BridgeResult<OrderBy> {
+    let mut model = model;
+    let mut relations = Vec::new();
 
-    OrderBy {
+    for name in ord.relations.iter() {
+        let relation_field = model.fields().find_from_relation_fields(name)?;
+
+        model = relation_field.related_model();
+        relations.push(relation_field);
+    }
+
+    let field = model.fields().find_from_scalar(&ord.scalar_field)?;
+
+    let related_count = match ord.related_count {
+        Some(ref name) => Some(model.fields().find_from_relation_fields(name)?),
+        None => None,
+    };
+
+    let path = match ord.path {
+        Some(ref path) => Some(path.parse::<JsonPath>()?),
+        None => None,
+    };
+
+    Ok(OrderBy {
         field: field,
+        relations,
         sort_order: ord.sort_order().into(),
-    }
+        nulls: ord.nulls.and_then(NullsOrder::from_i32).map(|nulls| nulls.into()),
+        search: ord.search,
+        path,
+        related_count,
+    })
 }
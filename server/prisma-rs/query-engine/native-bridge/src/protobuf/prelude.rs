@@ -1,6 +1,6 @@
 pub use super::prisma::{
     self, filter, graphql_id, graphql_id::IdValue, order_by, relation_filter, result, rpc_response as rpc,
-    scalar_filter, CountByModelInput, CountByTableInput, Error as ProtoError, ExecuteRawInput, ExecuteRawResult,
-    GetNodeByWhereInput, GetNodesInput, GetRelatedNodesInput, GetScalarListValues, GetScalarListValuesByNodeIds,
-    Header, MultiContainer, QueryArguments, RpcResponse, ValueContainer,
+    scalar_filter, AggregateInput, CountByModelInput, CountByTableInput, Error as ProtoError, ExecuteRawInput,
+    ExecuteRawResult, GetNodeByWhereInput, GetNodesInput, GetRelatedNodesInput, GetScalarListValues,
+    GetScalarListValuesByNodeIds, Header, MultiContainer, QueryArguments, RpcResponse, ValueContainer,
 };
@@ -1,15 +1,29 @@
 use super::{filter::IntoFilter, order_by};
-use crate::protobuf::QueryArguments;
-use prisma_models::ModelRef;
+use crate::{protobuf::QueryArguments, BridgeResult};
+use prisma_models::{DomainResult, ModelRef};
 
-pub fn into_model_query_arguments(model: ModelRef, args: QueryArguments) -> connector::QueryArguments {
-    connector::QueryArguments {
+pub fn into_model_query_arguments(model: ModelRef, args: QueryArguments) -> BridgeResult<connector::QueryArguments> {
+    let filter = match args.filter {
+        Some(filter) => Some(filter.into_filter(model.clone())?),
+        None => None,
+    };
+
+    Ok(connector::QueryArguments {
         skip: args.skip,
         after: args.after.map(|x| x.into()),
         first: args.first,
         before: args.before.map(|x| x.into()),
         last: args.last,
-        filter: args.filter.map(|x| x.into_filter(model.clone())),
-        order_by: args.order_by.map(|x| order_by::into_model_order_by(model.clone(), x)),
-    }
+        filter,
+        order_by: args
+            .order_by
+            .into_iter()
+            .map(|x| order_by::into_model_order_by(model.clone(), x))
+            .collect::<BridgeResult<_>>()?,
+        distinct: args
+            .distinct
+            .iter()
+            .map(|name| model.fields().find_from_scalar(name))
+            .collect::<DomainResult<_>>()?,
+    })
 }
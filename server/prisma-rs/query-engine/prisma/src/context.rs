@@ -1,5 +1,8 @@
-use crate::{schema, PrismaResult};
-use core::QueryExecutor;
+use crate::{
+    schema::{self, GraphQlSchema, SchemaBuilder},
+    PrismaResult,
+};
+use core::{MutationExecutor, QueryExecutor};
 use prisma_common::config::{self, ConnectionLimit, PrismaConfig, PrismaDatabase};
 use prisma_models::SchemaRef;
 use sqlite_connector::Sqlite;
@@ -8,13 +11,15 @@ use std::sync::Arc;
 pub struct PrismaContext {
     pub config: PrismaConfig,
     pub schema: SchemaRef,
+    pub graphql_schema: GraphQlSchema,
     pub query_executor: QueryExecutor,
+    pub mutation_executor: MutationExecutor,
 }
 
 impl PrismaContext {
     pub fn new() -> PrismaResult<Self> {
         let config = config::load().unwrap();
-        let data_resolver = match config.databases.get("default") {
+        let sqlite = match config.databases.get("default") {
             Some(PrismaDatabase::Explicit(ref config)) if config.connector == "sqlite-native" => {
                 let test_mode = false;
                 let sqlite = Sqlite::new(config.limit(), test_mode).unwrap();
@@ -23,7 +28,9 @@ impl PrismaContext {
             _ => panic!("Database connector is not supported, use sqlite with a file for now!"),
         };
 
-        let query_executor: QueryExecutor = QueryExecutor { data_resolver };
+        let query_executor: QueryExecutor = QueryExecutor {
+            data_resolver: Arc::clone(&sqlite),
+        };
 
         let db_name = config
             .databases
@@ -32,11 +39,20 @@ impl PrismaContext {
             .db_name()
             .expect("database was not set");
 
+        let mutation_executor = MutationExecutor {
+            db_name: db_name.clone(),
+            database_executor: sqlite,
+        };
+
         let schema = schema::load_schema(db_name)?;
+        let graphql_schema = SchemaBuilder::build(Arc::clone(&schema));
+
         Ok(Self {
             config: config,
             schema: schema,
+            graphql_schema: graphql_schema,
             query_executor: query_executor,
+            mutation_executor: mutation_executor,
         })
     }
 }
@@ -1,5 +1,8 @@
+use crate::schema::{Location, ValidationError};
+use connector::error::ConnectorError;
 use core::CoreError;
 use failure::{Error, Fail};
+use serde::Serialize;
 use serde_json;
 
 #[derive(Debug, Fail)]
@@ -10,6 +13,9 @@ pub enum PrismaError {
     #[fail(display = "{}", _0)]
     QueryValidationError(String),
 
+    #[fail(display = "Query validation failed")]
+    QueryValidationErrors(Vec<ValidationError>),
+
     #[fail(display = "{}", _0)]
     SerializationError(String),
 
@@ -26,6 +32,93 @@ pub enum PrismaError {
     IOError(Error),
 }
 
+/// An entry of the `errors` array of a GraphQL response
+#[derive(Debug, Serialize)]
+pub struct ResponseError {
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Location>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<PathSegment>,
+
+    pub extensions: ErrorExtensions,
+}
+
+/// A response name, or the index of an item in a list
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorExtensions {
+    pub code: &'static str,
+}
+
+impl ResponseError {
+    pub fn new(message: String, code: &'static str) -> Self {
+        Self {
+            message,
+            locations: vec![],
+            path: vec![],
+            extensions: ErrorExtensions { code },
+        }
+    }
+}
+
+impl PrismaError {
+    /// A machine readable code, reported as `extensions.code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            PrismaError::QueryParsingError(_) => "GRAPHQL_PARSE_FAILED",
+            PrismaError::QueryValidationError(_) | PrismaError::QueryValidationErrors(_) => "GRAPHQL_VALIDATION_FAILED",
+            PrismaError::CoreError(e) => core_error_code(e),
+            _ => "INTERNAL_SERVER_ERROR",
+        }
+    }
+
+    /// Converts the error into entries of the `errors` array of a response.
+    /// Validation reports all problems of a document at once.
+    pub fn into_response_errors(self) -> Vec<ResponseError> {
+        let code = self.code();
+
+        match self {
+            PrismaError::QueryValidationErrors(errors) => errors
+                .into_iter()
+                .map(|error| ResponseError {
+                    locations: error.locations,
+                    ..ResponseError::new(error.message, code)
+                })
+                .collect(),
+            err => vec![ResponseError::new(format!("{}", err), code)],
+        }
+    }
+}
+
+impl From<&CoreError> for ResponseError {
+    fn from(e: &CoreError) -> ResponseError {
+        ResponseError::new(format!("{}", e), core_error_code(e))
+    }
+}
+
+fn core_error_code(e: &CoreError) -> &'static str {
+    match e {
+        CoreError::QueryValidationError(_) => "BAD_USER_INPUT",
+        CoreError::ConnectorError(e) => match e {
+            ConnectorError::UniqueConstraintViolation { .. } => "UNIQUE_CONSTRAINT_VIOLATION",
+            ConnectorError::NodeDoesNotExist | ConnectorError::NodeNotFoundForWhere(_) => "NOT_FOUND",
+            ConnectorError::FieldCannotBeNull { .. } | ConnectorError::InvalidConnectionArguments => "BAD_USER_INPUT",
+            ConnectorError::RelationViolation { .. } | ConnectorError::NodesNotConnected { .. } => "RELATION_VIOLATION",
+            _ => "INTERNAL_SERVER_ERROR",
+        },
+        _ => "INTERNAL_SERVER_ERROR",
+    }
+}
+
 impl From<CoreError> for PrismaError {
     fn from(e: CoreError) -> PrismaError {
         PrismaError::CoreError(e)
@@ -9,6 +9,9 @@ mod utilities;
 
 mod serializer;
 
+#[cfg(test)]
+mod test_utils;
+
 use actix_web::{fs, http::Method, server, App, HttpRequest, Json, Responder};
 use context::PrismaContext;
 use error::PrismaError;
@@ -45,6 +48,7 @@ fn main() {
                 r.method(Method::POST).with(handler);
             })
             .resource("/datamodel", |r| r.method(Method::GET).with(data_model_handler))
+            .resource("/sdl", |r| r.method(Method::GET).with(sdl_handler))
     })
     .bind(address)
     .unwrap()
@@ -74,6 +78,10 @@ fn data_model_handler<T>(_: HttpRequest<T>) -> impl Responder {
     schema::load_datamodel_file().unwrap()
 }
 
+fn sdl_handler(req: HttpRequest<Arc<HttpHandler>>) -> impl Responder {
+    req.state().context.graphql_schema.render_sdl()
+}
+
 fn playground<T>(_: HttpRequest<T>) -> impl Responder {
     fs::NamedFile::open("prisma-rs/playground.html")
 }
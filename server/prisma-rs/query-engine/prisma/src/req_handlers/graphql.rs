@@ -1,9 +1,15 @@
 use super::{PrismaRequest, RequestHandler};
-use crate::{context::PrismaContext, error::PrismaError, schema::Validatable, PrismaResult};
-use core::{PrismaQuery, PrismaQueryResult, RootQueryBuilder};
+use crate::{
+    context::PrismaContext,
+    error::{PathSegment, PrismaError, ResponseError},
+    schema::{self, GraphQlSchema, Location, Validatable},
+    PrismaResult,
+};
+use core::{is_meta_field, resolve_operation, CoreResult, OperationKind, PrismaQueryResult, RootQueryBuilder};
+use gql::query::{Field, Selection};
 use graphql_parser as gql;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::BTreeMap, slice};
 
 use serde_json::{Map, Value};
 
@@ -16,7 +22,7 @@ type JsonMap = Map<String, Value>;
 pub struct GraphQlBody {
     query: String,
     operation_name: Option<String>,
-    variables: HashMap<String, String>,
+    variables: Option<JsonMap>,
 }
 
 pub struct GraphQlRequestHandler;
@@ -29,10 +35,14 @@ impl RequestHandler for GraphQlRequestHandler {
         // Handle incoming request and deal with errors properly
         match handle_safely(req.into(), ctx) {
             Ok(val) => val,
+            // Requests that fail before execution have no `data`
             Err(err) => {
                 let mut map = Map::new();
-                map.insert("reason".into(), format!("{}", err).into());
-                json_envelope("error", map)
+                map.insert(
+                    "errors".into(),
+                    serde_json::to_value(err.into_response_errors()).expect("Response errors are always serializable"),
+                );
+                Value::Object(map)
             }
         }
     }
@@ -41,39 +51,233 @@ impl RequestHandler for GraphQlRequestHandler {
 fn handle_safely(req: PrismaRequest<GraphQlBody>, ctx: &PrismaContext) -> PrismaResult<Value> {
     let query_doc = match gql::parse_query(&req.body.query) {
         Ok(doc) => doc,
-        Err(e) => return Err(PrismaError::QueryParsingError(format!("{:?}", e))),
+        Err(e) => return Err(PrismaError::QueryParsingError(format!("{}", e))),
     };
 
-    // Let's validate the schema!
-    if let Err(_) = ctx.schema.validate(&query_doc) {
-        return Err(PrismaError::QueryValidationError(
-            "Schema validation failed for unknown reasons".into(),
-        ));
+    if let Err(errors) = ctx.graphql_schema.validate(&query_doc) {
+        return Err(PrismaError::QueryValidationErrors(errors));
     }
 
-    dbg!(&query_doc);
+    let variables: BTreeMap<String, gql::query::Value> = req
+        .body
+        .variables
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name, json_to_gql(value)))
+        .collect();
+
+    let operation_name = req.body.operation_name.as_ref().map(String::as_str);
+    let operation = resolve_operation(&query_doc, operation_name, &variables)?;
 
     let qb = RootQueryBuilder {
         query: query_doc,
         schema: ctx.schema.clone(),
         operation_name: req.body.operation_name,
+        variables,
     };
 
-    let queries: Vec<PrismaQuery> = qb.build()?;
+    let root_type_name = match operation.kind {
+        OperationKind::Query => GraphQlSchema::QUERY_TYPE,
+        OperationKind::Mutation => GraphQlSchema::MUTATION_TYPE,
+    };
 
-    let results: Vec<PrismaQueryResult> = dbg!(ctx.query_executor.execute(&queries))?
-        .into_iter()
-        .map(|r| r.filter())
-        .collect();
+    let root_type = ctx.graphql_schema.find_type(root_type_name);
+    let root_fields = operation.selections.iter().filter_map(|selection| match selection {
+        Selection::Field(field) => Some(field),
+        _ => None,
+    });
 
-    Ok(json::serialize(
-        results.iter().fold(IrBuilder::new(), |b, res| b.add(res)).build(),
-    ))
+    // Root fields are built and executed one by one, so that a failing field doesn't fail the others.
+    // Mutations are executed in document order. Meta fields are resolved against the GraphQL schema.
+    let execute = |field: &Field| -> CoreResult<Vec<PrismaQueryResult>> {
+        match operation.kind {
+            OperationKind::Query => {
+                let query = qb.build_query_field(field)?;
+                ctx.query_executor.execute(slice::from_ref(&query))
+            }
+            OperationKind::Mutation => {
+                let mutation = qb.build_mutation_field(field)?;
+                ctx.mutation_executor
+                    .execute(slice::from_ref(&mutation), &ctx.query_executor)
+            }
+        }
+    };
+
+    let responses = root_fields
+        .fold(IrBuilder::new(), |builder, field| {
+            let name = field.alias.as_ref().unwrap_or(&field.name).clone();
+            let nullable = root_type
+                .and_then(|typ| typ.find_field(&field.name))
+                .map(|definition| !definition.typ.is_non_null())
+                .unwrap_or(true);
+
+            if is_meta_field(field) {
+                return match schema::resolve_meta_field(&ctx.graphql_schema, field, root_type_name) {
+                    Ok(value) => builder.add_meta(name, value),
+                    Err(err) => builder.add_error(name.clone(), nullable, field_error(field, name, err)),
+                };
+            }
+
+            match execute(field) {
+                Ok(results) => results
+                    .into_iter()
+                    .fold(builder, |builder, result| builder.add(&result.filter(), nullable)),
+                Err(err) => builder.add_error(name.clone(), nullable, field_error(field, name, err.into())),
+            }
+        })
+        .build();
+
+    Ok(json::serialize(responses))
+}
+
+/// The error of a root field, reported at its position
+fn field_error(field: &Field, name: String, err: PrismaError) -> ResponseError {
+    ResponseError {
+        locations: vec![Location::from(field.position)],
+        path: vec![PathSegment::Field(name)],
+        ..err.into_response_errors().remove(0)
+    }
 }
 
-/// Create a json envelope
-fn json_envelope(id: &str, map: serde_json::Map<String, Value>) -> Value {
-    let mut envelope = JsonMap::new();
-    envelope.insert(id.to_owned(), Value::Object(map));
-    Value::Object(envelope)
+/// Converts a JSON variable value into a GraphQL value. Values are coerced
+/// into the type of the field they are used for when the query is built.
+fn json_to_gql(value: Value) -> gql::query::Value {
+    match value {
+        Value::Null => gql::query::Value::Null,
+        Value::Bool(b) => gql::query::Value::Boolean(b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) if i >= i64::from(std::i32::MIN) && i <= i64::from(std::i32::MAX) => {
+                gql::query::Value::Int(gql::query::Number::from(i as i32))
+            }
+            _ => gql::query::Value::Float(n.as_f64().unwrap_or(std::f64::NAN)),
+        },
+        Value::String(s) => gql::query::Value::String(s),
+        Value::Array(values) => gql::query::Value::List(values.into_iter().map(json_to_gql).collect()),
+        Value::Object(obj) => gql::query::Value::Object(
+            obj.into_iter()
+                .map(|(key, value)| (key, json_to_gql(value)))
+                .collect::<BTreeMap<String, gql::query::Value>>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_errors_have_no_data() {
+        let ctx = context("graphql_parse_errors");
+        let response = request(&ctx, "{ users { id }");
+
+        assert!(response.get("data").is_none());
+        assert_eq!(
+            json!("GRAPHQL_PARSE_FAILED"),
+            response["errors"][0]["extensions"]["code"]
+        );
+    }
+
+    #[test]
+    fn validation_errors_have_locations() {
+        let ctx = context("graphql_validation_errors");
+        let response = request(&ctx, "{\n  users {\n    nickname\n  }\n}");
+
+        assert!(response.get("data").is_none());
+        assert_eq!(json!([{ "line": 3, "column": 5 }]), response["errors"][0]["locations"]);
+        assert_eq!(
+            json!("GRAPHQL_VALIDATION_FAILED"),
+            response["errors"][0]["extensions"]["code"]
+        );
+    }
+
+    #[test]
+    fn a_failing_nullable_root_field_is_null_next_to_the_others() {
+        let ctx = context("graphql_nullable_root_field");
+        let response = request(
+            &ctx,
+            "mutation {\n  alice: createUser(data: { name: \"Alice\" }) { name }\n  \
+             missing: updateUser(where: { id: \"missing\" }, data: { name: \"Bob\" }) { name }\n}",
+        );
+
+        assert_eq!(
+            json!({ "alice": { "name": "Alice" }, "missing": null }),
+            response["data"]
+        );
+
+        let errors = response["errors"].as_array().unwrap();
+
+        assert_eq!(1, errors.len());
+        assert_eq!(json!(["missing"]), errors[0]["path"]);
+        assert_eq!(json!([{ "line": 3, "column": 3 }]), errors[0]["locations"]);
+        assert_eq!(json!("NOT_FOUND"), errors[0]["extensions"]["code"]);
+    }
+
+    #[test]
+    fn a_root_field_that_fails_to_build_is_null_next_to_the_others() {
+        let ctx = context("graphql_root_field_build_error");
+        request(&ctx, "mutation { createUser(data: { name: \"Alice\" }) { name } }");
+
+        let response = request(&ctx, "{\n  users { name }\n  empty: user(where: {}) { name }\n}");
+
+        assert_eq!(
+            json!({ "users": [{ "name": "Alice" }], "empty": null }),
+            response["data"]
+        );
+
+        let errors = response["errors"].as_array().unwrap();
+
+        assert_eq!(1, errors.len());
+        assert_eq!(json!(["empty"]), errors[0]["path"]);
+        assert_eq!(json!([{ "line": 3, "column": 3 }]), errors[0]["locations"]);
+    }
+
+    #[test]
+    fn a_failing_non_nullable_root_field_nulls_the_data() {
+        let ctx = context("graphql_non_nullable_root_field");
+        let create = "mutation { createAccount(data: { email: \"a@prisma.io\" }) { email } }";
+
+        assert_eq!(
+            json!({ "createAccount": { "email": "a@prisma.io" } }),
+            request(&ctx, create)["data"]
+        );
+
+        let response = request(&ctx, create);
+
+        assert_eq!(Value::Null, response["data"]);
+        assert_eq!(json!(["createAccount"]), response["errors"][0]["path"]);
+        assert_eq!(
+            json!("UNIQUE_CONSTRAINT_VIOLATION"),
+            response["errors"][0]["extensions"]["code"]
+        );
+    }
+
+    #[test]
+    fn meta_root_fields_are_resolved_next_to_data_fields() {
+        let ctx = context("graphql_meta_root_fields");
+        let response = request(
+            &ctx,
+            "mutation { __typename createUser(data: { name: \"Alice\" }) { name } }",
+        );
+
+        assert_eq!(
+            json!({ "__typename": "Mutation", "createUser": { "name": "Alice" } }),
+            response["data"]
+        );
+
+        let response = request(
+            &ctx,
+            "{ users { name } __typename schema: __schema { queryType { name } } }",
+        );
+
+        assert_eq!(
+            json!({
+                "users": [{ "name": "Alice" }],
+                "__typename": "Query",
+                "schema": { "queryType": { "name": "Query" } }
+            }),
+            response["data"]
+        );
+    }
 }
@@ -0,0 +1,1087 @@
+//! Derives the Prisma CRUD API from the data model
+//!
+//! Input types for nested mutations are named after the relation field on the other side,
+//! e.g. `PostCreateManyWithoutAuthorInput`, and are created on first use.
+
+use super::types::*;
+use connector::Aggregation;
+use inflector::Inflector;
+use prisma_models::{
+    Field as ModelField, ModelRef, RelationFieldRef, ScalarField, SchemaRef, TypeIdentifier, UniqueConstraint,
+};
+use std::collections::{BTreeMap, HashSet};
+
+const BUILTIN_SCALARS: &[&str] = &["String", "Int", "Float", "Boolean", "ID"];
+
+pub struct SchemaBuilder {
+    schema: SchemaRef,
+    types: BTreeMap<String, GraphQlType>,
+
+    /// Input types currently being built, to break cycles between related models
+    pending: HashSet<String>,
+
+    /// Input types that would have no fields and are therefore left out
+    empty: HashSet<String>,
+}
+
+impl SchemaBuilder {
+    pub fn build(schema: SchemaRef) -> GraphQlSchema {
+        let mut builder = Self {
+            schema,
+            types: BTreeMap::new(),
+            pending: HashSet::new(),
+            empty: HashSet::new(),
+        };
+
+        builder.add_builtin_types();
+
+        let models = builder.schema.models().to_vec();
+        let mut query_fields = vec![];
+        let mut mutation_fields = vec![];
+
+        for model in models.iter() {
+            builder.add_model_type(model);
+            builder.add_connection_types(model);
+
+            query_fields.append(&mut builder.query_fields(model));
+            mutation_fields.append(&mut builder.mutation_fields(model));
+        }
+
+        builder.register(GraphQlType::object(GraphQlSchema::QUERY_TYPE, query_fields));
+        builder.register(GraphQlType::object(GraphQlSchema::MUTATION_TYPE, mutation_fields));
+
+        GraphQlSchema {
+            types: builder.types.into_iter().map(|(_, typ)| typ).collect(),
+        }
+    }
+
+    pub fn is_builtin_scalar(name: &str) -> bool {
+        BUILTIN_SCALARS.contains(&name)
+    }
+
+    fn add_builtin_types(&mut self) {
+        for name in BUILTIN_SCALARS {
+            self.register(GraphQlType::scalar(name, None));
+        }
+
+        self.register(GraphQlType::scalar("DateTime", Some("An ISO-8601 encoded UTC date string")));
+        self.register(GraphQlType::scalar("Json", Some("Raw JSON value")));
+        self.register(GraphQlType::scalar("UUID", None));
+
+        self.register(GraphQlType::object(
+            "PageInfo",
+            vec![
+                FieldDefinition::new("hasNextPage", TypeRef::named("Boolean").non_null()),
+                FieldDefinition::new("hasPreviousPage", TypeRef::named("Boolean").non_null()),
+                FieldDefinition::new("startCursor", TypeRef::named("String")),
+                FieldDefinition::new("endCursor", TypeRef::named("String")),
+            ],
+        ));
+
+        self.register(GraphQlType::object(
+            "BatchPayload",
+            vec![FieldDefinition::new("count", TypeRef::named("Int").non_null())],
+        ));
+
+        let enums: Vec<GraphQlType> = self
+            .schema
+            .enums
+            .iter()
+            .map(|e| GraphQlType::enumeration(&e.name, e.values.clone()))
+            .collect();
+
+        for typ in enums {
+            self.register(typ);
+        }
+    }
+
+    fn add_model_type(&mut self, model: &ModelRef) {
+        let mut fields = vec![];
+
+        for field in model.fields().all.iter() {
+            match field {
+                ModelField::Scalar(sf) if !sf.is_hidden => {
+                    fields.push(FieldDefinition::new(&sf.name, scalar_output_type(sf)));
+                }
+                ModelField::Relation(rf) if !rf.is_hidden => {
+                    let related = rf.related_model();
+                    let typ = TypeRef::named(&related.name);
+
+                    let definition = if rf.is_list {
+                        FieldDefinition::with_arguments(&rf.name, self.many_arguments(&related), typ.non_null().list())
+                    } else if rf.is_required {
+                        FieldDefinition::new(&rf.name, typ.non_null())
+                    } else {
+                        FieldDefinition::new(&rf.name, typ)
+                    };
+
+                    fields.push(definition);
+
+                    if rf.is_list {
+                        fields.push(FieldDefinition::with_arguments(
+                            &format!("{}Connection", rf.name),
+                            self.many_arguments(&related),
+                            TypeRef::named(&format!("{}Connection", related.name)).non_null(),
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        self.register(GraphQlType::object(&model.name, fields));
+    }
+
+    fn add_connection_types(&mut self, model: &ModelRef) {
+        let edge = format!("{}Edge", model.name);
+        let aggregate = format!("Aggregate{}", model.name);
+
+        self.register(GraphQlType::object(
+            &edge,
+            vec![
+                FieldDefinition::new("node", TypeRef::named(&model.name).non_null()),
+                FieldDefinition::new("cursor", TypeRef::named("String").non_null()),
+            ],
+        ));
+
+        let mut aggregate_fields = vec![FieldDefinition::new("count", TypeRef::named("Int").non_null())];
+
+        // Functions of a field, e.g. `sum { price }`, are null if no node has a value.
+        let functions: &[(&str, fn(&ScalarField) -> bool)] = &[
+            ("sum", Aggregation::is_numeric),
+            ("avg", Aggregation::is_numeric),
+            ("min", Aggregation::is_comparable),
+            ("max", Aggregation::is_comparable),
+        ];
+
+        for (function, applies) in functions {
+            let fields: Vec<FieldDefinition> = model
+                .fields()
+                .scalar_non_list()
+                .into_iter()
+                .filter(|sf| !sf.is_hidden && applies(sf.as_ref()))
+                .map(|sf| match *function {
+                    "avg" => FieldDefinition::new(&sf.name, TypeRef::named("Float")),
+                    _ => FieldDefinition::new(&sf.name, TypeRef::named(&scalar_type_name(&sf))),
+                })
+                .collect();
+
+            if !fields.is_empty() {
+                let name = format!("{}{}Aggregate", model.name, capitalize(function));
+
+                self.register(GraphQlType::object(&name, fields));
+                aggregate_fields.push(FieldDefinition::new(function, TypeRef::named(&name).non_null()));
+            }
+        }
+
+        self.register(GraphQlType::object(&aggregate, aggregate_fields));
+
+        self.register(GraphQlType::object(
+            &format!("{}Connection", model.name),
+            vec![
+                FieldDefinition::new("pageInfo", TypeRef::named("PageInfo").non_null()),
+                FieldDefinition::new("edges", TypeRef::named(&edge).list().non_null()),
+                FieldDefinition::new("aggregate", TypeRef::named(&aggregate).non_null()),
+            ],
+        ));
+    }
+
+    fn query_fields(&mut self, model: &ModelRef) -> Vec<FieldDefinition> {
+        let name = model.name.to_camel_case();
+        let typ = TypeRef::named(&model.name);
+        let where_unique = self.where_unique_input(model).non_null();
+
+        vec![
+            FieldDefinition::with_arguments(
+                &name.to_singular(),
+                vec![InputValue::new("where", where_unique)],
+                typ.clone(),
+            ),
+            FieldDefinition::with_arguments(&name.to_plural(), self.many_arguments(model), typ.list().non_null()),
+            FieldDefinition::with_arguments(
+                &format!("{}Connection", name.to_plural()),
+                self.many_arguments(model),
+                TypeRef::named(&format!("{}Connection", model.name)).non_null(),
+            ),
+        ]
+    }
+
+    fn mutation_fields(&mut self, model: &ModelRef) -> Vec<FieldDefinition> {
+        let name = &model.name;
+        let plural = model.name.to_plural();
+        let typ = TypeRef::named(name);
+        let batch_payload = TypeRef::named("BatchPayload").non_null();
+
+        let where_unique = self.where_unique_input(model).non_null();
+        let where_ = self.where_input(model);
+        let create = self.create_input(model, None).map(TypeRef::non_null);
+        let update = self.update_input(model, true).map(TypeRef::non_null);
+        let create_many = self.create_many_input(model).map(TypeRef::non_null);
+        let update_many = self.update_input(model, false).map(TypeRef::non_null);
+
+        let mut fields = vec![];
+
+        if let Some(ref create) = create {
+            fields.push(FieldDefinition::with_arguments(
+                &format!("create{}", name),
+                vec![InputValue::new("data", create.clone())],
+                typ.clone().non_null(),
+            ));
+        }
+
+        if let Some(ref update) = update {
+            fields.push(FieldDefinition::with_arguments(
+                &format!("update{}", name),
+                vec![
+                    InputValue::new("data", update.clone()),
+                    InputValue::new("where", where_unique.clone()),
+                ],
+                typ.clone(),
+            ));
+        }
+
+        if let (Some(create), Some(update)) = (create, update) {
+            fields.push(FieldDefinition::with_arguments(
+                &format!("upsert{}", name),
+                vec![
+                    InputValue::new("where", where_unique.clone()),
+                    InputValue::new("create", create),
+                    InputValue::new("update", update),
+                ],
+                typ.clone().non_null(),
+            ));
+        }
+
+        fields.push(FieldDefinition::with_arguments(
+            &format!("delete{}", name),
+            vec![InputValue::new("where", where_unique)],
+            typ,
+        ));
+
+        if let Some(create_many) = create_many {
+            fields.push(FieldDefinition::with_arguments(
+                &format!("createMany{}", plural),
+                vec![
+                    InputValue::new("data", create_many.list().non_null()),
+                    InputValue::new("skipDuplicates", TypeRef::named("Boolean")),
+                ],
+                batch_payload.clone(),
+            ));
+        }
+
+        if let Some(update_many) = update_many {
+            fields.push(FieldDefinition::with_arguments(
+                &format!("updateMany{}", plural),
+                vec![
+                    InputValue::new("data", update_many),
+                    InputValue::new("where", where_.clone()),
+                ],
+                batch_payload.clone(),
+            ));
+        }
+
+        fields.push(FieldDefinition::with_arguments(
+            &format!("deleteMany{}", plural),
+            vec![InputValue::new("where", where_)],
+            batch_payload,
+        ));
+
+        fields
+    }
+
+    /// Arguments of fields returning many records of the model
+    fn many_arguments(&mut self, model: &ModelRef) -> Vec<InputValue> {
+        let mut arguments = vec![
+            InputValue::new("where", self.where_input(model)),
+            InputValue::new("orderBy", self.order_by_input(model).non_null().list()),
+            InputValue::new("distinct", self.distinct_field_enum(model).non_null().list()),
+            InputValue::new("skip", TypeRef::named("Int")),
+            InputValue::new("after", TypeRef::named("String")),
+            InputValue::new("before", TypeRef::named("String")),
+            InputValue::new("first", TypeRef::named("Int")),
+            InputValue::new("last", TypeRef::named("Int")),
+        ];
+
+        // Orders a Json field given in `orderBy` by the value at the path.
+        let has_json_fields = model
+            .fields()
+            .scalar_non_list()
+            .iter()
+            .any(|sf| !sf.is_hidden && sf.type_identifier == TypeIdentifier::Json);
+
+        if has_json_fields {
+            arguments.push(InputValue::new("orderByPath", TypeRef::named("String")));
+        }
+
+        arguments
+    }
+
+    fn where_input(&mut self, model: &ModelRef) -> TypeRef {
+        let name = format!("{}WhereInput", model.name);
+        let self_list = TypeRef::named(&name).non_null().list();
+
+        let input = self.input_object(name, |builder| {
+            let mut fields = vec![];
+
+            for field in model.fields().all.iter() {
+                match field {
+                    ModelField::Scalar(sf) if !sf.is_hidden && sf.is_list => {
+                        fields.append(&mut scalar_list_filters(sf))
+                    }
+                    ModelField::Scalar(sf) if !sf.is_hidden && sf.type_identifier == TypeIdentifier::Json => {
+                        let path_filter = builder.json_path_filter();
+
+                        fields.append(&mut scalar_filters(sf));
+                        fields.push(InputValue::new(&format!("{}_path", sf.name), path_filter));
+                    }
+                    ModelField::Scalar(sf) if !sf.is_hidden => fields.append(&mut scalar_filters(sf)),
+                    ModelField::Relation(rf) if !rf.is_hidden => {
+                        let related = builder.where_input(&rf.related_model());
+
+                        if rf.is_list {
+                            for suffix in &["_every", "_some", "_none"] {
+                                fields.push(InputValue::new(&format!("{}{}", rf.name, suffix), related.clone()));
+                            }
+
+                            let count_filter = builder.count_filter(&rf.related_model());
+                            fields.push(InputValue::new(&format!("{}_count", rf.name), count_filter));
+                        } else {
+                            fields.push(InputValue::new(&rf.name, related));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+
+            fields.push(InputValue::new("AND", self_list.clone()));
+            fields.push(InputValue::new("OR", self_list.clone()));
+            fields.push(InputValue::new("NOT", self_list));
+
+            fields
+        });
+
+        input.expect("Where inputs always have fields")
+    }
+
+    fn where_unique_input(&mut self, model: &ModelRef) -> TypeRef {
+        let name = format!("{}WhereUniqueInput", model.name);
+
+        let input = self.input_object(name, |builder| {
+            let mut fields: Vec<InputValue> = model
+                .fields()
+                .scalar_non_list()
+                .into_iter()
+                .filter(|sf| !sf.is_hidden && (sf.is_unique || sf.is_id()))
+                .map(|sf| InputValue::new(&sf.name, TypeRef::named(&scalar_type_name(&sf))))
+                .collect();
+
+            // Multi-field unique constraints, e.g. `tenantId_slug: { tenantId: "...", slug: "..." }`.
+            for constraint in model.unique_constraints.iter() {
+                let typ = builder.compound_unique_input(model, constraint);
+                fields.push(InputValue::new(&constraint.name, typ));
+            }
+
+            fields
+        });
+
+        input.expect("Models always have an id field")
+    }
+
+    /// `{Model}{Constraint}CompoundUniqueInput`, the values of all fields of a
+    /// multi-field unique constraint.
+    fn compound_unique_input(&mut self, model: &ModelRef, constraint: &UniqueConstraint) -> TypeRef {
+        let name = format!("{}{}CompoundUniqueInput", model.name, constraint.name.to_pascal_case());
+
+        let fields = model
+            .unique_constraint_fields(constraint)
+            .expect("Unique constraints consist of scalar fields of the model")
+            .into_iter()
+            .map(|sf| InputValue::new(&sf.name, TypeRef::named(&scalar_type_name(&sf)).non_null()))
+            .collect();
+
+        self.register(GraphQlType::input_object(&name, fields));
+        TypeRef::Named(name)
+    }
+
+    fn order_by_input(&mut self, model: &ModelRef) -> TypeRef {
+        let name = format!("{}OrderByInput", model.name);
+
+        let orderings = |prefix: &str, model: &ModelRef| -> Vec<String> {
+            model
+                .fields()
+                .scalar_non_list()
+                .into_iter()
+                .filter(|sf| !sf.is_hidden)
+                .flat_map(|sf| {
+                    SORT_SUFFIXES
+                        .iter()
+                        .map(|suffix| format!("{}{}{}", prefix, sf.name, suffix))
+                        .collect::<Vec<String>>()
+                })
+                .collect()
+        };
+
+        let mut values = orderings("", model);
+
+        // Ranks the nodes by the `{field}_search` filter.
+        for sf in model.fields().scalar_non_list() {
+            if model.is_searchable && !sf.is_hidden && sf.type_identifier == TypeIdentifier::String {
+                values.push(format!("{}_RELEVANCE", sf.name));
+            }
+        }
+
+        // Fields of models related through to-one relations, e.g. `author_name_ASC`,
+        // and the number of nodes related through list relations, e.g. `posts_COUNT_DESC`.
+        for rf in model.fields().relation() {
+            if !rf.is_hidden && !rf.is_list {
+                values.append(&mut orderings(&format!("{}_", rf.name), &rf.related_model()));
+            } else if !rf.is_hidden {
+                values.push(format!("{}_COUNT_ASC", rf.name));
+                values.push(format!("{}_COUNT_DESC", rf.name));
+            }
+        }
+
+        self.register(GraphQlType::enumeration(&name, values));
+        TypeRef::Named(name)
+    }
+
+    /// `{Model}DistinctFieldEnum`, the fields nodes can be distinct by.
+    fn distinct_field_enum(&mut self, model: &ModelRef) -> TypeRef {
+        let name = format!("{}DistinctFieldEnum", model.name);
+
+        let values = model
+            .fields()
+            .scalar_non_list()
+            .into_iter()
+            .filter(|sf| !sf.is_hidden)
+            .map(|sf| sf.name.clone())
+            .collect();
+
+        self.register(GraphQlType::enumeration(&name, values));
+        TypeRef::Named(name)
+    }
+
+    /// `{Model}CreateInput`, or `{Model}CreateWithout{Field}Input` for nested creates
+    /// through the relation field `without`, which is left out.
+    fn create_input(&mut self, model: &ModelRef, without: Option<&RelationFieldRef>) -> Option<TypeRef> {
+        let name = match without {
+            Some(field) => format!("{}CreateWithout{}Input", model.name, capitalize(&field.name)),
+            None => format!("{}CreateInput", model.name),
+        };
+
+        self.input_object(name, |builder| {
+            let mut fields = vec![];
+
+            for field in model.fields().all.iter() {
+                match field {
+                    ModelField::Scalar(sf) if sf.is_hidden || !sf.is_writable() => (),
+                    ModelField::Scalar(sf) if sf.is_list => {
+                        let typ = builder.scalar_list_input(model, sf, "Create");
+                        fields.push(InputValue::new(&sf.name, typ));
+                    }
+                    ModelField::Scalar(sf) => {
+                        let typ = TypeRef::named(&scalar_type_name(sf));
+                        let typ = if sf.is_required { typ.non_null() } else { typ };
+
+                        fields.push(InputValue::new(&sf.name, typ));
+                    }
+                    ModelField::Relation(rf) if rf.is_hidden || without.map(|w| w.name == rf.name).unwrap_or(false) => (),
+                    ModelField::Relation(rf) => {
+                        let typ = builder.nested_create_input(rf);
+                        let typ = if rf.is_required && !rf.is_list { typ.non_null() } else { typ };
+
+                        fields.push(InputValue::new(&rf.name, typ));
+                    }
+                }
+            }
+
+            fields
+        })
+    }
+
+    /// `{Model}CreateManyInput`, a record of a batch create without relations
+    /// and scalar lists.
+    fn create_many_input(&mut self, model: &ModelRef) -> Option<TypeRef> {
+        self.input_object(format!("{}CreateManyInput", model.name), |_| {
+            model
+                .fields()
+                .scalar_non_list()
+                .iter()
+                .filter(|sf| !sf.is_hidden && sf.is_writable())
+                .map(|sf| {
+                    let typ = TypeRef::named(&scalar_type_name(sf));
+                    let typ = if sf.is_required { typ.non_null() } else { typ };
+
+                    InputValue::new(&sf.name, typ)
+                })
+                .collect()
+        })
+    }
+
+    /// `{Model}UpdateInput`, or `{Model}UpdateManyMutationInput` without relations.
+    fn update_input(&mut self, model: &ModelRef, with_relations: bool) -> Option<TypeRef> {
+        let name = if with_relations {
+            format!("{}UpdateInput", model.name)
+        } else {
+            format!("{}UpdateManyMutationInput", model.name)
+        };
+
+        self.update_input_object(name, model, with_relations, None)
+    }
+
+    /// `{Model}UpdateWithout{Field}DataInput`, the data of nested updates through
+    /// the relation field `without`, which is left out.
+    fn update_data_input(&mut self, model: &ModelRef, without: &RelationFieldRef) -> Option<TypeRef> {
+        let name = format!("{}UpdateWithout{}DataInput", model.name, capitalize(&without.name));
+        self.update_input_object(name, model, true, Some(without))
+    }
+
+    fn update_input_object(
+        &mut self,
+        name: String,
+        model: &ModelRef,
+        with_relations: bool,
+        without: Option<&RelationFieldRef>,
+    ) -> Option<TypeRef> {
+        self.input_object(name, |builder| {
+            let mut fields = vec![];
+
+            for field in model.fields().all.iter() {
+                match field {
+                    ModelField::Scalar(sf) if sf.is_hidden || !sf.is_writable() => (),
+                    ModelField::Scalar(sf) if sf.is_list => {
+                        let typ = builder.scalar_list_input(model, sf, "Update");
+                        fields.push(InputValue::new(&sf.name, typ));
+                    }
+                    ModelField::Scalar(sf) => {
+                        let typ = TypeRef::named(&scalar_type_name(sf));
+
+                        // Arithmetic on the current value, e.g. `views_increment`.
+                        if sf.type_identifier == TypeIdentifier::Int || sf.type_identifier == TypeIdentifier::Float {
+                            for suffix in &["increment", "decrement", "multiply", "divide"] {
+                                fields.push(InputValue::new(&format!("{}_{}", sf.name, suffix), typ.clone()));
+                            }
+                        }
+
+                        fields.push(InputValue::new(&sf.name, typ));
+                    }
+                    ModelField::Relation(rf) if rf.is_hidden || without.map(|w| w.name == rf.name).unwrap_or(false) => (),
+                    ModelField::Relation(rf) if with_relations => {
+                        let typ = builder.nested_update_input(rf);
+                        fields.push(InputValue::new(&rf.name, typ));
+                    }
+                    ModelField::Relation(_) => (),
+                }
+            }
+
+            fields
+        })
+    }
+
+    /// E.g. `PostCreateManyWithoutAuthorInput` for the `posts` field of a `User`.
+    fn nested_create_input(&mut self, field: &RelationFieldRef) -> TypeRef {
+        let related = field.related_model();
+        let back = field.related_field();
+        let cardinality = if field.is_list { "Many" } else { "One" };
+        let name = format!("{}Create{}Without{}Input", related.name, cardinality, capitalize(&back.name));
+
+        let input = self.input_object(name, |builder| {
+            let wrap = |typ: TypeRef| if field.is_list { typ.non_null().list() } else { typ };
+            let mut fields = vec![];
+
+            if let Some(create) = builder.create_input(&related, Some(&back)) {
+                fields.push(InputValue::new("create", wrap(create)));
+            }
+
+            fields.push(InputValue::new("connect", wrap(builder.where_unique_input(&related))));
+            fields
+        });
+
+        input.expect("Nested create inputs always allow to connect")
+    }
+
+    /// E.g. `PostUpdateManyWithoutAuthorInput` for the `posts` field of a `User`.
+    /// Nested updates and upserts of a list relation select the related records
+    /// with a `where`. Required to-one relations can't be disconnected or deleted.
+    fn nested_update_input(&mut self, field: &RelationFieldRef) -> TypeRef {
+        let related = field.related_model();
+        let back = field.related_field();
+        let cardinality = match (field.is_list, field.is_required) {
+            (true, _) => "Many",
+            (false, true) => "OneRequired",
+            (false, false) => "One",
+        };
+        let name = format!("{}Update{}Without{}Input", related.name, cardinality, capitalize(&back.name));
+
+        let input = self.input_object(name, |builder| {
+            let where_unique = builder.where_unique_input(&related);
+            let create = builder.create_input(&related, Some(&back));
+            let update = builder.update_data_input(&related, &back);
+            let mut fields = vec![];
+
+            if field.is_list {
+                let where_uniques = where_unique.clone().non_null().list();
+
+                if let Some(ref create) = create {
+                    fields.push(InputValue::new("create", create.clone().non_null().list()));
+                }
+
+                fields.push(InputValue::new("connect", where_uniques.clone()));
+                fields.push(InputValue::new("disconnect", where_uniques.clone()));
+                fields.push(InputValue::new("set", where_uniques.clone()));
+
+                if let Some(ref update) = update {
+                    let name = format!("{}UpdateWithWhereUniqueWithout{}Input", related.name, capitalize(&back.name));
+                    let typ = builder.register_input(
+                        name,
+                        vec![
+                            InputValue::new("where", where_unique.clone().non_null()),
+                            InputValue::new("data", update.clone().non_null()),
+                        ],
+                    );
+
+                    fields.push(InputValue::new("update", typ.non_null().list()));
+                }
+
+                if let (Some(create), Some(update)) = (create, update) {
+                    let name = format!("{}UpsertWithWhereUniqueWithout{}Input", related.name, capitalize(&back.name));
+                    let typ = builder.register_input(
+                        name,
+                        vec![
+                            InputValue::new("where", where_unique.non_null()),
+                            InputValue::new("update", update.non_null()),
+                            InputValue::new("create", create.non_null()),
+                        ],
+                    );
+
+                    fields.push(InputValue::new("upsert", typ.non_null().list()));
+                }
+
+                fields.push(InputValue::new("delete", where_uniques));
+
+                let where_ = builder.where_input(&related);
+
+                if let Some(update_many) = builder.update_input(&related, false) {
+                    let name = format!("{}UpdateManyWithWhereNestedInput", related.name);
+                    let typ = builder.register_input(
+                        name,
+                        vec![
+                            InputValue::new("where", where_.clone()),
+                            InputValue::new("data", update_many.non_null()),
+                        ],
+                    );
+
+                    fields.push(InputValue::new("updateMany", typ.non_null().list()));
+                }
+
+                fields.push(InputValue::new("deleteMany", where_.non_null().list()));
+            } else {
+                if let Some(ref create) = create {
+                    fields.push(InputValue::new("create", create.clone()));
+                }
+
+                fields.push(InputValue::new("connect", where_unique));
+
+                if let Some(ref update) = update {
+                    fields.push(InputValue::new("update", update.clone()));
+                }
+
+                if let (Some(create), Some(update)) = (create, update) {
+                    let name = format!("{}UpsertWithout{}Input", related.name, capitalize(&back.name));
+                    let typ = builder.register_input(
+                        name,
+                        vec![
+                            InputValue::new("update", update.non_null()),
+                            InputValue::new("create", create.non_null()),
+                        ],
+                    );
+
+                    fields.push(InputValue::new("upsert", typ));
+                }
+
+                if !field.is_required {
+                    fields.push(InputValue::new("disconnect", TypeRef::named("Boolean")));
+                    fields.push(InputValue::new("delete", TypeRef::named("Boolean")));
+                }
+            }
+
+            fields
+        });
+
+        input.expect("Nested update inputs always allow to connect")
+    }
+
+    /// E.g. `UserCreatetagsInput`, which only allows to `set` the whole list,
+    /// or `UserUpdatetagsInput`, which also changes single values.
+    fn scalar_list_input(&mut self, model: &ModelRef, field: &ScalarField, operation: &str) -> TypeRef {
+        let name = format!("{}{}{}Input", model.name, operation, field.name);
+        let value = TypeRef::named(&scalar_type_name(field));
+        let values = value.clone().non_null().list();
+
+        let mut fields = vec![InputValue::new("set", values.clone())];
+
+        if operation == "Update" {
+            fields.push(InputValue::new("push", values.clone()));
+            fields.push(InputValue::new("unshift", values));
+            fields.push(InputValue::new("removeAt", TypeRef::named("Int")));
+            fields.push(InputValue::new("removeValue", value));
+        }
+
+        self.register(GraphQlType::input_object(&name, fields));
+        TypeRef::Named(name)
+    }
+
+    /// Conditions on the value at a path of a Json field, shared by all Json fields.
+    fn json_path_filter(&mut self) -> TypeRef {
+        let name = "JsonPathFilter";
+
+        self.register(GraphQlType::input_object(
+            name,
+            vec![
+                InputValue::new("path", TypeRef::named("String").non_null()),
+                InputValue::new("equals", TypeRef::named("Json")),
+                InputValue::new("string_contains", TypeRef::named("String")),
+                InputValue::new("string_contains_insensitive", TypeRef::named("String")),
+                InputValue::new("string_contains_sensitive", TypeRef::named("String")),
+                InputValue::new("array_contains", TypeRef::named("Json")),
+                InputValue::new("exists", TypeRef::named("Boolean")),
+            ],
+        ));
+
+        TypeRef::named(name)
+    }
+
+    /// `{Model}CountFilter`, conditions on the number of related nodes of the
+    /// model that match the `where` input.
+    fn count_filter(&mut self, model: &ModelRef) -> TypeRef {
+        let name = format!("{}CountFilter", model.name);
+
+        let input = self.input_object(name, |builder| {
+            let mut fields: Vec<InputValue> = ["equals", "not", "lt", "lte", "gt", "gte"]
+                .iter()
+                .map(|condition| InputValue::new(condition, TypeRef::named("Int")))
+                .collect();
+
+            fields.push(InputValue::new("where", builder.where_input(model)));
+            fields
+        });
+
+        input.expect("Count filters always have fields")
+    }
+
+    /// Registers an input object with the fields returned by `build`, unless it already exists.
+    /// Returns `None` if the input object would be empty.
+    fn input_object<F>(&mut self, name: String, build: F) -> Option<TypeRef>
+    where
+        F: FnOnce(&mut Self) -> Vec<InputValue>,
+    {
+        if self.types.contains_key(&name) || self.pending.contains(&name) {
+            return Some(TypeRef::Named(name));
+        }
+
+        if self.empty.contains(&name) {
+            return None;
+        }
+
+        self.pending.insert(name.clone());
+        let fields = build(self);
+        self.pending.remove(&name);
+
+        if fields.is_empty() {
+            self.empty.insert(name);
+            None
+        } else {
+            self.register(GraphQlType::input_object(&name, fields));
+            Some(TypeRef::Named(name))
+        }
+    }
+
+    /// Registers an input object with the given fields and returns a reference to it.
+    fn register_input(&mut self, name: String, fields: Vec<InputValue>) -> TypeRef {
+        self.register(GraphQlType::input_object(&name, fields));
+        TypeRef::Named(name)
+    }
+
+    fn register(&mut self, typ: GraphQlType) {
+        self.types.entry(typ.name.clone()).or_insert(typ);
+    }
+}
+
+fn scalar_type_name(field: &ScalarField) -> String {
+    match (field.type_identifier, &field.internal_enum) {
+        (TypeIdentifier::Enum, Some(e)) => e.name.clone(),
+        (TypeIdentifier::Enum, None) => String::from("String"),
+        (typ, _) => typ.user_friendly_type_name(),
+    }
+}
+
+fn scalar_output_type(field: &ScalarField) -> TypeRef {
+    let typ = TypeRef::named(&scalar_type_name(field));
+
+    match (field.is_list, field.is_required) {
+        (true, _) => typ.non_null().list().non_null(),
+        (false, true) => typ.non_null(),
+        (false, false) => typ,
+    }
+}
+
+/// Orderings of a field, named like the suffixes `core` accepts.
+const SORT_SUFFIXES: &[&str] = &[
+    "_ASC",
+    "_DESC",
+    "_ASC_NULLS_FIRST",
+    "_ASC_NULLS_LAST",
+    "_DESC_NULLS_FIRST",
+    "_DESC_NULLS_LAST",
+];
+
+/// Filters of a scalar field in a where input, named like the suffixes `core` accepts.
+fn scalar_filters(field: &ScalarField) -> Vec<InputValue> {
+    let typ = TypeRef::named(&scalar_type_name(field));
+    let list = typ.clone().non_null().list();
+
+    let mut filters = match field.type_identifier {
+        TypeIdentifier::Json => vec![],
+        TypeIdentifier::Boolean => vec![("", typ.clone()), ("_not", typ.clone())],
+        _ => vec![
+            ("", typ.clone()),
+            ("_not", typ.clone()),
+            ("_in", list.clone()),
+            ("_not_in", list),
+        ],
+    };
+
+    match field.type_identifier {
+        TypeIdentifier::Int
+        | TypeIdentifier::Float
+        | TypeIdentifier::DateTime
+        | TypeIdentifier::String
+        | TypeIdentifier::GraphQLID
+        | TypeIdentifier::UUID => {
+            for suffix in &["_lt", "_lte", "_gt", "_gte"] {
+                filters.push((*suffix, typ.clone()));
+            }
+        }
+        _ => (),
+    }
+
+    match field.type_identifier {
+        TypeIdentifier::String | TypeIdentifier::GraphQLID | TypeIdentifier::UUID => {
+            for suffix in &[
+                "_contains",
+                "_not_contains",
+                "_starts_with",
+                "_not_starts_with",
+                "_ends_with",
+                "_not_ends_with",
+            ] {
+                filters.push((*suffix, typ.clone()));
+            }
+        }
+        _ => (),
+    }
+
+    if field.type_identifier == TypeIdentifier::String {
+        for suffix in &[
+            "_like",
+            "_like_insensitive",
+            "_contains_insensitive",
+            "_not_contains_insensitive",
+            "_starts_with_insensitive",
+            "_not_starts_with_insensitive",
+            "_ends_with_insensitive",
+            "_not_ends_with_insensitive",
+            "_like_sensitive",
+            "_contains_sensitive",
+            "_not_contains_sensitive",
+            "_starts_with_sensitive",
+            "_not_starts_with_sensitive",
+            "_ends_with_sensitive",
+            "_not_ends_with_sensitive",
+        ] {
+            filters.push((*suffix, typ.clone()));
+        }
+
+        if field.model().is_searchable {
+            filters.push(("_search", typ.clone()));
+        }
+    }
+
+    filters
+        .into_iter()
+        .map(|(suffix, typ)| InputValue::new(&format!("{}{}", field.name, suffix), typ))
+        .collect()
+}
+
+/// Filters on the elements of a scalar list field
+fn scalar_list_filters(field: &ScalarField) -> Vec<InputValue> {
+    let typ = TypeRef::named(&scalar_type_name(field));
+    let list = typ.clone().non_null().list();
+
+    vec![("_contains", typ), ("_contains_every", list.clone()), ("_contains_some", list)]
+        .into_iter()
+        .map(|(suffix, typ)| InputValue::new(&format!("{}{}", field.name, suffix), typ))
+        .collect()
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn field_names(typ: &GraphQlType) -> Vec<&str> {
+        typ.fields.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    fn input_field_names(schema: &GraphQlSchema, name: &str) -> Vec<String> {
+        let typ = schema.find_type(name).unwrap();
+        typ.input_fields.iter().map(|f| f.name.clone()).collect()
+    }
+
+    #[test]
+    fn query_and_mutation_fields_of_each_model() {
+        let schema = graphql_schema();
+
+        let query = field_names(schema.find_type(GraphQlSchema::QUERY_TYPE).unwrap());
+        let mutation = field_names(schema.find_type(GraphQlSchema::MUTATION_TYPE).unwrap());
+
+        for name in &[
+            "user",
+            "users",
+            "usersConnection",
+            "account",
+            "accounts",
+            "accountsConnection",
+        ] {
+            assert!(query.contains(name), "Missing query field {}", name);
+        }
+
+        for name in &[
+            "createUser",
+            "updateUser",
+            "upsertUser",
+            "deleteUser",
+            "createManyUsers",
+            "updateManyUsers",
+            "deleteManyUsers",
+        ] {
+            assert!(mutation.contains(name), "Missing mutation field {}", name);
+        }
+    }
+
+    #[test]
+    fn model_types_and_their_fields() {
+        let schema = graphql_schema();
+        let user = schema.find_type("User").unwrap();
+
+        assert_eq!(TypeKind::Object, user.kind);
+        assert_eq!(vec!["id", "name", "sites", "cats"], field_names(user));
+        assert_eq!("ID!", user.find_field("id").unwrap().typ.to_string());
+        assert_eq!("Site", user.find_field("sites").unwrap().typ.to_string());
+        assert_eq!("[Int!]!", user.find_field("cats").unwrap().typ.to_string());
+    }
+
+    #[test]
+    fn unique_inputs_contain_unique_fields_and_constraints() {
+        let schema = graphql_schema();
+
+        assert_eq!(
+            vec!["id", "name_id"],
+            input_field_names(&schema, "UserWhereUniqueInput")
+        );
+        assert_eq!(
+            vec!["id", "email"],
+            input_field_names(&schema, "AccountWhereUniqueInput")
+        );
+        assert_eq!(
+            vec!["name", "id"],
+            input_field_names(&schema, "UserNameIdCompoundUniqueInput")
+        );
+    }
+
+    #[test]
+    fn where_inputs_contain_filters_of_all_fields() {
+        let schema = graphql_schema();
+        let filters = input_field_names(&schema, "UserWhereInput");
+
+        for name in &[
+            "name",
+            "name_not_in",
+            "name_contains_insensitive",
+            "name_contains_sensitive",
+            "sites",
+            "cats_contains_every",
+            "AND",
+            "OR",
+            "NOT",
+        ] {
+            assert!(filters.contains(&name.to_string()), "Missing filter {}", name);
+        }
+    }
+
+    #[test]
+    fn only_searchable_models_offer_search_filters_and_relevance_orderings() {
+        let schema = graphql_schema();
+        let user_order_by = &schema.find_type("UserOrderByInput").unwrap().enum_values;
+        let article_order_by = &schema.find_type("ArticleOrderByInput").unwrap().enum_values;
+
+        assert!(!input_field_names(&schema, "UserWhereInput").contains(&String::from("name_search")));
+        assert!(input_field_names(&schema, "ArticleWhereInput").contains(&String::from("title_search")));
+
+        assert!(!user_order_by.contains(&String::from("name_RELEVANCE")));
+        assert!(article_order_by.contains(&String::from("title_RELEVANCE")));
+    }
+
+    #[test]
+    fn nested_inputs_of_a_to_one_relation() {
+        let schema = graphql_schema();
+
+        assert_eq!(
+            vec!["create", "connect"],
+            input_field_names(&schema, "SiteCreateOneWithoutUserInput")
+        );
+        assert_eq!(
+            vec!["create", "connect", "update", "upsert", "disconnect", "delete"],
+            input_field_names(&schema, "SiteUpdateOneWithoutUserInput")
+        );
+        assert_eq!(
+            vec!["update", "create"],
+            input_field_names(&schema, "SiteUpsertWithoutUserInput")
+        );
+        assert!(!input_field_names(&schema, "SiteUpdateWithoutUserDataInput").contains(&String::from("user")));
+    }
+
+    #[test]
+    fn order_by_enum_contains_field_and_related_orderings() {
+        let schema = graphql_schema();
+        let order_by = &schema.find_type("UserOrderByInput").unwrap().enum_values;
+
+        for name in &["name_ASC", "name_DESC_NULLS_LAST", "sites_name_ASC"] {
+            assert!(order_by.contains(&name.to_string()), "Missing ordering {}", name);
+        }
+    }
+
+    #[test]
+    fn connection_types_contain_aggregates_of_matching_fields() {
+        let schema = graphql_schema();
+
+        assert_eq!(
+            vec!["pageInfo", "edges", "aggregate"],
+            field_names(schema.find_type("UserConnection").unwrap())
+        );
+        assert_eq!(
+            vec!["count", "min", "max"],
+            field_names(schema.find_type("AggregateUser").unwrap())
+        );
+        assert!(schema.find_type("UserSumAggregate").is_none());
+    }
+}
@@ -0,0 +1,289 @@
+//! Execution of introspection queries (`__schema`, `__type` and `__typename`) against the GraphQL schema
+
+use super::types::*;
+use crate::{error::PrismaError, PrismaResult};
+use graphql_parser::query::{Field, Selection, Value as GqlValue};
+use serde_json::{Map, Value};
+
+/// Built-in directives and their descriptions
+const DIRECTIVES: &[(&str, &str)] = &[
+    ("include", "Directs the executor to include this field or fragment only when the `if` argument is true."),
+    ("skip", "Directs the executor to skip this field or fragment when the `if` argument is true."),
+];
+
+/// Locations both built-in directives are allowed in
+const DIRECTIVE_LOCATIONS: &[&str] = &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"];
+
+/// Answers a meta root field of an operation, `__typename` resolving to the name of the root type.
+/// The field is expected to have fragments and variables resolved.
+pub fn resolve(schema: &GraphQlSchema, field: &Field, root_type: &str) -> PrismaResult<Value> {
+    match field.name.as_str() {
+        "__schema" => resolve_schema(schema, field),
+        "__type" => match field.arguments.iter().find(|(name, _)| name == "name") {
+            Some((_, GqlValue::String(name))) => match schema.find_type(name) {
+                Some(typ) => resolve_type(schema, typ, field),
+                None => Ok(Value::Null),
+            },
+            _ => Err(PrismaError::QueryValidationError(
+                "Field `__type` requires a String argument `name`".into(),
+            )),
+        },
+        "__typename" => Ok(Value::String(root_type.into())),
+        name => Err(PrismaError::QueryValidationError(format!(
+            "Cannot query field `{}` on type `{}`",
+            name, root_type
+        ))),
+    }
+}
+
+fn resolve_schema(schema: &GraphQlSchema, field: &Field) -> PrismaResult<Value> {
+    resolve_object(field, "__Schema", |selected| match selected.name.as_str() {
+        "types" => Some(resolve_list(schema.types.iter(), |typ| resolve_type(schema, typ, selected))),
+        "queryType" => Some(resolve_named(schema, GraphQlSchema::QUERY_TYPE, selected)),
+        "mutationType" => Some(resolve_named(schema, GraphQlSchema::MUTATION_TYPE, selected)),
+        "subscriptionType" => Some(Ok(Value::Null)),
+        "directives" => Some(resolve_list(DIRECTIVES.iter(), |directive| {
+            resolve_directive(schema, directive, selected)
+        })),
+        _ => None,
+    })
+}
+
+fn resolve_directive(
+    schema: &GraphQlSchema,
+    (name, description): &(&str, &str),
+    field: &Field,
+) -> PrismaResult<Value> {
+    let argument = InputValue::new("if", TypeRef::named("Boolean").non_null());
+
+    resolve_object(field, "__Directive", |selected| match selected.name.as_str() {
+        "name" => Some(Ok(Value::String(name.to_string()))),
+        "description" => Some(Ok(Value::String(description.to_string()))),
+        "locations" => Some(Ok(Value::Array(
+            DIRECTIVE_LOCATIONS.iter().map(|l| Value::String(l.to_string())).collect(),
+        ))),
+        "args" => Some(resolve_list(vec![&argument], |arg| resolve_input_value(schema, arg, selected))),
+        _ => None,
+    })
+}
+
+fn resolve_named(schema: &GraphQlSchema, name: &str, field: &Field) -> PrismaResult<Value> {
+    match schema.find_type(name) {
+        Some(typ) => resolve_type(schema, typ, field),
+        None => Err(PrismaError::QueryValidationError(format!("Unknown type `{}`", name))),
+    }
+}
+
+fn resolve_type(schema: &GraphQlSchema, typ: &GraphQlType, field: &Field) -> PrismaResult<Value> {
+    let kind = match typ.kind {
+        TypeKind::Scalar => "SCALAR",
+        TypeKind::Object => "OBJECT",
+        TypeKind::InputObject => "INPUT_OBJECT",
+        TypeKind::Enum => "ENUM",
+    };
+
+    resolve_object(field, "__Type", |selected| match selected.name.as_str() {
+        "kind" => Some(Ok(Value::String(kind.into()))),
+        "name" => Some(Ok(Value::String(typ.name.clone()))),
+        "description" => Some(Ok(typ.description.clone().map(Value::String).unwrap_or(Value::Null))),
+        "fields" if typ.kind == TypeKind::Object => Some(resolve_list(typ.fields.iter(), |f| {
+            resolve_field(schema, f, selected)
+        })),
+        "interfaces" if typ.kind == TypeKind::Object => Some(Ok(Value::Array(vec![]))),
+        "enumValues" if typ.kind == TypeKind::Enum => Some(resolve_list(typ.enum_values.iter(), |value| {
+            resolve_enum_value(value, selected)
+        })),
+        "inputFields" if typ.kind == TypeKind::InputObject => Some(resolve_list(typ.input_fields.iter(), |f| {
+            resolve_input_value(schema, f, selected)
+        })),
+        "fields" | "interfaces" | "possibleTypes" | "enumValues" | "inputFields" | "ofType" => Some(Ok(Value::Null)),
+        _ => None,
+    })
+}
+
+/// Resolves a type reference, which is either a named type or a `LIST` or `NON_NULL` wrapper.
+fn resolve_type_ref(schema: &GraphQlSchema, typ: &TypeRef, field: &Field) -> PrismaResult<Value> {
+    let (kind, inner) = match typ {
+        TypeRef::Named(name) => return resolve_named(schema, name, field),
+        TypeRef::List(inner) => ("LIST", inner),
+        TypeRef::NonNull(inner) => ("NON_NULL", inner),
+    };
+
+    resolve_object(field, "__Type", |selected| match selected.name.as_str() {
+        "kind" => Some(Ok(Value::String(kind.into()))),
+        "ofType" => Some(resolve_type_ref(schema, inner, selected)),
+        "name" | "description" | "fields" | "interfaces" | "possibleTypes" | "enumValues" | "inputFields" => {
+            Some(Ok(Value::Null))
+        }
+        _ => None,
+    })
+}
+
+fn resolve_field(schema: &GraphQlSchema, definition: &FieldDefinition, field: &Field) -> PrismaResult<Value> {
+    resolve_object(field, "__Field", |selected| match selected.name.as_str() {
+        "name" => Some(Ok(Value::String(definition.name.clone()))),
+        "description" | "deprecationReason" => Some(Ok(Value::Null)),
+        "args" => Some(resolve_list(definition.arguments.iter(), |arg| {
+            resolve_input_value(schema, arg, selected)
+        })),
+        "type" => Some(resolve_type_ref(schema, &definition.typ, selected)),
+        "isDeprecated" => Some(Ok(Value::Bool(false))),
+        _ => None,
+    })
+}
+
+fn resolve_input_value(schema: &GraphQlSchema, input: &InputValue, field: &Field) -> PrismaResult<Value> {
+    resolve_object(field, "__InputValue", |selected| match selected.name.as_str() {
+        "name" => Some(Ok(Value::String(input.name.clone()))),
+        "description" | "defaultValue" => Some(Ok(Value::Null)),
+        "type" => Some(resolve_type_ref(schema, &input.typ, selected)),
+        _ => None,
+    })
+}
+
+fn resolve_enum_value(value: &str, field: &Field) -> PrismaResult<Value> {
+    resolve_object(field, "__EnumValue", |selected| match selected.name.as_str() {
+        "name" => Some(Ok(Value::String(value.into()))),
+        "description" | "deprecationReason" => Some(Ok(Value::Null)),
+        "isDeprecated" => Some(Ok(Value::Bool(false))),
+        _ => None,
+    })
+}
+
+/// Resolves the selection set of `field` on an introspection type.
+/// `resolve` returns `None` for fields the type doesn't have.
+fn resolve_object<F>(field: &Field, type_name: &str, resolve: F) -> PrismaResult<Value>
+where
+    F: Fn(&Field) -> Option<PrismaResult<Value>>,
+{
+    let mut object = Map::new();
+
+    for selected in fields(&field.selection_set.items) {
+        let value = match selected.name.as_str() {
+            "__typename" => Value::String(type_name.into()),
+            name => resolve(selected).unwrap_or_else(|| {
+                Err(PrismaError::QueryValidationError(format!(
+                    "Cannot query field `{}` on type `{}`",
+                    name, type_name
+                )))
+            })?,
+        };
+
+        object.insert(response_key(selected).into(), value);
+    }
+
+    Ok(Value::Object(object))
+}
+
+fn resolve_list<I, F>(items: I, resolve: F) -> PrismaResult<Value>
+where
+    I: IntoIterator,
+    F: Fn(I::Item) -> PrismaResult<Value>,
+{
+    items
+        .into_iter()
+        .map(resolve)
+        .collect::<PrismaResult<Vec<Value>>>()
+        .map(Value::Array)
+}
+
+fn fields(selections: &[Selection]) -> impl Iterator<Item = &Field> {
+    selections.iter().filter_map(|selection| match selection {
+        Selection::Field(field) => Some(field),
+        _ => None,
+    })
+}
+
+fn response_key(field: &Field) -> &str {
+    field.alias.as_ref().unwrap_or(&field.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use serde_json::json;
+
+    fn introspect(query: &str) -> PrismaResult<Value> {
+        let schema = graphql_schema();
+        let selections = selections(query);
+        let mut data = Map::new();
+
+        for field in fields(&selections) {
+            let value = resolve(&schema, field, GraphQlSchema::QUERY_TYPE)?;
+            data.insert(response_key(field).into(), value);
+        }
+
+        Ok(Value::Object(data))
+    }
+
+    #[test]
+    fn type_with_wrapped_field_types() {
+        let data = introspect(
+            "query { __type(name: \"Account\") { kind name fields { name type { kind name ofType { name } } } } }",
+        )
+        .unwrap();
+
+        let expected = json!({
+            "__type": {
+                "kind": "OBJECT",
+                "name": "Account",
+                "fields": [
+                    { "name": "id", "type": { "kind": "NON_NULL", "name": null, "ofType": { "name": "ID" } } },
+                    { "name": "email", "type": { "kind": "NON_NULL", "name": null, "ofType": { "name": "String" } } }
+                ]
+            }
+        });
+
+        assert_eq!(expected, data);
+    }
+
+    #[test]
+    fn unknown_types_are_null() {
+        let data = introspect("query { __type(name: \"Unknown\") { name } }").unwrap();
+
+        assert_eq!(json!({ "__type": null }), data);
+    }
+
+    #[test]
+    fn schema_root_types_and_aliases() {
+        let data = introspect(
+            "query { schema: __schema { queryType { name } mutationType { name } subscriptionType { name } } }",
+        )
+        .unwrap();
+
+        let expected = json!({
+            "schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": { "name": "Mutation" },
+                "subscriptionType": null
+            }
+        });
+
+        assert_eq!(expected, data);
+    }
+
+    #[test]
+    fn input_objects_and_enums() {
+        let data = introspect(
+            "query {
+                input: __type(name: \"UserNameIdCompoundUniqueInput\") { kind inputFields { name } }
+                enum: __type(name: \"UserDistinctFieldEnum\") { kind enumValues { name } }
+            }",
+        )
+        .unwrap();
+
+        let expected = json!({
+            "input": { "kind": "INPUT_OBJECT", "inputFields": [{ "name": "name" }, { "name": "id" }] },
+            "enum": { "kind": "ENUM", "enumValues": [{ "name": "id" }, { "name": "name" }] }
+        });
+
+        assert_eq!(expected, data);
+    }
+
+    #[test]
+    fn unknown_introspection_fields_are_errors() {
+        assert!(introspect("query { __type(name: \"User\") { name nickname } }").is_err());
+        assert!(introspect("query { __typo }").is_err());
+    }
+}
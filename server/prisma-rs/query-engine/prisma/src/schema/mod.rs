@@ -1,5 +1,15 @@
+mod builder;
+mod introspection;
+mod sdl;
+mod types;
+mod validation;
+
+pub use builder::SchemaBuilder;
+pub use introspection::resolve as resolve_meta_field;
+pub use types::*;
+pub use validation::*;
+
 use crate::{utilities, PrismaResult};
-use graphql_parser::query;
 use prisma_models::{SchemaRef, SchemaTemplate};
 use serde::Serialize;
 use serde_json;
@@ -9,27 +19,6 @@ use std::{
     process::{Command, Stdio},
 };
 
-pub enum ValidationError {
-    #[allow(dead_code)]
-    EverythingIsBroken,
-    #[allow(dead_code)]
-    Problematic(String),
-    #[allow(dead_code)]
-    Duplicate(String),
-}
-
-// todo: Return crate::error errors, removing the above?
-pub trait Validatable {
-    fn validate(&self, doc: &query::Document) -> Result<(), ValidationError>;
-}
-
-impl Validatable for SchemaRef {
-    fn validate(&self, _: &query::Document) -> Result<(), ValidationError> {
-        // It's not really ok 😭
-        Ok(())
-    }
-}
-
 pub fn load_schema(db_name: String) -> PrismaResult<SchemaRef> {
     let schema_json = load_schema_from_env().or_else(|_| load_datamodel_file())?;
     Ok(serde_json::from_str::<SchemaTemplate>(&schema_json)?.build(db_name))
@@ -0,0 +1,95 @@
+//! Rendering of the GraphQL schema as SDL
+
+use super::{builder::SchemaBuilder, types::*};
+use std::fmt::Write;
+
+impl GraphQlSchema {
+    /// Renders the schema in the GraphQL schema definition language.
+    /// Built-in scalars are left out, as the spec requires.
+    pub fn render_sdl(&self) -> String {
+        let mut sdl = String::new();
+
+        writeln!(
+            sdl,
+            "schema {{\n  query: {}\n  mutation: {}\n}}",
+            Self::QUERY_TYPE,
+            Self::MUTATION_TYPE
+        )
+        .unwrap();
+
+        for typ in self.types.iter() {
+            if typ.kind == TypeKind::Scalar && SchemaBuilder::is_builtin_scalar(&typ.name) {
+                continue;
+            }
+
+            sdl.push('\n');
+            render_type(&mut sdl, typ);
+        }
+
+        sdl
+    }
+}
+
+fn render_type(sdl: &mut String, typ: &GraphQlType) {
+    if let Some(ref description) = typ.description {
+        writeln!(sdl, "\"\"\"{}\"\"\"", description).unwrap();
+    }
+
+    match typ.kind {
+        TypeKind::Scalar => writeln!(sdl, "scalar {}", typ.name).unwrap(),
+        TypeKind::Enum => {
+            writeln!(sdl, "enum {} {{", typ.name).unwrap();
+
+            for value in typ.enum_values.iter() {
+                writeln!(sdl, "  {}", value).unwrap();
+            }
+
+            writeln!(sdl, "}}").unwrap();
+        }
+        TypeKind::InputObject => {
+            writeln!(sdl, "input {} {{", typ.name).unwrap();
+
+            for field in typ.input_fields.iter() {
+                writeln!(sdl, "  {}: {}", field.name, field.typ).unwrap();
+            }
+
+            writeln!(sdl, "}}").unwrap();
+        }
+        TypeKind::Object => {
+            writeln!(sdl, "type {} {{", typ.name).unwrap();
+
+            for field in typ.fields.iter() {
+                if field.arguments.is_empty() {
+                    writeln!(sdl, "  {}: {}", field.name, field.typ).unwrap();
+                } else {
+                    let arguments: Vec<String> = field
+                        .arguments
+                        .iter()
+                        .map(|arg| format!("{}: {}", arg.name, arg.typ))
+                        .collect();
+
+                    writeln!(sdl, "  {}({}): {}", field.name, arguments.join(", "), field.typ).unwrap();
+                }
+            }
+
+            writeln!(sdl, "}}").unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+
+    #[test]
+    fn renders_types_without_builtin_scalars() {
+        let sdl = graphql_schema().render_sdl();
+
+        assert!(sdl.starts_with("schema {\n  query: Query\n  mutation: Mutation\n}\n"));
+        assert!(sdl.contains("\n\"\"\"An ISO-8601 encoded UTC date string\"\"\"\nscalar DateTime\n"));
+        assert!(!sdl.contains("scalar String"));
+        assert!(sdl.contains("\ntype Account {\n  id: ID!\n  email: String!\n}\n"));
+        assert!(sdl.contains("\ninput UserNameIdCompoundUniqueInput {\n  name: String!\n  id: ID!\n}\n"));
+        assert!(sdl.contains("  account(where: AccountWhereUniqueInput!): Account\n"));
+    }
+}
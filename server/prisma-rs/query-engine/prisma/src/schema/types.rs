@@ -0,0 +1,179 @@
+//! A minimal GraphQL type system, used to render SDL, answer introspection
+//! queries and validate incoming documents.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    InputObject,
+    Enum,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+#[derive(Debug)]
+pub struct GraphQlType {
+    pub name: String,
+    pub kind: TypeKind,
+    pub description: Option<String>,
+    pub fields: Vec<FieldDefinition>,
+    pub input_fields: Vec<InputValue>,
+    pub enum_values: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct FieldDefinition {
+    pub name: String,
+    pub arguments: Vec<InputValue>,
+    pub typ: TypeRef,
+}
+
+#[derive(Debug)]
+pub struct InputValue {
+    pub name: String,
+    pub typ: TypeRef,
+}
+
+#[derive(Debug)]
+pub struct GraphQlSchema {
+    pub types: Vec<GraphQlType>,
+}
+
+impl GraphQlSchema {
+    pub const QUERY_TYPE: &'static str = "Query";
+    pub const MUTATION_TYPE: &'static str = "Mutation";
+
+    pub fn find_type(&self, name: &str) -> Option<&GraphQlType> {
+        self.types.iter().find(|t| t.name == name)
+    }
+}
+
+impl GraphQlType {
+    pub fn scalar(name: &str, description: Option<&str>) -> Self {
+        Self::new(name, TypeKind::Scalar).description(description)
+    }
+
+    pub fn object(name: &str, fields: Vec<FieldDefinition>) -> Self {
+        Self {
+            fields,
+            ..Self::new(name, TypeKind::Object)
+        }
+    }
+
+    pub fn input_object(name: &str, input_fields: Vec<InputValue>) -> Self {
+        Self {
+            input_fields,
+            ..Self::new(name, TypeKind::InputObject)
+        }
+    }
+
+    pub fn enumeration(name: &str, enum_values: Vec<String>) -> Self {
+        Self {
+            enum_values,
+            ..Self::new(name, TypeKind::Enum)
+        }
+    }
+
+    fn new(name: &str, kind: TypeKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            description: None,
+            fields: vec![],
+            input_fields: vec![],
+            enum_values: vec![],
+        }
+    }
+
+    fn description(self, description: Option<&str>) -> Self {
+        Self {
+            description: description.map(String::from),
+            ..self
+        }
+    }
+
+    pub fn find_field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn find_input_field(&self, name: &str) -> Option<&InputValue> {
+        self.input_fields.iter().find(|f| f.name == name)
+    }
+}
+
+impl FieldDefinition {
+    pub fn new(name: &str, typ: TypeRef) -> Self {
+        Self {
+            name: name.into(),
+            arguments: vec![],
+            typ,
+        }
+    }
+
+    pub fn with_arguments(name: &str, arguments: Vec<InputValue>, typ: TypeRef) -> Self {
+        Self {
+            name: name.into(),
+            arguments,
+            typ,
+        }
+    }
+
+    pub fn find_argument(&self, name: &str) -> Option<&InputValue> {
+        self.arguments.iter().find(|a| a.name == name)
+    }
+}
+
+impl InputValue {
+    pub fn new(name: &str, typ: TypeRef) -> Self {
+        Self { name: name.into(), typ }
+    }
+}
+
+impl TypeRef {
+    pub fn named(name: &str) -> Self {
+        TypeRef::Named(name.into())
+    }
+
+    pub fn list(self) -> Self {
+        TypeRef::List(Box::new(self))
+    }
+
+    pub fn non_null(self) -> Self {
+        match self {
+            TypeRef::NonNull(_) => self,
+            typ => TypeRef::NonNull(Box::new(typ)),
+        }
+    }
+
+    /// The name of the innermost named type
+    pub fn type_name(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::List(typ) | TypeRef::NonNull(typ) => typ.type_name(),
+        }
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        match self {
+            TypeRef::NonNull(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeRef::Named(name) => write!(f, "{}", name),
+            TypeRef::List(typ) => write!(f, "[{}]", typ),
+            TypeRef::NonNull(typ) => write!(f, "{}!", typ),
+        }
+    }
+}
@@ -0,0 +1,709 @@
+//! Validation of GraphQL documents against the generated schema
+//!
+//! Implements the subset of the validation rules of the GraphQL spec that matter for
+//! a schema without interfaces, unions or subscriptions. All errors of a document
+//! are collected, each with the locations in the document it refers to.
+
+use super::types::*;
+use graphql_parser::{query::*, Pos};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationError {
+    pub message: String,
+    pub locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl From<Pos> for Location {
+    fn from(pos: Pos) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+        }
+    }
+}
+
+impl ValidationError {
+    fn new(message: String, pos: Pos) -> Self {
+        Self {
+            message,
+            locations: vec![pos.into()],
+        }
+    }
+}
+
+pub trait Validatable {
+    fn validate(&self, doc: &Document) -> Result<(), Vec<ValidationError>>;
+}
+
+impl Validatable for GraphQlSchema {
+    fn validate(&self, doc: &Document) -> Result<(), Vec<ValidationError>> {
+        let mut validator = Validator {
+            schema: self,
+            fragments: HashMap::new(),
+            used_fragments: HashSet::new(),
+            errors: vec![],
+        };
+
+        validator.validate_document(doc);
+
+        let mut errors: Vec<ValidationError> = vec![];
+
+        // Fragments are validated at every spread, which can report the same error more than once.
+        for error in validator.errors {
+            if !errors.contains(&error) {
+                errors.push(error);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Variables of the operation being validated
+struct Scope<'a> {
+    operation_name: Option<&'a str>,
+    variables: HashMap<&'a str, &'a VariableDefinition>,
+    used_variables: HashSet<&'a str>,
+    visited_fragments: HashSet<&'a str>,
+}
+
+struct Validator<'a> {
+    schema: &'a GraphQlSchema,
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    used_fragments: HashSet<&'a str>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    fn validate_document(&mut self, doc: &'a Document) {
+        let mut operations = vec![];
+
+        for definition in doc.definitions.iter() {
+            match definition {
+                Definition::Operation(operation) => operations.push(operation),
+                Definition::Fragment(fragment) => {
+                    if self.fragments.contains_key(fragment.name.as_str()) {
+                        self.error(
+                            format!("There can be only one fragment named `{}`.", fragment.name),
+                            fragment.position,
+                        );
+                    } else {
+                        self.fragments.insert(&fragment.name, fragment);
+                    }
+                }
+            }
+        }
+
+        let mut operation_names = HashSet::new();
+
+        for operation in operations.iter() {
+            let (name, position) = match operation {
+                OperationDefinition::SelectionSet(s) => (None, s.span.0),
+                OperationDefinition::Query(q) => (q.name.as_ref(), q.position),
+                OperationDefinition::Mutation(m) => (m.name.as_ref(), m.position),
+                OperationDefinition::Subscription(s) => (s.name.as_ref(), s.position),
+            };
+
+            match name {
+                None if operations.len() > 1 => self.error(
+                    "This anonymous operation must be the only defined operation.".into(),
+                    position,
+                ),
+                Some(name) if !operation_names.insert(name) => {
+                    self.error(format!("There can be only one operation named `{}`.", name), position)
+                }
+                _ => (),
+            }
+        }
+
+        for operation in operations {
+            self.validate_operation(operation);
+        }
+
+        for (name, fragment) in self.fragments.clone() {
+            let TypeCondition::On(ref type_name) = fragment.type_condition;
+
+            match self.schema.find_type(type_name) {
+                Some(typ) if typ.kind == TypeKind::Object => (),
+                Some(_) => self.error(
+                    format!("Fragment `{}` cannot condition on non composite type `{}`.", name, type_name),
+                    fragment.position,
+                ),
+                None => self.error(format!("Unknown type `{}`.", type_name), fragment.position),
+            }
+
+            if !self.used_fragments.contains(name) {
+                self.error(format!("Fragment `{}` is never used.", name), fragment.position);
+            }
+        }
+    }
+
+    fn validate_operation(&mut self, operation: &'a OperationDefinition) {
+        let (root, name, variable_definitions, selection_set) = match operation {
+            OperationDefinition::SelectionSet(s) => (GraphQlSchema::QUERY_TYPE, None, &[][..], s),
+            OperationDefinition::Query(q) => (
+                GraphQlSchema::QUERY_TYPE,
+                q.name.as_ref().map(String::as_str),
+                &q.variable_definitions[..],
+                &q.selection_set,
+            ),
+            OperationDefinition::Mutation(m) => (
+                GraphQlSchema::MUTATION_TYPE,
+                m.name.as_ref().map(String::as_str),
+                &m.variable_definitions[..],
+                &m.selection_set,
+            ),
+            OperationDefinition::Subscription(s) => {
+                return self.error("Subscriptions are not supported.".into(), s.position);
+            }
+        };
+
+        let mut scope = Scope {
+            operation_name: name,
+            variables: HashMap::new(),
+            used_variables: HashSet::new(),
+            visited_fragments: HashSet::new(),
+        };
+
+        for definition in variable_definitions {
+            self.validate_variable_definition(definition, &mut scope);
+        }
+
+        self.validate_selection_set(root, selection_set, &mut scope);
+
+        for definition in variable_definitions {
+            if !scope.used_variables.contains(definition.name.as_str()) {
+                let message = match scope.operation_name {
+                    Some(operation) => format!(
+                        "Variable `${}` is never used in operation `{}`.",
+                        definition.name, operation
+                    ),
+                    None => format!("Variable `${}` is never used.", definition.name),
+                };
+
+                self.error(message, definition.position);
+            }
+        }
+    }
+
+    fn validate_variable_definition(&mut self, definition: &'a VariableDefinition, scope: &mut Scope<'a>) {
+        if scope.variables.contains_key(definition.name.as_str()) {
+            return self.error(
+                format!("There can be only one variable named `${}`.", definition.name),
+                definition.position,
+            );
+        }
+
+        scope.variables.insert(&definition.name, definition);
+
+        let typ = type_ref(&definition.var_type);
+
+        match self.schema.find_type(typ.type_name()) {
+            Some(t) if t.kind == TypeKind::Object => self.error(
+                format!(
+                    "Variable `${}` cannot be non-input type `{}`.",
+                    definition.name, typ
+                ),
+                definition.position,
+            ),
+            Some(_) => {
+                if let Some(ref default) = definition.default_value {
+                    // Default values can't reference variables, so they are checked without any.
+                    let mut empty = Scope {
+                        operation_name: None,
+                        variables: HashMap::new(),
+                        used_variables: HashSet::new(),
+                        visited_fragments: HashSet::new(),
+                    };
+
+                    if let Err(message) = self.validate_value(default, &typ, &mut empty) {
+                        self.error(
+                            format!(
+                                "Variable `${}` has an invalid default value: {}",
+                                definition.name, message
+                            ),
+                            definition.position,
+                        );
+                    }
+                }
+            }
+            None => self.error(format!("Unknown type `{}`.", typ.type_name()), definition.position),
+        }
+    }
+
+    fn validate_selection_set(&mut self, type_name: &str, selection_set: &'a SelectionSet, scope: &mut Scope<'a>) {
+        let schema = self.schema;
+        let typ = match schema.find_type(type_name) {
+            Some(typ) => typ,
+            None => return,
+        };
+
+        for selection in selection_set.items.iter() {
+            match selection {
+                Selection::Field(field) => {
+                    self.validate_directives(&field.directives, scope);
+                    self.validate_field(typ, field, scope);
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.validate_directives(&fragment.directives, scope);
+
+                    let condition = match fragment.type_condition {
+                        Some(TypeCondition::On(ref name)) => name.as_str(),
+                        None => type_name,
+                    };
+
+                    if self.validate_spread_type(type_name, condition, None, fragment.position) {
+                        self.validate_selection_set(condition, &fragment.selection_set, scope);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.validate_directives(&spread.directives, scope);
+
+                    let name = spread.fragment_name.as_str();
+                    let fragment = match self.fragments.get(name) {
+                        Some(fragment) => *fragment,
+                        None => {
+                            self.error(format!("Unknown fragment `{}`.", name), spread.position);
+                            continue;
+                        }
+                    };
+
+                    self.used_fragments.insert(name);
+
+                    let TypeCondition::On(ref condition) = fragment.type_condition;
+
+                    if self.validate_spread_type(type_name, condition, Some(name), spread.position)
+                        && scope.visited_fragments.insert(name)
+                    {
+                        self.validate_selection_set(condition, &fragment.selection_set, scope);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Without interfaces and unions, fragments can only be spread on their own type.
+    fn validate_spread_type(&mut self, parent: &str, condition: &str, fragment: Option<&str>, pos: Pos) -> bool {
+        if parent == condition {
+            return true;
+        }
+
+        if self.schema.find_type(condition).is_some() {
+            let message = match fragment {
+                Some(name) => format!(
+                    "Fragment `{}` cannot be spread here as objects of type `{}` can never be of type `{}`.",
+                    name, parent, condition
+                ),
+                None => format!(
+                    "Fragment cannot be spread here as objects of type `{}` can never be of type `{}`.",
+                    parent, condition
+                ),
+            };
+
+            self.error(message, pos);
+        } else if fragment.is_none() {
+            // Unknown types of named fragments are reported with the fragment definition.
+            self.error(format!("Unknown type `{}`.", condition), pos);
+        }
+
+        false
+    }
+
+    fn validate_field(&mut self, parent: &'a GraphQlType, field: &'a Field, scope: &mut Scope<'a>) {
+        let is_root_query = parent.name == GraphQlSchema::QUERY_TYPE;
+
+        match field.name.as_str() {
+            "__typename" => return self.validate_leaf(field, "String"),
+            // Introspection queries are checked when they are executed.
+            "__schema" | "__type" if is_root_query => return,
+            _ => (),
+        }
+
+        let definition = match parent.find_field(&field.name) {
+            Some(definition) => definition,
+            None => {
+                return self.error(
+                    format!("Cannot query field `{}` on type `{}`.", field.name, parent.name),
+                    field.position,
+                )
+            }
+        };
+
+        self.validate_arguments(parent, definition, field, scope);
+
+        let field_type = definition.typ.type_name();
+        let is_object = self
+            .schema
+            .find_type(field_type)
+            .map(|t| t.kind == TypeKind::Object)
+            .unwrap_or(false);
+
+        if is_object {
+            if field.selection_set.items.is_empty() {
+                self.error(
+                    format!(
+                        "Field `{}` of type `{}` must have a selection of subfields. Did you mean `{} {{ ... }}`?",
+                        field.name, definition.typ, field.name
+                    ),
+                    field.position,
+                );
+            } else {
+                self.validate_selection_set(field_type, &field.selection_set, scope);
+            }
+        } else {
+            self.validate_leaf(field, &definition.typ.to_string());
+        }
+    }
+
+    fn validate_leaf(&mut self, field: &Field, typ: &str) {
+        if !field.selection_set.items.is_empty() {
+            self.error(
+                format!(
+                    "Field `{}` must not have a selection since type `{}` has no subfields.",
+                    field.name, typ
+                ),
+                field.position,
+            );
+        }
+    }
+
+    fn validate_arguments(
+        &mut self,
+        parent: &GraphQlType,
+        definition: &FieldDefinition,
+        field: &'a Field,
+        scope: &mut Scope<'a>,
+    ) {
+        let mut seen = HashSet::new();
+
+        for (name, value) in field.arguments.iter() {
+            if !seen.insert(name.as_str()) {
+                self.error(
+                    format!("There can be only one argument named `{}`.", name),
+                    field.position,
+                );
+                continue;
+            }
+
+            match definition.find_argument(name) {
+                Some(argument) => {
+                    if let Err(message) = self.validate_value(value, &argument.typ, scope) {
+                        self.error(
+                            format!(
+                                "Argument `{}` on field `{}.{}` has an invalid value: {}",
+                                name, parent.name, field.name, message
+                            ),
+                            field.position,
+                        );
+                    }
+                }
+                None => self.error(
+                    format!("Unknown argument `{}` on field `{}.{}`.", name, parent.name, field.name),
+                    field.position,
+                ),
+            }
+        }
+
+        for argument in definition.arguments.iter() {
+            if argument.typ.is_non_null() && !seen.contains(argument.name.as_str()) {
+                self.error(
+                    format!(
+                        "Field `{}.{}` argument `{}` of type `{}` is required, but it was not provided.",
+                        parent.name, field.name, argument.name, argument.typ
+                    ),
+                    field.position,
+                );
+            }
+        }
+    }
+
+    /// Only the built-in `@skip` and `@include` directives are supported.
+    fn validate_directives(&mut self, directives: &'a [Directive], scope: &mut Scope<'a>) {
+        let condition = TypeRef::named("Boolean").non_null();
+
+        for directive in directives {
+            if directive.name != "skip" && directive.name != "include" {
+                self.error(format!("Unknown directive `@{}`.", directive.name), directive.position);
+                continue;
+            }
+
+            match directive.arguments.as_slice() {
+                [(name, value)] if name == "if" => {
+                    if let Err(message) = self.validate_value(value, &condition, scope) {
+                        self.error(
+                            format!(
+                                "Argument `if` of directive `@{}` has an invalid value: {}",
+                                directive.name, message
+                            ),
+                            directive.position,
+                        );
+                    }
+                }
+                _ => self.error(
+                    format!(
+                        "Directive `@{}` requires exactly one argument `if` of type `Boolean!`.",
+                        directive.name
+                    ),
+                    directive.position,
+                ),
+            }
+        }
+    }
+
+    /// Checks that an input value can be coerced into the given type.
+    /// Returns a message describing the first problem found.
+    fn validate_value(&self, value: &'a Value, typ: &TypeRef, scope: &mut Scope<'a>) -> Result<(), String> {
+        match (typ, value) {
+            (_, Value::Variable(name)) => {
+                let definition = scope
+                    .variables
+                    .get(name.as_str())
+                    .cloned()
+                    .ok_or_else(|| format!("Variable `${}` is not defined.", name))?;
+
+                scope.used_variables.insert(name.as_str());
+
+                let var_type = type_ref(&definition.var_type);
+                let var_type = match definition.default_value {
+                    Some(ref default) if *default != Value::Null => var_type.non_null(),
+                    _ => var_type,
+                };
+
+                if is_compatible(&var_type, typ) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Variable `${}` of type `{}` used in position expecting type `{}`.",
+                        name,
+                        type_ref(&definition.var_type),
+                        typ
+                    ))
+                }
+            }
+            (TypeRef::NonNull(_), Value::Null) => Err(format!("Expected non-null value of type `{}`.", typ)),
+            (TypeRef::NonNull(inner), value) => self.validate_value(value, inner, scope),
+            (_, Value::Null) => Ok(()),
+            (TypeRef::List(inner), Value::List(values)) => values
+                .iter()
+                .try_for_each(|value| self.validate_value(value, inner, scope)),
+            // Single values are coerced into lists of one element.
+            (TypeRef::List(inner), value) => self.validate_value(value, inner, scope),
+            (TypeRef::Named(name), value) => self.validate_named_value(name, value, scope),
+        }
+    }
+
+    fn validate_named_value(&self, type_name: &str, value: &'a Value, scope: &mut Scope<'a>) -> Result<(), String> {
+        let typ = self
+            .schema
+            .find_type(type_name)
+            .ok_or_else(|| format!("Unknown type `{}`.", type_name))?;
+
+        let invalid = || Err(format!("Expected type `{}`, found `{}`.", type_name, render_value(value)));
+
+        match (typ.kind, value) {
+            (TypeKind::Scalar, value) => match (type_name, value) {
+                ("Int", Value::Int(_)) => Ok(()),
+                ("Float", Value::Int(_)) | ("Float", Value::Float(_)) => Ok(()),
+                ("Boolean", Value::Boolean(_)) => Ok(()),
+                ("ID", Value::String(_)) | ("ID", Value::Int(_)) => Ok(()),
+                ("String", Value::String(_))
+                | ("DateTime", Value::String(_))
+                | ("Json", Value::String(_))
+                | ("UUID", Value::String(_)) => Ok(()),
+                _ => invalid(),
+            },
+            (TypeKind::Enum, Value::Enum(name)) if typ.enum_values.contains(name) => Ok(()),
+            (TypeKind::InputObject, Value::Object(obj)) => {
+                for (name, value) in obj.iter() {
+                    match typ.find_input_field(name) {
+                        Some(field) => self
+                            .validate_value(value, &field.typ, scope)
+                            .map_err(|message| format!("In field `{}`: {}", name, message))?,
+                        None => return Err(format!("Field `{}` is not defined by type `{}`.", name, type_name)),
+                    }
+                }
+
+                match typ
+                    .input_fields
+                    .iter()
+                    .find(|field| field.typ.is_non_null() && !obj.contains_key(&field.name))
+                {
+                    Some(field) => Err(format!(
+                        "Field `{}.{}` of required type `{}` was not provided.",
+                        type_name, field.name, field.typ
+                    )),
+                    None => Ok(()),
+                }
+            }
+            _ => invalid(),
+        }
+    }
+
+    fn error(&mut self, message: String, pos: Pos) {
+        self.errors.push(ValidationError::new(message, pos));
+    }
+}
+
+fn type_ref(typ: &Type) -> TypeRef {
+    match typ {
+        Type::NamedType(name) => TypeRef::named(name),
+        Type::ListType(inner) => type_ref(inner).list(),
+        Type::NonNullType(inner) => type_ref(inner).non_null(),
+    }
+}
+
+fn render_value(value: &Value) -> String {
+    match value {
+        Value::Variable(name) => format!("${}", name),
+        Value::Int(num) => num.as_i64().map(|i| i.to_string()).unwrap_or_default(),
+        Value::Float(f) => f.to_string(),
+        Value::String(s) => format!("{:?}", s),
+        Value::Boolean(b) => b.to_string(),
+        Value::Null => "null".into(),
+        Value::Enum(e) => e.clone(),
+        Value::List(values) => {
+            let values: Vec<String> = values.iter().map(render_value).collect();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Object(obj) => {
+            let fields: Vec<String> = obj
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, render_value(value)))
+                .collect();
+
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+/// Whether a variable of type `var_type` can be used where `expected` is expected
+fn is_compatible(var_type: &TypeRef, expected: &TypeRef) -> bool {
+    match (var_type, expected) {
+        (TypeRef::NonNull(var_type), TypeRef::NonNull(expected)) => is_compatible(var_type, expected),
+        (_, TypeRef::NonNull(_)) => false,
+        (TypeRef::NonNull(var_type), expected) => is_compatible(var_type, expected),
+        (TypeRef::List(var_type), TypeRef::List(expected)) => is_compatible(var_type, expected),
+        (TypeRef::Named(var_type), TypeRef::Named(expected)) => var_type == expected,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn validate(query: &str) -> Result<(), Vec<ValidationError>> {
+        graphql_schema().validate(&graphql_parser::parse_query(query).unwrap())
+    }
+
+    fn error(message: &str, line: usize, column: usize) -> ValidationError {
+        ValidationError {
+            message: String::from(message),
+            locations: vec![Location { line, column }],
+        }
+    }
+
+    #[test]
+    fn valid_documents() {
+        assert_eq!(
+            Ok(()),
+            validate("query { users(where: { name_contains: \"a\" }, first: 10) { id cats } }")
+        );
+        assert_eq!(
+            Ok(()),
+            validate(
+                "mutation($id: ID!) {
+                    updateUser(where: { id: $id }, data: { sites: { update: { name: \"Site\" } } }) { id }
+                }"
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_fields_at_their_location() {
+        let errors = validate("query {\n  users {\n    nickname\n  }\n}").unwrap_err();
+
+        assert_eq!(
+            vec![error("Cannot query field `nickname` on type `User`.", 3, 5)],
+            errors
+        );
+    }
+
+    #[test]
+    fn all_errors_of_a_document() {
+        let errors = validate("query {\n  users(first: \"ten\") {\n    id\n  }\n  user {\n    id\n  }\n}").unwrap_err();
+
+        assert_eq!(2, errors.len());
+        assert!(errors[0]
+            .message
+            .starts_with("Argument `first` on field `Query.users` has an invalid value"));
+        assert_eq!(vec![Location { line: 2, column: 3 }], errors[0].locations);
+        assert_eq!(
+            error(
+                "Field `Query.user` argument `where` of type `UserWhereUniqueInput!` is required, but it was not provided.",
+                5,
+                3
+            ),
+            errors[1]
+        );
+    }
+
+    #[test]
+    fn selections_of_leaf_and_object_fields() {
+        let errors = validate("query {\n  users {\n    name {\n      id\n    }\n    sites\n  }\n}").unwrap_err();
+
+        assert_eq!(
+            vec![
+                error(
+                    "Field `name` must not have a selection since type `String!` has no subfields.",
+                    3,
+                    5
+                ),
+                error(
+                    "Field `sites` of type `Site` must have a selection of subfields. Did you mean `sites { ... }`?",
+                    6,
+                    5
+                ),
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn unused_variables_and_fragments() {
+        let errors =
+            validate("query($name: String) {\n  users {\n    id\n  }\n}\nfragment F on User {\n  id\n}").unwrap_err();
+
+        assert_eq!(
+            vec![
+                error("Variable `$name` is never used.", 1, 7),
+                error("Fragment `F` is never used.", 6, 1),
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn errors_in_fragments_are_reported_once() {
+        let query =
+            "query {\n  users {\n    ...F\n  }\n  a: users {\n    ...F\n  }\n}\nfragment F on User {\n  nickname\n}";
+
+        assert_eq!(
+            Err(vec![error("Cannot query field `nickname` on type `User`.", 10, 3)]),
+            validate(query)
+        );
+    }
+}
@@ -9,18 +9,31 @@
 //! This IR fixes that issue, allowing us to serialize to various
 //! flexible formats.
 
-use core::{MultiPrismaQueryResult, PrismaQueryResult, SinglePrismaQueryResult};
-use prisma_models::PrismaValue;
+use crate::error::{PathSegment, ResponseError};
+use core::{
+    ConnectionResult, ConnectionSelection, MultiPrismaQueryResult, PrismaQueryResult, SinglePrismaQueryResult,
+    TYPENAME_FIELD,
+};
+use prisma_models::{GraphqlId, PrismaValue};
 use serde::Serialize;
 use std::collections::BTreeMap;
 
-/// A set of responses to provided queries
-pub type Responses = Vec<IrResponse>;
+/// The responses to the root fields, and the errors of all fields
+pub struct Responses {
+    pub fields: Vec<IrResponse>,
+    pub errors: Vec<ResponseError>,
+}
 
-#[allow(dead_code)]
+/// The response to a root field, keyed by its response name
 pub enum IrResponse {
-    Data(Item),
-    Error(String), // TODO: Get a better error kind?
+    Data(String, Item),
+
+    /// A root field that failed, or one of the non-nullable fields of which
+    /// did. Failures of non-nullable root fields null out all data.
+    Null {
+        name: String,
+        nullable: bool,
+    },
 }
 
 /// A key -> value map to an IR item
@@ -35,35 +48,101 @@ pub enum Item {
     Map(Map),
     List(List),
     Value(PrismaValue),
+
+    /// A meta field resolved against the GraphQL schema, e.g. `__schema`
+    Meta(serde_json::Value),
 }
 
+/// The path of a field in the response, reported with its errors
+type Path = Vec<PathSegment>;
+
 /// A serialization IR builder utility
-pub struct IrBuilder<'results>(Vec<&'results PrismaQueryResult>);
+pub struct IrBuilder(Responses);
 
-impl<'results> IrBuilder<'results> {
+impl IrBuilder {
     pub fn new() -> Self {
-        Self(vec![])
+        Self(Responses {
+            fields: vec![],
+            errors: vec![],
+        })
+    }
+
+    /// Add a single query result to the builder. A failed nested field nulls
+    /// out the nearest nullable field, up to the root field itself.
+    pub fn add(mut self, q: &PrismaQueryResult, nullable: bool) -> Self {
+        let name = response_name(q).to_owned();
+        let path = vec![PathSegment::Field(name.clone())];
+
+        self.0.fields.push(match build_field(q, &path, &mut self.0.errors) {
+            Some(item) => IrResponse::Data(name, item),
+            None => IrResponse::Null { name, nullable },
+        });
+
+        self
+    }
+
+    /// Add a meta root field, which was resolved against the GraphQL schema instead of being queried
+    pub fn add_meta(mut self, name: String, value: serde_json::Value) -> Self {
+        self.0.fields.push(IrResponse::Data(name, Item::Meta(value)));
+        self
     }
 
-    /// Add a single query result to the builder
-    pub fn add(mut self, q: &'results PrismaQueryResult) -> Self {
-        self.0.push(q);
+    /// Add the error of a root field that couldn't be resolved
+    pub fn add_error(mut self, name: String, nullable: bool, error: ResponseError) -> Self {
+        self.0.fields.push(IrResponse::Null { name, nullable });
+        self.0.errors.push(error);
         self
     }
 
-    /// Parse collected queries into a wrapper type
+    /// Returns the collected responses in the order they were added
     pub fn build(self) -> Responses {
-        self.0.into_iter().fold(vec![], |mut vec, res| {
-            vec.push(match res {
-                PrismaQueryResult::Single(query) => IrResponse::Data(Item::Map(build_map(query))),
-                PrismaQueryResult::Multi(query) => IrResponse::Data(Item::List(build_list(query))),
+        self.0
+    }
+}
+
+fn response_name(q: &PrismaQueryResult) -> &str {
+    match q {
+        PrismaQueryResult::Single(query) => &query.name,
+        PrismaQueryResult::Multi(query) => &query.name,
+        PrismaQueryResult::Failed(query) => &query.name,
+    }
+}
+
+fn child(path: &[PathSegment], segment: PathSegment) -> Path {
+    let mut path = path.to_vec();
+    path.push(segment);
+    path
+}
+
+/// The value of a field, or `None` if it failed, the errors being collected
+fn build_field(q: &PrismaQueryResult, path: &[PathSegment], errors: &mut Vec<ResponseError>) -> Option<Item> {
+    match q {
+        PrismaQueryResult::Single(query) => build_single(query, path, errors),
+        PrismaQueryResult::Multi(query) => build_many(query, path, errors),
+        PrismaQueryResult::Failed(query) => {
+            errors.push(ResponseError {
+                path: path.to_vec(),
+                ..ResponseError::from(&*query.error)
             });
-            vec
-        })
+
+            None
+        }
+    }
+}
+
+/// A single record, or null if none was found
+fn build_single(
+    result: &SinglePrismaQueryResult,
+    path: &[PathSegment],
+    errors: &mut Vec<ResponseError>,
+) -> Option<Item> {
+    match result.result {
+        Some(_) => build_map(result, path, errors).map(Item::Map),
+        None => Some(Item::Value(PrismaValue::Null)),
     }
 }
 
-fn build_map(result: &SinglePrismaQueryResult) -> Map {
+fn build_map(result: &SinglePrismaQueryResult, path: &[PathSegment], errors: &mut Vec<ResponseError>) -> Option<Map> {
     // Build selected fields first
     let outer = match &result.result {
         Some(single) => single
@@ -74,42 +153,640 @@ fn build_map(result: &SinglePrismaQueryResult) -> Map {
                 map.insert(name.clone(), Item::Value(val.clone()));
                 map
             }),
-        None => panic!("No result found"), // FIXME: Can this ever happen?
+        None => Map::new(),
     };
 
+    let outer = add_typename(outer, &result.typename);
+
     // Then add nested selected fields
-    result.nested.iter().fold(outer, |mut map, query| {
-        match query {
-            PrismaQueryResult::Single(nested) => map.insert(nested.name.clone(), Item::Map(build_map(nested))),
-            PrismaQueryResult::Multi(nested) => map.insert(nested.name.clone(), Item::List(build_list(nested))),
-        };
+    add_nested(outer, &result.nested, path, errors)
+}
 
-        map
-    })
+/// Adds the selected `__typename` of a node, which isn't queried but resolved to the name of its type
+fn add_typename(mut map: Map, typename: &Option<String>) -> Map {
+    if let Some(typename) = typename {
+        map.insert(TYPENAME_FIELD.into(), typename_value(typename));
+    }
+
+    map
+}
+
+fn typename_value(typename: &str) -> Item {
+    Item::Value(PrismaValue::String(typename.into()))
+}
+
+/// Adds the nested results of a single node to its map, or returns `None` if
+/// a non-nullable one failed. All nested fields are built to collect their errors.
+fn add_nested(
+    mut map: Map,
+    nested: &[PrismaQueryResult],
+    path: &[PathSegment],
+    errors: &mut Vec<ResponseError>,
+) -> Option<Map> {
+    let mut failed = false;
+
+    for query in nested {
+        let name = response_name(query);
+        let field_path = child(path, PathSegment::Field(name.to_owned()));
+
+        match build_field(query, &field_path, errors) {
+            Some(item) => {
+                map.insert(name.to_owned(), item);
+            }
+            None if query.is_nullable() => {
+                map.insert(name.to_owned(), Item::Value(PrismaValue::Null));
+            }
+            None => failed = true,
+        }
+    }
+
+    if failed {
+        None
+    } else {
+        Some(map)
+    }
+}
+
+/// A list of records, or the connection wrapping them
+fn build_many(result: &MultiPrismaQueryResult, path: &[PathSegment], errors: &mut Vec<ResponseError>) -> Option<Item> {
+    match result.connection {
+        Some(ref connection) => Some(Item::Map(build_connection(result, connection, path, errors))),
+        None => build_list(result, path, errors).map(Item::List),
+    }
+}
+
+/// The nodes of the list, `None` for a failed node that can't be `null`
+fn build_list(result: &MultiPrismaQueryResult, path: &[PathSegment], errors: &mut Vec<ResponseError>) -> Option<List> {
+    let nodes = build_nodes(result, |i| child(path, PathSegment::Index(i)), errors);
+    let nullable = result.has_nullable_nodes();
+
+    nodes
+        .into_iter()
+        .map(|node| match node {
+            Some(map) => Some(Item::Map(map)),
+            None if nullable => Some(Item::Value(PrismaValue::Null)),
+            None => None,
+        })
+        .collect()
 }
 
-fn build_list(result: &MultiPrismaQueryResult) -> List {
-    let mut vec: Vec<Item> = result
+/// The maps of the nodes, `None` for the ones with a failed non-nullable field
+fn build_nodes<F>(result: &MultiPrismaQueryResult, node_path: F, errors: &mut Vec<ResponseError>) -> Vec<Option<Map>>
+where
+    F: Fn(usize) -> Path,
+{
+    result
         .result
         .as_pairs()
-        .iter()
-        .map(|vec| {
-            Item::Map(vec.iter().fold(Map::new(), |mut map, (name, value)| {
-                map.insert(name.clone(), Item::Value(value.clone()));
+        .into_iter()
+        .zip(&result.nested)
+        .enumerate()
+        .map(|(i, (pairs, nested))| {
+            let map = pairs.into_iter().fold(Map::new(), |mut map, (name, value)| {
+                map.insert(name, Item::Value(value));
                 map
-            }))
+            });
+
+            let map = add_typename(map, &result.typename);
+            add_nested(map, nested, &node_path(i), errors)
         })
-        .collect();
-
-    result.nested.iter().zip(&mut vec).for_each(|(nested, map)| {
-        match map {
-            Item::Map(ref mut map) => match nested {
-                PrismaQueryResult::Single(nested) => map.insert(nested.name.clone(), Item::Map(build_map(nested))),
-                PrismaQueryResult::Multi(nested) => map.insert(nested.name.clone(), Item::List(build_list(nested))),
-            },
-            _ => unreachable!(),
+        .collect()
+}
+
+/// Builds the selected parts of a connection: `edges`, `pageInfo` and `aggregate`.
+/// A failed node nulls out its edge, edges being nullable.
+fn build_connection(
+    result: &MultiPrismaQueryResult,
+    connection: &ConnectionResult,
+    path: &[PathSegment],
+    errors: &mut Vec<ResponseError>,
+) -> Map {
+    let selection = &connection.selection;
+    let model_name = &selection.model_name;
+    let mut map = Map::new();
+
+    if selection.typename {
+        let typename = format!("{}{}", model_name, ConnectionSelection::SUFFIX);
+        map.insert(TYPENAME_FIELD.into(), typename_value(&typename));
+    }
+
+    if let Some(ref fields) = selection.edges {
+        let selects = |name: &str| fields.iter().any(|field| field == name);
+        let edges_path = child(path, PathSegment::Field("edges".into()));
+
+        let node_path = |i| {
+            let edge_path = child(&edges_path, PathSegment::Index(i));
+            child(&edge_path, PathSegment::Field("node".into()))
+        };
+
+        let edges = build_nodes(result, node_path, errors)
+            .into_iter()
+            .zip(&connection.cursors)
+            .map(|(node, cursor)| {
+                let node = match node {
+                    Some(node) => node,
+                    None => return Item::Value(PrismaValue::Null),
+                };
+
+                let mut edge = Map::new();
+
+                if selects("node") {
+                    edge.insert("node".into(), Item::Map(node));
+                }
+
+                if selects("cursor") {
+                    edge.insert("cursor".into(), Item::Value(cursor_value(cursor)));
+                }
+
+                if selects(TYPENAME_FIELD) {
+                    edge.insert(TYPENAME_FIELD.into(), typename_value(&format!("{}Edge", model_name)));
+                }
+
+                Item::Map(edge)
+            })
+            .collect();
+
+        map.insert("edges".into(), Item::List(edges));
+    }
+
+    if let Some(ref fields) = selection.page_info {
+        let page_info = fields.iter().fold(Map::new(), |mut map, field| {
+            let value = match field.as_str() {
+                "hasNextPage" => PrismaValue::Boolean(result.result.has_next_page),
+                "hasPreviousPage" => PrismaValue::Boolean(result.result.has_previous_page),
+                "startCursor" => connection.cursors.first().map(cursor_value).unwrap_or(PrismaValue::Null),
+                "endCursor" => connection.cursors.last().map(cursor_value).unwrap_or(PrismaValue::Null),
+                TYPENAME_FIELD => PrismaValue::String("PageInfo".into()),
+                _ => return map,
+            };
+
+            map.insert(field.clone(), Item::Value(value));
+            map
+        });
+
+        map.insert("pageInfo".into(), Item::Map(page_info));
+    }
+
+    if let (Some(aggregations), Some(values)) = (&selection.aggregate, &connection.aggregates) {
+        // Functions of a field nest the result under the field name, e.g. `sum { price }`
+        let aggregate = aggregations
+            .iter()
+            .zip(values)
+            .fold(Map::new(), |mut map, (aggregation, value)| {
+                let value = Item::Value(value.clone());
+
+                match aggregation.field() {
+                    Some(field) => {
+                        let entry = map
+                            .entry(aggregation.name().into())
+                            .or_insert_with(|| Item::Map(Map::new()));
+
+                        if let Item::Map(fields) = entry {
+                            fields.insert(field.name.clone(), value);
+                        }
+                    }
+                    None => {
+                        map.insert(aggregation.name().into(), value);
+                    }
+                }
+
+                map
+            });
+
+        let aggregate = if selection.aggregate_typename {
+            let typename = format!("Aggregate{}", model_name);
+            add_typename(aggregate, &Some(typename))
+        } else {
+            aggregate
+        };
+
+        map.insert("aggregate".into(), Item::Map(aggregate));
+    }
+
+    map
+}
+
+/// Cursors are the ids of the nodes, as strings
+fn cursor_value(id: &GraphqlId) -> PrismaValue {
+    PrismaValue::String(match id {
+        GraphqlId::String(s) => s.clone(),
+        GraphqlId::Int(i) => i.to_string(),
+        GraphqlId::UUID(uuid) => uuid.to_hyphenated().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{context::PrismaContext, test_utils::*};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn scalar_lists_are_resolved_in_position_order_for_each_node() {
+        let ctx = context("ir_scalar_lists");
+
+        request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Alice", cats: { set: [3, 1, 2] } }) { id } }"#,
+        );
+        request(&ctx, r#"mutation { createUser(data: { name: "Bob" }) { id } }"#);
+
+        let carol = request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Carol", cats: { set: [5] } }) { id } }"#,
+        );
+
+        let response = request(&ctx, "{ users(orderBy: name_ASC) { name cats } }");
+
+        assert_eq!(
+            json!({ "users": [
+                { "name": "Alice", "cats": [3, 1, 2] },
+                { "name": "Bob", "cats": [] },
+                { "name": "Carol", "cats": [5] }
+            ] }),
+            response["data"]
+        );
+
+        let query = format!(
+            r#"{{ user(where: {{ id: {} }}) {{ cats }} }}"#,
+            carol["data"]["createUser"]["id"]
+        );
+        let response = request(&ctx, &query);
+
+        assert_eq!(json!({ "user": { "cats": [5] } }), response["data"]);
+    }
+
+    #[test]
+    fn scalar_lists_of_related_nodes_are_resolved() {
+        let ctx = context("ir_nested_scalar_lists");
+
+        request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Alice", sites: { create: { name: "Blog", tags: { set: [2, 1] } } } }) { id } }"#,
+        );
+        request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Bob", sites: { create: { name: "Shop", tags: { set: [7] } } } }) { id } }"#,
+        );
+
+        let response = request(&ctx, "{ users(orderBy: name_ASC) { name sites { tags } } }");
+
+        assert_eq!(
+            json!({ "users": [
+                { "name": "Alice", "sites": { "tags": [2, 1] } },
+                { "name": "Bob", "sites": { "tags": [7] } }
+            ] }),
+            response["data"]
+        );
+    }
+
+    #[test]
+    fn related_nodes_are_grouped_by_their_parent() {
+        let ctx = context("ir_nested_relations");
+
+        request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Alice", sites: { create: { name: "Blog" } } }) { id } }"#,
+        );
+        request(&ctx, r#"mutation { createUser(data: { name: "Bob" }) { id } }"#);
+        request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Carol", sites: { create: { name: "Shop" } } }) { id } }"#,
+        );
+
+        let response = request(
+            &ctx,
+            "{ users(orderBy: name_DESC) { name sites { name user { name } } } }",
+        );
+
+        assert_eq!(
+            json!({ "users": [
+                { "name": "Carol", "sites": { "name": "Shop", "user": { "name": "Carol" } } },
+                { "name": "Bob", "sites": null },
+                { "name": "Alice", "sites": { "name": "Blog", "user": { "name": "Alice" } } }
+            ] }),
+            response["data"]
+        );
+
+        let response = request(&ctx, "{ sites(orderBy: name_ASC) { name user { name } } }");
+
+        assert_eq!(
+            json!({ "sites": [
+                { "name": "Blog", "user": { "name": "Alice" } },
+                { "name": "Shop", "user": { "name": "Carol" } }
+            ] }),
+            response["data"]
+        );
+    }
+
+    #[test]
+    fn typenames_resolve_to_the_types_of_nested_nodes() {
+        let ctx = context("ir_typenames");
+
+        let created = request(
+            &ctx,
+            r#"mutation { createUser(data: { name: "Alice", sites: { create: { name: "Blog" } } }) { __typename name sites { __typename name } } }"#,
+        );
+
+        assert_eq!(
+            json!({ "createUser": { "__typename": "User", "name": "Alice", "sites": { "__typename": "Site", "name": "Blog" } } }),
+            created["data"]
+        );
+
+        let response = request(
+            &ctx,
+            "{ users { __typename name sites { __typename name user { __typename name } } } }",
+        );
+
+        assert_eq!(
+            json!({ "users": [{
+                "__typename": "User",
+                "name": "Alice",
+                "sites": { "__typename": "Site", "name": "Blog", "user": { "__typename": "User", "name": "Alice" } }
+            }] }),
+            response["data"]
+        );
+
+        let response = request(
+            &ctx,
+            "{ usersConnection { __typename \
+             edges { __typename node { __typename name } } \
+             pageInfo { __typename hasNextPage } \
+             aggregate { __typename count } } }",
+        );
+
+        assert_eq!(
+            json!({ "usersConnection": {
+                "__typename": "UserConnection",
+                "edges": [{ "__typename": "UserEdge", "node": { "__typename": "User", "name": "Alice" } }],
+                "pageInfo": { "__typename": "PageInfo", "hasNextPage": false },
+                "aggregate": { "__typename": "AggregateUser", "count": 1 }
+            } }),
+            response["data"]
+        );
+
+        let updated = request(
+            &ctx,
+            r#"mutation { updateManyUsers(data: { name: "Bob" }) { __typename count } }"#,
+        );
+
+        assert_eq!(
+            json!({ "updateManyUsers": { "__typename": "BatchPayload", "count": 1 } }),
+            updated["data"]
+        );
+    }
+
+    fn connection(ctx: &PrismaContext, arguments: &str) -> Value {
+        let query = format!(
+            "{{ usersConnection({}) {{ \
+             pageInfo {{ hasNextPage hasPreviousPage startCursor endCursor }} \
+             edges {{ node {{ name }} cursor }} \
+             aggregate {{ count }} }} }}",
+            arguments
+        );
+
+        request(ctx, &query)["data"]["usersConnection"].clone()
+    }
+
+    fn names(connection: &Value) -> Vec<&str> {
+        connection["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| edge["node"]["name"].as_str().unwrap())
+            .collect()
+    }
+
+    fn seed_users(ctx: &PrismaContext) {
+        for name in &["Alice", "Bob", "Carol"] {
+            request(
+                ctx,
+                &format!(r#"mutation {{ createUser(data: {{ name: "{}" }}) {{ id }} }}"#, name),
+            );
+        }
+    }
+
+    #[test]
+    fn connections_tell_whether_there_are_more_pages() {
+        let ctx = context("ir_connection_pages");
+        seed_users(&ctx);
+
+        let first_page = connection(&ctx, "orderBy: name_ASC, first: 2");
+
+        assert_eq!(vec!["Alice", "Bob"], names(&first_page));
+        assert_eq!(json!(true), first_page["pageInfo"]["hasNextPage"]);
+        assert_eq!(json!(false), first_page["pageInfo"]["hasPreviousPage"]);
+        assert_eq!(first_page["edges"][0]["cursor"], first_page["pageInfo"]["startCursor"]);
+        assert_eq!(first_page["edges"][1]["cursor"], first_page["pageInfo"]["endCursor"]);
+        assert_eq!(json!({ "count": 3 }), first_page["aggregate"]);
+
+        let after = first_page["pageInfo"]["endCursor"].clone();
+        let last_page = connection(&ctx, &format!("orderBy: name_ASC, first: 2, after: {}", after));
+
+        assert_eq!(vec!["Carol"], names(&last_page));
+        assert_eq!(json!(false), last_page["pageInfo"]["hasNextPage"]);
+        assert_eq!(json!({ "count": 3 }), last_page["aggregate"]);
+
+        let all = connection(&ctx, "orderBy: name_ASC, first: 3");
+
+        assert_eq!(vec!["Alice", "Bob", "Carol"], names(&all));
+        assert_eq!(json!(false), all["pageInfo"]["hasNextPage"]);
+    }
+
+    #[test]
+    fn connections_paginated_backwards_tell_whether_there_are_previous_pages() {
+        let ctx = context("ir_connection_last");
+        seed_users(&ctx);
+
+        let last_page = connection(&ctx, "orderBy: name_ASC, last: 2");
+
+        assert_eq!(vec!["Bob", "Carol"], names(&last_page));
+        assert_eq!(json!(true), last_page["pageInfo"]["hasPreviousPage"]);
+        assert_eq!(json!(false), last_page["pageInfo"]["hasNextPage"]);
+        assert_eq!(last_page["edges"][0]["cursor"], last_page["pageInfo"]["startCursor"]);
+
+        let all = connection(&ctx, "orderBy: name_ASC, last: 3");
+
+        assert_eq!(vec!["Alice", "Bob", "Carol"], names(&all));
+        assert_eq!(json!(false), all["pageInfo"]["hasPreviousPage"]);
+    }
+
+    #[test]
+    fn empty_connections_have_no_cursors() {
+        let ctx = context("ir_connection_empty");
+        seed_users(&ctx);
+
+        let empty = connection(&ctx, r#"where: { name: "Dave" }"#);
+
+        assert_eq!(
+            json!({
+                "pageInfo": { "hasNextPage": false, "hasPreviousPage": false, "startCursor": null, "endCursor": null },
+                "edges": [],
+                "aggregate": { "count": 0 }
+            }),
+            empty
+        );
+    }
+
+    #[test]
+    fn connections_aggregate_the_filtered_nodes_of_all_pages() {
+        let ctx = context("ir_connection_aggregate");
+
+        for data in &[
+            r#"{ name: "Apple", price: 1.5, stock: 10 }"#,
+            r#"{ name: "Banana", price: 0.5 }"#,
+            r#"{ name: "Cherry", price: 4.0, stock: 2 }"#,
+        ] {
+            request(&ctx, &format!("mutation {{ createProduct(data: {}) {{ id }} }}", data));
+        }
+
+        let aggregate = |arguments: &str| {
+            let query = format!(
+                "{{ productsConnection({}) {{ aggregate {{ \
+                 count sum {{ price stock }} avg {{ price }} min {{ name }} max {{ stock }} }} }} }}",
+                arguments
+            );
+
+            request(&ctx, &query)["data"]["productsConnection"]["aggregate"].clone()
         };
-    });
 
-    vec
+        assert_eq!(
+            json!({
+                "count": 2,
+                "sum": { "price": 5.5, "stock": 12 },
+                "avg": { "price": 2.75 },
+                "min": { "name": "Apple" },
+                "max": { "stock": 10 }
+            }),
+            aggregate("where: { price_gt: 1.0 }, orderBy: name_ASC, first: 1")
+        );
+
+        assert_eq!(
+            json!({
+                "count": 3,
+                "sum": { "price": 6.0, "stock": 12 },
+                "avg": { "price": 2.0 },
+                "min": { "name": "Apple" },
+                "max": { "stock": 10 }
+            }),
+            aggregate("orderBy: name_ASC, last: 1")
+        );
+
+        assert_eq!(
+            json!({
+                "count": 0,
+                "sum": { "price": null, "stock": null },
+                "avg": { "price": null },
+                "min": { "name": null },
+                "max": { "stock": null }
+            }),
+            aggregate(r#"where: { name: "Durian" }"#)
+        );
+    }
+
+    fn seed_products(ctx: &PrismaContext) {
+        request(
+            ctx,
+            r#"mutation {
+                a: createProduct(data: { id: "a", name: "Apple", price: 1.0, reviews: { create: [
+                    { id: "r1", author: "Ann", rating: 5 },
+                    { id: "r2", author: "Ben", rating: 5 },
+                    { id: "r3", author: "Ann", rating: 3 }
+                ] } }) { id }
+                b: createProduct(data: { id: "b", name: "Apple", price: 2.0 }) { id }
+                c: createProduct(data: { id: "c", name: "Pear", price: 3.0, reviews: { create: [
+                    { id: "r4", author: "Ann", rating: 4 },
+                    { id: "r5", author: "Cid", rating: 4 }
+                ] } }) { id }
+                d: createProduct(data: { id: "d", name: "Plum", price: 3.0 }) { id }
+            }"#,
+        );
+    }
+
+    fn product_ids(ctx: &PrismaContext, arguments: &str) -> Value {
+        let query = format!("{{ products({}) {{ id }} }}", arguments);
+        let products = request(ctx, &query)["data"]["products"].clone();
+
+        products
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|product| product["id"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn distinct_nodes_are_the_first_of_each_combination_of_values() {
+        let ctx = context("ir_distinct");
+        seed_products(&ctx);
+
+        assert_eq!(
+            json!(["c", "d", "b"]),
+            product_ids(&ctx, "distinct: name, orderBy: price_DESC")
+        );
+        assert_eq!(
+            json!(["a", "b", "c"]),
+            product_ids(&ctx, "distinct: [price], orderBy: id_ASC")
+        );
+        assert_eq!(
+            json!(["a", "b", "c", "d"]),
+            product_ids(&ctx, "distinct: [name, price], orderBy: id_ASC")
+        );
+        assert_eq!(
+            json!(["c"]),
+            product_ids(
+                &ctx,
+                r#"distinct: name, where: { price_gt: 2.5 }, orderBy: id_ASC, first: 1"#
+            )
+        );
+    }
+
+    #[test]
+    fn distinct_nodes_do_not_depend_on_pagination() {
+        let ctx = context("ir_distinct_pages");
+        seed_products(&ctx);
+
+        // `b` is not the first Apple, even on a page after `a`.
+        assert_eq!(
+            json!(["c", "d"]),
+            product_ids(&ctx, r#"distinct: name, orderBy: id_ASC, after: "a""#)
+        );
+        assert_eq!(
+            json!(["c"]),
+            product_ids(&ctx, "distinct: name, orderBy: id_ASC, skip: 1, first: 1")
+        );
+        assert_eq!(
+            json!(["c", "d"]),
+            product_ids(&ctx, "distinct: name, orderBy: id_ASC, last: 2")
+        );
+    }
+
+    #[test]
+    fn related_nodes_are_distinct_per_parent() {
+        let ctx = context("ir_distinct_related");
+        seed_products(&ctx);
+
+        let reviews = |arguments: &str| {
+            let query = format!(
+                r#"{{ products(where: {{ id_in: ["a", "c"] }}, orderBy: id_ASC) {{ reviews({}) {{ id }} }} }}"#,
+                arguments
+            );
+
+            request(&ctx, &query)["data"]["products"].clone()
+        };
+
+        assert_eq!(
+            json!([{ "reviews": [{ "id": "r1" }, { "id": "r3" }] }, { "reviews": [{ "id": "r4" }] }]),
+            reviews("distinct: rating, orderBy: id_ASC")
+        );
+        assert_eq!(
+            json!([{ "reviews": [{ "id": "r3" }, { "id": "r2" }] }, { "reviews": [{ "id": "r4" }, { "id": "r5" }] }]),
+            reviews("distinct: author, orderBy: rating_ASC")
+        );
+
+        // Paginated related nodes are read in a window per parent.
+        assert_eq!(
+            json!([{ "reviews": [{ "id": "r3" }] }, { "reviews": [] }]),
+            reviews("distinct: rating, orderBy: id_ASC, skip: 1, first: 1")
+        );
+    }
 }
@@ -1,43 +1,61 @@
 //! Json serialisation endpoint from IR
 
 use crate::{PrismaError, PrismaResult};
+use chrono::SecondsFormat;
 use prisma_models::{GraphqlId, PrismaValue};
 use serde_json::{Map, Number, Value};
 use std::collections::BTreeMap;
 
-use super::ir::{Responses, IrResponse, Item};
+use super::ir::{IrResponse, Item, Responses};
 
 type JsonMap = Map<String, Value>;
 type JsonVec = Vec<Value>;
 
-pub fn serialize(resp: Responses) -> Value {
-    let mut map = Map::new();
-
-    let vals: Vec<Value> = resp.into_iter().map(|res| match res {
-        IrResponse::Data(Item::List(list)) => Value::Array(serialize_list(list)),
-        IrResponse::Data(Item::Map(map)) => Value::Object(serialize_map(map)),
-        _ => unreachable!()
-    }).collect();
-
-    map.insert("data".into(), if vals.len() == 1 {
-         vals.first().unwrap().clone()
-    } else {
-        Value::Array(vals)
-    });
-
-    Value::Object(map)
-}
-
 macro_rules! match_serialize {
     ($val:ident) => {
         match $val {
             Item::List(l) => Value::Array(serialize_list(l)),
             Item::Map(m) => Value::Object(serialize_map(m)),
             Item::Value(v) => serialize_prisma_value(v).unwrap(),
+            Item::Meta(v) => v,
         }
     };
 }
 
+/// Serializes the responses into a `{ data, errors }` envelope.
+/// `errors` is left out if all fields were resolved.
+pub fn serialize(resp: Responses) -> Value {
+    let mut data = JsonMap::new();
+    let mut data_is_null = false;
+
+    for res in resp.fields {
+        match res {
+            IrResponse::Data(name, item) => {
+                data.insert(name, match_serialize!(item));
+            }
+            IrResponse::Null { name, nullable } => {
+                if nullable {
+                    data.insert(name, Value::Null);
+                } else {
+                    data_is_null = true;
+                }
+            }
+        }
+    }
+
+    let mut map = Map::new();
+    map.insert("data".into(), if data_is_null { Value::Null } else { Value::Object(data) });
+
+    if !resp.errors.is_empty() {
+        map.insert(
+            "errors".into(),
+            serde_json::to_value(resp.errors).expect("Response errors are always serializable"),
+        );
+    }
+
+    Value::Object(map)
+}
+
 /// Recursively serialize query results
 fn serialize_map(map: BTreeMap<String, Item>) -> JsonMap {
     map.into_iter().fold(JsonMap::new(), |mut map, (k, v)| {
@@ -61,28 +79,90 @@ fn serialize_prisma_value(value: PrismaValue) -> PrismaResult<Value> {
             None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
         }),
         PrismaValue::Boolean(x) => Value::Bool(x),
-        PrismaValue::DateTime(_) => unimplemented!(),
+        PrismaValue::DateTime(x) => Value::String(x.to_rfc3339_opts(SecondsFormat::Millis, true)),
         PrismaValue::Enum(x) => Value::String(x.clone()),
         PrismaValue::Json(x) => serde_json::from_str(&x)?,
-        PrismaValue::Int(x) => Value::Number(match Number::from_f64(x as f64) {
-            Some(num) => num,
-            None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
-        }),
-        PrismaValue::Relation(_) => unimplemented!(),
+        PrismaValue::Int(x) => Value::Number(Number::from(x)),
+        PrismaValue::Relation(x) => Value::Number(Number::from(x as u64)),
         PrismaValue::Null => Value::Null,
         PrismaValue::Uuid(x) => Value::String(x.to_hyphenated().to_string()),
         PrismaValue::GraphqlId(x) => serialize_graphql_id(&x)?,
-        PrismaValue::List(_) => unimplemented!(),
+        PrismaValue::List(x) => Value::Array(
+            x.into_iter()
+                .map(serialize_prisma_value)
+                .collect::<PrismaResult<JsonVec>>()?,
+        ),
     })
 }
 
 fn serialize_graphql_id(id: &GraphqlId) -> PrismaResult<Value> {
     Ok(match id {
         GraphqlId::String(x) => Value::String(x.clone()),
-        GraphqlId::Int(x) => Value::Number(match serde_json::Number::from_f64(*x as f64) {
-            Some(num) => num,
-            None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
-        }),
+        GraphqlId::Int(x) => Value::Number(Number::from(*x as u64)),
         GraphqlId::UUID(x) => Value::String(x.to_hyphenated().to_string()),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serializer::ir::IrResponse;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn serialize_value(value: PrismaValue) -> Value {
+        serialize_prisma_value(value).unwrap()
+    }
+
+    #[test]
+    fn date_times_are_iso_8601_strings_in_utc_with_milliseconds() {
+        let date_time = Utc.ymd(2019, 2, 14).and_hms_milli(9, 30, 5, 42);
+
+        assert_eq!(
+            json!("2019-02-14T09:30:05.042Z"),
+            serialize_value(PrismaValue::DateTime(date_time))
+        );
+    }
+
+    #[test]
+    fn ints_are_serialized_without_a_fraction() {
+        let value = serialize_value(PrismaValue::Int(42));
+
+        assert_eq!("42", value.to_string());
+        assert!(value.is_i64());
+    }
+
+    #[test]
+    fn lists_are_arrays_of_their_serialized_values() {
+        let list = PrismaValue::List(vec![PrismaValue::Int(1), PrismaValue::Null, PrismaValue::Int(3)]);
+
+        assert_eq!(json!([1, null, 3]), serialize_value(list));
+        assert_eq!(json!([]), serialize_value(PrismaValue::List(vec![])));
+    }
+
+    #[test]
+    fn json_is_embedded_instead_of_quoted() {
+        let value = PrismaValue::Json(String::from(r#"{"theme":"dark"}"#));
+
+        assert_eq!(json!({ "theme": "dark" }), serialize_value(value));
+    }
+
+    #[test]
+    fn ids_keep_their_type() {
+        assert_eq!(
+            json!("cjs"),
+            serialize_value(PrismaValue::GraphqlId(GraphqlId::String("cjs".into())))
+        );
+        assert_eq!(json!(7), serialize_value(PrismaValue::GraphqlId(GraphqlId::Int(7))));
+    }
+
+    #[test]
+    fn errors_are_left_out_if_all_fields_were_resolved() {
+        let responses = Responses {
+            fields: vec![IrResponse::Data("count".into(), Item::Value(PrismaValue::Int(1)))],
+            errors: vec![],
+        };
+
+        assert_eq!(json!({ "data": { "count": 1 } }), serialize(responses));
+    }
+}
@@ -0,0 +1,84 @@
+//! Helpers for the tests of the schema, validation, serialization and request
+//! handling, using the schema of `test_schema.json` in the SQLite connector.
+
+use crate::{
+    context::PrismaContext,
+    req_handlers::{GraphQlBody, GraphQlRequestHandler, PrismaRequest, RequestHandler},
+    schema::{GraphQlSchema, SchemaBuilder},
+};
+use core::{MutationExecutor, QueryExecutor};
+use graphql_parser::query::Selection;
+use prisma_common::config::PrismaConfig;
+use prisma_models::{SchemaRef, SchemaTemplate};
+use serde_json::{json, Value};
+use sqlite_connector::Sqlite;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    sync::Arc,
+};
+
+pub fn schema() -> SchemaRef {
+    schema_for("test")
+}
+
+fn schema_for(db_name: &str) -> SchemaRef {
+    let template: SchemaTemplate =
+        serde_json::from_reader(File::open("../connectors/sqlite-connector/test_schema.json").unwrap()).unwrap();
+
+    template.build(String::from(db_name))
+}
+
+pub fn graphql_schema() -> GraphQlSchema {
+    SchemaBuilder::build(schema())
+}
+
+/// A context on a new, empty database with the tables of the schema.
+pub fn context(db_name: &str) -> PrismaContext {
+    let _ = std::fs::create_dir_all("db");
+    let _ = std::fs::remove_file(format!("db/{}.db", db_name));
+
+    let schema = schema_for(db_name);
+    let sqlite = Arc::new(Sqlite::new(1, false).unwrap());
+    sqlite.create_tables(Arc::clone(&schema)).unwrap();
+
+    PrismaContext {
+        config: PrismaConfig {
+            port: 4466,
+            management_api_secret: None,
+            cluster_address: None,
+            rabbit_uri: None,
+            enable_management_api: None,
+            databases: BTreeMap::new(),
+        },
+        graphql_schema: SchemaBuilder::build(Arc::clone(&schema)),
+        schema,
+        query_executor: QueryExecutor {
+            data_resolver: Arc::clone(&sqlite),
+        },
+        mutation_executor: MutationExecutor {
+            db_name: String::from(db_name),
+            database_executor: sqlite,
+        },
+    }
+}
+
+/// The root selections of the only operation of the document.
+pub fn selections(query: &str) -> Vec<Selection> {
+    let document = graphql_parser::parse_query(query).unwrap();
+    core::resolve_operation(&document, None, &BTreeMap::new())
+        .unwrap()
+        .selections
+}
+
+/// Handles the given document like a request to the server would be.
+pub fn request(ctx: &PrismaContext, query: &str) -> Value {
+    let body: GraphQlBody = serde_json::from_value(json!({ "query": query })).unwrap();
+    let req = PrismaRequest {
+        body,
+        headers: HashMap::new(),
+        path: String::from("/"),
+    };
+
+    GraphQlRequestHandler.handle(req, ctx)
+}